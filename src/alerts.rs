@@ -0,0 +1,731 @@
+//! Threshold alerts: rules from pstoprc, checked at the end of every
+//! `Collector::refresh` in the UI and in `pstop --alerts` (headless).
+//!
+//! ```text
+//! alert_log=C:\Logs\pstop-alerts.log
+//! alert.cpu_hot.when=cpu > 90
+//! alert.cpu_hot.for=30s
+//! alert.cpu_hot.clear=80
+//! alert.cpu_hot.actions=banner,bell,log,command
+//! alert.cpu_hot.command=notify.cmd
+//! alert.big_rss.when=any res > 4G
+//! alert.agent_gone.when=none name == "agent"
+//! ```
+//!
+//! `when` is either a system metric compared with a number (`cpu`, `mem` and
+//! `swap` in percent, `load1`/`load5`/`load15`, `tasks`, `threads`,
+//! `net_rx`/`net_tx` in bytes/sec), or `any <filter>` / `none <filter>` with
+//! the F4 filter language over the process list.
+//!
+//! A rule fires once `when` has held for `for` (default 0). It resolves when
+//! it has been clear for `clear_for` (default 0); for metric rules `clear`
+//! moves the clear line away from the threshold, so `cpu > 90` with
+//! `clear=80` stays firing until CPU drops below 80.
+//!
+//! Actions (`banner` when none are given): `banner` flashes the footer (or
+//! prints a line headless), `bell` rings the terminal bell, `log` appends to
+//! `alert_log` (default `alerts.log` next to pstoprc) and `command` runs
+//! `command` through the shell with the alert in `PSTOP_ALERT_*` variables.
+
+use std::io::Write;
+use std::process::{Command, Stdio};
+use std::time::{Duration, Instant};
+
+use anyhow::{bail, Result};
+
+use crate::app::App;
+use crate::config::PstopConfig;
+use crate::filter::{parse_number, Cmp, Filter};
+use crate::system::collector::Collector;
+use crate::system::memory::format_bytes;
+use crate::ui::header::format_rate;
+
+// ── Rules ───────────────────────────────────────────────────────────────
+
+/// System-wide values a rule can compare
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Metric {
+    Cpu,
+    Mem,
+    Swap,
+    Load1,
+    Load5,
+    Load15,
+    Tasks,
+    Threads,
+    NetRx,
+    NetTx,
+}
+
+impl Metric {
+    const ALL: [Self; 10] = [
+        Self::Cpu,
+        Self::Mem,
+        Self::Swap,
+        Self::Load1,
+        Self::Load5,
+        Self::Load15,
+        Self::Tasks,
+        Self::Threads,
+        Self::NetRx,
+        Self::NetTx,
+    ];
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            Self::Cpu => "cpu",
+            Self::Mem => "mem",
+            Self::Swap => "swap",
+            Self::Load1 => "load1",
+            Self::Load5 => "load5",
+            Self::Load15 => "load15",
+            Self::Tasks => "tasks",
+            Self::Threads => "threads",
+            Self::NetRx => "net_rx",
+            Self::NetTx => "net_tx",
+        }
+    }
+
+    fn lookup(word: &str) -> Option<Self> {
+        Self::ALL.into_iter().find(|m| m.name().eq_ignore_ascii_case(word))
+    }
+
+    pub fn value(&self, app: &App) -> f64 {
+        let percent = |part: u64, total: u64| if total > 0 { part as f64 * 100.0 / total as f64 } else { 0.0 };
+        match self {
+            Self::Cpu => {
+                let cores = &app.cpu_info.cores;
+                cores.iter().map(|c| c.usage_percent as f64).sum::<f64>() / cores.len().max(1) as f64
+            }
+            Self::Mem => percent(app.memory_info.used_mem, app.memory_info.total_mem),
+            Self::Swap => percent(app.memory_info.used_swap, app.memory_info.total_swap),
+            Self::Load1 => app.load_avg_1,
+            Self::Load5 => app.load_avg_5,
+            Self::Load15 => app.load_avg_15,
+            Self::Tasks => app.total_tasks as f64,
+            Self::Threads => app.total_threads as f64,
+            Self::NetRx => app.network_info.rx_bytes_per_sec,
+            Self::NetTx => app.network_info.tx_bytes_per_sec,
+        }
+    }
+
+    fn format(&self, v: f64) -> String {
+        match self {
+            Self::Cpu | Self::Mem | Self::Swap => format!("{:.1}%", v),
+            Self::Load1 | Self::Load5 | Self::Load15 => format!("{:.2}", v),
+            Self::Tasks | Self::Threads => format!("{:.0}", v),
+            Self::NetRx | Self::NetTx => format_rate(v),
+        }
+    }
+}
+
+/// A compiled `when`
+#[derive(Debug, Clone)]
+pub enum Condition {
+    Metric(Metric, Cmp, f64),
+    /// At least one process matches
+    Any(Filter),
+    /// No process matches
+    None(Filter),
+}
+
+impl Condition {
+    pub fn parse(when: &str) -> Result<Self, String> {
+        let when = when.trim();
+        let (head, rest) = when.split_once(char::is_whitespace).unwrap_or((when, ""));
+        match head.to_ascii_lowercase().as_str() {
+            "any" | "none" if rest.trim().is_empty() => Err(format!("'{}' needs a filter", head)),
+            "any" => Filter::parse(rest).map(Self::Any).map_err(|e| e.to_string()),
+            "none" => Filter::parse(rest).map(Self::None).map_err(|e| e.to_string()),
+            _ => Self::parse_metric(when),
+        }
+    }
+
+    fn parse_metric(when: &str) -> Result<Self, String> {
+        // Two-character operators first, so `>=` isn't read as `>`
+        const OPS: [(&str, Cmp); 6] =
+            [(">=", Cmp::Ge), ("<=", Cmp::Le), ("==", Cmp::Eq), ("!=", Cmp::Ne), (">", Cmp::Gt), ("<", Cmp::Lt)];
+        let Some((at, op, cmp)) = OPS
+            .iter()
+            .filter_map(|&(op, cmp)| when.find(op).map(|at| (at, op, cmp)))
+            .min_by_key(|&(at, _, _)| at)
+        else {
+            return Err("expected '<metric> <op> <number>', 'any <filter>' or 'none <filter>'".into());
+        };
+        let name = when[..at].trim();
+        let Some(metric) = Metric::lookup(name) else {
+            let names: Vec<&str> = Metric::ALL.iter().map(Metric::name).collect();
+            return Err(format!("unknown metric '{}' (expected {})", name, names.join(", ")));
+        };
+        let threshold = threshold(&when[at + op.len()..])?;
+        Ok(Self::Metric(metric, cmp, threshold))
+    }
+}
+
+/// `90`, `90%`, `100M` → number
+fn threshold(value: &str) -> Result<f64, String> {
+    let value = value.trim();
+    parse_number(value.trim_end_matches('%'), false).ok_or_else(|| format!("'{}' is not a number", value))
+}
+
+/// What to do when a rule fires or resolves
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Action {
+    Banner,
+    Bell,
+    Log,
+    Command,
+}
+
+impl Action {
+    pub fn name(&self) -> &'static str {
+        match self {
+            Self::Banner => "banner",
+            Self::Bell => "bell",
+            Self::Log => "log",
+            Self::Command => "command",
+        }
+    }
+
+    const ALL: [Self; 4] = [Self::Banner, Self::Bell, Self::Log, Self::Command];
+
+    pub fn parse(s: &str) -> Option<Self> {
+        Self::ALL.into_iter().find(|a| a.name().eq_ignore_ascii_case(s.trim()))
+    }
+
+    /// A comma-separated `actions` list; any unknown name is an error
+    fn parse_list(list: &str) -> Result<Vec<Self>, String> {
+        list.split(',')
+            .map(str::trim)
+            .filter(|word| !word.is_empty())
+            .map(|word| {
+                Self::parse(word).ok_or_else(|| {
+                    let names: Vec<&str> = Self::ALL.iter().map(Self::name).collect();
+                    format!("unknown action '{}' (expected {})", word, names.join(", "))
+                })
+            })
+            .collect()
+    }
+}
+
+/// One `alert.<name>.*` rule as written in the config file
+#[derive(Debug, Clone)]
+pub struct AlertRule {
+    pub name: String,
+    pub when: String,
+    /// Seconds `when` must hold before the rule fires
+    pub sustain_secs: u64,
+    /// Clear line for metric rules (empty = the threshold)
+    pub clear: String,
+    /// Seconds the rule must be clear before it resolves
+    pub clear_for_secs: u64,
+    /// `actions` as written, kept for the config file
+    pub actions_text: String,
+    pub actions: Vec<Action>,
+    pub command: String,
+    condition: Result<Condition, String>,
+    clear_at: Option<f64>,
+}
+
+impl AlertRule {
+    /// A rule with no condition yet, for the config loader to fill in
+    pub fn named(name: &str) -> Self {
+        Self {
+            name: name.to_string(),
+            when: String::new(),
+            sustain_secs: 0,
+            clear: String::new(),
+            clear_for_secs: 0,
+            actions_text: Action::Banner.name().to_string(),
+            actions: vec![Action::Banner],
+            command: String::new(),
+            condition: Err("no 'when' condition".into()),
+            clear_at: None,
+        }
+    }
+
+    /// The compiled condition, or why `when`/`clear` didn't parse
+    pub fn condition(&self) -> Result<&Condition, &str> {
+        self.condition.as_ref().map_err(String::as_str)
+    }
+
+    /// Set one field from a config line; unknown keys are ignored
+    pub fn set(&mut self, key: &str, value: &str) {
+        match key {
+            "when" => self.when = value.to_string(),
+            "for" => self.sustain_secs = seconds(value),
+            "clear" => self.clear = value.to_string(),
+            "clear_for" => self.clear_for_secs = seconds(value),
+            "actions" => self.actions_text = value.to_string(),
+            "command" => self.command = value.to_string(),
+            _ => return,
+        }
+        self.compile();
+    }
+
+    fn compile(&mut self) {
+        self.condition = Condition::parse(&self.when);
+        self.clear_at = None;
+        match Action::parse_list(&self.actions_text) {
+            Ok(actions) => self.actions = actions,
+            Err(e) => {
+                self.actions.clear();
+                if self.condition.is_ok() {
+                    self.condition = Err(format!("actions: {}", e));
+                }
+                return;
+            }
+        }
+        if self.clear.trim().is_empty() {
+            return;
+        }
+        match (&self.condition, threshold(&self.clear)) {
+            (Ok(Condition::Metric(..)), Ok(v)) => self.clear_at = Some(v),
+            (Ok(_), Ok(_)) => self.condition = Err("'clear' only applies to metric rules (use 'clear_for')".into()),
+            (Ok(_), Err(e)) => self.condition = Err(format!("clear: {}", e)),
+            (Err(_), _) => {}
+        }
+    }
+
+    /// `alert.<name>.<key>=<value>` lines for the config file
+    pub fn config_lines(&self) -> Vec<String> {
+        let prefix = format!("alert.{}.", self.name);
+        let mut lines = vec![format!("{}when={}", prefix, self.when)];
+        if self.sustain_secs > 0 {
+            lines.push(format!("{}for={}", prefix, format_secs(self.sustain_secs)));
+        }
+        if !self.clear.is_empty() {
+            lines.push(format!("{}clear={}", prefix, self.clear));
+        }
+        if self.clear_for_secs > 0 {
+            lines.push(format!("{}clear_for={}", prefix, format_secs(self.clear_for_secs)));
+        }
+        lines.push(format!("{}actions={}", prefix, self.actions_text));
+        if !self.command.is_empty() {
+            lines.push(format!("{}command={}", prefix, self.command));
+        }
+        lines
+    }
+
+    /// Check the rule against the current tick
+    fn observe(&self, app: &App) -> Option<Observation> {
+        let condition = self.condition.as_ref().ok()?;
+        Some(match condition {
+            Condition::Metric(metric, cmp, threshold) => {
+                let value = metric.value(app);
+                let active = cmp.holds(value, *threshold);
+                let clear = match self.clear_at {
+                    Some(line) => !cmp.holds(value, line),
+                    None => !active,
+                };
+                let detail = if active {
+                    format!("{} {} {} {}", metric.name(), metric.format(value), cmp_symbol(*cmp), metric.format(*threshold))
+                } else {
+                    format!("{} back to {}", metric.name(), metric.format(value))
+                };
+                Observation { active, clear, value, detail, process: None }
+            }
+            Condition::Any(filter) | Condition::None(filter) => {
+                let matching: Vec<_> = app.processes.iter().filter(|p| filter.matches(p)).collect();
+                let query = self.when.trim().split_once(char::is_whitespace).map_or("", |(_, q)| q.trim());
+                let any = !matching.is_empty();
+                let active = matches!(condition, Condition::Any(_)) == any;
+                let detail = match matching.first() {
+                    Some(p) => {
+                        let more = match matching.len() {
+                            1 => String::new(),
+                            n => format!(" (+{} more)", n - 1),
+                        };
+                        let what = if active { format!("matches {}", query) } else { "is back".to_string() };
+                        format!("{} ({}) {}{}", p.name, p.pid, what, more)
+                    }
+                    None => format!("no process matches {}", query),
+                };
+                let process = matching.first().map(|p| (p.pid, p.name.clone(), p.resident_mem));
+                Observation { active, clear: !active, value: matching.len() as f64, detail, process }
+            }
+        })
+    }
+}
+
+fn cmp_symbol(cmp: Cmp) -> &'static str {
+    match cmp {
+        Cmp::Eq => "==",
+        Cmp::Ne => "!=",
+        Cmp::Gt => ">",
+        Cmp::Ge => ">=",
+        Cmp::Lt => "<",
+        Cmp::Le => "<=",
+    }
+}
+
+/// `30s`, `2m`, `1h`, `45` → seconds (0 if unreadable)
+fn seconds(value: &str) -> u64 {
+    parse_number(value.trim(), true).map_or(0, |s| s.max(0.0).round() as u64)
+}
+
+fn format_secs(secs: u64) -> String {
+    if secs.is_multiple_of(3600) {
+        format!("{}h", secs / 3600)
+    } else if secs.is_multiple_of(60) {
+        format!("{}m", secs / 60)
+    } else {
+        format!("{}s", secs)
+    }
+}
+
+// ── Evaluation ──────────────────────────────────────────────────────────
+
+/// A rule checked against one tick
+struct Observation {
+    /// `when` holds
+    active: bool,
+    /// The rule may resolve (hysteresis applied)
+    clear: bool,
+    value: f64,
+    detail: String,
+    /// First matching process of an any/none rule: pid, name, RSS
+    process: Option<(u32, String, u64)>,
+}
+
+/// Where a rule is in its fire/resolve cycle; times are epoch millis
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+enum Phase {
+    #[default]
+    Idle,
+    Pending(i64),
+    Firing,
+    Clearing(i64),
+}
+
+/// A rule firing or resolving
+#[derive(Debug, Clone, PartialEq)]
+pub struct AlertEvent {
+    pub rule: String,
+    pub firing: bool,
+    pub message: String,
+    pub value: f64,
+    pub process: Option<(u32, String, u64)>,
+    pub at_ms: i64,
+}
+
+/// The configured rules and their state
+#[derive(Debug, Clone, Default)]
+pub struct Alerts {
+    pub rules: Vec<AlertRule>,
+    /// `alert_log`; empty = `alerts.log` next to pstoprc
+    pub log_path: String,
+    /// Only the UI and `--alerts` check rules; batch, dump and the metrics
+    /// exporter share the collector but never alert
+    pub enabled: bool,
+    /// Print events instead of flashing the footer
+    pub headless: bool,
+    phases: Vec<Phase>,
+}
+
+impl Alerts {
+    /// Check every rule against `app`; returns the rules that fired or
+    /// resolved on this tick
+    pub fn evaluate(&mut self, app: &App, now_ms: i64) -> Vec<AlertEvent> {
+        self.phases.resize(self.rules.len(), Phase::Idle);
+        let mut events = Vec::new();
+        for (rule, phase) in self.rules.iter().zip(self.phases.iter_mut()) {
+            let Some(obs) = rule.observe(app) else { continue };
+            let sustained = |since: i64, secs: u64| now_ms - since >= secs as i64 * 1000;
+            let (next, fired) = match *phase {
+                Phase::Idle | Phase::Pending(_) if !obs.active => (Phase::Idle, None),
+                Phase::Idle if rule.sustain_secs == 0 => (Phase::Firing, Some(true)),
+                Phase::Idle => (Phase::Pending(now_ms), None),
+                Phase::Pending(since) if sustained(since, rule.sustain_secs) => (Phase::Firing, Some(true)),
+                Phase::Pending(since) => (Phase::Pending(since), None),
+                Phase::Firing | Phase::Clearing(_) if !obs.clear => (Phase::Firing, None),
+                Phase::Firing if rule.clear_for_secs == 0 => (Phase::Idle, Some(false)),
+                Phase::Firing => (Phase::Clearing(now_ms), None),
+                Phase::Clearing(since) if sustained(since, rule.clear_for_secs) => (Phase::Idle, Some(false)),
+                Phase::Clearing(since) => (Phase::Clearing(since), None),
+            };
+            *phase = next;
+            if let Some(firing) = fired {
+                let message = match (firing, rule.sustain_secs) {
+                    (true, secs) if secs > 0 => format!("{} for {}", obs.detail, format_secs(secs)),
+                    _ => obs.detail,
+                };
+                events.push(AlertEvent {
+                    rule: rule.name.clone(),
+                    firing,
+                    message,
+                    value: obs.value,
+                    process: obs.process,
+                    at_ms: now_ms,
+                });
+            }
+        }
+        events
+    }
+
+    /// Names of the rules currently firing
+    pub fn firing(&self) -> Vec<&str> {
+        self.rules
+            .iter()
+            .zip(&self.phases)
+            .filter(|(_, phase)| matches!(phase, Phase::Firing | Phase::Clearing(_)))
+            .map(|(rule, _)| rule.name.as_str())
+            .collect()
+    }
+
+    /// Rules that won't be checked, with the reason
+    pub fn errors(&self) -> Vec<String> {
+        self.rules
+            .iter()
+            .filter_map(|r| r.condition().err().map(|e| format!("alert '{}': {}", r.name, e)))
+            .collect()
+    }
+}
+
+// ── Actions ─────────────────────────────────────────────────────────────
+
+/// Carry out the actions of the rules in `events`
+pub fn act(app: &mut App, events: &[AlertEvent]) {
+    let mut banners = Vec::new();
+    for event in events {
+        let Some(rule) = app.alerts.rules.iter().find(|r| r.name == event.rule).cloned() else { continue };
+        let state = if event.firing { "FIRING" } else { "RESOLVED" };
+        let time = chrono::DateTime::from_timestamp_millis(event.at_ms)
+            .map(|t| t.with_timezone(&chrono::Local).format("%Y-%m-%d %H:%M:%S").to_string())
+            .unwrap_or_default();
+        let line = format!("{} {} {}: {}", time, state, rule.name, event.message);
+
+        if app.alerts.headless {
+            println!("{}", line);
+        } else if rule.actions.contains(&Action::Banner) {
+            banners.push(if event.firing {
+                format!("ALERT {}: {}", rule.name, event.message)
+            } else {
+                format!("Resolved {}: {}", rule.name, event.message)
+            });
+        }
+        if rule.actions.contains(&Action::Bell) && event.firing {
+            let mut out = std::io::stdout();
+            let _ = out.write_all(b"\x07").and_then(|_| out.flush());
+        }
+        if rule.actions.contains(&Action::Log) {
+            if let Err(e) = append_log(&app.alerts.log_path, &line) {
+                report(app, format!("Alert log: {}", e));
+            }
+        }
+        if rule.actions.contains(&Action::Command) && !rule.command.is_empty() {
+            if let Err(e) = spawn_command(&rule, event, state, app.alerts.headless) {
+                report(app, format!("Alert command for '{}' failed: {}", rule.name, e));
+            }
+        }
+    }
+    // One footer line: the first event, and how many more there were
+    if let Some(first) = banners.first() {
+        let banner = match banners.len() {
+            1 => first.clone(),
+            n => format!("{} (+{} more)", first, n - 1),
+        };
+        app.footer_message = Some((banner, Instant::now()));
+    }
+}
+
+/// An action went wrong: footer in the UI, stderr headless
+fn report(app: &mut App, message: String) {
+    if app.alerts.headless {
+        eprintln!("{}", message);
+    } else {
+        app.footer_message = Some((message, Instant::now()));
+    }
+}
+
+fn append_log(path: &str, line: &str) -> std::io::Result<()> {
+    let path = if path.is_empty() {
+        crate::config::config_dir()
+            .map(|dir| dir.join("alerts.log"))
+            .ok_or_else(|| std::io::Error::other("no config directory for alerts.log"))?
+    } else {
+        std::path::PathBuf::from(path)
+    };
+    if let Some(parent) = path.parent().filter(|p| !p.as_os_str().is_empty()) {
+        std::fs::create_dir_all(parent)?;
+    }
+    let mut file = std::fs::OpenOptions::new().create(true).append(true).open(&path)?;
+    writeln!(file, "{}", line)
+}
+
+/// Run the rule's command through the shell without waiting for it
+fn spawn_command(rule: &AlertRule, event: &AlertEvent, state: &str, headless: bool) -> std::io::Result<()> {
+    #[cfg(windows)]
+    let mut command = {
+        let mut c = Command::new("cmd");
+        c.arg("/C").arg(&rule.command);
+        c
+    };
+    #[cfg(not(windows))]
+    let mut command = {
+        let mut c = Command::new("sh");
+        c.arg("-c").arg(&rule.command);
+        c
+    };
+    let (pid, name, rss) = match &event.process {
+        Some((pid, name, rss)) => (pid.to_string(), name.clone(), format_bytes(*rss)),
+        None => Default::default(),
+    };
+    command
+        .env("PSTOP_ALERT", &rule.name)
+        .env("PSTOP_ALERT_STATE", state.to_ascii_lowercase())
+        .env("PSTOP_ALERT_WHEN", &rule.when)
+        .env("PSTOP_ALERT_MESSAGE", &event.message)
+        .env("PSTOP_ALERT_VALUE", event.value.to_string())
+        .env("PSTOP_ALERT_TIME", event.at_ms.to_string())
+        .env("PSTOP_ALERT_PID", pid)
+        .env("PSTOP_ALERT_PROCESS", name)
+        .env("PSTOP_ALERT_RSS", rss)
+        .stdin(Stdio::null());
+    // The UI owns the terminal; headless output can go to ours
+    if !headless {
+        command.stdout(Stdio::null()).stderr(Stdio::null());
+    }
+    let mut child = command.spawn()?;
+    // Reap it in the background so it doesn't linger as a zombie
+    std::thread::spawn(move || child.wait());
+    Ok(())
+}
+
+// ── Headless ────────────────────────────────────────────────────────────
+
+/// `pstop --alerts`: check the rules every update interval and print each
+/// event until interrupted
+pub fn run(interval_ms: Option<u64>) -> Result<()> {
+    let mut app = App::new();
+    PstopConfig::load().apply_to(&mut app);
+    app.alerts.enabled = true;
+    app.alerts.headless = true;
+
+    for error in app.alerts.errors() {
+        eprintln!("pstop: {}", error);
+    }
+    let checked = app.alerts.rules.iter().filter(|r| r.condition().is_ok()).count();
+    if checked == 0 {
+        bail!("no alert rules to check; add alert.<name>.when=... lines to pstoprc");
+    }
+
+    let delay = Duration::from_millis(interval_ms.unwrap_or(app.update_interval_ms));
+    eprintln!("pstop: checking {} alert rule(s) every {}ms (Ctrl+C to stop)", checked, delay.as_millis());
+    let mut collector = Collector::new();
+    loop {
+        let started = Instant::now();
+        collector.refresh(&mut app);
+        std::thread::sleep(delay.saturating_sub(started.elapsed()));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::system::fake::FakeSource;
+
+    fn rule(name: &str, fields: &[(&str, &str)]) -> AlertRule {
+        let mut rule = AlertRule::named(name);
+        for (key, value) in fields {
+            rule.set(key, value);
+        }
+        rule
+    }
+
+    fn set_cpu(app: &mut App, percent: f32) {
+        for core in &mut app.cpu_info.cores {
+            core.usage_percent = percent;
+        }
+    }
+
+    #[test]
+    fn parse_rules_and_config_lines() {
+        let r = rule("hot", &[("when", "cpu >= 90%"), ("for", "30s"), ("clear", "80"), ("actions", "bell, log")]);
+        assert!(matches!(r.condition(), Ok(Condition::Metric(Metric::Cpu, Cmp::Ge, v)) if *v == 90.0));
+        assert_eq!(r.clear_at, Some(80.0));
+        assert_eq!(r.actions, vec![Action::Bell, Action::Log]);
+        assert_eq!(
+            r.config_lines(),
+            ["alert.hot.when=cpu >= 90%", "alert.hot.for=30s", "alert.hot.clear=80", "alert.hot.actions=bell, log"]
+        );
+        // A misspelled action disables the rule and is written back as typed
+        let typo = rule("hot", &[("when", "cpu > 90"), ("actions", "bell, log,nope")]);
+        assert_eq!(typo.condition().unwrap_err(), "actions: unknown action 'nope' (expected banner, bell, log, command)");
+        assert!(typo.actions.is_empty());
+        assert_eq!(typo.config_lines()[1], "alert.hot.actions=bell, log,nope");
+        assert_eq!(rule("x", &[("when", "cpu > 90")]).actions, vec![Action::Banner]);
+
+        assert!(matches!(rule("big", &[("when", "any res > 4G")]).condition(), Ok(Condition::Any(_))));
+        assert!(matches!(rule("gone", &[("when", "none agent")]).condition(), Ok(Condition::None(_))));
+        assert!(rule("x", &[("when", "net_rx > 100M")]).condition().is_ok());
+
+        assert!(rule("x", &[("when", "disk > 5")]).condition().unwrap_err().starts_with("unknown metric 'disk'"));
+        assert_eq!(rule("x", &[("when", "cpu > lots")]).condition().unwrap_err(), "'lots' is not a number");
+        assert!(rule("x", &[("when", "any")]).condition().is_err());
+        assert!(rule("x", &[("when", "any res > 1G"), ("clear", "1")]).condition().is_err());
+        assert_eq!(rule("x", &[]).condition().unwrap_err(), "no 'when' condition");
+    }
+
+    #[test]
+    fn sustain_and_hysteresis() {
+        let mut app = FakeSource::basic_app();
+        let mut alerts = Alerts {
+            rules: vec![rule("hot", &[("when", "cpu > 90"), ("for", "10s"), ("clear", "80"), ("clear_for", "5s")])],
+            ..Default::default()
+        };
+
+        set_cpu(&mut app, 95.0);
+        assert!(alerts.evaluate(&app, 0).is_empty());
+        // A dip below the threshold restarts the sustain timer
+        set_cpu(&mut app, 85.0);
+        assert!(alerts.evaluate(&app, 4_000).is_empty());
+        set_cpu(&mut app, 95.0);
+        assert!(alerts.evaluate(&app, 5_000).is_empty());
+        assert!(alerts.evaluate(&app, 14_000).is_empty());
+        let fired = alerts.evaluate(&app, 15_000);
+        assert_eq!(fired.len(), 1);
+        assert!(fired[0].firing);
+        assert_eq!(fired[0].message, "cpu 95.0% > 90.0% for 10s");
+        assert_eq!(alerts.firing(), ["hot"]);
+
+        // Between the clear line and the threshold it keeps firing
+        set_cpu(&mut app, 85.0);
+        assert!(alerts.evaluate(&app, 20_000).is_empty());
+        // Below the clear line it resolves after clear_for
+        set_cpu(&mut app, 50.0);
+        assert!(alerts.evaluate(&app, 30_000).is_empty());
+        assert_eq!(alerts.firing(), ["hot"]);
+        let resolved = alerts.evaluate(&app, 35_000);
+        assert_eq!(resolved.len(), 1);
+        assert!(!resolved[0].firing);
+        assert_eq!(resolved[0].message, "cpu back to 50.0%");
+        assert!(alerts.firing().is_empty());
+    }
+
+    #[test]
+    fn process_rules() {
+        let mut app = FakeSource::basic_app();
+        let mut alerts = Alerts {
+            rules: vec![
+                rule("big", &[("when", "any res > 1G")]),
+                rule("pg_gone", &[("when", "none name == postgres")]),
+                rule("agent_gone", &[("when", "none agent")]),
+            ],
+            ..Default::default()
+        };
+        let events = alerts.evaluate(&app, 0);
+        let names: Vec<&str> = events.iter().map(|e| e.rule.as_str()).collect();
+        assert_eq!(names, ["big", "agent_gone"]);
+        assert_eq!(events[0].message, "firefox (3001) matches res > 1G");
+        assert_eq!(events[0].process, Some((3001, "firefox".to_string(), 2 * 1024 * 1024 * 1024)));
+        assert_eq!(events[1].message, "no process matches agent");
+
+        app.processes.retain(|p| p.name != "postgres" && p.name != "firefox");
+        let events = alerts.evaluate(&app, 1_000);
+        assert_eq!(events.len(), 2);
+        assert_eq!((events[0].rule.as_str(), events[0].firing), ("big", false));
+        assert_eq!(events[0].message, "no process matches res > 1G");
+        assert_eq!((events[1].rule.as_str(), events[1].firing), ("pg_gone", true));
+    }
+}
@@ -1,12 +1,25 @@
 use std::collections::{HashMap, HashSet};
 
+use crate::alerts::Alerts;
 use crate::color_scheme::{ColorScheme, ColorSchemeId};
+use crate::dashboard::Dashboard;
+use crate::export::ExportFormat;
+use crate::filter::{Filter, ParseError};
+use crate::governor::Governor;
+use crate::history::ProcessHistory;
+use crate::lifecycle::{EventKind, Lifecycle, LifecycleEvent};
+use crate::meters::{meters, Meter, MeterHistory};
+use crate::record::{PlaybackCommand, PlaybackStatus};
+use crate::system::backend::{self, HandleInfo, MemoryRegion, ProcessEnvironment, QueryError};
 use crate::system::cpu::CpuInfo;
+use crate::system::disk::{DiskDevice, DiskIoInfo};
+use crate::system::filesystem::FilesystemInfo;
 use crate::system::gpu::GpuProcessInfo;
 use crate::system::memory::MemoryInfo;
-use crate::system::netstat::ProcessNetBandwidth;
+use crate::system::netstat::{Connection, ListeningPort, ProcessNetBandwidth, TcpState};
 use crate::system::network::NetworkInfo;
 use crate::system::process::{ProcessInfo, ProcessSortField};
+use crate::views::SavedView;
 
 /// Which tab is active (htop Tab key switches between these)
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
@@ -15,6 +28,50 @@ pub enum ProcessTab {
     Io,    // I/O-focused view
     Net,   // Network connections view (real per-process connections)
     Gpu,   // GPU usage per process (GPU-agnostic via PDH)
+    Ports, // Listening TCP and bound UDP sockets with their owners
+}
+
+impl ProcessTab {
+    /// Lowercase name, for file names, exports and saved views
+    pub fn name(&self) -> &'static str {
+        match self {
+            Self::Main => "main",
+            Self::Io => "io",
+            Self::Net => "net",
+            Self::Gpu => "gpu",
+            Self::Ports => "ports",
+        }
+    }
+
+    pub fn parse(s: &str) -> Option<Self> {
+        [Self::Main, Self::Io, Self::Net, Self::Gpu, Self::Ports].into_iter().find(|t| t.name().eq_ignore_ascii_case(s))
+    }
+}
+
+/// Order of the open files/handles viewer (`s` cycles)
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum HandleSort {
+    Id,   // fd / handle number
+    Type, // grouped by type, numbers within
+    Name,
+}
+
+impl HandleSort {
+    pub fn label(&self) -> &'static str {
+        match self {
+            Self::Id => "number",
+            Self::Type => "type",
+            Self::Name => "name",
+        }
+    }
+
+    pub fn next(&self) -> Self {
+        match self {
+            Self::Id => Self::Type,
+            Self::Type => Self::Name,
+            Self::Name => Self::Id,
+        }
+    }
 }
 
 /// Which view/mode the app is currently in
@@ -31,6 +88,14 @@ pub enum AppMode {
     Environment, // e: show process details/environment
     Setup,       // F2: setup menu (column/display configuration)
     Handles,     // l: list open files/handles (lsof equivalent)
+    Views,       // v: saved views picker
+    Dashboard,   // D: full-screen dashboard of one process
+    Governor,    // A: governor policies and action history
+    Events,      // E: process start/exit log
+    MemoryMap,   // m: virtual memory regions of a process (pmap equivalent)
+    Connections, // Enter on the Net tab: sockets of a process (netstat equivalent)
+    Disks,       // O: per-device disk throughput, IOPS and utilisation (iostat equivalent)
+    Filesystems, // d: mounted filesystems with space and inode usage (df equivalent)
 }
 
 /// Main application state
@@ -47,6 +112,11 @@ pub struct App {
     pub cpu_info: CpuInfo,
     pub memory_info: MemoryInfo,
     pub network_info: NetworkInfo,
+    pub disk_io_info: DiskIoInfo,
+    /// Physical block devices over the last refresh (O overlay)
+    pub disks: Vec<DiskDevice>,
+    /// Mounted filesystems, by mount point (Disk usage meter, d overlay)
+    pub filesystems: Vec<FilesystemInfo>,
     pub processes: Vec<ProcessInfo>,
     pub filtered_processes: Vec<ProcessInfo>,
 
@@ -54,6 +124,8 @@ pub struct App {
     pub net_processes: Vec<ProcessNetBandwidth>,
     pub net_selected_index: usize,
     pub net_scroll_offset: usize,
+    /// Every socket with its endpoints, refreshed with the Net tab
+    pub connections: Vec<Connection>,
 
     // GPU per-process data (GPU tab)
     pub gpu_processes: Vec<GpuProcessInfo>,
@@ -64,6 +136,11 @@ pub struct App {
     pub gpu_selected_index: usize,
     pub gpu_scroll_offset: usize,
 
+    // Listening sockets with their owners (Ports tab)
+    pub ports: Vec<ListeningPort>,
+    pub ports_selected_index: usize,
+    pub ports_scroll_offset: usize,
+
     // Process table state
     pub selected_index: usize,
     pub scroll_offset: usize,
@@ -83,24 +160,66 @@ pub struct App {
     pub gpu_sort_field: ProcessSortField,
     pub gpu_sort_ascending: bool,
 
+    // Sorting (Ports tab)
+    pub ports_sort_field: ProcessSortField,
+    pub ports_sort_ascending: bool,
+
     // Search (F3) — transient, doesn't filter
     pub search_query: String,
     pub search_not_found: bool,
 
     // Filter (F4) — persistent filter, hides non-matches
     pub filter_query: String,
+    /// `filter_query` compiled; kept from the last valid query while the
+    /// current one has a parse error
+    pub filter: Filter,
+    /// Query `filter` was last compiled from
+    filter_compiled: String,
+    pub filter_error: Option<ParseError>,
 
     // User filter
     pub user_filter: Option<String>,
     pub available_users: Vec<String>,
     pub user_menu_index: usize,
 
+    // Saved views (v picker, --view)
+    pub saved_views: Vec<SavedView>,
+    pub view_menu_index: usize,
+    /// Name being typed for "Save current view" (None = not naming)
+    pub view_name_input: Option<String>,
+    /// View last applied, marked in the picker
+    pub active_view: Option<String>,
+
     // Process tagging
     pub tagged_pids: HashSet<u32>,
 
     // Follow process
     pub follow_pid: Option<u32>,
 
+    // Process dashboard (D): the watched PID and its samples
+    pub dashboard: Option<Dashboard>,
+
+    // Alert rules from the config file and their state
+    pub alerts: Alerts,
+
+    // Governor policies from the config file and what they've done
+    pub governor: Governor,
+    /// Rows scrolled back in the action history overlay (A)
+    pub governor_scroll: usize,
+
+    // Process event log overlay (E): its filter and scroll position
+    pub events_filter: String,
+    pub events_filter_editing: bool,
+    /// Only starts or only exits (Tab cycles)
+    pub events_kind: Option<EventKind>,
+    /// Rows scrolled back from the newest event
+    pub events_scroll: usize,
+    /// Also append each event here as it happens ("" = off)
+    pub event_log_path: String,
+    /// Set by the live UI only: replays, rewinds and the headless modes
+    /// never append to `event_log_path`
+    pub event_log_live: bool,
+
     // Tree view
     pub tree_view: bool,
     /// Collapsed PIDs in tree view (collapsed subtree roots)
@@ -139,6 +258,45 @@ pub struct App {
     // CPU affinity mode
     pub affinity_cpus: Vec<bool>, // CPU selection state (true = enabled)
 
+    // Handles view: snapshot taken when the viewer is opened, with its
+    // filter, order and scroll position
+    pub handles_pid: u32,
+    pub handles: Vec<HandleInfo>,
+    pub handles_filter: String,
+    pub handles_filter_editing: bool,
+    pub handles_sort: HandleSort,
+    pub handles_scroll: usize,
+
+    // Environment view (e): read when the viewer is opened (None while
+    // viewing recorded data), with its search and scroll position
+    pub environment_pid: u32,
+    pub environment: Option<ProcessEnvironment>,
+    pub environment_search: String,
+    pub environment_search_editing: bool,
+    /// Variables scrolled past
+    pub environment_scroll: usize,
+
+    // Memory map view (m): read when opened (None while viewing recorded
+    // data); Tab switches between regions and per-file totals
+    pub memory_map_pid: u32,
+    pub memory_map: Option<Result<Vec<MemoryRegion>, QueryError>>,
+    pub memory_map_by_file: bool,
+    pub memory_map_scroll: usize,
+
+    // Connections view (Enter on a Net tab row): the sockets of one process,
+    // or of all of them, filtered by state, port or address
+    pub connections_pid: u32,
+    pub connections_all: bool,
+    pub connections_filter: String,
+    pub connections_filter_editing: bool,
+    pub connections_scroll: usize,
+
+    // Disk I/O view (O): devices scrolled past
+    pub disks_scroll: usize,
+
+    // Filesystems view (d): mounts scrolled past
+    pub filesystems_scroll: usize,
+
     // Column visibility (F2 Setup menu)
     pub visible_columns: std::collections::HashSet<ProcessSortField>,
     pub setup_menu_index: usize,
@@ -147,8 +305,8 @@ pub struct App {
     pub setup_meter_col: usize,     // 0=left, 1=right, 2=available (Meters category)
     pub setup_available_index: usize, // Selected index in available meters list
     pub setup_meter_target: usize,  // 0=left, 1=right — target column for adding from available
-    pub left_meters: Vec<String>,   // Configurable left header meters
-    pub right_meters: Vec<String>,  // Configurable right header meters
+    pub left_meters: Vec<Meter>,    // Configurable left header meters
+    pub right_meters: Vec<Meter>,   // Configurable right header meters
 
     // Display options (F2 Setup → Display options) — full htop parity
     pub show_tree_by_default: bool,
@@ -164,7 +322,13 @@ pub struct App {
     pub show_thread_names: bool,        // Show custom thread names
     pub enable_mouse: bool,             // Mouse support on/off
     pub vim_keys: bool,                 // Vim-style keybindings (j/k/g/G/Ctrl-u/Ctrl-d)
+    pub highlight_changes: bool,        // Tint new processes, keep exited ones as ghost rows
+    pub highlight_changes_secs: u64,    // How long both last
     pub update_interval_ms: u64,        // Configurable refresh rate
+    pub rewind_minutes: u64,            // Live rewind history kept (0 = off)
+    pub rewind_max_mb: u64,             // Memory cap for the rewind ring
+    pub export_format: ExportFormat,    // Ctrl-S file format
+    pub export_dir: String,             // Ctrl-S target directory ("" = working dir)
 
     // Color scheme
     pub color_scheme_id: ColorSchemeId,
@@ -173,6 +337,21 @@ pub struct App {
     // Tick counter for refresh
     pub tick: u64,
 
+    // Last few ticks per PID (sparkline columns, average/peak sorts)
+    pub history: ProcessHistory,
+    // Recent CPU/memory/swap/network/GPU values for Graph-mode meters
+    pub meter_history: MeterHistory,
+    // PIDs with their start times, for new/exited highlighting
+    pub lifecycle: Lifecycle,
+
+    // Short confirmation shown in place of the F-key bar until it expires
+    pub footer_message: Option<(String, std::time::Instant)>,
+
+    // Replay (--replay) and live rewind ([/]): position shown in the header,
+    // key requests for the player or rewind buffer
+    pub playback: Option<PlaybackStatus>,
+    pub playback_command: Option<PlaybackCommand>,
+
     // Compact mode: minimal header for small screens/mobile
     pub compact_mode: bool,
 
@@ -198,15 +377,22 @@ impl App {
             should_quit: false,
             paused: false,
 
-            current_user: std::env::var("USERNAME").unwrap_or_default().to_lowercase(),
+            current_user: std::env::var("USERNAME")
+                .or_else(|_| std::env::var("USER"))
+                .unwrap_or_default()
+                .to_lowercase(),
 
             cpu_info: CpuInfo::default(),
             memory_info: MemoryInfo::default(),
             network_info: NetworkInfo::default(),
+            disk_io_info: DiskIoInfo::default(),
+            disks: Vec::new(),
+            filesystems: Vec::new(),
             processes: Vec::new(),
             filtered_processes: Vec::new(),
 
             net_processes: Vec::new(),
+            connections: Vec::new(),
             net_selected_index: 0,
             net_scroll_offset: 0,
 
@@ -217,6 +403,9 @@ impl App {
             gpu_shared_mem: 0,
             gpu_selected_index: 0,
             gpu_scroll_offset: 0,
+            ports: Vec::new(),
+            ports_selected_index: 0,
+            ports_scroll_offset: 0,
 
             selected_index: 0,
             scroll_offset: 0,
@@ -232,17 +421,37 @@ impl App {
 
             gpu_sort_field: ProcessSortField::Cpu,  // GPU% by default
             gpu_sort_ascending: false,
+            ports_sort_field: ProcessSortField::Cpu, // PORT column
+            ports_sort_ascending: true,
 
             search_query: String::new(),
             search_not_found: false,
             filter_query: String::new(),
+            filter: Filter::All,
+            filter_compiled: String::new(),
+            filter_error: None,
 
             user_filter: None,
             available_users: Vec::new(),
             user_menu_index: 0,
 
+            saved_views: Vec::new(),
+            view_menu_index: 0,
+            view_name_input: None,
+            active_view: None,
+
             tagged_pids: HashSet::new(),
             follow_pid: None,
+            dashboard: None,
+            alerts: Alerts::default(),
+            governor: Governor::default(),
+            governor_scroll: 0,
+            events_filter: String::new(),
+            events_filter_editing: false,
+            events_kind: None,
+            events_scroll: 0,
+            event_log_path: String::new(),
+            event_log_live: false,
 
             tree_view: false,
             collapsed_pids: HashSet::new(),
@@ -268,6 +477,28 @@ impl App {
             kill_signal_index: 1, // Default to SIGKILL (force) on Windows
 
             affinity_cpus: Vec::new(),
+            handles_pid: 0,
+            handles: Vec::new(),
+            handles_filter: String::new(),
+            handles_filter_editing: false,
+            handles_sort: HandleSort::Id,
+            handles_scroll: 0,
+            environment_pid: 0,
+            environment: None,
+            environment_search: String::new(),
+            environment_search_editing: false,
+            environment_scroll: 0,
+            memory_map_pid: 0,
+            memory_map: None,
+            memory_map_by_file: false,
+            memory_map_scroll: 0,
+            connections_pid: 0,
+            connections_all: false,
+            connections_filter: String::new(),
+            connections_filter_editing: false,
+            connections_scroll: 0,
+            disks_scroll: 0,
+            filesystems_scroll: 0,
 
             // Default visible columns (htop default set)
             visible_columns: [
@@ -290,18 +521,8 @@ impl App {
             setup_meter_col: 0,
             setup_available_index: 0,
             setup_meter_target: 0,
-            left_meters: vec![
-                "AllCPUs".to_string(),
-                "Memory".to_string(),
-                "Swap".to_string(),
-                "Network".to_string(),
-            ],
-            right_meters: vec![
-                "AllCPUs".to_string(),
-                "Tasks".to_string(),
-                "Load average".to_string(),
-                "Uptime".to_string(),
-            ],
+            left_meters: meters(&["AllCPUs", "Memory", "Swap", "Network"]),
+            right_meters: meters(&["AllCPUs", "Tasks", "Load average", "Uptime"]),
             show_tree_by_default: false,
             highlight_base_name: true,
             shadow_other_users: false,
@@ -315,13 +536,28 @@ impl App {
             show_thread_names: false,
             enable_mouse: true,
             vim_keys: false,
+            highlight_changes: false,
+            highlight_changes_secs: crate::lifecycle::DEFAULT_HIGHLIGHT_SECS,
             update_interval_ms: 1500,
+            rewind_minutes: crate::rewind::DEFAULT_MINUTES,
+            rewind_max_mb: crate::rewind::DEFAULT_MAX_MB,
+            export_format: ExportFormat::Csv,
+            export_dir: String::new(),
 
             color_scheme_id: ColorSchemeId::Default,
             color_scheme: ColorScheme::from_id(ColorSchemeId::Default),
 
             tick: 0,
 
+            history: ProcessHistory::default(),
+            lifecycle: Lifecycle::default(),
+            meter_history: MeterHistory::default(),
+
+            footer_message: None,
+
+            playback: None,
+            playback_command: None,
+
             compact_mode: false,
 
             startup_first_frame_ms: 0,
@@ -333,6 +569,7 @@ impl App {
     pub fn sort_processes(&mut self) {
         let ascending = self.sort_ascending;
         let field = self.sort_field;
+        let history = &self.history;
 
         self.filtered_processes.sort_by(|a, b| {
             let ord = match field {
@@ -371,22 +608,130 @@ impl App {
                     let b_total = b.io_read_rate + b.io_write_rate;
                     a_total.total_cmp(&b_total)
                 }
+                // A PID without history yet sorts by its current value
+                ProcessSortField::CpuHist | ProcessSortField::CpuPeak => {
+                    let value = |p: &ProcessInfo| match history.get(p.pid) {
+                        Some(h) if field == ProcessSortField::CpuHist => h.cpu_avg(),
+                        Some(h) => h.cpu_peak(),
+                        None => p.cpu_usage,
+                    };
+                    value(a).total_cmp(&value(b))
+                }
+                ProcessSortField::ResHist | ProcessSortField::ResPeak => {
+                    let value = |p: &ProcessInfo| match history.get(p.pid) {
+                        Some(h) if field == ProcessSortField::ResHist => h.res_avg(),
+                        Some(h) => h.res_peak(),
+                        None => p.resident_mem,
+                    };
+                    value(a).cmp(&value(b))
+                }
             };
             if ascending { ord } else { ord.reverse() }
         });
     }
 
+    /// Add this tick to the per-PID and meter histories
+    pub fn record_history(&mut self) {
+        self.history.record(&self.processes);
+        self.meter_history.record(&self.cpu_info, &self.memory_info, &self.network_info, &self.disk_io_info, self.gpu_overall_usage);
+        if let Some(dashboard) = &mut self.dashboard {
+            dashboard.record(&self.processes);
+        }
+        let events = self.lifecycle.update(&self.processes, self.uptime_seconds, self.clock_ms(), self.highlight_ms());
+        if self.event_log_live && !self.event_log_path.is_empty() && !events.is_empty() {
+            if let Err(e) = crate::lifecycle::append_events(&self.event_log_path, &events.iter().collect::<Vec<_>>()) {
+                self.footer_message = Some((format!("Event log: {}", e), std::time::Instant::now()));
+            }
+        }
+    }
+
+    /// Wall-clock time of the tick on screen: the recorded time when
+    /// replaying a file
+    pub fn clock_ms(&self) -> i64 {
+        match &self.playback {
+            Some(status) if status.live_offset_ms.is_none() => status.timestamp_ms,
+            _ => chrono::Utc::now().timestamp_millis(),
+        }
+    }
+
+    /// How long new processes stay tinted and exited ones linger
+    pub fn highlight_ms(&self) -> i64 {
+        self.highlight_changes_secs as i64 * 1000
+    }
+
+    /// Whether a header meter called `name` is configured, in any mode
+    pub fn has_meter(&self, name: &str) -> bool {
+        self.left_meters.iter().chain(&self.right_meters).any(|m| m.name == name)
+    }
+
+    /// Forget the histories, when the next tick doesn't follow the last one
+    pub fn clear_history(&mut self) {
+        self.history.clear();
+        self.meter_history.clear();
+        if let Some(dashboard) = &mut self.dashboard {
+            dashboard.clear();
+        }
+        self.lifecycle.clear();
+    }
+
+    /// Check the alert rules against this tick and carry out their actions
+    pub fn check_alerts(&mut self) {
+        if !self.alerts.enabled || self.alerts.rules.is_empty() {
+            return;
+        }
+        let mut alerts = std::mem::take(&mut self.alerts);
+        let events = alerts.evaluate(self, chrono::Utc::now().timestamp_millis());
+        self.alerts = alerts;
+        crate::alerts::act(self, &events);
+    }
+
+    /// Apply the governor policies to this tick's processes
+    pub fn run_governor(&mut self) {
+        if !self.governor.enabled || self.governor.policies.is_empty() {
+            return;
+        }
+        let now = chrono::Utc::now().timestamp_millis();
+        let records = self.governor.tick(&self.processes, now, |pid, action| {
+            crate::governor::apply(backend::native(), pid, action)
+        });
+        if self.governor.headless {
+            for record in &records {
+                println!("{}", crate::governor::format_record(record));
+            }
+        } else if let Some(first) = records.first() {
+            let mut banner = format!(
+                "Governor {}: {} {} ({}) [{}]",
+                first.policy,
+                first.action,
+                first.name,
+                first.pid,
+                first.outcome.label()
+            );
+            if records.len() > 1 {
+                banner.push_str(&format!(" (+{} more, A for history)", records.len() - 1));
+            }
+            self.footer_message = Some((banner, std::time::Instant::now()));
+        }
+    }
+
     /// Apply user filter and F4 filter query to process list
     pub fn apply_filter(&mut self) {
+        self.compile_filter();
+
         // Build filtered list from processes — filters inline to avoid full clone
         let user_filter = self.user_filter.as_ref().map(|u| u.to_lowercase());
         let hide_kernel = self.hide_kernel_threads;
-        let filter_empty = self.filter_query.is_empty();
-        let query_lower = self.filter_query.to_lowercase();
-        let terms: Vec<&str> = if !filter_empty { query_lower.split('|').collect() } else { vec![] };
 
         self.filtered_processes.clear();
-        for p in &self.processes {
+        // Exited processes linger as ghost rows while highlighting is on,
+        // unless the PID is in the list (a rewound tick from before the exit)
+        let ghosts: Vec<&ProcessInfo> = if self.highlight_changes && !self.lifecycle.ghosts().is_empty() {
+            let live: HashSet<u32> = self.processes.iter().map(|p| p.pid).collect();
+            self.lifecycle.ghosts().iter().map(|g| &g.process).filter(|p| !live.contains(&p.pid)).collect()
+        } else {
+            Vec::new()
+        };
+        for p in self.processes.iter().chain(ghosts) {
             // User filter
             if let Some(ref u) = user_filter {
                 if p.user.to_lowercase() != *u {
@@ -403,21 +748,64 @@ impl App {
             }
 
             // F4 persistent filter
-            if !filter_empty {
-                let name_lower = p.name.to_lowercase();
-                let cmd_lower = p.command.to_lowercase();
-                let matches = terms.iter().any(|term| {
-                    let t = term.trim();
-                    if t.is_empty() { return false; }
-                    name_lower.contains(t) || cmd_lower.contains(t)
-                });
-                if !matches {
-                    continue;
-                }
+            if !self.filter.matches(p) {
+                continue;
             }
 
             self.filtered_processes.push(p.clone());
         }
+        self.build_ports();
+    }
+
+    /// Ports tab rows: the listening sockets whose owner passed the filters
+    /// above, or whose protocol and address contain a plain-text F4 term.
+    /// Sockets with no known owner are kept only while nothing is filtered.
+    fn build_ports(&mut self) {
+        let shown: HashSet<u32> = self.filtered_processes.iter().map(|p| p.pid).collect();
+        let unfiltered = matches!(self.filter, Filter::All) && self.user_filter.is_none();
+        let terms: &[String] = match &self.filter {
+            Filter::Text(terms) => terms,
+            _ => &[],
+        };
+        let owners: HashMap<u32, &ProcessInfo> = self.processes.iter().map(|p| (p.pid, p)).collect();
+        self.ports = self
+            .connections
+            .iter()
+            .filter(|c| c.is_listening())
+            .filter(|c| {
+                let socket = format!("{} {}", c.protocol_label(), c.local);
+                shown.contains(&c.pid)
+                    || (c.pid == 0 && unfiltered)
+                    || terms.iter().any(|t| socket.contains(t.as_str()))
+            })
+            .map(|c| {
+                let owner = owners.get(&c.pid);
+                ListeningPort {
+                    socket: c.clone(),
+                    name: owner.map(|p| p.name.clone()).unwrap_or_default(),
+                    user: owner.map(|p| p.user.clone()).unwrap_or_default(),
+                    command: owner.map(|p| p.command.clone()).unwrap_or_default(),
+                }
+            })
+            .collect();
+        self.sort_ports();
+    }
+
+    /// Recompile `filter_query` when it changed. A query that doesn't parse
+    /// (often one still being typed) sets `filter_error` and leaves the
+    /// previous filter in effect.
+    pub fn compile_filter(&mut self) {
+        if self.filter_query == self.filter_compiled {
+            return;
+        }
+        self.filter_compiled = self.filter_query.clone();
+        match Filter::parse(&self.filter_query) {
+            Ok(filter) => {
+                self.filter = filter;
+                self.filter_error = None;
+            }
+            Err(e) => self.filter_error = Some(e),
+        }
     }
 
     /// F3 search: find next process matching search_query and jump to it
@@ -510,7 +898,7 @@ impl App {
             .collect::<HashSet<_>>()
             .into_iter()
             .collect();
-        users.sort_by(|a, b| a.to_lowercase().cmp(&b.to_lowercase()));
+        users.sort_by_key(|a| a.to_lowercase());
         self.available_users = users;
     }
 
@@ -684,6 +1072,7 @@ impl App {
             ProcessTab::Main | ProcessTab::Io => self.filtered_processes.len(),
             ProcessTab::Net => self.net_processes.len(),
             ProcessTab::Gpu => self.gpu_processes.len(),
+            ProcessTab::Ports => self.ports.len(),
         }
     }
 
@@ -693,6 +1082,7 @@ impl App {
             ProcessTab::Main | ProcessTab::Io => &mut self.selected_index,
             ProcessTab::Net => &mut self.net_selected_index,
             ProcessTab::Gpu => &mut self.gpu_selected_index,
+            ProcessTab::Ports => &mut self.ports_selected_index,
         }
     }
 
@@ -702,20 +1092,231 @@ impl App {
             ProcessTab::Main | ProcessTab::Io => &mut self.scroll_offset,
             ProcessTab::Net => &mut self.net_scroll_offset,
             ProcessTab::Gpu => &mut self.gpu_scroll_offset,
+            ProcessTab::Ports => &mut self.ports_scroll_offset,
         }
     }
 
+    /// Ctrl-S: write the current view to a file and confirm in the footer
+    pub fn export_current_view(&mut self) {
+        let dir = if self.export_dir.is_empty() {
+            std::env::current_dir().unwrap_or_else(|_| ".".into())
+        } else {
+            std::path::PathBuf::from(&self.export_dir)
+        };
+        let message = match crate::export::export_view(self, &dir, self.export_format) {
+            Ok(path) => format!("Saved {}", path.display()),
+            Err(e) => format!("Export failed: {:#}", e),
+        };
+        self.footer_message = Some((message, std::time::Instant::now()));
+    }
+
+    /// Event log entries passing the overlay's filters, oldest first. A
+    /// filter that doesn't parse (yet) lets everything through.
+    pub fn shown_events(&self) -> Vec<&LifecycleEvent> {
+        let filter = Filter::parse(&self.events_filter).unwrap_or(Filter::All);
+        self.lifecycle
+            .log()
+            .iter()
+            .filter(|e| self.events_kind.is_none_or(|kind| e.kind == kind) && filter.matches(&e.process))
+            .collect()
+    }
+
+    /// `w` in the event log: append the entries shown to the log file
+    pub fn save_events(&mut self) {
+        let events = self.shown_events();
+        let message = match crate::lifecycle::append_events(&self.event_log_path, &events) {
+            Ok(path) => format!("Appended {} events to {}", events.len(), path.display()),
+            Err(e) => format!("Event log: {}", e),
+        };
+        self.footer_message = Some((message, std::time::Instant::now()));
+    }
+
+    /// `l`: list the selected process's open files/handles and open the viewer
+    pub fn open_handles(&mut self) {
+        if let Some(pid) = self.selected_live_process().map(|p| p.pid) {
+            self.read_handles(pid);
+            self.handles_scroll = 0;
+            self.handles_filter_editing = false;
+            self.mode = AppMode::Handles;
+        }
+    }
+
+    /// (Re-)list the open files/handles of `pid` for the viewer
+    pub fn read_handles(&mut self, pid: u32) {
+        self.handles_pid = pid;
+        // A recorded PID may belong to some other live process by now
+        self.handles = if self.viewing_history() { Vec::new() } else { backend::native().handles(pid) };
+    }
+
+    /// Handles passing the viewer's filter (case-insensitive, on type, name,
+    /// access and lock), in the viewer's order
+    pub fn shown_handles(&self) -> Vec<&HandleInfo> {
+        let needle = self.handles_filter.to_lowercase();
+        let mut shown: Vec<&HandleInfo> = self
+            .handles
+            .iter()
+            .filter(|h| {
+                needle.is_empty()
+                    || [&h.handle_type, &h.name, &h.access, &h.lock].iter().any(|f| f.to_lowercase().contains(&needle))
+            })
+            .collect();
+        match self.handles_sort {
+            HandleSort::Id => shown.sort_by_key(|h| h.id),
+            HandleSort::Type => shown.sort_by(|a, b| a.handle_type.cmp(&b.handle_type).then(a.id.cmp(&b.id))),
+            HandleSort::Name => shown.sort_by(|a, b| a.name.cmp(&b.name).then(a.id.cmp(&b.id))),
+        }
+        shown
+    }
+
+    /// `e`: read the selected process's environment and open the viewer
+    pub fn open_environment(&mut self) {
+        if let Some(pid) = self.selected_live_process().map(|p| p.pid) {
+            self.read_environment(pid);
+            self.environment_scroll = 0;
+            self.environment_search_editing = false;
+            self.mode = AppMode::Environment;
+        }
+    }
+
+    /// (Re-)read the environment of `pid` for the viewer
+    pub fn read_environment(&mut self, pid: u32) {
+        self.environment_pid = pid;
+        // A recorded PID may belong to some other live process by now
+        self.environment = (!self.viewing_history()).then(|| backend::native().environment(pid));
+    }
+
+    /// The process the environment viewer is showing, while it's in the list
+    pub fn environment_process(&self) -> Option<&ProcessInfo> {
+        self.processes.iter().find(|p| p.pid == self.environment_pid)
+    }
+
+    /// `m`: read the selected process's memory map and open the viewer
+    pub fn open_memory_map(&mut self) {
+        if let Some(pid) = self.selected_live_process().map(|p| p.pid) {
+            self.read_memory_map(pid);
+            self.memory_map_scroll = 0;
+            self.mode = AppMode::MemoryMap;
+        }
+    }
+
+    /// (Re-)read the memory map of `pid` for the viewer
+    pub fn read_memory_map(&mut self, pid: u32) {
+        self.memory_map_pid = pid;
+        // A recorded PID may belong to some other live process by now
+        self.memory_map = (!self.viewing_history()).then(|| backend::native().memory_map(pid));
+    }
+
+    /// Rows in the memory map viewer: regions, or files when grouped
+    pub fn memory_map_rows(&self) -> usize {
+        match &self.memory_map {
+            Some(Ok(regions)) if self.memory_map_by_file => backend::mapping_totals(regions).len(),
+            Some(Ok(regions)) => regions.len(),
+            _ => 0,
+        }
+    }
+
+    /// Enter on a Net tab row: open the connections of that process
+    pub fn open_connections(&mut self) {
+        if let Some(pid) = self.net_processes.get(self.net_selected_index).map(|p| p.pid) {
+            self.connections_pid = pid;
+            self.connections_all = false;
+            self.connections_filter_editing = false;
+            self.connections_scroll = 0;
+            self.mode = AppMode::Connections;
+        }
+    }
+
+    /// Sockets in the connections viewer, of the process or of all of them,
+    /// that match every word of the filter: a TCP state (`established`,
+    /// `listen`, ...), a local or remote port, or text in the protocol,
+    /// addresses or owner's name. Ordered by protocol, state and endpoints.
+    pub fn shown_connections(&self) -> Vec<&Connection> {
+        let terms: Vec<String> = self.connections_filter.split_whitespace().map(|t| t.to_lowercase()).collect();
+        let names: HashMap<u32, String> = if self.connections_all && !terms.is_empty() {
+            self.processes.iter().map(|p| (p.pid, p.name.to_lowercase())).collect()
+        } else {
+            HashMap::new()
+        };
+        let matches = |c: &Connection, term: &str| {
+            if let Some(state) = TcpState::ALL.iter().find(|s| s.label().eq_ignore_ascii_case(term)) {
+                c.state == Some(*state)
+            } else if let Ok(port) = term.trim_start_matches(':').parse::<u16>() {
+                c.local.port() == port || c.remote.port() == port
+            } else {
+                c.protocol_label().contains(term)
+                    || c.local.to_string().contains(term)
+                    || c.remote_label().contains(term)
+                    || names.get(&c.pid).is_some_and(|name| name.contains(term))
+            }
+        };
+        let mut shown: Vec<&Connection> = self
+            .connections
+            .iter()
+            .filter(|c| self.connections_all || c.pid == self.connections_pid)
+            .filter(|c| terms.iter().all(|term| matches(c, term)))
+            .collect();
+        shown.sort_by_key(|c| (c.protocol, c.state, c.local, c.remote));
+        shown
+    }
+
+    /// Environment variables whose name or value contains the search text
+    /// (case-insensitive), in the process's order
+    pub fn shown_environment(&self) -> Vec<&(String, String)> {
+        let vars = match self.environment.as_ref().map(|env| &env.vars) {
+            Some(Ok(vars)) => vars,
+            _ => return Vec::new(),
+        };
+        let needle = self.environment_search.to_lowercase();
+        vars.iter()
+            .filter(|(name, value)| {
+                needle.is_empty() || name.to_lowercase().contains(&needle) || value.to_lowercase().contains(&needle)
+            })
+            .collect()
+    }
+
+    /// True while showing recorded or rewound data: PIDs on screen may not be
+    /// the live processes with those PIDs, so process actions are disabled
+    pub fn viewing_history(&self) -> bool {
+        self.playback.is_some()
+    }
+
     /// Get the currently selected process
     pub fn selected_process(&self) -> Option<&ProcessInfo> {
         self.filtered_processes.get(self.selected_index)
     }
 
+    /// An exited process that highlight_changes keeps on screen. Ghost rows
+    /// are only drawn: batch, export and the process actions skip them, as
+    /// their PID may already belong to something else.
+    pub fn is_ghost(&self, pid: u32) -> bool {
+        self.highlight_changes && self.lifecycle.is_ghost(pid)
+    }
+
+    /// The selected process unless it's a ghost: what F7/F8, affinity,
+    /// tagging and the per-process viewers act on
+    pub fn selected_live_process(&self) -> Option<&ProcessInfo> {
+        self.selected_process().filter(|p| !self.is_ghost(p.pid))
+    }
+
+    /// The process F9 signals when nothing is tagged: the selected one, or
+    /// on the Ports tab the owner of the selected socket
+    pub fn kill_target(&self) -> Option<&ProcessInfo> {
+        match self.active_tab {
+            ProcessTab::Ports => {
+                let pid = self.ports.get(self.ports_selected_index).map(|p| p.socket.pid)?;
+                self.processes.iter().find(|p| p.pid == pid && pid != 0)
+            }
+            _ => self.selected_live_process(),
+        }
+    }
+
     /// Get the active sort field for the current tab
     pub fn active_sort_field(&self) -> ProcessSortField {
         match self.active_tab {
             ProcessTab::Main | ProcessTab::Io => self.sort_field,
             ProcessTab::Net => self.net_sort_field,
             ProcessTab::Gpu => self.gpu_sort_field,
+            ProcessTab::Ports => self.ports_sort_field,
         }
     }
 
@@ -725,6 +1326,7 @@ impl App {
             ProcessTab::Main | ProcessTab::Io => self.sort_ascending,
             ProcessTab::Net => self.net_sort_ascending,
             ProcessTab::Gpu => self.gpu_sort_ascending,
+            ProcessTab::Ports => self.ports_sort_ascending,
         }
     }
 
@@ -761,6 +1363,15 @@ impl App {
                 }
                 self.sort_gpu_processes();
             }
+            ProcessTab::Ports => {
+                if self.ports_sort_field == field {
+                    self.ports_sort_ascending = !self.ports_sort_ascending;
+                } else {
+                    self.ports_sort_field = field;
+                    self.ports_sort_ascending = false;
+                }
+                self.sort_ports();
+            }
         }
     }
 
@@ -814,9 +1425,32 @@ impl App {
         });
     }
 
+    /// Sort Ports tab rows by current ports_sort_field, by port within
+    /// equal keys
+    pub fn sort_ports(&mut self) {
+        let ascending = self.ports_sort_ascending;
+        let field = self.ports_sort_field;
+
+        self.ports.sort_by(|a, b| {
+            let ord = match field {
+                ProcessSortField::Pid => a.socket.pid.cmp(&b.socket.pid),
+                ProcessSortField::Status => a.socket.protocol_label().cmp(b.socket.protocol_label()),
+                ProcessSortField::Nice => a.socket.local.ip().cmp(&b.socket.local.ip()),
+                ProcessSortField::User => a.user.to_lowercase().cmp(&b.user.to_lowercase()),
+                ProcessSortField::Threads => a.name.to_lowercase().cmp(&b.name.to_lowercase()),
+                ProcessSortField::Command => a.command.to_lowercase().cmp(&b.command.to_lowercase()),
+                // Default: sort by port
+                _ => std::cmp::Ordering::Equal,
+            }
+            .then(a.socket.local.port().cmp(&b.socket.local.port()))
+            .then(a.socket.protocol_label().cmp(b.socket.protocol_label()));
+            if ascending { ord } else { ord.reverse() }
+        });
+    }
+
     /// Toggle tag on selected process
     pub fn toggle_tag_selected(&mut self) {
-        if let Some(proc) = self.selected_process() {
+        if let Some(proc) = self.selected_live_process() {
             let pid = proc.pid;
             if self.tagged_pids.contains(&pid) {
                 self.tagged_pids.remove(&pid);
@@ -828,11 +1462,11 @@ impl App {
 
     /// Tag selected process and all its children (htop 'c')
     pub fn tag_with_children(&mut self) {
-        if let Some(proc) = self.selected_process() {
+        if let Some(proc) = self.selected_live_process() {
             let root_pid = proc.pid;
             // Build parent→children map for O(n) traversal
             let mut children_map: HashMap<u32, Vec<u32>> = HashMap::new();
-            for p in &self.filtered_processes {
+            for p in self.filtered_processes.iter().filter(|p| !self.is_ghost(p.pid)) {
                 children_map.entry(p.ppid).or_default().push(p.pid);
             }
             // BFS from root
@@ -868,6 +1502,19 @@ impl App {
         }
     }
 
+    /// Open the dashboard on the followed process, or the selected one.
+    /// It charts the live list tick by tick, so not while viewing history.
+    pub fn open_dashboard(&mut self) {
+        if self.viewing_history() {
+            return;
+        }
+        let followed = self.follow_pid.and_then(|pid| self.processes.iter().find(|p| p.pid == pid));
+        if let Some(proc) = followed.or_else(|| self.selected_live_process()) {
+            self.dashboard = Some(Dashboard::new(proc));
+            self.mode = AppMode::Dashboard;
+        }
+    }
+
     /// If following a process, keep it selected after sort/filter
     pub fn follow_process(&mut self) {
         if let Some(follow) = self.follow_pid {
@@ -901,5 +1548,12 @@ impl App {
         } else if self.gpu_selected_index >= self.gpu_processes.len() {
             self.gpu_selected_index = self.gpu_processes.len() - 1;
         }
+        // Clamp Ports tab selection
+        if self.ports.is_empty() {
+            self.ports_selected_index = 0;
+            self.ports_scroll_offset = 0;
+        } else if self.ports_selected_index >= self.ports.len() {
+            self.ports_selected_index = self.ports.len() - 1;
+        }
     }
 }
@@ -0,0 +1,173 @@
+//! `pstop --batch`: plain-text snapshots on stdout.
+//!
+//! For cron jobs and CI logs, where there is no terminal for raw mode or the
+//! alternate screen. Each iteration prints a short header summary followed by
+//! the Main-tab process table, using the saved columns and sort order.
+
+use std::io::{self, Write};
+use std::time::{Duration, Instant};
+
+use anyhow::Result;
+
+use crate::app::App;
+use crate::config::PstopConfig;
+use crate::system::collector::Collector;
+use crate::system::memory::format_bytes;
+use crate::ui::header::{format_rate, format_uptime};
+use crate::ui::process_table::plain_text_table;
+
+/// Command-line settings for a batch run
+pub struct BatchOptions {
+    pub iterations: u64,
+    /// Sampling interval; None = the saved update interval
+    pub delay_ms: Option<u64>,
+    /// F4 filter: plain text (`|` separates alternatives) or an expression
+    pub filter: Option<String>,
+}
+
+/// Print `iterations` snapshots, `delay_ms` apart
+pub fn run(opts: &BatchOptions) -> Result<()> {
+    sample(opts.iterations, opts.delay_ms, opts.filter.as_deref(), |app, i| {
+        let mut text = render(app, chrono::Local::now());
+        if i + 1 < opts.iterations {
+            text.push('\n');
+        }
+        text
+    })
+}
+
+/// Refresh `iterations` times, `delay_ms` apart (default: the saved update
+/// interval), writing whatever `emit` returns for each sample to stdout.
+/// Each sample measures CPU over the preceding interval, so the first one
+/// appears after one delay as well. Shared by `--batch` and `--dump`.
+pub fn sample(
+    iterations: u64,
+    delay_ms: Option<u64>,
+    filter: Option<&str>,
+    mut emit: impl FnMut(&App, u64) -> String,
+) -> Result<()> {
+    let mut app = App::new();
+    PstopConfig::load().apply_to(&mut app);
+    if let Some(filter) = filter {
+        app.filter_query = filter.to_string();
+        app.compile_filter();
+        if let Some(e) = &app.filter_error {
+            anyhow::bail!("invalid filter: {}", e);
+        }
+    }
+    let delay = Duration::from_millis(delay_ms.unwrap_or(app.update_interval_ms));
+
+    let mut collector = Collector::new();
+    collector.refresh(&mut app);
+    let mut last = Instant::now();
+
+    let stdout = io::stdout();
+    for i in 0..iterations {
+        std::thread::sleep(delay.saturating_sub(last.elapsed()));
+        last = Instant::now();
+        collector.refresh(&mut app);
+
+        let text = emit(&app, i);
+        let mut out = stdout.lock();
+        match out.write_all(text.as_bytes()).and_then(|_| out.flush()) {
+            Ok(()) => {}
+            // `pstop --batch | head` closing the pipe is a normal way to stop
+            Err(e) if e.kind() == io::ErrorKind::BrokenPipe => return Ok(()),
+            Err(e) => return Err(e.into()),
+        }
+    }
+    Ok(())
+}
+
+/// One iteration: summary lines, a blank line, then the process table
+pub fn render(app: &App, now: chrono::DateTime<chrono::Local>) -> String {
+    let mem = &app.memory_info;
+    let net = &app.network_info;
+    let mut lines = vec![
+        format!(
+            "pstop - {}  up {}  load average: {:.2} {:.2} {:.2}",
+            now.format("%Y-%m-%d %H:%M:%S"),
+            format_uptime(app.uptime_seconds),
+            app.load_avg_1,
+            app.load_avg_5,
+            app.load_avg_15,
+        ),
+        format!(
+            "Tasks: {}, {} thr; {} running",
+            app.total_tasks, app.total_threads, app.running_tasks,
+        ),
+        format!(
+            "CPU: {:.1}%  Mem: {}/{}  Swp: {}/{}  Net: rx {}  tx {}",
+            app.cpu_info.total_usage,
+            format_bytes(mem.used_mem),
+            format_bytes(mem.total_mem),
+            format_bytes(mem.used_swap),
+            format_bytes(mem.total_swap),
+            format_rate(net.rx_bytes_per_sec),
+            format_rate(net.tx_bytes_per_sec),
+        ),
+    ];
+    if !app.filter_query.is_empty() {
+        lines.push(format!("Filter: {}", app.filter_query));
+    }
+    lines.push(String::new());
+    lines.extend(plain_text_table(app));
+
+    let mut out = lines.join("\n");
+    out.push('\n');
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::system::fake::FakeSource;
+    use crate::system::process::ProcessSortField;
+    use chrono::TimeZone;
+
+    fn fixture_app(setup: impl FnOnce(&mut App)) -> App {
+        let mut app = App::new();
+        setup(&mut app);
+        FakeSource::basic().refresh(&mut app);
+        app
+    }
+
+    #[test]
+    fn summary_and_table_follow_saved_columns() {
+        let now = chrono::Local.with_ymd_and_hms(2026, 3, 14, 2, 17, 45).unwrap();
+        let app = fixture_app(|app| {
+            app.visible_columns = [ProcessSortField::Pid, ProcessSortField::Cpu, ProcessSortField::Command]
+                .into_iter()
+                .collect();
+            app.sort_field = ProcessSortField::Pid;
+            app.sort_ascending = true;
+        });
+        let text = render(&app, now);
+        let lines: Vec<&str> = text.lines().collect();
+        assert!(lines[0].starts_with("pstop - 2026-03-14 02:17:45  up 1 day, 02:03:04"));
+        assert_eq!(lines[1], format!("Tasks: {}, {} thr; {} running", app.total_tasks, app.total_threads, app.running_tasks));
+        assert_eq!(lines[3], "");
+        assert_eq!(lines[4], "PID    CPU%  Command");
+        assert_eq!(lines.len(), 5 + app.processes.len());
+
+        // Rows in PID order, only the chosen columns, full command
+        let first = &app.filtered_processes[0];
+        assert_eq!(lines[5], format!("{:>6} {:>5.1} {}", first.pid, first.cpu_usage, first.name));
+        let pids: Vec<u32> = lines[5..].iter().map(|l| l.split_whitespace().next().unwrap().parse().unwrap()).collect();
+        assert!(pids.windows(2).all(|w| w[0] < w[1]));
+    }
+
+    #[test]
+    fn filter_limits_rows() {
+        let now = chrono::Local::now();
+        let app = fixture_app(|app| {
+            app.filter_query = "firefox|postgres".to_string();
+            app.show_full_path = true;
+        });
+        let text = render(&app, now);
+        assert!(text.contains("\nFilter: firefox|postgres\n"));
+        let rows: Vec<&str> = text.lines().skip_while(|l| !l.is_empty()).skip(2).collect();
+        assert_eq!(rows.len(), 5);
+        assert!(rows.iter().all(|r| r.contains("firefox") || r.contains("postgres")));
+    }
+}
@@ -105,6 +105,8 @@ pub struct ColorScheme {
     pub process_selected_bg: Color, // Selected row background
     pub process_selected_fg: Color, // Selected row fg
     pub process_shadow: Color,      // Other users' processes (shadow)
+    pub process_new_bg: Color,      // Recently started processes (highlight_changes)
+    pub process_ghost: Color,       // Recently exited processes (highlight_changes)
 
     // Column-specific colors
     pub col_pid: Color,
@@ -202,6 +204,8 @@ impl ColorScheme {
             process_selected_bg: Color::Indexed(236),
             process_selected_fg: Color::White,
             process_shadow: Color::DarkGray,
+            process_new_bg: Color::Indexed(22),
+            process_ghost: Color::Indexed(243),
 
             col_pid: Color::Green,
             col_user: Color::White,
@@ -282,6 +286,8 @@ impl ColorScheme {
             process_selected_bg: Color::White,
             process_selected_fg: Color::Black,
             process_shadow: Color::DarkGray,
+            process_new_bg: Color::Indexed(238),
+            process_ghost: Color::Indexed(240),
 
             col_pid: Color::White,
             col_user: Color::White,
@@ -362,6 +368,8 @@ impl ColorScheme {
             process_selected_bg: Color::Indexed(236),
             process_selected_fg: Color::Indexed(250),
             process_shadow: Color::Indexed(240),
+            process_new_bg: Color::Indexed(22),
+            process_ghost: Color::Indexed(238),
 
             col_pid: Color::Green,
             col_user: Color::Indexed(250),
@@ -442,6 +450,8 @@ impl ColorScheme {
             process_selected_bg: Color::Indexed(153),
             process_selected_fg: Color::Black,
             process_shadow: Color::Indexed(245),
+            process_new_bg: Color::Indexed(194),
+            process_ghost: Color::Indexed(250),
 
             col_pid: Color::Blue,
             col_user: Color::Black,
@@ -522,6 +532,8 @@ impl ColorScheme {
             process_selected_bg: Color::Cyan,
             process_selected_fg: Color::Blue,
             process_shadow: Color::Indexed(67),
+            process_new_bg: Color::Indexed(28),
+            process_ghost: Color::Indexed(60),
 
             col_pid: Color::Yellow,
             col_user: Color::White,
@@ -602,6 +614,8 @@ impl ColorScheme {
             process_selected_bg: Color::Indexed(250),
             process_selected_fg: Color::Black,
             process_shadow: Color::Indexed(245),
+            process_new_bg: Color::Indexed(194),
+            process_ghost: Color::Indexed(250),
 
             col_pid: Color::Indexed(25),
             col_user: Color::Black,
@@ -682,6 +696,8 @@ impl ColorScheme {
             process_selected_bg: Color::Indexed(236),
             process_selected_fg: Color::Indexed(226),
             process_shadow: Color::Indexed(240),
+            process_new_bg: Color::Indexed(28),
+            process_ghost: Color::Indexed(238),
 
             col_pid: Color::Indexed(46),
             col_user: Color::Indexed(252),
@@ -761,4 +777,15 @@ impl ColorScheme {
     pub fn footer_label_style(&self) -> Style {
         Style::default().fg(self.footer_label_fg).bg(self.footer_label_bg)
     }
+
+    /// How full a filesystem is: warning from 75%, critical from 90%
+    pub fn fill_color(&self, percent: f64) -> Color {
+        if percent >= 90.0 {
+            self.col_cpu_high
+        } else if percent >= 75.0 {
+            self.col_cpu_medium
+        } else {
+            self.col_cpu_low
+        }
+    }
 }
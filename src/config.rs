@@ -1,21 +1,44 @@
 //! pstop configuration persistence (htoprc-style key=value format)
 //!
-//! Saves/loads settings to `%APPDATA%/pstop/pstoprc` on Windows.
+//! Saves/loads settings to `%APPDATA%/pstop/pstoprc` on Windows and
+//! `$XDG_CONFIG_HOME/pstop/pstoprc` (default `~/.config`) elsewhere.
 
 use std::fs;
 use std::io::Write;
 use std::path::PathBuf;
 
 use crate::color_scheme::{ColorScheme, ColorSchemeId};
+use crate::export::ExportFormat;
+use crate::meters::{meters, Meter};
 use crate::system::process::ProcessSortField;
+use crate::alerts::AlertRule;
+use crate::governor::Policy;
+use crate::views::SavedView;
 
 /// Get the config file path: %APPDATA%/pstop/pstoprc
+#[cfg(windows)]
 fn config_path() -> Option<PathBuf> {
     std::env::var("APPDATA").ok().map(|appdata| {
         PathBuf::from(appdata).join("pstop").join("pstoprc")
     })
 }
 
+/// Get the config file path: $XDG_CONFIG_HOME/pstop/pstoprc or ~/.config/pstop/pstoprc
+#[cfg(not(windows))]
+fn config_path() -> Option<PathBuf> {
+    let base = std::env::var("XDG_CONFIG_HOME")
+        .ok()
+        .filter(|d| !d.is_empty())
+        .map(PathBuf::from)
+        .or_else(|| std::env::var("HOME").ok().map(|home| PathBuf::from(home).join(".config")))?;
+    Some(base.join("pstop").join("pstoprc"))
+}
+
+/// Directory holding pstoprc (alert logs default to it)
+pub fn config_dir() -> Option<PathBuf> {
+    config_path().and_then(|p| p.parent().map(PathBuf::from))
+}
+
 /// Persistable settings (subset of App state)
 pub struct PstopConfig {
     // Display options
@@ -35,7 +58,15 @@ pub struct PstopConfig {
     pub show_thread_names: bool,
     pub enable_mouse: bool,
     pub vim_keys: bool,
+    pub highlight_changes: bool,
+    pub highlight_changes_secs: u64,
+    pub event_log: String,
     pub update_interval_ms: u64,
+    pub rewind_minutes: u64,
+    pub rewind_max_mb: u64,
+    pub history_ticks: usize,
+    pub export_format: ExportFormat,
+    pub export_dir: String,
 
     // Color scheme
     pub color_scheme_id: ColorSchemeId,
@@ -48,8 +79,22 @@ pub struct PstopConfig {
     pub visible_columns: Vec<ProcessSortField>,
 
     // Meters
-    pub left_meters: Vec<String>,
-    pub right_meters: Vec<String>,
+    pub left_meters: Vec<Meter>,
+    pub right_meters: Vec<Meter>,
+
+    // Saved views, in the order they were created
+    pub views: Vec<SavedView>,
+
+    // Alert rules (hand-written; kept as they are on save)
+    pub alerts: Vec<AlertRule>,
+    pub alert_log: String,
+
+    // Governor policies (hand-written; kept as they are on save)
+    pub policies: Vec<Policy>,
+    /// The UI applies the policies only when this is set; `apply_to` leaves
+    /// the governor off so headless modes never act on it
+    pub governor_enabled: bool,
+    pub governor_dry_run: bool,
 }
 
 impl Default for PstopConfig {
@@ -71,23 +116,28 @@ impl Default for PstopConfig {
             show_thread_names: false,
             enable_mouse: true,
             vim_keys: false,
+            highlight_changes: false,
+            highlight_changes_secs: crate::lifecycle::DEFAULT_HIGHLIGHT_SECS,
+            event_log: String::new(),
             update_interval_ms: 1500,
+            rewind_minutes: crate::rewind::DEFAULT_MINUTES,
+            rewind_max_mb: crate::rewind::DEFAULT_MAX_MB,
+            history_ticks: crate::history::DEFAULT_TICKS,
+            export_format: ExportFormat::Csv,
+            export_dir: String::new(),
             color_scheme_id: ColorSchemeId::Default,
             sort_field: ProcessSortField::Cpu,
             sort_ascending: false,
-            visible_columns: ProcessSortField::all().to_vec(),
-            left_meters: vec![
-                "AllCPUs".to_string(),
-                "Memory".to_string(),
-                "Swap".to_string(),
-                "Network".to_string(),
-            ],
-            right_meters: vec![
-                "AllCPUs".to_string(),
-                "Tasks".to_string(),
-                "Load average".to_string(),
-                "Uptime".to_string(),
-            ],
+            // The history sparklines are opt-in
+            visible_columns: ProcessSortField::all().iter().copied().filter(|f| !f.is_history()).collect(),
+            left_meters: meters(&["AllCPUs", "Memory", "Swap", "Network"]),
+            right_meters: meters(&["AllCPUs", "Tasks", "Load average", "Uptime"]),
+            views: Vec::new(),
+            alerts: Vec::new(),
+            alert_log: String::new(),
+            policies: Vec::new(),
+            governor_enabled: false,
+            governor_dry_run: false,
         }
     }
 }
@@ -100,11 +150,14 @@ impl PstopConfig {
             None => return Self::default(),
         };
 
-        let content = match fs::read_to_string(&path) {
-            Ok(c) => c,
-            Err(_) => return Self::default(),
-        };
+        match fs::read_to_string(&path) {
+            Ok(content) => Self::parse(&content),
+            Err(_) => Self::default(),
+        }
+    }
 
+    /// Parse pstoprc text; unknown keys are ignored
+    pub fn parse(content: &str) -> Self {
         let mut cfg = Self::default();
 
         for line in content.lines() {
@@ -132,9 +185,37 @@ impl PstopConfig {
                     "show_thread_names" => cfg.show_thread_names = value == "1",
                     "enable_mouse" => cfg.enable_mouse = value == "1",
                     "vim_keys" => cfg.vim_keys = value == "1",
+                    "highlight_changes" => cfg.highlight_changes = value == "1",
+                    "event_log" => cfg.event_log = value.to_string(),
+                    "highlight_changes_secs" => {
+                        if let Ok(v) = value.parse::<u64>() {
+                            cfg.highlight_changes_secs = v.clamp(1, 600);
+                        }
+                    }
                     "update_interval_ms" => {
                         if let Ok(v) = value.parse::<u64>() {
-                            cfg.update_interval_ms = v.max(200).min(10000);
+                            cfg.update_interval_ms = v.clamp(200, 10000);
+                        }
+                    }
+                    "rewind_minutes" => {
+                        if let Ok(v) = value.parse::<u64>() {
+                            cfg.rewind_minutes = v.min(120);
+                        }
+                    }
+                    "history_ticks" => {
+                        if let Ok(v) = value.parse::<usize>() {
+                            cfg.history_ticks = v.clamp(2, 600);
+                        }
+                    }
+                    "export_format" => {
+                        if let Some(format) = ExportFormat::parse(value) {
+                            cfg.export_format = format;
+                        }
+                    }
+                    "export_dir" => cfg.export_dir = value.to_string(),
+                    "rewind_max_mb" => {
+                        if let Ok(v) = value.parse::<u64>() {
+                            cfg.rewind_max_mb = v.clamp(1, 1024);
                         }
                     }
                     "color_scheme" => {
@@ -164,23 +245,67 @@ impl PstopConfig {
                         }
                     }
                     "left_meters" => {
-                        let meters: Vec<String> = value.split(';')
-                            .map(|s| s.trim().to_string())
+                        let meters: Vec<Meter> = value.split(';')
+                            .map(str::trim)
                             .filter(|s| !s.is_empty())
+                            .map(Meter::from_config)
                             .collect();
                         if !meters.is_empty() {
                             cfg.left_meters = meters;
                         }
                     }
                     "right_meters" => {
-                        let meters: Vec<String> = value.split(';')
-                            .map(|s| s.trim().to_string())
+                        let meters: Vec<Meter> = value.split(';')
+                            .map(str::trim)
                             .filter(|s| !s.is_empty())
+                            .map(Meter::from_config)
                             .collect();
                         if !meters.is_empty() {
                             cfg.right_meters = meters;
                         }
                     }
+                    "alert_log" => cfg.alert_log = value.to_string(),
+                    // alert.<name>.<field>
+                    _ if key.starts_with("alert.") => {
+                        if let Some((name, field)) = key["alert.".len()..].rsplit_once('.') {
+                            let index = match cfg.alerts.iter().position(|r| r.name == name) {
+                                Some(i) => i,
+                                None => {
+                                    cfg.alerts.push(AlertRule::named(name));
+                                    cfg.alerts.len() - 1
+                                }
+                            };
+                            cfg.alerts[index].set(field, value);
+                        }
+                    }
+                    "governor_enabled" => cfg.governor_enabled = value == "1",
+                    "governor_dry_run" => cfg.governor_dry_run = value == "1",
+                    // policy.<name>.<field>
+                    _ if key.starts_with("policy.") => {
+                        if let Some((name, field)) = key["policy.".len()..].rsplit_once('.') {
+                            let index = match cfg.policies.iter().position(|p| p.name == name) {
+                                Some(i) => i,
+                                None => {
+                                    cfg.policies.push(Policy::named(name));
+                                    cfg.policies.len() - 1
+                                }
+                            };
+                            cfg.policies[index].set(field, value);
+                        }
+                    }
+                    // view.<name>.<field> (names may contain dots)
+                    _ if key.starts_with("view.") => {
+                        if let Some((name, field)) = key["view.".len()..].rsplit_once('.') {
+                            let index = match cfg.views.iter().position(|v| v.name == name) {
+                                Some(i) => i,
+                                None => {
+                                    cfg.views.push(SavedView::named(name));
+                                    cfg.views.len() - 1
+                                }
+                            };
+                            cfg.views[index].set(field, value);
+                        }
+                    }
                     _ => {} // Ignore unknown keys
                 }
             }
@@ -203,7 +328,7 @@ impl PstopConfig {
 
         let mut lines = Vec::new();
         lines.push("# pstop configuration file".to_string());
-        lines.push(format!("# Auto-generated — do not edit while pstop is running"));
+        lines.push("# Auto-generated — do not edit while pstop is running".to_string());
         lines.push(String::new());
 
         let b = |v: bool| if v { "1" } else { "0" };
@@ -224,7 +349,17 @@ impl PstopConfig {
         lines.push(format!("show_thread_names={}", b(self.show_thread_names)));
         lines.push(format!("enable_mouse={}", b(self.enable_mouse)));
         lines.push(format!("vim_keys={}", b(self.vim_keys)));
+        lines.push(format!("highlight_changes={}", b(self.highlight_changes)));
+        lines.push(format!("highlight_changes_secs={}", self.highlight_changes_secs));
+        if !self.event_log.is_empty() {
+            lines.push(format!("event_log={}", self.event_log));
+        }
         lines.push(format!("update_interval_ms={}", self.update_interval_ms));
+        lines.push(format!("rewind_minutes={}", self.rewind_minutes));
+        lines.push(format!("rewind_max_mb={}", self.rewind_max_mb));
+        lines.push(format!("history_ticks={}", self.history_ticks));
+        lines.push(format!("export_format={}", self.export_format.name()));
+        lines.push(format!("export_dir={}", self.export_dir));
         lines.push(format!("color_scheme={}", self.color_scheme_id as usize));
         
         // Sort field index
@@ -241,8 +376,33 @@ impl PstopConfig {
         lines.push(format!("visible_columns={}", col_indices.join(",")));
 
         // Meters
-        lines.push(format!("left_meters={}", self.left_meters.join(";")));
-        lines.push(format!("right_meters={}", self.right_meters.join(";")));
+        let meter_list = |meters: &[Meter]| meters.iter().map(Meter::config_value).collect::<Vec<_>>().join(";");
+        lines.push(format!("left_meters={}", meter_list(&self.left_meters)));
+        lines.push(format!("right_meters={}", meter_list(&self.right_meters)));
+
+        // Saved views
+        for view in &self.views {
+            lines.extend(view.config_lines());
+        }
+
+        // Alert rules
+        if !self.alert_log.is_empty() {
+            lines.push(format!("alert_log={}", self.alert_log));
+        }
+        for rule in &self.alerts {
+            lines.extend(rule.config_lines());
+        }
+
+        // Governor policies
+        if self.governor_enabled {
+            lines.push("governor_enabled=1".to_string());
+        }
+        if self.governor_dry_run {
+            lines.push("governor_dry_run=1".to_string());
+        }
+        for policy in &self.policies {
+            lines.extend(policy.config_lines());
+        }
 
         let content = lines.join("\n") + "\n";
         let mut file = fs::File::create(&path)
@@ -272,13 +432,27 @@ impl PstopConfig {
             show_thread_names: app.show_thread_names,
             enable_mouse: app.enable_mouse,
             vim_keys: app.vim_keys,
+            highlight_changes: app.highlight_changes,
+            highlight_changes_secs: app.highlight_changes_secs,
+            event_log: app.event_log_path.clone(),
             update_interval_ms: app.update_interval_ms,
+            rewind_minutes: app.rewind_minutes,
+            rewind_max_mb: app.rewind_max_mb,
+            history_ticks: app.history.ticks(),
+            export_format: app.export_format,
+            export_dir: app.export_dir.clone(),
             color_scheme_id: app.color_scheme_id,
             sort_field: app.sort_field,
             sort_ascending: app.sort_ascending,
             visible_columns: app.visible_columns.iter().cloned().collect(),
             left_meters: app.left_meters.clone(),
             right_meters: app.right_meters.clone(),
+            views: app.saved_views.clone(),
+            alerts: app.alerts.rules.clone(),
+            alert_log: app.alerts.log_path.clone(),
+            policies: app.governor.policies.clone(),
+            governor_enabled: app.governor.enabled,
+            governor_dry_run: app.governor.dry_run,
         }
     }
 
@@ -304,7 +478,15 @@ impl PstopConfig {
         app.show_thread_names = self.show_thread_names;
         app.enable_mouse = self.enable_mouse;
         app.vim_keys = self.vim_keys;
+        app.highlight_changes = self.highlight_changes;
+        app.highlight_changes_secs = self.highlight_changes_secs;
+        app.event_log_path = self.event_log.clone();
         app.update_interval_ms = self.update_interval_ms;
+        app.rewind_minutes = self.rewind_minutes;
+        app.rewind_max_mb = self.rewind_max_mb;
+        app.history.set_ticks(self.history_ticks);
+        app.export_format = self.export_format;
+        app.export_dir = self.export_dir.clone();
         app.color_scheme_id = self.color_scheme_id;
         app.color_scheme = ColorScheme::from_id(self.color_scheme_id);
         app.sort_field = self.sort_field;
@@ -312,5 +494,10 @@ impl PstopConfig {
         app.visible_columns = self.visible_columns.iter().cloned().collect();
         app.left_meters = self.left_meters.clone();
         app.right_meters = self.right_meters.clone();
+        app.saved_views = self.views.clone();
+        app.alerts.rules = self.alerts.clone();
+        app.alerts.log_path = self.alert_log.clone();
+        app.governor.policies = self.policies.clone();
+        app.governor.dry_run = self.governor_dry_run;
     }
 }
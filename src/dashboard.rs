@@ -0,0 +1,193 @@
+//! Process dashboard ('D'): one PID watched full-screen, with everything it
+//! did since the dashboard was opened.
+//!
+//! Unlike `history`, which keeps a short window for every process, this
+//! keeps up to `MAX_SAMPLES` ticks of a single process, so a long-running
+//! build or service can be watched for an hour or so without the numbers
+//! scrolling away. If the PID exits (or is reused by a different program)
+//! the samples stay on screen and recording stops.
+
+use std::collections::VecDeque;
+
+use crate::system::process::ProcessInfo;
+
+/// Ticks kept before the oldest ones are dropped
+pub const MAX_SAMPLES: usize = 3600;
+
+/// One tick of the watched process
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub struct DashSample {
+    pub cpu: f64,
+    pub resident_mem: u64,
+    /// Resident memory not shared with other processes
+    pub private_mem: u64,
+    pub threads: u32,
+    pub io_read_rate: f64,
+    pub io_write_rate: f64,
+}
+
+impl DashSample {
+    fn of(p: &ProcessInfo) -> Self {
+        Self {
+            cpu: p.cpu_usage as f64,
+            resident_mem: p.resident_mem,
+            private_mem: p.resident_mem.saturating_sub(p.shared_mem),
+            threads: p.threads,
+            io_read_rate: p.io_read_rate,
+            io_write_rate: p.io_write_rate,
+        }
+    }
+}
+
+/// Min / average / max of one series
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub struct Stats {
+    pub min: f64,
+    pub avg: f64,
+    pub max: f64,
+}
+
+/// The process on the dashboard and its samples, oldest first
+#[derive(Debug, Clone)]
+pub struct Dashboard {
+    pub pid: u32,
+    pub name: String,
+    pub command: String,
+    /// The PID is gone, or now belongs to another program
+    pub exited: bool,
+    samples: VecDeque<DashSample>,
+}
+
+impl Dashboard {
+    /// Start watching `process`, seeded with its current values
+    pub fn new(process: &ProcessInfo) -> Self {
+        let mut samples = VecDeque::new();
+        samples.push_back(DashSample::of(process));
+        Self {
+            pid: process.pid,
+            name: process.name.clone(),
+            command: process.command.clone(),
+            exited: false,
+            samples,
+        }
+    }
+
+    pub fn samples(&self) -> &VecDeque<DashSample> {
+        &self.samples
+    }
+
+    /// Append this tick's values, or mark the process as exited
+    pub fn record(&mut self, processes: &[ProcessInfo]) {
+        if self.exited {
+            return;
+        }
+        match processes.iter().find(|p| p.pid == self.pid) {
+            Some(p) if p.name == self.name => {
+                if self.samples.len() == MAX_SAMPLES {
+                    self.samples.pop_front();
+                }
+                self.samples.push_back(DashSample::of(p));
+            }
+            _ => self.exited = true,
+        }
+    }
+
+    /// One series, oldest first
+    pub fn series(&self, value: impl Fn(&DashSample) -> f64) -> Vec<f64> {
+        self.samples.iter().map(value).collect()
+    }
+
+    pub fn stats(&self, value: impl Fn(&DashSample) -> f64) -> Stats {
+        if self.samples.is_empty() {
+            return Stats::default();
+        }
+        let mut stats = Stats { min: f64::MAX, avg: 0.0, max: f64::MIN };
+        for v in self.samples.iter().map(value) {
+            stats.min = stats.min.min(v);
+            stats.max = stats.max.max(v);
+            stats.avg += v;
+        }
+        stats.avg /= self.samples.len() as f64;
+        stats
+    }
+
+    /// Drop the samples but keep watching (replay seeks)
+    pub fn clear(&mut self) {
+        self.samples.clear();
+    }
+}
+
+/// `pid` and its parents up to the root, `pid` first. Stops at a PID that
+/// isn't in the list or when the chain loops back on itself.
+pub fn ancestry(processes: &[ProcessInfo], pid: u32) -> Vec<&ProcessInfo> {
+    let mut chain: Vec<&ProcessInfo> = Vec::new();
+    let mut next = Some(pid);
+    while let Some(p) = next.and_then(|pid| processes.iter().find(|p| p.pid == pid)) {
+        if chain.iter().any(|c| c.pid == p.pid) {
+            break;
+        }
+        chain.push(p);
+        next = (p.ppid != p.pid && p.ppid != 0).then_some(p.ppid);
+    }
+    chain
+}
+
+/// Direct children and all descendants of `pid`
+pub fn child_counts(processes: &[ProcessInfo], pid: u32) -> (usize, usize) {
+    let direct = processes.iter().filter(|p| p.ppid == pid && p.pid != pid).count();
+    let mut total = 0;
+    let mut frontier = vec![pid];
+    let mut seen = vec![pid];
+    while let Some(parent) = frontier.pop() {
+        for p in processes.iter().filter(|p| p.ppid == parent) {
+            if seen.contains(&p.pid) {
+                continue;
+            }
+            seen.push(p.pid);
+            frontier.push(p.pid);
+            total += 1;
+        }
+    }
+    (direct, total)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::system::fake::FakeSource;
+
+    #[test]
+    fn samples_stats_and_exit() {
+        let app = FakeSource::basic_app();
+        let mut procs = app.processes.clone();
+        let idx = procs.iter().position(|p| p.pid == 4100).unwrap();
+
+        let mut dash = Dashboard::new(&procs[idx]);
+        for cpu in [10.0, 50.0] {
+            procs[idx].cpu_usage = cpu;
+            dash.record(&procs);
+        }
+        let stats = dash.stats(|s| s.cpu);
+        assert_eq!(dash.samples().len(), 3);
+        assert_eq!((stats.min, stats.avg, stats.max), (10.0, 155.0 / 3.0, 95.0));
+        assert_eq!(dash.series(|s| s.threads as f64), vec![16.0; 3]);
+
+        // A reused PID counts as an exit; the samples stay
+        procs[idx].name = "make".to_string();
+        dash.record(&procs);
+        assert!(dash.exited);
+        procs[idx].name = "cargo".to_string();
+        dash.record(&procs);
+        assert_eq!(dash.samples().len(), 3);
+    }
+
+    #[test]
+    fn ancestry_and_children() {
+        let app = FakeSource::basic_app();
+        let chain: Vec<u32> = ancestry(&app.processes, 2210).iter().map(|p| p.pid).collect();
+        assert_eq!(chain, vec![2210, 1202, 1201, 733, 1]);
+        assert_eq!(child_counts(&app.processes, 1202), (3, 3));
+        assert_eq!(child_counts(&app.processes, 733), (1, 5));
+        assert!(ancestry(&app.processes, 99999).is_empty());
+    }
+}
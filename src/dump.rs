@@ -0,0 +1,328 @@
+//! `pstop --dump`: machine-readable snapshots for scripts and tooling.
+//!
+//! Formats (`--format`):
+//!   - `json`   one object per sample; several samples (`--count N`) form an array
+//!   - `ndjson` one object per sample, one per line
+//!   - `csv`    one row per process per sample, led by the sample timestamp
+//!
+//! The schema is versioned by `SCHEMA_VERSION`. Every JSON object carries
+//! `"schema": "pstop.dump"` and `"version"`; the CSV header row is fixed.
+//! Fields may be added within a version, but never renamed or removed.
+//! CSV holds the process rows only; the system data (per-core CPU, memory,
+//! network, load) is in the JSON formats.
+//!
+//! JSON object layout (version 1):
+//!
+//! ```text
+//! { schema, version, timestamp_ms, uptime_seconds, load_avg: [1, 5, 15],
+//!   tasks:   { total, running, sleeping, threads },
+//!   cpu:     { brand, physical_cores, logical_cores, total_usage,
+//!              user_frac, kernel_frac, dpc_frac, interrupt_frac,
+//!              cores: [{ id, usage_percent, user_frac, kernel_frac,
+//!                        dpc_frac, interrupt_frac, frequency_mhz }] },
+//!   memory:  { total_mem, used_mem, free_mem, cached_mem, buffered_mem,
+//!              total_swap, used_swap, free_swap },          (bytes)
+//!   network: { rx_bytes_per_sec, tx_bytes_per_sec, total_rx, total_tx },
+//!   processes: [{ pid, ppid, name, command, user, status, priority, nice,
+//!                 virtual_mem, resident_mem, shared_mem, cpu_usage, mem_usage,
+//!                 run_time, cpu_time_100ns, threads, io_read_rate, io_write_rate }] }
+//! ```
+
+use anyhow::Result;
+
+use crate::app::App;
+use crate::batch;
+use crate::system::process::ProcessInfo;
+
+/// Bump when a field is renamed or removed (adding fields is compatible)
+pub const SCHEMA_VERSION: u32 = 1;
+
+/// Process columns, in CSV order (the JSON keys use the same names)
+pub const PROCESS_FIELDS: &[&str] = &[
+    "pid", "ppid", "name", "command", "user", "status", "priority", "nice",
+    "virtual_mem", "resident_mem", "shared_mem", "cpu_usage", "mem_usage",
+    "run_time", "cpu_time_100ns", "threads", "io_read_rate", "io_write_rate",
+];
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DumpFormat {
+    Json,
+    Ndjson,
+    Csv,
+}
+
+impl DumpFormat {
+    pub fn parse(s: &str) -> Option<Self> {
+        match s.to_ascii_lowercase().as_str() {
+            "json" => Some(Self::Json),
+            "ndjson" | "jsonl" => Some(Self::Ndjson),
+            "csv" => Some(Self::Csv),
+            _ => None,
+        }
+    }
+}
+
+/// Command-line settings for a dump
+pub struct DumpOptions {
+    pub format: DumpFormat,
+    pub count: u64,
+    /// Sampling interval; None = the saved update interval
+    pub interval_ms: Option<u64>,
+}
+
+pub fn run(opts: &DumpOptions) -> Result<()> {
+    let count = opts.count;
+    batch::sample(count, opts.interval_ms, None, |app, i| {
+        let now = chrono::Utc::now().timestamp_millis();
+        let last = i + 1 == count;
+        match opts.format {
+            DumpFormat::Json if count == 1 => format!("{}\n", snapshot_json(app, now)),
+            DumpFormat::Json => {
+                let open = if i == 0 { "[\n" } else { "" };
+                let close = if last { "\n]\n" } else { ",\n" };
+                format!("{}{}{}", open, snapshot_json(app, now), close)
+            }
+            DumpFormat::Ndjson => format!("{}\n", snapshot_json(app, now)),
+            DumpFormat::Csv => {
+                let mut out = if i == 0 { csv_header() } else { String::new() };
+                out.push_str(&processes_csv(app, now));
+                out
+            }
+        }
+    })
+}
+
+// ── JSON ────────────────────────────────────────────────────────────────
+
+/// JSON string literal with the required escapes
+pub fn json_str(s: &str) -> String {
+    let mut out = String::with_capacity(s.len() + 2);
+    out.push('"');
+    for c in s.chars() {
+        match c {
+            '"' => out.push_str("\\\""),
+            '\\' => out.push_str("\\\\"),
+            '\n' => out.push_str("\\n"),
+            '\r' => out.push_str("\\r"),
+            '\t' => out.push_str("\\t"),
+            c if (c as u32) < 0x20 => out.push_str(&format!("\\u{:04x}", c as u32)),
+            c => out.push(c),
+        }
+    }
+    out.push('"');
+    out
+}
+
+/// JSON number; NaN and infinities (not valid JSON) become null. Takes f32
+/// as-is so it prints at f32 precision ("4.7619047", not "4.761904716491699").
+pub fn json_num<T: Into<f64> + std::fmt::Display + Copy>(v: T) -> String {
+    if v.into().is_finite() {
+        format!("{}", v)
+    } else {
+        "null".to_string()
+    }
+}
+
+/// `PROCESS_FIELDS` values for one process (numbers unquoted, text raw)
+pub fn process_values(p: &ProcessInfo) -> [String; 18] {
+    [
+        p.pid.to_string(),
+        p.ppid.to_string(),
+        p.name.clone(),
+        p.command.clone(),
+        p.user.clone(),
+        p.status.symbol().to_string(),
+        p.priority.to_string(),
+        p.nice.to_string(),
+        p.virtual_mem.to_string(),
+        p.resident_mem.to_string(),
+        p.shared_mem.to_string(),
+        json_num(p.cpu_usage),
+        json_num(p.mem_usage),
+        p.run_time.to_string(),
+        p.cpu_time_100ns.to_string(),
+        p.threads.to_string(),
+        json_num(p.io_read_rate),
+        json_num(p.io_write_rate),
+    ]
+}
+
+/// Indices of the text fields in `PROCESS_FIELDS` (quoted in JSON)
+pub const TEXT_FIELDS: &[usize] = &[2, 3, 4, 5];
+
+/// One process as a JSON object
+pub fn process_json(p: &ProcessInfo) -> String {
+    let fields: Vec<String> = process_values(p)
+        .iter()
+        .enumerate()
+        .map(|(i, v)| {
+            let value = if TEXT_FIELDS.contains(&i) { json_str(v) } else { v.clone() };
+            format!("\"{}\":{}", PROCESS_FIELDS[i], value)
+        })
+        .collect();
+    format!("{{{}}}", fields.join(","))
+}
+
+/// All processes, ordered by PID so consecutive dumps diff cleanly
+fn processes_by_pid(app: &App) -> Vec<&ProcessInfo> {
+    let mut procs: Vec<&ProcessInfo> = app.processes.iter().collect();
+    procs.sort_by_key(|p| p.pid);
+    procs
+}
+
+/// One sample as a single-line JSON object
+pub fn snapshot_json(app: &App, timestamp_ms: i64) -> String {
+    let cpu = &app.cpu_info;
+    let cores: Vec<String> = cpu
+        .cores
+        .iter()
+        .map(|c| {
+            format!(
+                "{{\"id\":{},\"usage_percent\":{},\"user_frac\":{},\"kernel_frac\":{},\"dpc_frac\":{},\"interrupt_frac\":{},\"frequency_mhz\":{}}}",
+                c.id,
+                json_num(c.usage_percent),
+                json_num(c.user_frac),
+                json_num(c.kernel_frac),
+                json_num(c.dpc_frac),
+                json_num(c.interrupt_frac),
+                c.frequency_mhz,
+            )
+        })
+        .collect();
+    let m = &app.memory_info;
+    let n = &app.network_info;
+    let processes: Vec<String> = processes_by_pid(app).into_iter().map(process_json).collect();
+
+    format!(
+        concat!(
+            "{{\"schema\":\"pstop.dump\",\"version\":{},\"timestamp_ms\":{},\"uptime_seconds\":{},",
+            "\"load_avg\":[{},{},{}],",
+            "\"tasks\":{{\"total\":{},\"running\":{},\"sleeping\":{},\"threads\":{}}},",
+            "\"cpu\":{{\"brand\":{},\"physical_cores\":{},\"logical_cores\":{},\"total_usage\":{},",
+            "\"user_frac\":{},\"kernel_frac\":{},\"dpc_frac\":{},\"interrupt_frac\":{},\"cores\":[{}]}},",
+            "\"memory\":{{\"total_mem\":{},\"used_mem\":{},\"free_mem\":{},\"cached_mem\":{},\"buffered_mem\":{},",
+            "\"total_swap\":{},\"used_swap\":{},\"free_swap\":{}}},",
+            "\"network\":{{\"rx_bytes_per_sec\":{},\"tx_bytes_per_sec\":{},\"total_rx\":{},\"total_tx\":{}}},",
+            "\"processes\":[{}]}}",
+        ),
+        SCHEMA_VERSION,
+        timestamp_ms,
+        app.uptime_seconds,
+        json_num(app.load_avg_1),
+        json_num(app.load_avg_5),
+        json_num(app.load_avg_15),
+        app.total_tasks,
+        app.running_tasks,
+        app.sleeping_tasks,
+        app.total_threads,
+        json_str(&cpu.brand),
+        cpu.physical_cores,
+        cpu.logical_cores,
+        json_num(cpu.total_usage),
+        json_num(app.cpu_user_frac),
+        json_num(app.cpu_kernel_frac),
+        json_num(app.cpu_dpc_frac),
+        json_num(app.cpu_interrupt_frac),
+        cores.join(","),
+        m.total_mem,
+        m.used_mem,
+        m.free_mem,
+        m.cached_mem,
+        m.buffered_mem,
+        m.total_swap,
+        m.used_swap,
+        m.free_swap,
+        json_num(n.rx_bytes_per_sec),
+        json_num(n.tx_bytes_per_sec),
+        n.total_rx,
+        n.total_tx,
+        processes.join(","),
+    )
+}
+
+// ── CSV ─────────────────────────────────────────────────────────────────
+
+/// CSV field, quoted when it contains a separator, quote or line break
+pub fn csv_field(s: &str) -> String {
+    if s.contains([',', '"', '\n', '\r']) {
+        format!("\"{}\"", s.replace('"', "\"\""))
+    } else {
+        s.to_string()
+    }
+}
+
+fn csv_header() -> String {
+    format!("timestamp_ms,{}\n", PROCESS_FIELDS.join(","))
+}
+
+fn processes_csv(app: &App, timestamp_ms: i64) -> String {
+    let mut out = String::new();
+    for p in processes_by_pid(app) {
+        let values: Vec<String> = process_values(p).iter().map(|v| csv_field(v)).collect();
+        out.push_str(&format!("{},{}\n", timestamp_ms, values.join(",")));
+    }
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::system::fake::FakeSource;
+
+    #[test]
+    fn json_carries_header_and_every_process() {
+        let mut app = FakeSource::basic_app();
+        app.processes[0].command = "say \"hi\"\\\n".to_string();
+        app.load_avg_1 = f64::NAN;
+        let json = snapshot_json(&app, 1_700_000_000_000);
+
+        assert!(json.starts_with("{\"schema\":\"pstop.dump\",\"version\":1,\"timestamp_ms\":1700000000000,"));
+        assert!(!json.contains('\n'));
+        assert!(json.contains("\"load_avg\":[null,"));
+        assert!(json.contains(&format!("\"logical_cores\":{}", app.cpu_info.logical_cores)));
+        assert!(json.contains("\"cores\":[{\"id\":0,"));
+        assert!(json.contains(&format!("\"total_mem\":{}", app.memory_info.total_mem)));
+        assert!(json.contains("\"command\":\"say \\\"hi\\\"\\\\\\n\""));
+        assert_eq!(json.matches("\"cpu_time_100ns\":").count(), app.processes.len());
+        assert_eq!(json.matches("\"frequency_mhz\":").count(), app.cpu_info.cores.len());
+        // Balanced braces and brackets outside strings
+        let (mut depth, mut in_str, mut escaped) = (0i32, false, false);
+        for c in json.chars() {
+            match (in_str, escaped, c) {
+                (true, true, _) => escaped = false,
+                (true, false, '\\') => escaped = true,
+                (_, false, '"') => in_str = !in_str,
+                (false, _, '{' | '[') => depth += 1,
+                (false, _, '}' | ']') => depth -= 1,
+                _ => {}
+            }
+            assert!(depth >= 0);
+        }
+        assert_eq!(depth, 0);
+    }
+
+    #[test]
+    fn csv_rows_are_quoted_and_ordered() {
+        let mut app = FakeSource::basic_app();
+        app.processes.reverse();
+        let last = app.processes.len() - 1;
+        app.processes[last].command = "a,b \"c\"".to_string();
+        let csv = format!("{}{}", csv_header(), processes_csv(&app, 42));
+        let lines: Vec<&str> = csv.lines().collect();
+
+        assert_eq!(lines[0], "timestamp_ms,pid,ppid,name,command,user,status,priority,nice,virtual_mem,resident_mem,shared_mem,cpu_usage,mem_usage,run_time,cpu_time_100ns,threads,io_read_rate,io_write_rate");
+        assert_eq!(lines.len(), 1 + app.processes.len());
+        let pids: Vec<u32> = lines[1..].iter().map(|l| l.split(',').nth(1).unwrap().parse().unwrap()).collect();
+        assert!(pids.windows(2).all(|w| w[0] < w[1]));
+        assert!(lines[1].starts_with("42,"));
+        assert!(csv.contains(",\"a,b \"\"c\"\"\","));
+    }
+
+    #[test]
+    fn format_names() {
+        assert_eq!(DumpFormat::parse("JSON"), Some(DumpFormat::Json));
+        assert_eq!(DumpFormat::parse("ndjson"), Some(DumpFormat::Ndjson));
+        assert_eq!(DumpFormat::parse("csv"), Some(DumpFormat::Csv));
+        assert_eq!(DumpFormat::parse("xml"), None);
+    }
+}
@@ -0,0 +1,282 @@
+//! Ctrl-S: save the current view to a timestamped file.
+//!
+//! Writes what the active tab shows — `filtered_processes` (Main and I/O,
+//! with the active sort, filters and tree order, minus the ghost rows of
+//! exited processes) or `net_processes` / `gpu_processes` — as CSV, JSON or
+//! the on-screen table in plain text.
+//! The format (`export_format`) and target directory (`export_dir`, default
+//! the working directory) come from the config file.
+
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use anyhow::{Context, Result};
+
+use crate::app::{App, ProcessTab};
+use crate::dump::{csv_field, json_num, json_str, process_values, PROCESS_FIELDS, TEXT_FIELDS};
+use crate::ui::process_table::plain_text_table;
+
+/// Bump when an exported field is renamed or removed
+pub const EXPORT_VERSION: u32 = 1;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ExportFormat {
+    Csv,
+    Json,
+    Text,
+}
+
+impl ExportFormat {
+    pub fn parse(s: &str) -> Option<Self> {
+        match s.to_ascii_lowercase().as_str() {
+            "csv" => Some(Self::Csv),
+            "json" => Some(Self::Json),
+            "txt" | "text" => Some(Self::Text),
+            _ => None,
+        }
+    }
+
+    /// Config value and file extension
+    pub fn name(&self) -> &'static str {
+        match self {
+            Self::Csv => "csv",
+            Self::Json => "json",
+            Self::Text => "txt",
+        }
+    }
+}
+
+/// A table cell: raw text, and whether JSON should quote it. Numbers go
+/// through `json_num`, so NaN and infinities are already `null`.
+type Cell = (String, bool);
+
+/// Column names and rows of the active tab, in display order
+fn table(app: &App) -> (Vec<&'static str>, Vec<Vec<Cell>>) {
+    match app.active_tab {
+        ProcessTab::Main | ProcessTab::Io => {
+            let mut columns = PROCESS_FIELDS.to_vec();
+            columns.extend(["depth", "is_last_child"]);
+            let rows = app
+                .filtered_processes
+                .iter()
+                .filter(|p| !app.is_ghost(p.pid))
+                .map(|p| {
+                    let mut row: Vec<Cell> = process_values(p)
+                        .into_iter()
+                        .enumerate()
+                        .map(|(i, v)| (v, TEXT_FIELDS.contains(&i)))
+                        .collect();
+                    row.push((p.depth.to_string(), false));
+                    row.push((p.is_last_child.to_string(), false));
+                    row
+                })
+                .collect();
+            (columns, rows)
+        }
+        ProcessTab::Net => {
+            let columns = vec!["pid", "name", "recv_bytes_per_sec", "send_bytes_per_sec", "connection_count"];
+            let rows = app
+                .net_processes
+                .iter()
+                .map(|p| {
+                    vec![
+                        (p.pid.to_string(), false),
+                        (p.name.clone(), true),
+                        (json_num(p.recv_bytes_per_sec), false),
+                        (json_num(p.send_bytes_per_sec), false),
+                        (p.connection_count.to_string(), false),
+                    ]
+                })
+                .collect();
+            (columns, rows)
+        }
+        ProcessTab::Gpu => {
+            let columns = vec!["pid", "name", "gpu_usage", "dedicated_mem", "shared_mem", "engine_type"];
+            let rows = app
+                .gpu_processes
+                .iter()
+                .map(|p| {
+                    vec![
+                        (p.pid.to_string(), false),
+                        (p.name.clone(), true),
+                        (json_num(p.gpu_usage), false),
+                        (p.dedicated_mem.to_string(), false),
+                        (p.shared_mem.to_string(), false),
+                        (p.engine_type.clone(), true),
+                    ]
+                })
+                .collect();
+            (columns, rows)
+        }
+        ProcessTab::Ports => {
+            let columns = vec!["port", "protocol", "address", "pid", "user", "name", "command"];
+            let rows = app
+                .ports
+                .iter()
+                .map(|p| {
+                    vec![
+                        (p.socket.local.port().to_string(), false),
+                        (p.socket.protocol_label().to_string(), true),
+                        (p.socket.local.ip().to_string(), true),
+                        (p.socket.pid.to_string(), false),
+                        (p.user.clone(), true),
+                        (p.name.clone(), true),
+                        (p.command.clone(), true),
+                    ]
+                })
+                .collect();
+            (columns, rows)
+        }
+    }
+}
+
+/// The active view in `format`
+pub fn render(app: &App, format: ExportFormat, now: chrono::DateTime<chrono::Local>) -> String {
+    let (sort_field, ascending) = (app.active_sort_field(), app.active_sort_ascending());
+    match format {
+        ExportFormat::Csv => {
+            let (columns, rows) = table(app);
+            let mut out = columns.join(",");
+            out.push('\n');
+            for row in rows {
+                let cells: Vec<String> = row.iter().map(|(v, _)| csv_field(v)).collect();
+                out.push_str(&cells.join(","));
+                out.push('\n');
+            }
+            out
+        }
+        ExportFormat::Json => {
+            let (columns, rows) = table(app);
+            let rows: Vec<String> = rows
+                .iter()
+                .map(|row| {
+                    let fields: Vec<String> = columns
+                        .iter()
+                        .zip(row)
+                        .map(|(name, (v, quoted))| {
+                            let value = if *quoted { json_str(v) } else { v.clone() };
+                            format!("\"{}\":{}", name, value)
+                        })
+                        .collect();
+                    format!("  {{{}}}", fields.join(","))
+                })
+                .collect();
+            let user_filter = app.user_filter.as_deref().map(json_str).unwrap_or_else(|| "null".to_string());
+            format!(
+                "{{\"schema\":\"pstop.export\",\"version\":{},\"exported_at\":{},\"tab\":\"{}\",\"sort\":{{\"field\":{},\"ascending\":{}}},\"filter\":{},\"user_filter\":{},\"tree_view\":{},\"rows\":[\n{}\n]}}\n",
+                EXPORT_VERSION,
+                json_str(&now.to_rfc3339()),
+                app.active_tab.name(),
+                json_str(sort_field.label().trim()),
+                ascending,
+                json_str(&app.filter_query),
+                user_filter,
+                app.tree_view,
+                rows.join(",\n"),
+            )
+        }
+        ExportFormat::Text => {
+            let mut out = format!(
+                "pstop {} view, {}  sorted by {} {}",
+                app.active_tab.name(),
+                now.format("%Y-%m-%d %H:%M:%S"),
+                sort_field.label().trim(),
+                if ascending { "ascending" } else { "descending" },
+            );
+            if !app.filter_query.is_empty() {
+                out.push_str(&format!("  filter: {}", app.filter_query));
+            }
+            if let Some(user) = &app.user_filter {
+                out.push_str(&format!("  user: {}", user));
+            }
+            out.push_str("\n\n");
+            for line in plain_text_table(app) {
+                out.push_str(&line);
+                out.push('\n');
+            }
+            out
+        }
+    }
+}
+
+/// Write the active view to `<dir>/pstop-<tab>-<YYYYmmdd-HHMMSS>.<ext>`
+/// and return the path
+pub fn export_view(app: &App, dir: &Path, format: ExportFormat) -> Result<PathBuf> {
+    let now = chrono::Local::now();
+    let name = format!(
+        "pstop-{}-{}.{}",
+        app.active_tab.name(),
+        now.format("%Y%m%d-%H%M%S"),
+        format.name(),
+    );
+    let path = dir.join(name);
+    fs::write(&path, render(app, format, now)).with_context(|| format!("cannot write {}", path.display()))?;
+    Ok(path)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::system::fake::FakeSource;
+    use crate::system::process::ProcessSortField;
+
+    #[test]
+    fn exports_follow_the_view() {
+        let now = chrono::Local::now();
+        let mut app = FakeSource::basic_app();
+        app.filter_query = "firefox|postgres".to_string();
+        app.sort_field = ProcessSortField::Pid;
+        app.sort_ascending = true;
+        app.tree_view = true;
+        app.apply_filter();
+        app.sort_processes();
+        app.build_tree_view();
+
+        let csv = render(&app, ExportFormat::Csv, now);
+        let lines: Vec<&str> = csv.lines().collect();
+        assert!(lines[0].ends_with(",depth,is_last_child"));
+        assert_eq!(lines.len(), 1 + app.filtered_processes.len());
+        // Same order and indentation as the screen
+        for (line, p) in lines[1..].iter().zip(&app.filtered_processes) {
+            assert!(line.starts_with(&format!("{},{},", p.pid, p.ppid)));
+            assert!(line.ends_with(&format!(",{},{}", p.depth, p.is_last_child)));
+        }
+        assert!(app.filtered_processes.iter().any(|p| p.depth > 0));
+
+        let json = render(&app, ExportFormat::Json, now);
+        assert!(json.contains("\"tab\":\"main\",\"sort\":{\"field\":\"PID\",\"ascending\":true},\"filter\":\"firefox|postgres\""));
+        assert_eq!(json.matches("\"depth\":").count(), app.filtered_processes.len());
+
+        let text = render(&app, ExportFormat::Text, now);
+        assert!(text.contains("filter: firefox|postgres"));
+        assert!(text.contains("└─"));
+    }
+
+    #[test]
+    fn net_and_gpu_tabs_export_their_lists() {
+        let now = chrono::Local::now();
+        let mut app = FakeSource::basic_app();
+        app.active_tab = ProcessTab::Net;
+        let csv = render(&app, ExportFormat::Csv, now);
+        assert!(csv.starts_with("pid,name,recv_bytes_per_sec,send_bytes_per_sec,connection_count\n"));
+        assert_eq!(csv.lines().count(), 1 + app.net_processes.len());
+        // Rates with no JSON form become null
+        app.net_processes[0].recv_bytes_per_sec = f64::NAN;
+        app.net_processes[0].send_bytes_per_sec = f64::INFINITY;
+        let json = render(&app, ExportFormat::Json, now);
+        assert!(json.contains("\"recv_bytes_per_sec\":null,\"send_bytes_per_sec\":null"));
+
+        app.active_tab = ProcessTab::Gpu;
+        let text = render(&app, ExportFormat::Text, now);
+        assert!(text.contains("\nPID    Process        GPU%"));
+        assert_eq!(text.lines().count(), 3 + app.gpu_processes.len());
+
+        let dir = std::env::temp_dir().join(format!("pstop-export-test-{}", std::process::id()));
+        fs::create_dir_all(&dir).unwrap();
+        let path = export_view(&app, &dir, ExportFormat::Json).unwrap();
+        let name = path.file_name().unwrap().to_string_lossy().to_string();
+        assert!(name.starts_with("pstop-gpu-") && name.ends_with(".json"));
+        assert!(fs::read_to_string(&path).unwrap().contains("\"engine_type\":\"3D\""));
+        let _ = fs::remove_dir_all(&dir);
+    }
+}
@@ -0,0 +1,558 @@
+//! F4 filter language.
+//!
+//! Plain text works as it always has: case-insensitive substrings of the
+//! name or command, `|` separating alternatives. A query that opens with a
+//! field and a comparison (`cpu > 20 …`), or with `!` or `(`, is an
+//! expression over the process fields instead:
+//!
+//! ```text
+//! cpu > 20 && user == "svc_build" && !name ~ "chrome"
+//! (res > 500M || threads >= 100) && state != S
+//! ```
+//!
+//!   - comparisons: `==` `!=` `>` `>=` `<` `<=`, `~` / `!~` (regex, case-insensitive)
+//!   - logic: `&&` `||` `!` and parentheses (`&` / `|` work too)
+//!   - fields: the sort menu names (`PERCENT_CPU`, `M_RESIDENT`, …), the
+//!     column headers (`CPU%`, `RES`, …), short aliases (`cpu`, `mem`, `res`,
+//!     `rss`, `virt`, `read`, `write`, …) and `name`; case-insensitive
+//!   - sizes take K/M/G/T suffixes (`res > 1.5G`); TIME+ is in seconds, or
+//!     with s/m/h/d (`time > 2h`)
+//!   - a bare word or string matches the name or command, as in plain text
+//!
+//! Text such as `~/bin` or `a<b` stays plain text, and so does a `!`/`(`
+//! query that doesn't parse (`(sd-pam`). A field-led query that doesn't
+//! parse is an error, so a half-typed one can be flagged.
+
+use regex::{Regex, RegexBuilder};
+
+use crate::system::process::{ProcessInfo, ProcessSortField};
+
+/// A compiled F4 query
+#[derive(Debug, Clone)]
+pub enum Filter {
+    /// Empty query: everything matches
+    All,
+    /// Plain text: lowercase alternatives
+    Text(Vec<String>),
+    Expr(Expr),
+}
+
+#[derive(Debug, Clone)]
+pub enum Expr {
+    And(Box<Expr>, Box<Expr>),
+    Or(Box<Expr>, Box<Expr>),
+    Not(Box<Expr>),
+    /// Bare word: substring of name or command (lowercase)
+    Text(String),
+    Number(Field, Cmp, f64),
+    Str(Field, bool, String),
+    Regex(Field, bool, Regex),
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Cmp {
+    Eq,
+    Ne,
+    Gt,
+    Ge,
+    Lt,
+    Le,
+}
+
+impl Cmp {
+    /// `x <op> v`
+    pub fn holds(&self, x: f64, v: f64) -> bool {
+        match self {
+            Self::Eq => x == v,
+            Self::Ne => x != v,
+            Self::Gt => x > v,
+            Self::Ge => x >= v,
+            Self::Lt => x < v,
+            Self::Le => x <= v,
+        }
+    }
+}
+
+/// What a query can look at: a table column or the process name
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Field {
+    Column(ProcessSortField),
+    Name,
+}
+
+/// Why a query didn't parse, and where (byte offset)
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ParseError {
+    pub message: String,
+    pub pos: usize,
+}
+
+impl std::fmt::Display for ParseError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{} (at {})", self.message, self.pos + 1)
+    }
+}
+
+impl Filter {
+    pub fn parse(query: &str) -> Result<Self, ParseError> {
+        if query.trim().is_empty() {
+            return Ok(Self::All);
+        }
+        if !is_expression(query) {
+            return Ok(Self::text(query));
+        }
+        let field_led = !query.trim_start().starts_with(['!', '(']);
+        match Self::parse_expression(query) {
+            Err(_) if !field_led => Ok(Self::text(query)),
+            result => result,
+        }
+    }
+
+    fn text(query: &str) -> Self {
+        let lower = query.to_lowercase();
+        Self::Text(lower.split('|').map(|t| t.trim().to_string()).filter(|t| !t.is_empty()).collect())
+    }
+
+    fn parse_expression(query: &str) -> Result<Self, ParseError> {
+        let tokens = tokenize(query)?;
+        let mut parser = Parser { tokens, pos: 0, end: query.len() };
+        let expr = parser.or()?;
+        match parser.peek() {
+            None => Ok(Self::Expr(expr)),
+            Some((tok, at)) => Err(ParseError { message: format!("unexpected {}", tok.describe()), pos: at }),
+        }
+    }
+
+    pub fn matches(&self, p: &ProcessInfo) -> bool {
+        match self {
+            Self::All => true,
+            Self::Text(terms) => {
+                let name = p.name.to_lowercase();
+                let cmd = p.command.to_lowercase();
+                terms.iter().any(|t| name.contains(t.as_str()) || cmd.contains(t.as_str()))
+            }
+            Self::Expr(e) => e.matches(p),
+        }
+    }
+}
+
+impl Expr {
+    fn matches(&self, p: &ProcessInfo) -> bool {
+        match self {
+            Self::And(a, b) => a.matches(p) && b.matches(p),
+            Self::Or(a, b) => a.matches(p) || b.matches(p),
+            Self::Not(e) => !e.matches(p),
+            Self::Text(t) => p.name.to_lowercase().contains(t.as_str()) || p.command.to_lowercase().contains(t.as_str()),
+            Self::Number(field, cmp, v) => cmp.holds(field.number(p), *v),
+            Self::Str(field, equal, s) => field.text(p).eq_ignore_ascii_case(s) == *equal,
+            Self::Regex(field, want, re) => re.is_match(&field.text(p)) == *want,
+        }
+    }
+}
+
+/// Does `query` use the expression syntax, rather than plain text? Only
+/// when it opens with `!`, `(` or a known field followed by a comparison.
+fn is_expression(query: &str) -> bool {
+    let query = query.trim_start();
+    if query.starts_with(['!', '(']) {
+        return true;
+    }
+    let end = query
+        .find(|c: char| c.is_whitespace() || "()&|=!<>~\"".contains(c))
+        .unwrap_or(query.len());
+    let rest = query[end..].trim_start();
+    Field::lookup(&query[..end]).is_some() && ["=", "!=", "!~", "<", ">", "~"].iter().any(|op| rest.starts_with(op))
+}
+
+// ── Fields ──────────────────────────────────────────────────────────────
+
+impl Field {
+    fn lookup(word: &str) -> Option<Self> {
+        let w = word.to_ascii_lowercase();
+        let alias = match w.as_str() {
+            "name" => return Some(Self::Name),
+            "cpu" => Some(ProcessSortField::Cpu),
+            "mem" => Some(ProcessSortField::Mem),
+            "res" | "rss" | "resident" => Some(ProcessSortField::ResMem),
+            "virt" => Some(ProcessSortField::VirtMem),
+            "shr" | "shared" => Some(ProcessSortField::SharedMem),
+            "pri" => Some(ProcessSortField::Priority),
+            "ni" => Some(ProcessSortField::Nice),
+            "s" | "status" => Some(ProcessSortField::Status),
+            "time" => Some(ProcessSortField::Time),
+            "thr" => Some(ProcessSortField::Threads),
+            "cmd" => Some(ProcessSortField::Command),
+            "read" => Some(ProcessSortField::IoReadRate),
+            "write" => Some(ProcessSortField::IoWriteRate),
+            "io" => Some(ProcessSortField::IoRate),
+            _ => None,
+        };
+        alias
+            .or_else(|| {
+                ProcessSortField::all()
+                    .iter()
+                    .copied()
+                    // The history columns need the App, not just the process
+                    .filter(|f| !f.is_history())
+                    .find(|f| f.long_label().eq_ignore_ascii_case(&w) || f.label().eq_ignore_ascii_case(&w))
+            })
+            .map(Self::Column)
+    }
+
+    fn is_numeric(&self) -> bool {
+        !matches!(
+            self,
+            Self::Name
+                | Self::Column(ProcessSortField::User | ProcessSortField::Status | ProcessSortField::Command)
+        )
+    }
+
+    fn number(&self, p: &ProcessInfo) -> f64 {
+        let Self::Column(field) = self else { return 0.0 };
+        match field {
+            ProcessSortField::Pid => p.pid as f64,
+            ProcessSortField::Ppid => p.ppid as f64,
+            ProcessSortField::Priority => p.priority as f64,
+            ProcessSortField::Nice => p.nice as f64,
+            ProcessSortField::VirtMem => p.virtual_mem as f64,
+            ProcessSortField::ResMem => p.resident_mem as f64,
+            ProcessSortField::SharedMem => p.shared_mem as f64,
+            ProcessSortField::Cpu => p.cpu_usage as f64,
+            ProcessSortField::Mem => p.mem_usage as f64,
+            // Same source as the TIME+ column and its sort
+            ProcessSortField::Time if p.cpu_time_100ns > 0 => p.cpu_time_100ns as f64 / 10_000_000.0,
+            ProcessSortField::Time => p.run_time as f64,
+            ProcessSortField::Threads => p.threads as f64,
+            ProcessSortField::IoReadRate => p.io_read_rate,
+            ProcessSortField::IoWriteRate => p.io_write_rate,
+            ProcessSortField::IoRate => p.io_read_rate + p.io_write_rate,
+            ProcessSortField::CpuHist | ProcessSortField::CpuPeak => p.cpu_usage as f64,
+            ProcessSortField::ResHist | ProcessSortField::ResPeak => p.resident_mem as f64,
+            ProcessSortField::User | ProcessSortField::Status | ProcessSortField::Command => 0.0,
+        }
+    }
+
+    fn text(&self, p: &ProcessInfo) -> String {
+        match self {
+            Self::Name => p.name.clone(),
+            Self::Column(ProcessSortField::User) => p.user.clone(),
+            Self::Column(ProcessSortField::Status) => p.status.symbol().to_string(),
+            Self::Column(ProcessSortField::Command) => p.command.clone(),
+            _ => self.number(p).to_string(),
+        }
+    }
+}
+
+/// `500M`, `1.5g`, `1KiB`, `512B`, `20` → number; K/M/G/T are powers of 1024. For TIME+
+/// (`time`) the suffixes are s/m/h/d instead.
+pub fn parse_number(word: &str, time: bool) -> Option<f64> {
+    let lower = word.to_ascii_lowercase();
+    let (trimmed, binary) = if time {
+        (lower.as_str(), false)
+    } else if let Some(rest) = lower.strip_suffix("ib") {
+        (rest, true)
+    } else {
+        (lower.strip_suffix('b').unwrap_or(&lower), false)
+    };
+    let scale = match (time, trimmed.chars().last()?) {
+        (false, 'k') => 1024f64,
+        (false, 'm') => 1024f64.powi(2),
+        (false, 'g') => 1024f64.powi(3),
+        (false, 't') => 1024f64.powi(4),
+        (true, 's') => 1.0,
+        (true, 'm') => 60.0,
+        (true, 'h') => 3600.0,
+        (true, 'd') => 86400.0,
+        // `ib` needs a unit letter before it (KiB); a lone `b` means bytes
+        _ if binary => return None,
+        _ => return trimmed.parse::<f64>().ok().filter(|v| v.is_finite()),
+    };
+    let digits = &trimmed[..trimmed.len() - 1];
+    digits.parse::<f64>().ok().filter(|v| v.is_finite()).map(|v| v * scale)
+}
+
+// ── Tokens ──────────────────────────────────────────────────────────────
+
+#[derive(Debug, Clone, PartialEq)]
+enum Token {
+    LParen,
+    RParen,
+    And,
+    Or,
+    Not,
+    Cmp(Cmp),
+    Match,
+    NotMatch,
+    Word(String),
+    Str(String),
+}
+
+impl Token {
+    fn describe(&self) -> String {
+        match self {
+            Self::LParen => "'('".into(),
+            Self::RParen => "')'".into(),
+            Self::And => "'&&'".into(),
+            Self::Or => "'||'".into(),
+            Self::Not => "'!'".into(),
+            Self::Cmp(_) | Self::Match | Self::NotMatch => "operator".into(),
+            Self::Word(w) => format!("'{}'", w),
+            Self::Str(s) => format!("\"{}\"", s),
+        }
+    }
+}
+
+fn tokenize(query: &str) -> Result<Vec<(Token, usize)>, ParseError> {
+    let mut tokens = Vec::new();
+    let mut chars = query.char_indices().peekable();
+    while let Some(&(at, c)) = chars.peek() {
+        chars.next();
+        let next = chars.peek().map(|&(_, c)| c);
+        let mut take = |tok: Token, two: bool| {
+            if two {
+                chars.next();
+            }
+            tokens.push((tok, at));
+        };
+        match (c, next) {
+            (c, _) if c.is_whitespace() => {}
+            ('(', _) => take(Token::LParen, false),
+            (')', _) => take(Token::RParen, false),
+            ('&', Some('&')) => take(Token::And, true),
+            ('&', _) => take(Token::And, false),
+            ('|', Some('|')) => take(Token::Or, true),
+            ('|', _) => take(Token::Or, false),
+            ('=', Some('=')) => take(Token::Cmp(Cmp::Eq), true),
+            ('=', _) => take(Token::Cmp(Cmp::Eq), false),
+            ('!', Some('=')) => take(Token::Cmp(Cmp::Ne), true),
+            ('!', Some('~')) => take(Token::NotMatch, true),
+            ('!', _) => take(Token::Not, false),
+            ('>', Some('=')) => take(Token::Cmp(Cmp::Ge), true),
+            ('>', _) => take(Token::Cmp(Cmp::Gt), false),
+            ('<', Some('=')) => take(Token::Cmp(Cmp::Le), true),
+            ('<', _) => take(Token::Cmp(Cmp::Lt), false),
+            ('~', _) => take(Token::Match, false),
+            ('"', _) => {
+                let mut s = String::new();
+                loop {
+                    match chars.next() {
+                        Some((_, '"')) => break,
+                        Some((_, '\\')) => match chars.next() {
+                            Some((_, c)) => s.push(c),
+                            None => return Err(ParseError { message: "unterminated string".into(), pos: at }),
+                        },
+                        Some((_, c)) => s.push(c),
+                        None => return Err(ParseError { message: "unterminated string".into(), pos: at }),
+                    }
+                }
+                tokens.push((Token::Str(s), at));
+            }
+            (c, _) => {
+                let mut word = c.to_string();
+                while let Some(&(_, c)) = chars.peek() {
+                    if c.is_whitespace() || "()&|=!<>~\"".contains(c) {
+                        break;
+                    }
+                    word.push(c);
+                    chars.next();
+                }
+                tokens.push((Token::Word(word), at));
+            }
+        }
+    }
+    Ok(tokens)
+}
+
+// ── Parser ──────────────────────────────────────────────────────────────
+
+/// Recursive descent: `||` binds loosest, then `&&`, then `!`
+struct Parser {
+    tokens: Vec<(Token, usize)>,
+    pos: usize,
+    /// Query length, for errors at the end of input
+    end: usize,
+}
+
+impl Parser {
+    fn peek(&self) -> Option<(&Token, usize)> {
+        self.tokens.get(self.pos).map(|(t, at)| (t, *at))
+    }
+
+    fn next(&mut self) -> Option<(Token, usize)> {
+        let tok = self.tokens.get(self.pos).cloned();
+        self.pos += 1;
+        tok
+    }
+
+    fn error<T>(&self, message: impl Into<String>, pos: usize) -> Result<T, ParseError> {
+        Err(ParseError { message: message.into(), pos })
+    }
+
+    fn or(&mut self) -> Result<Expr, ParseError> {
+        let mut left = self.and()?;
+        while let Some((Token::Or, _)) = self.peek() {
+            self.next();
+            left = Expr::Or(Box::new(left), Box::new(self.and()?));
+        }
+        Ok(left)
+    }
+
+    fn and(&mut self) -> Result<Expr, ParseError> {
+        let mut left = self.unary()?;
+        while let Some((Token::And, _)) = self.peek() {
+            self.next();
+            left = Expr::And(Box::new(left), Box::new(self.unary()?));
+        }
+        Ok(left)
+    }
+
+    fn unary(&mut self) -> Result<Expr, ParseError> {
+        match self.next() {
+            Some((Token::Not, _)) => Ok(Expr::Not(Box::new(self.unary()?))),
+            Some((Token::LParen, at)) => {
+                let inner = self.or()?;
+                match self.next() {
+                    Some((Token::RParen, _)) => Ok(inner),
+                    Some((tok, pos)) => self.error(format!("expected ')', found {}", tok.describe()), pos),
+                    None => self.error("unclosed '('", at),
+                }
+            }
+            Some((Token::Word(word), at)) => match self.peek() {
+                Some((Token::Cmp(_) | Token::Match | Token::NotMatch, _)) => self.comparison(&word, at),
+                _ => Ok(Expr::Text(word.to_lowercase())),
+            },
+            Some((Token::Str(s), _)) => Ok(Expr::Text(s.to_lowercase())),
+            Some((tok, at)) => self.error(format!("unexpected {}", tok.describe()), at),
+            None => self.error("expected a condition", self.end),
+        }
+    }
+
+    /// `field op value`, with the field word already consumed
+    fn comparison(&mut self, word: &str, at: usize) -> Result<Expr, ParseError> {
+        let Some(field) = Field::lookup(word) else {
+            return self.error(format!("unknown field '{}'", word), at);
+        };
+        let (op, op_at) = self.next().expect("caller saw an operator");
+        let (value, value_at) = match self.next() {
+            Some((Token::Word(v) | Token::Str(v), pos)) => (v, pos),
+            Some((tok, pos)) => return self.error(format!("expected a value, found {}", tok.describe()), pos),
+            None => return self.error("expected a value", self.end),
+        };
+        match op {
+            Token::Match | Token::NotMatch => {
+                match RegexBuilder::new(&value).case_insensitive(true).build() {
+                    Ok(re) => Ok(Expr::Regex(field, op == Token::Match, re)),
+                    Err(_) => self.error("invalid regex", value_at),
+                }
+            }
+            Token::Cmp(cmp) if field.is_numeric() => match parse_number(&value, field == Field::Column(ProcessSortField::Time)) {
+                Some(v) => Ok(Expr::Number(field, cmp, v)),
+                None => self.error(format!("'{}' is not a number", value), value_at),
+            },
+            Token::Cmp(cmp @ (Cmp::Eq | Cmp::Ne)) => Ok(Expr::Str(field, cmp == Cmp::Eq, value)),
+            _ => self.error(format!("'{}' is text: use ==, != or ~", word), op_at),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::system::process::ProcessStatus;
+
+    fn process(name: &str, user: &str, cpu: f32, res_mb: u64) -> ProcessInfo {
+        ProcessInfo {
+            pid: 100,
+            ppid: 1,
+            name: name.to_string(),
+            command: format!("/usr/bin/{} --flag", name),
+            user: user.to_string(),
+            status: ProcessStatus::Running,
+            priority: 20,
+            nice: 0,
+            virtual_mem: res_mb * 4 * 1024 * 1024,
+            resident_mem: res_mb * 1024 * 1024,
+            shared_mem: 0,
+            cpu_usage: cpu,
+            mem_usage: 1.0,
+            run_time: 60,
+            cpu_time_100ns: 125 * 10_000_000,
+            threads: 8,
+            io_read_rate: 0.0,
+            io_write_rate: 2048.0,
+            depth: 0,
+            is_last_child: false,
+            is_thread: false,
+            wait_reason: String::new(),
+            start_address: None,
+            context_switches: 0,
+        }
+    }
+
+    fn matches(query: &str, p: &ProcessInfo) -> bool {
+        Filter::parse(query).unwrap_or_else(|e| panic!("{}: {}", query, e)).matches(p)
+    }
+
+    #[test]
+    fn plain_text_is_unchanged() {
+        let chrome = process("chrome", "alice", 1.0, 10);
+        assert!(matches("", &chrome));
+        assert!(matches("CHROME", &chrome));
+        assert!(matches("firefox|chrom", &chrome));
+        assert!(matches("  | usr/bin ", &chrome));
+        assert!(!matches("firefox|", &chrome));
+        assert!(matches!(Filter::parse("google chrome|x").unwrap(), Filter::Text(t) if t == ["google chrome", "x"]));
+    }
+
+    #[test]
+    fn operators_in_text_stay_text() {
+        let mut p = process("bash", "alice", 1.0, 10);
+        p.command = "bash ~/bin/run a<b x==y".to_string();
+        for q in ["~/bin", "a<b", "x==y", "bash ~", "(sd-pam", "cpux > 5"] {
+            assert!(matches!(Filter::parse(q).unwrap(), Filter::Text(_)), "{}", q);
+        }
+        assert!(matches("~/bin", &p));
+        assert!(matches("A<B", &p));
+        assert!(matches("x==y|nothing", &p));
+        // `!`/`(` queries that parse are still expressions
+        assert!(matches("!(cpu > 5)", &p));
+        assert!(matches("(bash)", &p));
+    }
+
+    #[test]
+    fn expressions() {
+        let build = process("cc1plus", "svc_build", 35.5, 600);
+        let chrome = process("chrome", "svc_build", 35.5, 600);
+        let q = r#"cpu > 20 && user == "svc_build" && !name ~ "chrome""#;
+        assert!(matches(q, &build));
+        assert!(!matches(q, &chrome));
+
+        assert!(matches("res > 500M", &build));
+        assert!(!matches("M_RESIDENT > 0.6g", &build));
+        assert!(matches("PERCENT_CPU >= 35.5 && CPU% < 36", &build));
+        assert!(matches("time > 2m || time == 125", &build));
+        assert!(matches("(threads < 4 || write >= 2K) && state == R", &build));
+        assert!(matches("name ~ \"^cc\" || name ~ ^CC", &build));
+        assert!(matches("cmd !~ chrome && cc1 && pid = 100", &build));
+        assert!(matches("!(user != svc_build)", &build));
+        assert!(matches("cpu > 40 | cpu < 40 & res < 1G", &build));
+        assert!(matches("res > 512B && res > 512b && res > 1KiB && res < 601MiB", &build));
+    }
+
+    #[test]
+    fn errors_point_at_the_problem() {
+        let err = |q: &str| Filter::parse(q).unwrap_err();
+        assert_eq!(err("cpu >").message, "expected a value");
+        assert_eq!(err("cpu >").pos, 5);
+        assert_eq!(err("cpu > 5 && cpux > 5").message, "unknown field 'cpux'");
+        assert_eq!(err("cpu > lots").pos, 6);
+        assert_eq!(err("res > 5X").message, "'5X' is not a number");
+        assert_eq!(err("res > 5iB").message, "'5iB' is not a number");
+        assert_eq!(err("cpu > 5 && (res > 1G").message, "unclosed '('");
+        assert_eq!(err("cpu > 5 )").message, "unexpected ')'");
+        assert_eq!(err("name ~ \"(\"").message, "invalid regex");
+        assert_eq!(err("user > bob").message, "'user' is text: use ==, != or ~");
+        assert_eq!(err("name == \"x").message, "unterminated string");
+        assert_eq!(err("cpu > 5 &&").pos, 10);
+    }
+}
@@ -0,0 +1,567 @@
+//! Process governor: policies from pstoprc that renice, pin or kill
+//! matching processes automatically, checked after every live refresh.
+//!
+//! ```text
+//! governor_enabled=1
+//! governor_dry_run=0
+//! policy.cl_hog.match=name == "cl.exe" && cpu > 80
+//! policy.cl_hog.for=60s
+//! policy.cl_hog.action=lower_priority
+//! policy.indexer.match=name == indexer
+//! policy.indexer.action=affinity 0-1
+//! policy.leaky.match=name == "leaky.exe" && res > 8G
+//! policy.leaky.action=kill
+//! policy.leaky.dry_run=1
+//! ```
+//!
+//! `match` is an F4 filter. A process has to keep matching for `for`
+//! (default 0) before the action runs; afterwards the same policy leaves that
+//! process alone for `cooldown` (default 60s). Actions are `lower_priority`
+//! (one nice step, as F8), `affinity <cpus>` (`0-1,4`) and `kill [signal]`
+//! (a number from the F9 menu, default 15). With `dry_run` (per policy, or
+//! `governor_dry_run` for all of them) actions are only recorded. Every
+//! action lands in the history shown by the `A` overlay.
+//!
+//! The UI only applies policies with `governor_enabled=1`; `pstop --govern`
+//! always does. Init, kernel threads and the Windows system processes are
+//! never touched.
+
+use std::collections::{HashMap, VecDeque};
+use std::time::{Duration, Instant};
+
+use anyhow::{bail, Result};
+
+use crate::app::{App, KILL_SIGNALS};
+use crate::config::PstopConfig;
+use crate::filter::{parse_number, Filter};
+use crate::system::backend::SystemBackend;
+use crate::system::collector::Collector;
+use crate::system::process::ProcessInfo;
+
+/// Cooldown when a policy doesn't set one
+pub const DEFAULT_COOLDOWN_SECS: u64 = 60;
+
+/// Entries kept in the action history
+pub const HISTORY_LEN: usize = 500;
+
+// ── Policies ────────────────────────────────────────────────────────────
+
+/// What a policy does to a matching process
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum PolicyAction {
+    LowerPriority,
+    /// CPU mask, bit 0 = CPU 0
+    Affinity(usize),
+    /// Index into `KILL_SIGNALS`
+    Kill(usize),
+}
+
+impl PolicyAction {
+    pub fn parse(value: &str) -> Result<Self, String> {
+        let value = value.trim();
+        let (verb, arg) = value.split_once(char::is_whitespace).map_or((value, ""), |(v, a)| (v, a.trim()));
+        match verb.to_ascii_lowercase().as_str() {
+            "lower_priority" | "renice" if arg.is_empty() => Ok(Self::LowerPriority),
+            "affinity" => parse_cpu_list(arg).map(Self::Affinity),
+            "kill" if arg.is_empty() => Ok(Self::Kill(0)),
+            "kill" => {
+                let signal = arg.trim_start_matches('-');
+                KILL_SIGNALS
+                    .iter()
+                    .position(|(num, _)| *num == signal)
+                    .map(Self::Kill)
+                    .ok_or_else(|| format!("unknown signal '{}'", arg))
+            }
+            _ => Err(format!("unknown action '{}' (expected lower_priority, affinity <cpus> or kill [signal])", value)),
+        }
+    }
+
+    /// Short description for the history
+    pub fn describe(&self) -> String {
+        match self {
+            Self::LowerPriority => "lower priority".to_string(),
+            Self::Affinity(mask) => format!("pin to CPUs {}", format_cpu_list(*mask)),
+            Self::Kill(i) => format!("kill -{}", KILL_SIGNALS.get(*i).map_or("15", |(num, _)| num)),
+        }
+    }
+}
+
+/// `0-1,4` → mask with bits 0, 1 and 4
+fn parse_cpu_list(spec: &str) -> Result<usize, String> {
+    let mut mask = 0usize;
+    for part in spec.split(',').map(str::trim).filter(|p| !p.is_empty()) {
+        let (lo, hi) = part.split_once('-').unwrap_or((part, part));
+        let (Ok(lo), Ok(hi)) = (lo.trim().parse::<u32>(), hi.trim().parse::<u32>()) else {
+            return Err(format!("bad CPU list '{}'", spec));
+        };
+        if lo > hi || hi >= usize::BITS {
+            return Err(format!("bad CPU range '{}'", part));
+        }
+        for cpu in lo..=hi {
+            mask |= 1 << cpu;
+        }
+    }
+    if mask == 0 {
+        return Err("affinity needs a CPU list, e.g. 'affinity 0-1'".into());
+    }
+    Ok(mask)
+}
+
+/// Mask → `0-1,4`
+fn format_cpu_list(mask: usize) -> String {
+    let mut parts = Vec::new();
+    let mut cpu = 0;
+    while cpu < usize::BITS as usize {
+        if mask & (1 << cpu) == 0 {
+            cpu += 1;
+            continue;
+        }
+        let start = cpu;
+        while cpu + 1 < usize::BITS as usize && mask & (1 << (cpu + 1)) != 0 {
+            cpu += 1;
+        }
+        parts.push(if start == cpu { start.to_string() } else { format!("{}-{}", start, cpu) });
+        cpu += 1;
+    }
+    parts.join(",")
+}
+
+/// One `policy.<name>.*` rule as written in the config file
+#[derive(Debug, Clone)]
+pub struct Policy {
+    pub name: String,
+    pub matches: String,
+    pub action_text: String,
+    /// Seconds a process must keep matching before the action runs
+    pub sustain_secs: u64,
+    /// Seconds before this policy acts on the same process again
+    pub cooldown_secs: u64,
+    pub dry_run: bool,
+    filter: Result<Filter, String>,
+    action: Result<PolicyAction, String>,
+}
+
+impl Policy {
+    /// A policy with nothing set yet, for the config loader to fill in
+    pub fn named(name: &str) -> Self {
+        Self {
+            name: name.to_string(),
+            matches: String::new(),
+            action_text: String::new(),
+            sustain_secs: 0,
+            cooldown_secs: DEFAULT_COOLDOWN_SECS,
+            dry_run: false,
+            filter: Err("no 'match' filter".into()),
+            action: Err("no 'action'".into()),
+        }
+    }
+
+    /// Set one field from a config line; unknown keys are ignored
+    pub fn set(&mut self, key: &str, value: &str) {
+        match key {
+            "match" => {
+                self.matches = value.to_string();
+                // An empty filter would match every process
+                self.filter = match value.trim() {
+                    "" => Err("no 'match' filter".into()),
+                    q => Filter::parse(q).map_err(|e| e.to_string()),
+                };
+            }
+            "action" => {
+                self.action_text = value.to_string();
+                self.action = PolicyAction::parse(value);
+            }
+            "for" => self.sustain_secs = seconds(value),
+            "cooldown" => self.cooldown_secs = seconds(value),
+            "dry_run" => self.dry_run = value == "1",
+            _ => {}
+        }
+    }
+
+    pub fn action(&self) -> Option<&PolicyAction> {
+        self.action.as_ref().ok()
+    }
+
+    /// Why the policy won't run, if it won't
+    pub fn error(&self) -> Option<&str> {
+        self.filter.as_ref().err().or(self.action.as_ref().err()).map(String::as_str)
+    }
+
+    /// `policy.<name>.<key>=<value>` lines for the config file
+    pub fn config_lines(&self) -> Vec<String> {
+        let prefix = format!("policy.{}.", self.name);
+        let mut lines = vec![format!("{}match={}", prefix, self.matches), format!("{}action={}", prefix, self.action_text)];
+        if self.sustain_secs > 0 {
+            lines.push(format!("{}for={}s", prefix, self.sustain_secs));
+        }
+        if self.cooldown_secs != DEFAULT_COOLDOWN_SECS {
+            lines.push(format!("{}cooldown={}s", prefix, self.cooldown_secs));
+        }
+        if self.dry_run {
+            lines.push(format!("{}dry_run=1", prefix));
+        }
+        lines
+    }
+}
+
+/// `30s`, `2m`, `45` → seconds (0 if unreadable)
+fn seconds(value: &str) -> u64 {
+    parse_number(value.trim(), true).map_or(0, |s| s.max(0.0).round() as u64)
+}
+
+// ── Governor ────────────────────────────────────────────────────────────
+
+/// How an action went
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Outcome {
+    Done,
+    /// A signal was delivered; the process may still be running
+    Sent,
+    Failed,
+    DryRun,
+}
+
+impl Outcome {
+    pub fn label(&self) -> &'static str {
+        match self {
+            Self::Done => "done",
+            Self::Sent => "sent",
+            Self::Failed => "FAILED",
+            Self::DryRun => "dry run",
+        }
+    }
+}
+
+/// One entry of the action history
+#[derive(Debug, Clone, PartialEq)]
+pub struct ActionRecord {
+    pub at_ms: i64,
+    pub policy: String,
+    pub pid: u32,
+    pub name: String,
+    pub action: String,
+    pub outcome: Outcome,
+}
+
+/// The configured policies, their timers and what they've done
+#[derive(Debug, Clone, Default)]
+pub struct Governor {
+    pub policies: Vec<Policy>,
+    /// `governor_dry_run`: record every action without taking it
+    pub dry_run: bool,
+    /// `governor_enabled` in the UI, always with `--govern`; other headless
+    /// modes never act
+    pub enabled: bool,
+    /// Print actions instead of flashing the footer
+    pub headless: bool,
+    /// Newest last
+    pub history: VecDeque<ActionRecord>,
+    /// (policy, pid) → when the process started matching
+    matching_since: HashMap<(usize, u32), i64>,
+    /// (policy, pid) → when the policy last acted on it
+    last_acted: HashMap<(usize, u32), i64>,
+}
+
+impl Governor {
+    /// Check every policy against this tick. `apply` carries out an action
+    /// and says how it went, or None if there was nothing to change.
+    /// Returns the new history entries.
+    pub fn tick(
+        &mut self,
+        processes: &[ProcessInfo],
+        now_ms: i64,
+        mut apply: impl FnMut(u32, &PolicyAction) -> Option<Outcome>,
+    ) -> Vec<ActionRecord> {
+        let own_pid = std::process::id();
+        let mut matching = HashMap::new();
+        let mut records = Vec::new();
+
+        for (index, policy) in self.policies.iter().enumerate() {
+            let (Ok(filter), Ok(action)) = (&policy.filter, &policy.action) else { continue };
+            // Thread rows (H) aren't processes
+            for p in processes
+                .iter()
+                .filter(|p| !p.is_thread && p.pid != own_pid && !is_protected(p) && filter.matches(p))
+            {
+                let key = (index, p.pid);
+                let since = *self.matching_since.get(&key).unwrap_or(&now_ms);
+                matching.insert(key, since);
+                if now_ms - since < policy.sustain_secs as i64 * 1000 {
+                    continue;
+                }
+                if let Some(&last) = self.last_acted.get(&key) {
+                    if now_ms - last < policy.cooldown_secs as i64 * 1000 {
+                        continue;
+                    }
+                }
+                self.last_acted.insert(key, now_ms);
+
+                let outcome = if self.dry_run || policy.dry_run {
+                    Outcome::DryRun
+                } else {
+                    match apply(p.pid, action) {
+                        Some(outcome) => outcome,
+                        None => continue,
+                    }
+                };
+                records.push(ActionRecord {
+                    at_ms: now_ms,
+                    policy: policy.name.clone(),
+                    pid: p.pid,
+                    name: p.name.clone(),
+                    action: action.describe(),
+                    outcome,
+                });
+            }
+        }
+
+        // A process that stops matching starts its timer over; forget
+        // cooldowns of processes that are gone
+        self.matching_since = matching;
+        self.last_acted.retain(|(_, pid), _| processes.iter().any(|p| p.pid == *pid));
+        for record in &records {
+            if self.history.len() == HISTORY_LEN {
+                self.history.pop_front();
+            }
+            self.history.push_back(record.clone());
+        }
+        records
+    }
+
+    /// Policies that won't run, with the reason
+    pub fn errors(&self) -> Vec<String> {
+        self.policies
+            .iter()
+            .filter_map(|p| p.error().map(|e| format!("policy '{}': {}", p.name, e)))
+            .collect()
+    }
+}
+
+/// Processes no policy may touch: init and the kernel's threads on Linux,
+/// Idle, System and the session-critical processes on Windows
+fn is_protected(p: &ProcessInfo) -> bool {
+    if p.pid <= 1 {
+        return true;
+    }
+    if cfg!(windows) {
+        p.pid == 4
+            || matches!(
+                p.name.to_ascii_lowercase().as_str(),
+                "system"
+                    | "registry"
+                    | "memory compression"
+                    | "smss.exe"
+                    | "csrss.exe"
+                    | "wininit.exe"
+                    | "winlogon.exe"
+                    | "services.exe"
+                    | "lsass.exe"
+            )
+    } else {
+        // kthreadd and everything it spawns
+        p.pid == 2 || p.ppid == 2
+    }
+}
+
+/// Carry out an action through the platform backend
+pub fn apply(backend: &dyn SystemBackend, pid: u32, action: &PolicyAction) -> Option<Outcome> {
+    let done = |ok: bool| if ok { Outcome::Done } else { Outcome::Failed };
+    match action {
+        PolicyAction::LowerPriority => Some(done(backend.lower_priority(pid))),
+        PolicyAction::Affinity(mask) => {
+            let (current, system, ok) = backend.get_affinity(pid);
+            let wanted = if system != 0 { mask & system } else { *mask };
+            if ok && current == wanted {
+                return None;
+            }
+            Some(done(wanted != 0 && backend.set_affinity(pid, wanted)))
+        }
+        PolicyAction::Kill(signal) => Some(match backend.kill(pid, *signal) {
+            Ok(()) => Outcome::Sent,
+            Err(_) => Outcome::Failed,
+        }),
+    }
+}
+
+/// One history entry as a line of text
+pub fn format_record(record: &ActionRecord) -> String {
+    let time = chrono::DateTime::from_timestamp_millis(record.at_ms)
+        .map(|t| t.with_timezone(&chrono::Local).format("%Y-%m-%d %H:%M:%S").to_string())
+        .unwrap_or_default();
+    format!(
+        "{} {}: {} {} ({}) [{}]",
+        time,
+        record.policy,
+        record.action,
+        record.name,
+        record.pid,
+        record.outcome.label()
+    )
+}
+
+// ── Headless ────────────────────────────────────────────────────────────
+
+/// `pstop --govern`: apply the policies every update interval and print
+/// each action until interrupted
+pub fn run(interval_ms: Option<u64>, dry_run: bool) -> Result<()> {
+    let mut app = App::new();
+    PstopConfig::load().apply_to(&mut app);
+    app.governor.enabled = true;
+    app.governor.headless = true;
+    app.governor.dry_run |= dry_run;
+
+    for error in app.governor.errors() {
+        eprintln!("pstop: {}", error);
+    }
+    let active = app.governor.policies.iter().filter(|p| p.error().is_none()).count();
+    if active == 0 {
+        bail!("no policies to apply; add policy.<name>.match=... and .action=... lines to pstoprc");
+    }
+
+    let delay = Duration::from_millis(interval_ms.unwrap_or(app.update_interval_ms));
+    eprintln!(
+        "pstop: applying {} polic{} every {}ms{} (Ctrl+C to stop)",
+        active,
+        if active == 1 { "y" } else { "ies" },
+        delay.as_millis(),
+        if app.governor.dry_run { ", dry run" } else { "" },
+    );
+    let mut collector = Collector::new();
+    loop {
+        let started = Instant::now();
+        collector.refresh(&mut app);
+        std::thread::sleep(delay.saturating_sub(started.elapsed()));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::system::fake::FakeSource;
+
+    fn policy(name: &str, fields: &[(&str, &str)]) -> Policy {
+        let mut policy = Policy::named(name);
+        for (key, value) in fields {
+            policy.set(key, value);
+        }
+        policy
+    }
+
+    #[test]
+    fn parse_actions_and_policies() {
+        assert_eq!(PolicyAction::parse("lower_priority"), Ok(PolicyAction::LowerPriority));
+        assert_eq!(PolicyAction::parse("affinity 0-1, 4"), Ok(PolicyAction::Affinity(0b10011)));
+        assert_eq!(PolicyAction::parse("kill"), Ok(PolicyAction::Kill(0)));
+        assert_eq!(PolicyAction::parse("kill -9"), Ok(PolicyAction::Kill(1)));
+        assert!(PolicyAction::parse("kill 77").is_err());
+        assert!(PolicyAction::parse("affinity").is_err());
+        assert!(PolicyAction::parse("affinity 3-1").is_err());
+        assert!(PolicyAction::parse("suspend").is_err());
+        assert_eq!(PolicyAction::Affinity(0b1110_0011).describe(), "pin to CPUs 0-1,5-7");
+
+        let p = policy("hog", &[("match", "name == cargo && cpu > 80"), ("action", "lower_priority"), ("for", "1m"), ("cooldown", "5m")]);
+        assert_eq!(p.error(), None);
+        assert_eq!(
+            p.config_lines(),
+            ["policy.hog.match=name == cargo && cpu > 80", "policy.hog.action=lower_priority", "policy.hog.for=60s", "policy.hog.cooldown=300s"]
+        );
+        assert_eq!(policy("all", &[("action", "kill")]).error(), Some("no 'match' filter"));
+        assert!(policy("bad", &[("match", "cpu >"), ("action", "kill")]).error().is_some());
+    }
+
+    #[test]
+    fn sustain_cooldown_and_dry_run() {
+        let app = FakeSource::basic_app();
+        let mut governor = Governor {
+            policies: vec![
+                policy("hog", &[("match", "cpu > 80"), ("action", "lower_priority"), ("for", "10s"), ("cooldown", "30s")]),
+                policy("pin", &[("match", "name == dockerd"), ("action", "affinity 0-1")]),
+                policy("reaper", &[("match", "name == dnsmasq"), ("action", "kill 9"), ("dry_run", "1")]),
+            ],
+            ..Default::default()
+        };
+        let applied = std::cell::RefCell::new(Vec::new());
+        let tick = |governor: &mut Governor, processes: &[ProcessInfo], now: i64| {
+            governor.tick(processes, now, |pid, action| {
+                let mut applied = applied.borrow_mut();
+                applied.push((pid, action.clone()));
+                // dockerd is already pinned the second time round
+                if pid == 5000 && applied.iter().filter(|(p, _)| *p == 5000).count() > 1 {
+                    return None;
+                }
+                Some(Outcome::Done)
+            })
+        };
+        let pids = |records: &[ActionRecord]| records.iter().map(|r| r.pid).collect::<Vec<u32>>();
+
+        let first = tick(&mut governor, &app.processes, 0);
+        let summary: Vec<(&str, u32, Outcome)> = first.iter().map(|r| (r.policy.as_str(), r.pid, r.outcome)).collect();
+        assert_eq!(summary, [("pin", 5000, Outcome::Done), ("reaper", 8123, Outcome::DryRun)]);
+        assert_eq!(first[1].action, "kill -9");
+
+        // cargo and rustc have held > 80% for 10s; pin and reaper are cooling down
+        assert_eq!(pids(&tick(&mut governor, &app.processes, 10_000)), [4100, 4101]);
+        assert!(tick(&mut governor, &app.processes, 20_000).is_empty());
+
+        // rustc drops below the line: its timer starts over when it's back
+        let mut procs = app.processes.clone();
+        procs.iter_mut().find(|p| p.pid == 4101).unwrap().cpu_usage = 10.0;
+        assert!(tick(&mut governor, &procs, 30_000).is_empty());
+        procs.iter_mut().find(|p| p.pid == 4101).unwrap().cpu_usage = 90.0;
+        assert_eq!(pids(&tick(&mut governor, &procs, 45_000)), [4100]);
+
+        // rustc has matched for 10s again; dockerd is already pinned, so
+        // only the dry run is recorded for the other two policies
+        assert_eq!(pids(&tick(&mut governor, &procs, 60_000)), [4101, 8123]);
+        assert_eq!(governor.history.len(), 7);
+
+        // Global dry run: nothing reaches the backend
+        governor.dry_run = true;
+        let before = applied.borrow().len();
+        let records = tick(&mut governor, &procs, 200_000);
+        assert_eq!(pids(&records), [4100, 4101, 5000, 8123]);
+        assert!(records.iter().all(|r| r.outcome == Outcome::DryRun));
+        assert_eq!(applied.borrow().len(), before);
+    }
+
+    #[test]
+    fn system_processes_are_never_touched() {
+        let app = FakeSource::basic_app();
+        let mut governor = Governor {
+            policies: vec![policy("all", &[("match", "pid > 0"), ("action", "kill 9")])],
+            ..Default::default()
+        };
+        let mut processes = app.processes.clone();
+        let mut kworker = processes.iter().find(|p| p.pid == 4100).unwrap().clone();
+        (kworker.pid, kworker.ppid, kworker.name) = (57, 2, "kworker/0:1".into());
+        processes.push(kworker);
+
+        let records = governor.tick(&processes, 0, |_, _| Some(Outcome::Sent));
+        assert!(records.iter().all(|r| r.pid > 1 && r.outcome == Outcome::Sent));
+        assert!(!records.iter().any(|r| r.pid == 1));
+        assert_eq!(records.iter().any(|r| r.pid == 57), cfg!(windows));
+    }
+
+    #[test]
+    fn config_alone_never_enables_the_governor() {
+        let cfg = PstopConfig::parse(
+            "governor_enabled=1\npolicy.hog.match=cpu > 80\npolicy.hog.action=lower_priority\npolicy.hog.dry_run=1\n",
+        );
+        assert!(cfg.governor_enabled);
+
+        // What batch::sample and the other headless modes do; Collector::refresh
+        // then calls run_governor every tick
+        let mut app = App::new();
+        cfg.apply_to(&mut app);
+        FakeSource::basic().refresh(&mut app);
+        app.run_governor();
+        assert!(!app.governor.enabled);
+        assert!(app.governor.history.is_empty());
+
+        // run_app opts in from the config (the dry run keeps the backend out of it)
+        app.governor.enabled = cfg.governor_enabled;
+        app.run_governor();
+        let pids: Vec<(u32, Outcome)> = app.governor.history.iter().map(|r| (r.pid, r.outcome)).collect();
+        assert_eq!(pids, [(4100, Outcome::DryRun), (4101, Outcome::DryRun)]);
+    }
+}
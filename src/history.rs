@@ -0,0 +1,226 @@
+//! Per-process history: the last few ticks of CPU, resident memory and I/O
+//! for every live PID.
+//!
+//! The collectors rebuild `ProcessInfo` from scratch each tick; this ring
+//! per PID is what lets the table tell a process spiking right now from one
+//! that has been pegged for minutes (the CPU HIST / RES HIST sparklines and
+//! the average/peak sorts). A PID whose process name changes is treated as
+//! reused and starts over.
+
+use std::collections::{HashMap, VecDeque};
+
+use crate::system::process::ProcessInfo;
+
+/// Ticks kept per PID unless `history_ticks` says otherwise
+pub const DEFAULT_TICKS: usize = 60;
+
+/// One tick of a process
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub struct Sample {
+    pub cpu: f32,
+    pub resident_mem: u64,
+    pub io_read_rate: f64,
+    pub io_write_rate: f64,
+}
+
+/// Recent samples of one PID, oldest first
+#[derive(Debug, Clone, Default)]
+pub struct PidHistory {
+    name: String,
+    samples: VecDeque<Sample>,
+}
+
+impl PidHistory {
+    pub fn samples(&self) -> &VecDeque<Sample> {
+        &self.samples
+    }
+
+    pub fn cpu_avg(&self) -> f32 {
+        if self.samples.is_empty() {
+            return 0.0;
+        }
+        self.samples.iter().map(|s| s.cpu).sum::<f32>() / self.samples.len() as f32
+    }
+
+    pub fn cpu_peak(&self) -> f32 {
+        self.samples.iter().map(|s| s.cpu).fold(0.0, f32::max)
+    }
+
+    pub fn res_avg(&self) -> u64 {
+        if self.samples.is_empty() {
+            return 0;
+        }
+        self.samples.iter().map(|s| s.resident_mem).sum::<u64>() / self.samples.len() as u64
+    }
+
+    pub fn res_peak(&self) -> u64 {
+        self.samples.iter().map(|s| s.resident_mem).max().unwrap_or(0)
+    }
+}
+
+/// History of every PID seen on the last tick
+#[derive(Debug, Clone)]
+pub struct ProcessHistory {
+    ticks: usize,
+    pids: HashMap<u32, PidHistory>,
+}
+
+impl Default for ProcessHistory {
+    fn default() -> Self {
+        Self::new(DEFAULT_TICKS)
+    }
+}
+
+impl ProcessHistory {
+    pub fn new(ticks: usize) -> Self {
+        Self { ticks: ticks.max(1), pids: HashMap::new() }
+    }
+
+    /// Ticks kept per PID
+    pub fn ticks(&self) -> usize {
+        self.ticks
+    }
+
+    pub fn set_ticks(&mut self, ticks: usize) {
+        self.ticks = ticks.max(1);
+        for h in self.pids.values_mut() {
+            while h.samples.len() > self.ticks {
+                h.samples.pop_front();
+            }
+        }
+    }
+
+    /// Append this tick's values; PIDs that are gone are forgotten
+    pub fn record(&mut self, processes: &[ProcessInfo]) {
+        let mut pids = HashMap::with_capacity(processes.len());
+        for p in processes {
+            let mut h = match self.pids.remove(&p.pid) {
+                Some(h) if h.name == p.name => h,
+                _ => PidHistory { name: p.name.clone(), samples: VecDeque::with_capacity(self.ticks) },
+            };
+            if h.samples.len() == self.ticks {
+                h.samples.pop_front();
+            }
+            h.samples.push_back(Sample {
+                cpu: p.cpu_usage,
+                resident_mem: p.resident_mem,
+                io_read_rate: p.io_read_rate,
+                io_write_rate: p.io_write_rate,
+            });
+            pids.insert(p.pid, h);
+        }
+        self.pids = pids;
+    }
+
+    pub fn get(&self, pid: u32) -> Option<&PidHistory> {
+        self.pids.get(&pid)
+    }
+
+    /// Forget everything (e.g. after a replay seek, where the ticks no
+    /// longer follow each other)
+    pub fn clear(&mut self) {
+        self.pids.clear();
+    }
+}
+
+/// Eight-level bars for sparklines and graphs, lowest first
+pub const BARS: [char; 8] = ['▁', '▂', '▃', '▄', '▅', '▆', '▇', '█'];
+
+/// The last `width` values as a sparkline scaled to `max`, newest on the
+/// right; left-padded with spaces while the history is shorter than `width`
+pub fn sparkline(values: impl DoubleEndedIterator<Item = f64>, width: usize, max: f64) -> String {
+    let mut bars: Vec<char> = values
+        .rev()
+        .take(width)
+        .map(|v| {
+            if max <= 0.0 || !v.is_finite() || v <= 0.0 {
+                BARS[0]
+            } else {
+                let level = ((v / max) * BARS.len() as f64).ceil() as usize;
+                BARS[level.clamp(1, BARS.len()) - 1]
+            }
+        })
+        .collect();
+    bars.reverse();
+    format!("{:>width$}", bars.into_iter().collect::<String>(), width = width)
+}
+
+/// The last `width` values as a `height`-row bar chart scaled to `max`
+/// (0 = the largest value shown), top row first and newest on the right.
+/// Each row is exactly `width` characters.
+pub fn graph_rows(values: &[f64], width: usize, height: usize, max: f64) -> Vec<String> {
+    let shown = &values[values.len().saturating_sub(width)..];
+    let scale = if max > 0.0 { max } else { shown.iter().copied().fold(0.0, f64::max) };
+    // Eighths of a row per column
+    let levels: Vec<usize> = shown
+        .iter()
+        .map(|&v| if scale > 0.0 && v.is_finite() { ((v / scale).clamp(0.0, 1.0) * (height * 8) as f64).ceil() as usize } else { 0 })
+        .collect();
+    let pad = width - levels.len();
+
+    (0..height)
+        .map(|row| {
+            let floor = (height - 1 - row) * 8;
+            std::iter::repeat_n(' ', pad)
+                .chain(levels.iter().map(|&level| match level.saturating_sub(floor).min(8) {
+                    0 => ' ',
+                    fill => BARS[fill - 1],
+                }))
+                .collect()
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::system::fake::FakeSource;
+
+    #[test]
+    fn ring_per_pid_forgets_exits_and_reuse() {
+        let app = FakeSource::basic_app();
+        let mut procs = app.processes.clone();
+        let mut history = ProcessHistory::new(4);
+
+        for i in 0..6 {
+            procs[0].cpu_usage = i as f32 * 10.0;
+            procs[0].resident_mem = 1000 + i;
+            history.record(&procs);
+        }
+        let h = history.get(procs[0].pid).unwrap();
+        assert_eq!(h.samples().len(), 4);
+        assert_eq!(h.cpu_avg(), 35.0);
+        assert_eq!(h.cpu_peak(), 50.0);
+        assert_eq!(h.res_avg(), 1003);
+        assert_eq!(h.res_peak(), 1005);
+
+        // Shrinking the window trims every ring
+        history.set_ticks(2);
+        assert_eq!(history.get(procs[0].pid).unwrap().samples().len(), 2);
+
+        // Exited PIDs are dropped; a reused PID starts over
+        let gone = procs.pop().unwrap();
+        procs[0].name = "something-else".to_string();
+        history.record(&procs);
+        assert!(history.get(gone.pid).is_none());
+        assert_eq!(history.get(procs[0].pid).unwrap().samples().len(), 1);
+        assert_eq!(history.get(procs[1].pid).unwrap().samples().len(), 2);
+    }
+
+    #[test]
+    fn sparkline_scales_and_pads() {
+        let values = [0.0, 25.0, 50.0, 100.0, 150.0];
+        assert_eq!(sparkline(values.into_iter(), 8, 100.0), "   ▁▂▄██");
+        assert_eq!(sparkline(values.into_iter(), 3, 200.0), "▂▄▆");
+        assert_eq!(sparkline(std::iter::empty(), 4, 100.0), "    ");
+    }
+
+    #[test]
+    fn graph_rows_stack_eighths() {
+        let rows = graph_rows(&[0.0, 25.0, 100.0, 60.0], 5, 2, 100.0);
+        assert_eq!(rows, vec!["   █▂", "  ▄██"]);
+        // Autoscaled to the largest value shown, older values cut off
+        let rows = graph_rows(&[1000.0, 1.0, 2.0], 2, 1, 0.0);
+        assert_eq!(rows, vec!["▄█"]);
+    }
+}
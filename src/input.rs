@@ -1,8 +1,10 @@
 use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
 
 use crate::app::{App, AppMode, ProcessTab, KILL_SIGNALS};
+use crate::record::PlaybackCommand;
 use crate::system::process::ProcessSortField;
-use crate::system::winapi;
+use crate::system::backend;
+use crate::views::SavedView;
 
 /// Handle a single key input event.
 pub fn handle_input(app: &mut App, key: KeyEvent) {
@@ -24,11 +26,32 @@ pub fn handle_input(app: &mut App, key: KeyEvent) {
         AppMode::Environment => handle_environment_mode(app, key),
         AppMode::Setup     => handle_setup_mode(app, key),
         AppMode::Handles   => handle_handles_mode(app, key),
+        AppMode::Views     => handle_views_mode(app, key),
+        AppMode::Dashboard => handle_dashboard_mode(app, key),
+        AppMode::Governor => handle_governor_mode(app, key),
+        AppMode::Events => handle_events_mode(app, key),
+        AppMode::MemoryMap => handle_memory_map_mode(app, key),
+        AppMode::Connections => handle_connections_mode(app, key),
+        AppMode::Disks => handle_disks_mode(app, key),
+        AppMode::Filesystems => handle_filesystems_mode(app, key),
     }
 }
 
 // ── Normal mode ─────────────────────────────────────────────────────────
 
+/// Keys that act on the live process behind a PID: renice, signals,
+/// affinity and the handles/memory map/environment/dashboard viewers. While
+/// viewing recorded data the PID may belong to an unrelated process by now.
+fn is_live_process_action(key: &KeyEvent) -> bool {
+    !key.modifiers.contains(KeyModifiers::CONTROL)
+        && matches!(
+            key.code,
+            KeyCode::F(7) | KeyCode::F(8) | KeyCode::F(9) | KeyCode::Char('k' | 'x' | 'a' | 'l' | 'm' | 'e' | 'D')
+        )
+}
+
+// An `if` guard would let a key fall through to later arms
+#[allow(clippy::collapsible_match)]
 fn handle_normal_mode(app: &mut App, key: KeyEvent) {
     match key.code {
         // ── Quit ──
@@ -51,22 +74,47 @@ fn handle_normal_mode(app: &mut App, key: KeyEvent) {
         KeyCode::Home  => app.select_first(),
         KeyCode::End   => app.select_last(),
 
-        // ── Tab key: switch between Main, I/O, and Net tabs ──
+        // ── Ctrl-S — save the current view to a file ──
+        KeyCode::Char('s') if key.modifiers.contains(KeyModifiers::CONTROL) => app.export_current_view(),
+
+        // ── Replay controls (--replay): step, seek ±1 min, speed ──
+        KeyCode::Left | KeyCode::Right if app.viewing_history() => {
+            let dir = if key.code == KeyCode::Left { -1 } else { 1 };
+            app.playback_command = Some(if key.modifiers.contains(KeyModifiers::SHIFT) {
+                PlaybackCommand::Seek(dir * 60)
+            } else {
+                PlaybackCommand::Step(dir)
+            });
+        }
+        KeyCode::Char('f') if app.viewing_history() => app.playback_command = Some(PlaybackCommand::Faster),
+        KeyCode::Char('s') if app.viewing_history() => app.playback_command = Some(PlaybackCommand::Slower),
+
+        // ── Live rewind: [ / ] step one tick through recent history, Esc back to live ──
+        KeyCode::Char('[') => app.playback_command = Some(PlaybackCommand::Step(-1)),
+        KeyCode::Char(']') => app.playback_command = Some(PlaybackCommand::Step(1)),
+        KeyCode::Esc if app.viewing_history() => app.playback_command = Some(PlaybackCommand::Live),
+
+        // ── Process actions are disabled on recorded data ──
+        _ if app.viewing_history() && is_live_process_action(&key) => {}
+
+        // ── Tab key: switch between Main, I/O, Net, GPU and Ports tabs ──
         KeyCode::Tab => {
             app.active_tab = match app.active_tab {
                 ProcessTab::Main => ProcessTab::Io,
                 ProcessTab::Io => ProcessTab::Net,
                 ProcessTab::Net => ProcessTab::Gpu,
-                ProcessTab::Gpu => ProcessTab::Main,
+                ProcessTab::Gpu => ProcessTab::Ports,
+                ProcessTab::Ports => ProcessTab::Main,
             };
         }
         KeyCode::BackTab => {
             // Shift+Tab goes backwards
             app.active_tab = match app.active_tab {
-                ProcessTab::Main => ProcessTab::Gpu,
+                ProcessTab::Main => ProcessTab::Ports,
                 ProcessTab::Io => ProcessTab::Main,
                 ProcessTab::Net => ProcessTab::Io,
                 ProcessTab::Gpu => ProcessTab::Net,
+                ProcessTab::Ports => ProcessTab::Gpu,
             };
         }
 
@@ -129,20 +177,21 @@ fn handle_normal_mode(app: &mut App, key: KeyEvent) {
                 }
                 ProcessTab::Net => { app.net_sort_ascending = !app.net_sort_ascending; app.sort_net_processes(); }
                 ProcessTab::Gpu => { app.gpu_sort_ascending = !app.gpu_sort_ascending; app.sort_gpu_processes(); }
+                ProcessTab::Ports => { app.ports_sort_ascending = !app.ports_sort_ascending; app.sort_ports(); }
             }
         }
 
         // ── F7 — Nice - (raise priority / lower nice) ──
         KeyCode::F(7) => {
-            if let Some(proc) = app.selected_process() {
-                let _ok = winapi::raise_priority(proc.pid);
+            if let Some(proc) = app.selected_live_process() {
+                let _ok = backend::native().raise_priority(proc.pid);
             }
         }
 
         // ── F8 — Nice + (lower priority / raise nice) ──
         KeyCode::F(8) => {
-            if let Some(proc) = app.selected_process() {
-                let _ok = winapi::lower_priority(proc.pid);
+            if let Some(proc) = app.selected_live_process() {
+                let _ok = backend::native().lower_priority(proc.pid);
             }
         }
 
@@ -163,6 +212,17 @@ fn handle_normal_mode(app: &mut App, key: KeyEvent) {
             app.mode = AppMode::UserFilter;
         }
 
+        // ── Saved views picker ──
+        KeyCode::Char('v') => {
+            app.view_menu_index = app
+                .active_view
+                .as_ref()
+                .and_then(|name| app.saved_views.iter().position(|v| &v.name == name))
+                .unwrap_or(0);
+            app.view_name_input = None;
+            app.mode = AppMode::Views;
+        }
+
         // ── Follow process (htop 'F') ──
         KeyCode::Char('F') => app.toggle_follow(),
 
@@ -225,9 +285,9 @@ fn handle_normal_mode(app: &mut App, key: KeyEvent) {
 
         // ── CPU affinity (htop 'a') ──
         KeyCode::Char('a') => {
-            if let Some(proc) = app.selected_process() {
-                let cpu_count = winapi::get_cpu_count();
-                let (proc_mask, _sys_mask, success) = winapi::get_process_affinity(proc.pid);
+            if let Some(proc) = app.selected_live_process() {
+                let cpu_count = backend::native().cpu_count();
+                let (proc_mask, _sys_mask, success) = backend::native().get_affinity(proc.pid);
                 if success {
                     // Initialize affinity_cpus based on current mask
                     app.affinity_cpus = (0..cpu_count)
@@ -239,19 +299,45 @@ fn handle_normal_mode(app: &mut App, key: KeyEvent) {
         }
 
         // ── Show process environment/details (htop 'e') ──
-        KeyCode::Char('e') => {
-            if app.selected_process().is_some() {
-                app.mode = AppMode::Environment;
-            }
+        KeyCode::Char('e') => app.open_environment(),
+
+        // ── Full-screen dashboard of the selected/followed process ──
+        KeyCode::Char('D') => app.open_dashboard(),
+
+        // ── Process start/exit log ──
+        KeyCode::Char('E') => {
+            app.events_scroll = 0;
+            app.events_filter_editing = false;
+            app.mode = AppMode::Events;
+        }
+
+        // ── Governor policies and action history ──
+        KeyCode::Char('A') => {
+            app.governor_scroll = 0;
+            app.mode = AppMode::Governor;
         }
 
         // ── List open files/handles (htop 'l' - lsof equivalent) ──
-        KeyCode::Char('l') => {
-            if app.selected_process().is_some() {
-                app.mode = AppMode::Handles;
-            }
+        KeyCode::Char('l') => app.open_handles(),
+
+        // ── Memory map of the selected process (pmap equivalent) ──
+        KeyCode::Char('m') => app.open_memory_map(),
+
+        // ── Disk I/O per block device (iostat equivalent) ──
+        KeyCode::Char('O') => {
+            app.disks_scroll = 0;
+            app.mode = AppMode::Disks;
+        }
+
+        // ── Mounted filesystems: space and inodes (df equivalent) ──
+        KeyCode::Char('d') if key.modifiers == KeyModifiers::NONE => {
+            app.filesystems_scroll = 0;
+            app.mode = AppMode::Filesystems;
         }
 
+        // ── Net tab: connections of the selected process (netstat equivalent) ──
+        KeyCode::Enter if app.active_tab == ProcessTab::Net => app.open_connections(),
+
         // ── Number keys: quick PID search ──
         KeyCode::Char(c) if c.is_ascii_digit() => {
             // Switch to search mode with the digit pre-filled
@@ -360,6 +446,7 @@ fn handle_help_mode(app: &mut App, key: KeyEvent) {
 
 // ── Sort selection mode — arrow-key navigable ───────────────────────────
 
+#[allow(clippy::collapsible_match)]
 fn handle_sort_mode(app: &mut App, key: KeyEvent) {
     let field_count = ProcessSortField::all().len();
     // Estimate visible items in sort menu (70% of terminal, minus borders/hints)
@@ -410,6 +497,7 @@ fn handle_sort_mode(app: &mut App, key: KeyEvent) {
 
 // ── Kill mode — signal selection ────────────────────────────────────────
 
+#[allow(clippy::collapsible_match)]
 fn handle_kill_mode(app: &mut App, key: KeyEvent) {
     match key.code {
         KeyCode::Esc => app.mode = AppMode::Normal,
@@ -425,15 +513,17 @@ fn handle_kill_mode(app: &mut App, key: KeyEvent) {
         }
         KeyCode::Enter => {
             let pids: Vec<u32> = if !app.tagged_pids.is_empty() {
-                app.tagged_pids.iter().copied().collect()
-            } else if let Some(proc) = app.selected_process() {
+                app.tagged_pids.iter().copied().filter(|&pid| !app.is_ghost(pid)).collect()
+            } else if let Some(proc) = app.kill_target() {
                 vec![proc.pid]
             } else {
                 vec![]
             };
 
             for pid in pids {
-                kill_process_with_signal(pid, app.kill_signal_index);
+                if let Err(e) = backend::native().kill(pid, app.kill_signal_index) {
+                    app.footer_message = Some((format!("Kill {}: {}", pid, e), std::time::Instant::now()));
+                }
             }
             app.tagged_pids.clear();
             app.mode = AppMode::Normal;
@@ -444,6 +534,7 @@ fn handle_kill_mode(app: &mut App, key: KeyEvent) {
 
 // ── User filter mode — pick a user from the list ────────────────────────
 
+#[allow(clippy::collapsible_match)]
 fn handle_user_filter_mode(app: &mut App, key: KeyEvent) {
     let max_idx = app.available_users.len(); // 0 = "All users", 1..N = actual users
     match key.code {
@@ -477,8 +568,65 @@ fn handle_user_filter_mode(app: &mut App, key: KeyEvent) {
     }
 }
 
+// ── Saved views picker ──────────────────────────────────────────────────
+
+fn handle_views_mode(app: &mut App, key: KeyEvent) {
+    // Naming a new view: the last row turned into a text field
+    if let Some(name) = &mut app.view_name_input {
+        match key.code {
+            KeyCode::Esc => app.view_name_input = None,
+            KeyCode::Backspace => { name.pop(); }
+            KeyCode::Char(c) if key.modifiers == KeyModifiers::NONE || key.modifiers == KeyModifiers::SHIFT => name.push(c),
+            KeyCode::Enter if crate::views::valid_name(name) => {
+                let name = name.trim().to_string();
+                let view = SavedView::capture(app, &name);
+                match app.saved_views.iter().position(|v| v.name == view.name) {
+                    Some(i) => {
+                        app.saved_views[i] = view;
+                        app.view_menu_index = i;
+                    }
+                    None => {
+                        app.saved_views.push(view);
+                        app.view_menu_index = app.saved_views.len() - 1;
+                    }
+                }
+                app.active_view = Some(app.saved_views[app.view_menu_index].name.clone());
+                app.view_name_input = None;
+                let _ = crate::config::PstopConfig::from_app(app).save();
+            }
+            _ => {}
+        }
+        return;
+    }
+
+    // Rows: the saved views, then "Save current view"
+    let save_row = app.saved_views.len();
+    match key.code {
+        KeyCode::Esc | KeyCode::Char('v') => app.mode = AppMode::Normal,
+        KeyCode::Up => app.view_menu_index = app.view_menu_index.saturating_sub(1),
+        KeyCode::Down => app.view_menu_index = (app.view_menu_index + 1).min(save_row),
+        KeyCode::Enter if app.view_menu_index == save_row => {
+            app.view_name_input = Some(app.active_view.clone().unwrap_or_default());
+        }
+        KeyCode::Enter => {
+            let view = app.saved_views[app.view_menu_index].clone();
+            view.apply(app);
+            app.mode = AppMode::Normal;
+        }
+        KeyCode::Delete | KeyCode::Char('d') if app.view_menu_index < save_row => {
+            let removed = app.saved_views.remove(app.view_menu_index);
+            if app.active_view.as_ref() == Some(&removed.name) {
+                app.active_view = None;
+            }
+            let _ = crate::config::PstopConfig::from_app(app).save();
+        }
+        _ => {}
+    }
+}
+
 // ── CPU Affinity mode ───────────────────────────────────────────────────
 
+#[allow(clippy::collapsible_match)]
 fn handle_affinity_mode(app: &mut App, key: KeyEvent) {
     match key.code {
         KeyCode::Esc => {
@@ -486,7 +634,7 @@ fn handle_affinity_mode(app: &mut App, key: KeyEvent) {
         }
         KeyCode::Enter => {
             // Apply the affinity mask
-            if let Some(proc) = app.selected_process() {
+            if let Some(proc) = app.selected_live_process() {
                 let mut mask: usize = 0;
                 for (i, &enabled) in app.affinity_cpus.iter().enumerate() {
                     if enabled {
@@ -494,7 +642,7 @@ fn handle_affinity_mode(app: &mut App, key: KeyEvent) {
                     }
                 }
                 if mask != 0 {
-                    let _ = winapi::set_process_affinity(proc.pid, mask);
+                    let _ = backend::native().set_affinity(proc.pid, mask);
                 }
             }
             app.mode = AppMode::Normal;
@@ -528,10 +676,213 @@ fn handle_affinity_mode(app: &mut App, key: KeyEvent) {
 // ── Environment/Details mode ────────────────────────────────────────────
 
 fn handle_environment_mode(app: &mut App, key: KeyEvent) {
+    // Typing into the variable search
+    if app.environment_search_editing {
+        match key.code {
+            KeyCode::Esc => {
+                app.environment_search.clear();
+                app.environment_search_editing = false;
+            }
+            KeyCode::Enter => app.environment_search_editing = false,
+            KeyCode::Backspace => {
+                app.environment_search.pop();
+            }
+            KeyCode::Char(c) if key.modifiers == KeyModifiers::NONE || key.modifiers == KeyModifiers::SHIFT => {
+                app.environment_search.push(c);
+            }
+            _ => {}
+        }
+        app.environment_scroll = 0;
+        return;
+    }
+
+    let last = app.shown_environment().len().saturating_sub(1);
     match key.code {
         KeyCode::Esc | KeyCode::Char('e') | KeyCode::Char('q') | KeyCode::Enter => {
             app.mode = AppMode::Normal;
         }
+        KeyCode::Char('/') | KeyCode::F(3) => app.environment_search_editing = true,
+        KeyCode::Char('r') => app.read_environment(app.environment_pid),
+        KeyCode::Up => app.environment_scroll = app.environment_scroll.saturating_sub(1),
+        KeyCode::Down => app.environment_scroll = (app.environment_scroll + 1).min(last),
+        KeyCode::PageUp => app.environment_scroll = app.environment_scroll.saturating_sub(10),
+        KeyCode::PageDown => app.environment_scroll = (app.environment_scroll + 10).min(last),
+        KeyCode::Home => app.environment_scroll = 0,
+        KeyCode::End => app.environment_scroll = last,
+        _ => {}
+    }
+}
+
+// ── Process dashboard mode (D) ──────────────────────────────────────────
+
+fn handle_dashboard_mode(app: &mut App, key: KeyEvent) {
+    match key.code {
+        KeyCode::Esc | KeyCode::Char('D') | KeyCode::Char('q') => {
+            app.dashboard = None;
+            app.mode = AppMode::Normal;
+        }
+        _ => {}
+    }
+}
+
+// ── Governor overlay (A) ─────────────────────────────────────────────────
+
+fn handle_governor_mode(app: &mut App, key: KeyEvent) {
+    match key.code {
+        KeyCode::Esc | KeyCode::Char('A') | KeyCode::Char('q') => app.mode = AppMode::Normal,
+        KeyCode::Up => app.governor_scroll = app.governor_scroll.saturating_sub(1),
+        KeyCode::Down => {
+            app.governor_scroll = (app.governor_scroll + 1).min(app.governor.history.len().saturating_sub(1));
+        }
+        KeyCode::PageUp => app.governor_scroll = app.governor_scroll.saturating_sub(10),
+        KeyCode::PageDown => {
+            app.governor_scroll = (app.governor_scroll + 10).min(app.governor.history.len().saturating_sub(1));
+        }
+        KeyCode::Home => app.governor_scroll = 0,
+        _ => {}
+    }
+}
+
+// ── Process event log (E) ───────────────────────────────────────────────
+
+fn handle_events_mode(app: &mut App, key: KeyEvent) {
+    // Typing into the overlay's own filter
+    if app.events_filter_editing {
+        match key.code {
+            KeyCode::Esc => {
+                app.events_filter.clear();
+                app.events_filter_editing = false;
+            }
+            KeyCode::Enter => app.events_filter_editing = false,
+            KeyCode::Backspace => {
+                app.events_filter.pop();
+            }
+            KeyCode::Char(c) if key.modifiers == KeyModifiers::NONE || key.modifiers == KeyModifiers::SHIFT => {
+                app.events_filter.push(c);
+            }
+            _ => {}
+        }
+        app.events_scroll = 0;
+        return;
+    }
+
+    let last = app.shown_events().len().saturating_sub(1);
+    match key.code {
+        KeyCode::Esc | KeyCode::Char('E') | KeyCode::Char('q') => app.mode = AppMode::Normal,
+        KeyCode::Char('/') | KeyCode::F(4) => app.events_filter_editing = true,
+        KeyCode::Tab => {
+            use crate::lifecycle::EventKind;
+            app.events_kind = match app.events_kind {
+                None => Some(EventKind::Started),
+                Some(EventKind::Started) => Some(EventKind::Exited),
+                Some(EventKind::Exited) => None,
+            };
+            app.events_scroll = 0;
+        }
+        KeyCode::Char('w') => app.save_events(),
+        // Scrolling goes back in time from the newest entry
+        KeyCode::Up => app.events_scroll = (app.events_scroll + 1).min(last),
+        KeyCode::Down => app.events_scroll = app.events_scroll.saturating_sub(1),
+        KeyCode::PageUp => app.events_scroll = (app.events_scroll + 10).min(last),
+        KeyCode::PageDown => app.events_scroll = app.events_scroll.saturating_sub(10),
+        KeyCode::Home => app.events_scroll = last,
+        KeyCode::End => app.events_scroll = 0,
+        _ => {}
+    }
+}
+
+// ── Memory map view (m - pmap) ──────────────────────────────────────────
+
+fn handle_memory_map_mode(app: &mut App, key: KeyEvent) {
+    let last = app.memory_map_rows().saturating_sub(1);
+    match key.code {
+        KeyCode::Esc | KeyCode::Char('m') | KeyCode::Char('q') | KeyCode::Enter => {
+            app.mode = AppMode::Normal;
+        }
+        KeyCode::Tab => {
+            app.memory_map_by_file = !app.memory_map_by_file;
+            app.memory_map_scroll = 0;
+        }
+        KeyCode::Char('r') => app.read_memory_map(app.memory_map_pid),
+        KeyCode::Up => app.memory_map_scroll = app.memory_map_scroll.saturating_sub(1),
+        KeyCode::Down => app.memory_map_scroll = (app.memory_map_scroll + 1).min(last),
+        KeyCode::PageUp => app.memory_map_scroll = app.memory_map_scroll.saturating_sub(10),
+        KeyCode::PageDown => app.memory_map_scroll = (app.memory_map_scroll + 10).min(last),
+        KeyCode::Home => app.memory_map_scroll = 0,
+        KeyCode::End => app.memory_map_scroll = last,
+        _ => {}
+    }
+}
+
+// ── Connections view (Enter on the Net tab - netstat) ───────────────────
+
+fn handle_connections_mode(app: &mut App, key: KeyEvent) {
+    // Typing into the viewer's filter
+    if app.connections_filter_editing {
+        match key.code {
+            KeyCode::Esc => {
+                app.connections_filter.clear();
+                app.connections_filter_editing = false;
+            }
+            KeyCode::Enter => app.connections_filter_editing = false,
+            KeyCode::Backspace => {
+                app.connections_filter.pop();
+            }
+            KeyCode::Char(c) if key.modifiers == KeyModifiers::NONE || key.modifiers == KeyModifiers::SHIFT => {
+                app.connections_filter.push(c);
+            }
+            _ => {}
+        }
+        app.connections_scroll = 0;
+        return;
+    }
+
+    let last = app.shown_connections().len().saturating_sub(1);
+    match key.code {
+        KeyCode::Esc | KeyCode::Char('q') | KeyCode::Enter => app.mode = AppMode::Normal,
+        KeyCode::Char('/') | KeyCode::F(4) => app.connections_filter_editing = true,
+        KeyCode::Tab => {
+            app.connections_all = !app.connections_all;
+            app.connections_scroll = 0;
+        }
+        KeyCode::Up => app.connections_scroll = app.connections_scroll.saturating_sub(1),
+        KeyCode::Down => app.connections_scroll = (app.connections_scroll + 1).min(last),
+        KeyCode::PageUp => app.connections_scroll = app.connections_scroll.saturating_sub(10),
+        KeyCode::PageDown => app.connections_scroll = (app.connections_scroll + 10).min(last),
+        KeyCode::Home => app.connections_scroll = 0,
+        KeyCode::End => app.connections_scroll = last,
+        _ => {}
+    }
+}
+
+// ── Disk I/O view (O - iostat) ──────────────────────────────────────────
+
+fn handle_disks_mode(app: &mut App, key: KeyEvent) {
+    let last = app.disks.len().saturating_sub(1);
+    match key.code {
+        KeyCode::Esc | KeyCode::Char('O') | KeyCode::Char('q') | KeyCode::Enter => app.mode = AppMode::Normal,
+        KeyCode::Up => app.disks_scroll = app.disks_scroll.saturating_sub(1),
+        KeyCode::Down => app.disks_scroll = (app.disks_scroll + 1).min(last),
+        KeyCode::PageUp => app.disks_scroll = app.disks_scroll.saturating_sub(10),
+        KeyCode::PageDown => app.disks_scroll = (app.disks_scroll + 10).min(last),
+        KeyCode::Home => app.disks_scroll = 0,
+        KeyCode::End => app.disks_scroll = last,
+        _ => {}
+    }
+}
+
+// ── Filesystems view (d - df) ───────────────────────────────────────────
+
+fn handle_filesystems_mode(app: &mut App, key: KeyEvent) {
+    let last = app.filesystems.len().saturating_sub(1);
+    match key.code {
+        KeyCode::Esc | KeyCode::Char('d') | KeyCode::Char('q') | KeyCode::Enter => app.mode = AppMode::Normal,
+        KeyCode::Up => app.filesystems_scroll = app.filesystems_scroll.saturating_sub(1),
+        KeyCode::Down => app.filesystems_scroll = (app.filesystems_scroll + 1).min(last),
+        KeyCode::PageUp => app.filesystems_scroll = app.filesystems_scroll.saturating_sub(10),
+        KeyCode::PageDown => app.filesystems_scroll = (app.filesystems_scroll + 10).min(last),
+        KeyCode::Home => app.filesystems_scroll = 0,
+        KeyCode::End => app.filesystems_scroll = last,
         _ => {}
     }
 }
@@ -539,18 +890,53 @@ fn handle_environment_mode(app: &mut App, key: KeyEvent) {
 // ── Handles view mode (l - lsof) ────────────────────────────────────────
 
 fn handle_handles_mode(app: &mut App, key: KeyEvent) {
+    // Typing into the viewer's filter
+    if app.handles_filter_editing {
+        match key.code {
+            KeyCode::Esc => {
+                app.handles_filter.clear();
+                app.handles_filter_editing = false;
+            }
+            KeyCode::Enter => app.handles_filter_editing = false,
+            KeyCode::Backspace => {
+                app.handles_filter.pop();
+            }
+            KeyCode::Char(c) if key.modifiers == KeyModifiers::NONE || key.modifiers == KeyModifiers::SHIFT => {
+                app.handles_filter.push(c);
+            }
+            _ => {}
+        }
+        app.handles_scroll = 0;
+        return;
+    }
+
+    let last = app.shown_handles().len().saturating_sub(1);
     match key.code {
         KeyCode::Esc | KeyCode::Char('l') | KeyCode::Char('q') | KeyCode::Enter => {
             app.mode = AppMode::Normal;
         }
+        KeyCode::Char('/') | KeyCode::F(4) => app.handles_filter_editing = true,
+        KeyCode::Char('s') => {
+            app.handles_sort = app.handles_sort.next();
+            app.handles_scroll = 0;
+        }
+        KeyCode::Char('r') => app.read_handles(app.handles_pid),
+        KeyCode::Up => app.handles_scroll = app.handles_scroll.saturating_sub(1),
+        KeyCode::Down => app.handles_scroll = (app.handles_scroll + 1).min(last),
+        KeyCode::PageUp => app.handles_scroll = app.handles_scroll.saturating_sub(10),
+        KeyCode::PageDown => app.handles_scroll = (app.handles_scroll + 10).min(last),
+        KeyCode::Home => app.handles_scroll = 0,
+        KeyCode::End => app.handles_scroll = last,
         _ => {}
     }
 }
 
 // ── Setup/Configuration mode (F2) ───────────────────────────────────────
 
+#[allow(clippy::collapsible_match)]
 fn handle_setup_mode(app: &mut App, key: KeyEvent) {
     use crate::color_scheme::{ColorScheme, ColorSchemeId};
+    use crate::meters::Meter;
     use crate::ui::setup_menu::AVAILABLE_METERS;
     let all_fields = ProcessSortField::all();
     let num_categories = 5usize; // Meters, Display options, Colors, Columns, Reset
@@ -566,7 +952,7 @@ fn handle_setup_mode(app: &mut App, key: KeyEvent) {
                 meter_list.len().saturating_sub(1)
             }
         }
-        1 => 15, // 15 display options + interval row
+        1 => 16, // 16 display options + interval row
         2 => ColorSchemeId::all().len().saturating_sub(1),
         3 => all_fields.len().saturating_sub(1), // All fields, not just visible ones
         4 => 1, // Reset: 0=confirm, 1=cancel
@@ -655,6 +1041,13 @@ fn handle_setup_mode(app: &mut App, key: KeyEvent) {
             }
         }
         // ── Actions ──
+        KeyCode::Char(' ') if app.setup_category == 0 && app.setup_panel == 1 && app.setup_meter_col <= 1 => {
+            // Space on a placed meter cycles its mode (htop: Bar → Text → Graph → LED)
+            let meters = if app.setup_meter_col == 0 { &mut app.left_meters } else { &mut app.right_meters };
+            if let Some(meter) = meters.get_mut(app.setup_menu_index) {
+                meter.cycle_mode();
+            }
+        }
         KeyCode::Char(' ') | KeyCode::Enter => {
             if app.setup_panel == 0 {
                 app.setup_panel = 1;
@@ -671,7 +1064,7 @@ fn handle_setup_mode(app: &mut App, key: KeyEvent) {
                                 } else {
                                     &mut app.right_meters
                                 };
-                                target.push(meter_name.to_string());
+                                target.push(Meter::new(meter_name));
                             }
                         } else {
                             // In left/right column — move to available meters to add
@@ -681,7 +1074,7 @@ fn handle_setup_mode(app: &mut App, key: KeyEvent) {
                         }
                     }
                     1 => {
-                        // Display options toggles (14 options + interval)
+                        // Display options toggles (16 options + interval)
                         match app.setup_menu_index {
                             0  => app.show_tree_by_default = !app.show_tree_by_default,
                             1  => app.shadow_other_users = !app.shadow_other_users,
@@ -698,6 +1091,7 @@ fn handle_setup_mode(app: &mut App, key: KeyEvent) {
                             12 => app.show_merged_command = !app.show_merged_command,
                             13 => app.enable_mouse = !app.enable_mouse,
                             14 => app.vim_keys = !app.vim_keys,
+                            15 => app.highlight_changes = !app.highlight_changes,
                             _ => {} // interval row, use +/-
                         }
                     }
@@ -800,61 +1194,9 @@ fn handle_setup_mode(app: &mut App, key: KeyEvent) {
 
 // ── Helpers ─────────────────────────────────────────────────────────────
 
-/// Kill a process by PID on Windows.
-/// signal_index: 0=SIGTERM (graceful), 1=SIGKILL (force), etc.
-///
-/// All paths are non-blocking so the TUI never freezes:
-/// - Force kill uses Win32 TerminateProcess (instant).
-/// - Graceful kill spawns taskkill in a background thread with a timeout.
-fn kill_process_with_signal(pid: u32, signal_index: usize) {
-    match signal_index {
-        0 => {
-            // SIGTERM equivalent: graceful close via taskkill, non-blocking
-            std::thread::spawn(move || {
-                use std::process::Command;
-                #[cfg(windows)]
-                use std::os::windows::process::CommandExt;
-                // CREATE_NO_WINDOW = 0x08000000
-                let child = Command::new("taskkill")
-                    .args(["/PID", &pid.to_string()])
-                    .creation_flags(0x08000000)
-                    .spawn();
-                if let Ok(mut child) = child {
-                    // Wait up to 5 seconds, then give up (don't block forever)
-                    let deadline = std::time::Instant::now()
-                        + std::time::Duration::from_secs(5);
-                    loop {
-                        match child.try_wait() {
-                            Ok(Some(_)) => break,
-                            Ok(None) => {
-                                if std::time::Instant::now() >= deadline {
-                                    let _ = child.kill();
-                                    break;
-                                }
-                                std::thread::sleep(std::time::Duration::from_millis(50));
-                            }
-                            Err(_) => break,
-                        }
-                    }
-                }
-            });
-        }
-        _ => {
-            // SIGKILL / force kill: use TerminateProcess directly (instant, no subprocess)
-            use windows::Win32::System::Threading::{OpenProcess, TerminateProcess, PROCESS_TERMINATE};
-            unsafe {
-                if let Ok(handle) = OpenProcess(PROCESS_TERMINATE, false, pid) {
-                    let _ = TerminateProcess(handle, 1);
-                    let _ = windows::Win32::Foundation::CloseHandle(handle);
-                }
-            }
-        }
-    }
-}
-
 /// Cycle through sort fields (tab-aware: uses header fields for current tab)
 fn cycle_sort_field(app: &mut App, forward: bool) {
-    use crate::ui::process_table::{HEADERS, IO_HEADERS, NET_HEADERS, GPU_HEADERS};
+    use crate::ui::process_table::{HEADERS, IO_HEADERS, NET_HEADERS, GPU_HEADERS, PORTS_HEADERS};
     use crate::app::ProcessTab;
 
     let headers: &[(&str, u16, ProcessSortField, u8)] = match app.active_tab {
@@ -862,6 +1204,7 @@ fn cycle_sort_field(app: &mut App, forward: bool) {
         ProcessTab::Io   => IO_HEADERS,
         ProcessTab::Net  => NET_HEADERS,
         ProcessTab::Gpu  => GPU_HEADERS,
+        ProcessTab::Ports => PORTS_HEADERS,
     };
     let fields: Vec<ProcessSortField> = headers.iter().map(|(_, _, f, _)| *f).collect();
     let current = app.active_sort_field();
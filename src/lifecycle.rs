@@ -0,0 +1,356 @@
+//! Process starts and exits, found by diffing the process list between
+//! ticks.
+//!
+//! A process is its PID plus its start time (as an offset from boot,
+//! `uptime - run_time`), so a PID reused by a new program counts as one exit
+//! and one start. With "Highlight new and old processes" on, the table tints
+//! recent starts and keeps recent exits as greyed-out ghost rows holding
+//! their final values.
+//!
+//! Every start and exit also goes into a chronological log (the `E`
+//! overlay), optionally appended to `event_log` as it happens in the live
+//! UI (never from replays, rewinds or the headless modes). Only
+//! processes alive at some refresh can be seen; a shorter update interval
+//! catches shorter-lived ones.
+
+use std::collections::{HashMap, VecDeque};
+use std::io::Write;
+use std::path::PathBuf;
+
+use crate::system::memory::format_bytes;
+use crate::system::process::ProcessInfo;
+
+/// How long new processes stay tinted and exited ones linger, as in htop
+pub const DEFAULT_HIGHLIGHT_SECS: u64 = 5;
+
+/// Events kept in the log
+pub const LOG_LEN: usize = 10_000;
+
+/// Start times this far apart are the same process: uptime and run time
+/// are rounded to whole seconds independently
+const START_SLACK_SECS: u64 = 1;
+
+#[derive(Debug, Clone, Copy)]
+struct Seen {
+    /// Seconds after boot the process started
+    start: u64,
+    /// When this tracker first saw it; None if it was already running when
+    /// tracking began
+    since_ms: Option<i64>,
+    peak_cpu: f32,
+    peak_res: u64,
+}
+
+/// An exited process and its values from the last tick it was alive
+#[derive(Debug, Clone)]
+pub struct Ghost {
+    pub process: ProcessInfo,
+    pub exited_ms: i64,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum EventKind {
+    Started,
+    Exited,
+}
+
+impl EventKind {
+    pub fn label(&self) -> &'static str {
+        match self {
+            Self::Started => "START",
+            Self::Exited => "EXIT",
+        }
+    }
+}
+
+/// One entry of the event log
+#[derive(Debug, Clone)]
+pub struct LifecycleEvent {
+    pub at_ms: i64,
+    pub kind: EventKind,
+    /// The process as first seen (start) or last seen (exit)
+    pub process: ProcessInfo,
+    /// Exits: run time when last seen, and the peaks over the ticks it was
+    /// seen (since tracking began, for processes that were already running)
+    pub lifetime_secs: u64,
+    pub peak_cpu: f32,
+    pub peak_res: u64,
+}
+
+impl LifecycleEvent {
+    /// The entry as a line of text for the log file
+    pub fn to_line(&self) -> String {
+        let p = &self.process;
+        let time = chrono::DateTime::from_timestamp_millis(self.at_ms)
+            .map(|t| t.with_timezone(&chrono::Local).format("%Y-%m-%d %H:%M:%S%.3f").to_string())
+            .unwrap_or_default();
+        let mut line = format!(
+            "{} {:<5} pid={} ppid={} user={} name={}",
+            time,
+            self.kind.label(),
+            p.pid,
+            p.ppid,
+            p.user,
+            p.name
+        );
+        if self.kind == EventKind::Exited {
+            line.push_str(&format!(
+                " lifetime={} peak_cpu={:.1}% peak_res={}",
+                format_lifetime(self.lifetime_secs),
+                self.peak_cpu,
+                format_bytes(self.peak_res)
+            ));
+        }
+        line.push_str(&format!(" cmd={:?}", p.command));
+        line
+    }
+}
+
+/// `45s`, `12m03s`, `3h07m`, `2d04h`
+pub fn format_lifetime(secs: u64) -> String {
+    match secs {
+        0..=59 => format!("{}s", secs),
+        60..=3599 => format!("{}m{:02}s", secs / 60, secs % 60),
+        3600..=86399 => format!("{}h{:02}m", secs / 3600, secs % 3600 / 60),
+        _ => format!("{}d{:02}h", secs / 86400, secs % 86400 / 3600),
+    }
+}
+
+/// Append events to `path`, or to `events.log` next to the config file
+pub fn append_events(path: &str, events: &[&LifecycleEvent]) -> std::io::Result<PathBuf> {
+    let path = if path.is_empty() {
+        crate::config::config_dir()
+            .map(|dir| dir.join("events.log"))
+            .ok_or_else(|| std::io::Error::other("no config directory for events.log"))?
+    } else {
+        PathBuf::from(path)
+    };
+    if let Some(parent) = path.parent().filter(|p| !p.as_os_str().is_empty()) {
+        std::fs::create_dir_all(parent)?;
+    }
+    let mut file = std::fs::OpenOptions::new().create(true).append(true).open(&path)?;
+    for event in events {
+        writeln!(file, "{}", event.to_line())?;
+    }
+    Ok(path)
+}
+
+/// Live PIDs with their start times, recent exits and the event log
+#[derive(Debug, Clone, Default)]
+pub struct Lifecycle {
+    seen: HashMap<u32, Seen>,
+    /// Last tick's processes, kept for their final values
+    last: HashMap<u32, ProcessInfo>,
+    ghosts: Vec<Ghost>,
+    /// Oldest first
+    log: VecDeque<LifecycleEvent>,
+    primed: bool,
+}
+
+impl Lifecycle {
+    /// Diff this tick against the last and log the starts and exits.
+    /// Ghosts older than `linger_ms` are dropped. The first call only learns
+    /// what's running. Returns the new log entries.
+    pub fn update(&mut self, processes: &[ProcessInfo], uptime: u64, now_ms: i64, linger_ms: i64) -> Vec<LifecycleEvent> {
+        let mut exited = Vec::new();
+        let mut started = Vec::new();
+        let mut seen = HashMap::with_capacity(processes.len());
+        let mut last = HashMap::with_capacity(processes.len());
+
+        // Thread rows come and go with the H toggle
+        for p in processes.iter().filter(|p| !p.is_thread) {
+            let start = uptime.saturating_sub(p.run_time);
+            let mut entry = match self.seen.get(&p.pid) {
+                Some(old) if old.start.abs_diff(start) <= START_SLACK_SECS => *old,
+                old => {
+                    if let Some(old) = old {
+                        // Same PID, different process: the old one is gone
+                        if let Some(prev) = self.last.get(&p.pid) {
+                            exited.push(self.exit_event(prev, old, now_ms));
+                        }
+                    }
+                    if self.primed {
+                        started.push(LifecycleEvent {
+                            at_ms: now_ms,
+                            kind: EventKind::Started,
+                            process: p.clone(),
+                            lifetime_secs: p.run_time,
+                            peak_cpu: p.cpu_usage,
+                            peak_res: p.resident_mem,
+                        });
+                    }
+                    Seen { start, since_ms: self.primed.then_some(now_ms), peak_cpu: 0.0, peak_res: 0 }
+                }
+            };
+            entry.peak_cpu = entry.peak_cpu.max(p.cpu_usage);
+            entry.peak_res = entry.peak_res.max(p.resident_mem);
+            seen.insert(p.pid, entry);
+            last.insert(p.pid, p.clone());
+        }
+        for (pid, prev) in &self.last {
+            if !seen.contains_key(pid) {
+                if let Some(old) = self.seen.get(pid) {
+                    exited.push(self.exit_event(prev, old, now_ms));
+                }
+            }
+        }
+
+        self.ghosts.retain(|g| now_ms - g.exited_ms < linger_ms && !seen.contains_key(&g.process.pid));
+        for event in &exited {
+            if !seen.contains_key(&event.process.pid) {
+                self.ghosts.push(Ghost { process: event.process.clone(), exited_ms: now_ms });
+            }
+        }
+
+        // Exits first, so a reused PID reads in order
+        exited.sort_by_key(|e| e.process.pid);
+        started.sort_by_key(|e| e.process.pid);
+        exited.append(&mut started);
+        for event in &exited {
+            if self.log.len() == LOG_LEN {
+                self.log.pop_front();
+            }
+            self.log.push_back(event.clone());
+        }
+
+        self.seen = seen;
+        self.last = last;
+        self.primed = true;
+        exited
+    }
+
+    fn exit_event(&self, prev: &ProcessInfo, seen: &Seen, now_ms: i64) -> LifecycleEvent {
+        LifecycleEvent {
+            at_ms: now_ms,
+            kind: EventKind::Exited,
+            process: prev.clone(),
+            lifetime_secs: prev.run_time,
+            peak_cpu: seen.peak_cpu,
+            peak_res: seen.peak_res,
+        }
+    }
+
+    /// Started less than `linger_ms` ago
+    pub fn is_new(&self, pid: u32, now_ms: i64, linger_ms: i64) -> bool {
+        self.seen
+            .get(&pid)
+            .and_then(|s| s.since_ms)
+            .is_some_and(|since| now_ms - since < linger_ms)
+    }
+
+    /// Recently exited processes, oldest first
+    pub fn ghosts(&self) -> &[Ghost] {
+        &self.ghosts
+    }
+
+    pub fn is_ghost(&self, pid: u32) -> bool {
+        !self.seen.contains_key(&pid) && self.ghosts.iter().any(|g| g.process.pid == pid)
+    }
+
+    /// Starts and exits so far, oldest first
+    pub fn log(&self) -> &VecDeque<LifecycleEvent> {
+        &self.log
+    }
+
+    /// Forget what's running; the next update starts tracking afresh. The
+    /// log is kept.
+    pub fn clear(&mut self) {
+        let log = std::mem::take(&mut self.log);
+        *self = Self { log, ..Self::default() };
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::system::fake::FakeSource;
+
+    #[test]
+    fn starts_exits_and_reused_pids() {
+        let app = FakeSource::basic_app();
+        let mut procs = app.processes.clone();
+        let mut life = Lifecycle::default();
+
+        // Whatever runs at the start isn't new
+        assert!(life.update(&procs, 100_000, 0, 5000).is_empty());
+        assert!(!life.is_new(4100, 0, 5000));
+
+        // Two seconds later: sleep exits, a new sleep starts, and 4101's PID
+        // is reused
+        for p in &mut procs {
+            p.run_time += 2;
+        }
+        let sleep = procs.iter().position(|p| p.pid == 7000).unwrap();
+        let gone = procs.remove(sleep);
+        let mut reborn = gone.clone();
+        reborn.pid = 7001;
+        reborn.run_time = 0;
+        procs.push(reborn);
+        let rustc = procs.iter_mut().find(|p| p.pid == 4101).unwrap();
+        rustc.name = "ld".to_string();
+        rustc.run_time = 0;
+        // Rounding: the same process may look a second older or younger
+        procs.iter_mut().find(|p| p.pid == 4100).unwrap().run_time += 1;
+
+        let events = life.update(&procs, 100_002, 2000, 5000);
+        let summary: Vec<(EventKind, u32, &str)> = events.iter().map(|e| (e.kind, e.process.pid, e.process.name.as_str())).collect();
+        assert_eq!(
+            summary,
+            vec![
+                (EventKind::Exited, 4101, "rustc"),
+                (EventKind::Exited, 7000, "sleep"),
+                (EventKind::Started, 4101, "ld"),
+                (EventKind::Started, 7001, "sleep"),
+            ]
+        );
+        assert_eq!(life.log().len(), 4);
+        // Exits carry the values from the last tick and the peaks seen
+        let rustc = &events[0];
+        assert_eq!((rustc.lifetime_secs, rustc.peak_cpu), (410, 88.3));
+        assert!(rustc.to_line().ends_with(
+            "EXIT  pid=4101 ppid=4100 user=alice name=rustc lifetime=6m50s peak_cpu=88.3% peak_res=980M \
+             cmd=\"rustc --crate-name pstop --edition=2021 src/main.rs\""
+        ));
+
+        assert!(life.is_new(7001, 2000, 5000) && life.is_new(4101, 6999, 5000));
+        assert!(!life.is_new(4101, 7000, 5000) && !life.is_new(4100, 2000, 5000));
+        // The reused PID is live, so only sleep lingers, with its last values
+        assert!(life.is_ghost(7000) && !life.is_ghost(4101));
+        assert_eq!(life.ghosts().len(), 1);
+        assert_eq!(life.ghosts()[0].process.command, gone.command);
+
+        for p in &mut procs {
+            p.run_time += 5;
+        }
+        life.update(&procs, 100_007, 7000, 5000);
+        assert!(life.ghosts().is_empty());
+    }
+
+    #[test]
+    fn event_log_is_written_by_the_live_ui_only() {
+        let script = "uptime 5000\n\
+            proc pid=1 ppid=0 user=root name=init cmd=/sbin/init time=400000\n\
+            proc pid=7000 ppid=1 user=alice name=sleep cmd=\"sleep 600\" time=100\n\
+            tick\n\
+            proc pid=1 ppid=0 user=root name=init cmd=/sbin/init time=400000\n\
+            tick\n";
+        let path = std::env::temp_dir().join(format!("pstop-event-log-{}.log", std::process::id()));
+        let run = |live: bool| {
+            let _ = std::fs::remove_file(&path);
+            let mut app = crate::app::App::new();
+            app.event_log_path = path.to_string_lossy().into_owned();
+            app.event_log_live = live;
+            let mut source = FakeSource::from_script(script);
+            source.refresh(&mut app);
+            source.refresh(&mut app);
+            assert_eq!(app.lifecycle.log().len(), 1);
+            std::fs::read_to_string(&path).ok()
+        };
+
+        assert_eq!(run(false), None);
+        let written = run(true).unwrap();
+        let _ = std::fs::remove_file(&path);
+        assert_eq!(written.lines().count(), 1);
+        assert!(written.contains(" EXIT  pid=7000 ppid=1 user=alice name=sleep"));
+    }
+}
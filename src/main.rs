@@ -13,15 +13,30 @@
 
 #![allow(dead_code)]
 
+mod alerts;
 mod app;
+mod batch;
 pub mod color_scheme;
 mod config;
+mod dashboard;
+mod dump;
+mod export;
+mod filter;
+mod governor;
+mod history;
 mod input;
+mod lifecycle;
+mod meters;
+mod metrics;
 mod mouse;
+mod record;
+mod rewind;
 mod system;
 mod ui;
+mod views;
 
 use std::io::{self, BufWriter};
+use std::path::Path;
 use std::time::{Duration, Instant};
 
 use anyhow::Result;
@@ -43,6 +58,7 @@ fn main() -> Result<()> {
     // when the terminal window is closed, user logs off, or system shuts down.
     // Without this, raw mode prevents the default handler from terminating the
     // process, leaving zombie pstop.exe instances in the process list.
+    #[cfg(windows)]
     install_console_ctrl_handler();
 
     let startup_time = Instant::now();
@@ -62,6 +78,78 @@ fn main() -> Result<()> {
             "--compact" | "-c" => {
                 // Compact mode handled below during app init
             }
+            "--record" => {
+                // Headless: no terminal, the recorder owns its own collector
+                drop(collector_handle);
+                let Some(path) = args.get(2) else {
+                    eprintln!("--record needs a file to write to");
+                    std::process::exit(1);
+                };
+                let max_bytes = flag_value(&args, "--max-size")
+                    .and_then(|v| v.parse::<u64>().ok())
+                    .map(|mib| mib * 1024 * 1024)
+                    .unwrap_or(record::DEFAULT_MAX_BYTES);
+                return run_record(Path::new(path), max_bytes);
+            }
+            "--replay" => {
+                // Replay runs the normal TUI on recorded data (loaded below)
+            }
+            "--view" => {
+                // Saved view applied once the first data is in (below)
+            }
+            "--batch" | "-b" => {
+                // Plain text on stdout: no raw mode or alternate screen
+                drop(collector_handle);
+                let opts = batch::BatchOptions {
+                    iterations: number_flag(&args, "-n").unwrap_or(1),
+                    delay_ms: number_flag(&args, "-d").map(|ms| ms.max(100)),
+                    filter: flag_value(&args, "--filter").map(str::to_string),
+                };
+                return batch::run(&opts);
+            }
+            "--dump" => {
+                drop(collector_handle);
+                let format = flag_value(&args, "--format").unwrap_or("json");
+                let Some(format) = dump::DumpFormat::parse(format) else {
+                    eprintln!("Unknown dump format '{}' (expected json, csv or ndjson)", format);
+                    std::process::exit(1);
+                };
+                let count = number_flag(&args, "--count").unwrap_or(1);
+                if count == 0 {
+                    eprintln!("--count must be at least 1");
+                    std::process::exit(1);
+                }
+                let opts = dump::DumpOptions {
+                    format,
+                    count,
+                    interval_ms: number_flag(&args, "--interval").map(|ms| ms.max(100)),
+                };
+                return dump::run(&opts);
+            }
+            "--serve-metrics" => {
+                drop(collector_handle);
+                let Some(addr) = args.get(2) else {
+                    eprintln!("--serve-metrics needs an address to listen on, e.g. 127.0.0.1:9184");
+                    std::process::exit(1);
+                };
+                let opts = metrics::MetricsOptions {
+                    addr: addr.clone(),
+                    top: number_flag(&args, "--top").map(|n| n as usize).unwrap_or(metrics::DEFAULT_TOP),
+                    interval_ms: number_flag(&args, "--interval").map(|ms| ms.max(100)),
+                };
+                return metrics::run(&opts);
+            }
+            "--alerts" => {
+                // Headless: check the alert rules from pstoprc and print events
+                drop(collector_handle);
+                return alerts::run(number_flag(&args, "--interval").map(|ms| ms.max(100)));
+            }
+            "--govern" => {
+                // Headless: apply the governor policies from pstoprc and print actions
+                drop(collector_handle);
+                let dry_run = args.iter().any(|a| a == "--dry-run");
+                return governor::run(number_flag(&args, "--interval").map(|ms| ms.max(100)), dry_run);
+            }
             "--bench" => {
                 // Benchmark mode: measure startup time and exit
                 // Drop the eagerly-spawned collector — benchmark creates its own
@@ -75,6 +163,26 @@ fn main() -> Result<()> {
                 println!();
                 println!("Options:");
                 println!("  --compact, -c     Compact mode (minimal header, ideal for small screens/mobile)");
+                println!("  --record <file>   Record refresh ticks to <file> without the UI (Ctrl+C to stop)");
+                println!("  --max-size <MiB>  Rotate the recording when it reaches this size (default 64)");
+                println!("  --replay <file>   Browse a recording: ←/→ step, Shift+←/→ seek, s/f speed");
+                println!("  --view <name>     Start with a saved view (save views with 'v')");
+                println!("  --batch, -b       Print the header summary and process table as plain text");
+                println!("    -n <count>        Number of iterations (default 1)");
+                println!("    -d <ms>           Delay between iterations (default: saved update interval)");
+                println!("    --filter <query>  Only processes matching an F4 filter (a|b, or e.g. \"cpu > 5 && user == root\")");
+                println!("  --dump            Print samples as machine-readable data (schema v{})", dump::SCHEMA_VERSION);
+                println!("    --format <fmt>    json (default), ndjson or csv");
+                println!("    --count <n>       Number of samples (default 1)");
+                println!("    --interval <ms>   Time between samples (default: saved update interval)");
+                println!("  --serve-metrics <addr>  Serve OpenMetrics at http://<addr>/metrics for scrapers");
+                println!("    --top <n>         Processes per per-process metric (default {})", metrics::DEFAULT_TOP);
+                println!("    --interval <ms>   Time between samples (default: saved update interval)");
+                println!("  --alerts          Check the alert rules in pstoprc without the UI and print events");
+                println!("    --interval <ms>   Time between checks (default: saved update interval)");
+                println!("  --govern          Apply the governor policies in pstoprc without the UI and print actions");
+                println!("    --dry-run         Record what would be done without doing it");
+                println!("    --interval <ms>   Time between checks (default: saved update interval)");
                 println!("  --bench           Benchmark startup time and exit");
                 println!("  --install-alias   Add 'htop' alias to your PowerShell profile");
                 println!("  --help, -h        Show this help message");
@@ -90,6 +198,41 @@ fn main() -> Result<()> {
 
     let compact = args.iter().any(|a| a == "--compact" || a == "-c");
 
+    // Load the recording before touching the terminal so errors print normally
+    let player = if args.get(1).map(String::as_str) == Some("--replay") {
+        let Some(path) = args.get(2) else {
+            eprintln!("--replay needs a recording file");
+            std::process::exit(1);
+        };
+        match record::Player::open(Path::new(path)) {
+            Ok(player) => Some(player),
+            Err(e) => {
+                eprintln!("Cannot replay: {:#}", e);
+                std::process::exit(1);
+            }
+        }
+    } else {
+        None
+    };
+
+    // Look up --view before touching the terminal so a typo prints normally
+    let view = if args.iter().any(|a| a == "--view") {
+        let Some(name) = flag_value(&args, "--view") else {
+            eprintln!("--view needs the name of a saved view");
+            std::process::exit(1);
+        };
+        let views = config::PstopConfig::load().views;
+        match views.into_iter().find(|v| v.name.eq_ignore_ascii_case(name)) {
+            Some(view) => Some(view),
+            None => {
+                eprintln!("No saved view named '{}' (save one with 'v' in pstop)", name);
+                std::process::exit(1);
+            }
+        }
+    } else {
+        None
+    };
+
     // Setup terminal
     enable_raw_mode()?;
     let mut stdout = io::stdout();
@@ -103,7 +246,7 @@ fn main() -> Result<()> {
     terminal.clear()?;
 
     // Run the app
-    let result = run_app(&mut terminal, compact, startup_time, collector_handle);
+    let result = run_app(&mut terminal, compact, startup_time, collector_handle, player, view);
 
     // Restore terminal
     disable_raw_mode()?;
@@ -128,6 +271,7 @@ fn main() -> Result<()> {
 /// on CTRL_CLOSE_EVENT (terminal window closed), CTRL_LOGOFF_EVENT, and
 /// CTRL_SHUTDOWN_EVENT. This prevents zombie processes when the user closes
 /// the terminal tab/window instead of pressing 'q'.
+#[cfg(windows)]
 fn install_console_ctrl_handler() {
     use windows::Win32::System::Console::{SetConsoleCtrlHandler, PHANDLER_ROUTINE};
     use windows::core::BOOL;
@@ -154,61 +298,103 @@ fn install_console_ctrl_handler() {
     }
 }
 
+/// Where refresh ticks come from (one instance for the life of the UI, so
+/// the variant size difference doesn't matter)
+#[allow(clippy::large_enum_variant)]
+enum Source {
+    Live {
+        collector: Collector,
+        rewind: Option<rewind::RewindBuffer>,
+    },
+    Replay(record::Player),
+}
+
+impl Source {
+    fn refresh(&mut self, app: &mut App) {
+        match self {
+            Source::Live { collector, rewind } => {
+                // Z freezes collection; the rewind ring stays browsable
+                if app.paused {
+                    return;
+                }
+                match rewind {
+                    Some(ring) => {
+                        ring.restore_live(app);
+                        collector.refresh(app);
+                        ring.record(app, chrono::Utc::now().timestamp_millis());
+                    }
+                    None => collector.refresh(app),
+                }
+            }
+            Source::Replay(player) => {
+                if !app.paused {
+                    player.advance(app);
+                }
+            }
+        }
+    }
+
+    /// Time until the next refresh
+    fn interval(&self, app: &App) -> Duration {
+        match self {
+            Source::Live { .. } => Duration::from_millis(app.update_interval_ms),
+            Source::Replay(player) => player.interval(app),
+        }
+    }
+}
+
 /// Main application loop
-fn run_app(terminal: &mut Terminal<CrosstermBackend<BufWriter<io::Stdout>>>, compact: bool, startup_time: Instant, collector_handle: std::thread::JoinHandle<Collector>) -> Result<()> {
+#[allow(clippy::collapsible_match)]
+fn run_app(terminal: &mut Terminal<CrosstermBackend<BufWriter<io::Stdout>>>, compact: bool, startup_time: Instant, collector_handle: std::thread::JoinHandle<Collector>, player: Option<record::Player>, view: Option<views::SavedView>) -> Result<()> {
     let mut app = App::new();
     app.compact_mode = compact;
 
     // Load saved configuration (fast file I/O, < 1ms)
     let cfg = config::PstopConfig::load();
     cfg.apply_to(&mut app);
-
-    // Collector initialization was already spawned at the very start of main(),
-    // maximizing overlap with terminal setup + first frame render.
-
-    // ── Instant first frame: render the UI skeleton before any data collection ──
-    // This makes the app appear immediately while system queries run.
-    {
-        let size = terminal.size()?;
-        let header_h = ui::header_height(&app, size.height, size.width) as usize;
-        let footer_h = 1;
-        let available = size.height as usize;
-        app.visible_rows = if available > header_h + footer_h + 2 { available - header_h - footer_h - 2 } else { 5 };
-
-        use std::io::Write;
-        queue!(terminal.backend_mut(), crossterm::terminal::BeginSynchronizedUpdate)?;
-        terminal.draw(|f| ui::draw(f, &app))?;
-        queue!(terminal.backend_mut(), crossterm::terminal::EndSynchronizedUpdate)?;
-        terminal.backend_mut().flush()?;
+    // Checked after each live refresh (replays never reach the collector)
+    app.alerts.enabled = true;
+    if let Some(error) = app.alerts.errors().into_iter().next() {
+        app.footer_message = Some((error, Instant::now()));
     }
-
-    let first_frame_ms = startup_time.elapsed().as_millis();
-
-    // Wait for collector init (most of the ~165ms already elapsed during frame render)
-    let mut collector = collector_handle.join().expect("Collector init panicked");
-
-    // ── Second frame: CPU bars + memory bars appear before process enumeration ──
-    // refresh_header_only() takes ~2ms vs 120ms for full refresh. This gives the
-    // user a responsive frame with live CPU/memory bars while processes load.
-    {
-        collector.refresh_header_only(&mut app);
-        use std::io::Write;
-        queue!(terminal.backend_mut(), crossterm::terminal::BeginSynchronizedUpdate)?;
-        terminal.draw(|f| ui::draw(f, &app))?;
-        queue!(terminal.backend_mut(), crossterm::terminal::EndSynchronizedUpdate)?;
-        terminal.backend_mut().flush()?;
+    // The governor only acts with governor_enabled=1 (pstop --govern always does)
+    app.governor.enabled = cfg.governor_enabled;
+    if let Some(error) = app.governor.errors().into_iter().next() {
+        app.footer_message = Some((error, Instant::now()));
     }
 
-    // Full refresh populates process table (~120ms)
-    collector.refresh(&mut app);
-
-    // Store startup timing for display
-    app.startup_first_frame_ms = first_frame_ms as u64;
-    app.startup_fully_loaded_ms = startup_time.elapsed().as_millis() as u64;
+    let mut source = match player {
+        Some(mut player) => {
+            player.seek(0, &mut app);
+            Source::Replay(player)
+        }
+        None => {
+            // Only ticks collected here go to the event log
+            app.event_log_live = true;
+            let collector = start_live(terminal, &mut app, startup_time, collector_handle)?;
+            let mut rewind = rewind::RewindBuffer::new(app.rewind_minutes, app.rewind_max_mb);
+            if let Some(ring) = &mut rewind {
+                ring.record(&mut app, chrono::Utc::now().timestamp_millis());
+            }
+            Source::Live { collector, rewind }
+        }
+    };
+    if let Some(view) = view {
+        view.apply(&mut app);
+    }
 
     let mut last_tick = Instant::now();
 
     loop {
+        // Carry out replay/rewind keys (step/seek/speed/live) before drawing
+        if let Some(cmd) = app.playback_command.take() {
+            match &mut source {
+                Source::Replay(player) => player.handle(cmd, &mut app),
+                Source::Live { rewind: Some(ring), .. } => ring.handle(cmd, &mut app),
+                Source::Live { rewind: None, .. } => {}
+            }
+        }
+
         // Update visible rows based on terminal size
         let size = terminal.size()?;
         let header_h = ui::header_height(&app, size.height, size.width) as usize;
@@ -275,18 +461,113 @@ fn run_app(terminal: &mut Terminal<CrosstermBackend<BufWriter<io::Stdout>>>, com
 
         // Check if it's time to refresh system data
         let now = Instant::now();
-        let dynamic_tick = Duration::from_millis(app.update_interval_ms);
+        let dynamic_tick = source.interval(&app);
         if now.duration_since(last_tick) >= dynamic_tick {
             should_refresh = true;
             last_tick = now;
         }
 
         if should_refresh {
-            collector.refresh(&mut app);
+            source.refresh(&mut app);
         }
     }
 }
 
+/// Live startup: skeleton frame, header-only frame, then the first full
+/// refresh, so the UI appears before process enumeration finishes
+fn start_live(terminal: &mut Terminal<CrosstermBackend<BufWriter<io::Stdout>>>, app: &mut App, startup_time: Instant, collector_handle: std::thread::JoinHandle<Collector>) -> Result<Collector> {
+    // Collector initialization was already spawned at the very start of main(),
+    // maximizing overlap with terminal setup + first frame render.
+
+    // ── Instant first frame: render the UI skeleton before any data collection ──
+    // This makes the app appear immediately while system queries run.
+    {
+        let size = terminal.size()?;
+        let header_h = ui::header_height(app, size.height, size.width) as usize;
+        let footer_h = 1;
+        let available = size.height as usize;
+        app.visible_rows = if available > header_h + footer_h + 2 { available - header_h - footer_h - 2 } else { 5 };
+
+        use std::io::Write;
+        queue!(terminal.backend_mut(), crossterm::terminal::BeginSynchronizedUpdate)?;
+        terminal.draw(|f| ui::draw(f, app))?;
+        queue!(terminal.backend_mut(), crossterm::terminal::EndSynchronizedUpdate)?;
+        terminal.backend_mut().flush()?;
+    }
+
+    let first_frame_ms = startup_time.elapsed().as_millis();
+
+    // Wait for collector init (most of the ~165ms already elapsed during frame render)
+    let mut collector = collector_handle.join().expect("Collector init panicked");
+
+    // ── Second frame: CPU bars + memory bars appear before process enumeration ──
+    // refresh_header_only() takes ~2ms vs 120ms for full refresh. This gives the
+    // user a responsive frame with live CPU/memory bars while processes load.
+    {
+        collector.refresh_header_only(app);
+        use std::io::Write;
+        queue!(terminal.backend_mut(), crossterm::terminal::BeginSynchronizedUpdate)?;
+        terminal.draw(|f| ui::draw(f, app))?;
+        queue!(terminal.backend_mut(), crossterm::terminal::EndSynchronizedUpdate)?;
+        terminal.backend_mut().flush()?;
+    }
+
+    // Full refresh populates process table (~120ms)
+    collector.refresh(app);
+
+    // Store startup timing for display
+    app.startup_first_frame_ms = first_frame_ms as u64;
+    app.startup_fully_loaded_ms = startup_time.elapsed().as_millis() as u64;
+
+    Ok(collector)
+}
+
+/// Value following `flag` on the command line (`--flag value`)
+fn flag_value<'a>(args: &'a [String], flag: &str) -> Option<&'a str> {
+    args.iter()
+        .position(|a| a == flag)
+        .and_then(|i| args.get(i + 1))
+        .map(String::as_str)
+}
+
+/// Numeric value following `flag`; exits with a message if it isn't a number
+fn number_flag(args: &[String], flag: &str) -> Option<u64> {
+    let value = flag_value(args, flag)?;
+    match value.parse::<u64>() {
+        Ok(n) => Some(n),
+        Err(_) => {
+            eprintln!("{} expects a number, got '{}'", flag, value);
+            std::process::exit(1);
+        }
+    }
+}
+
+/// Headless recording: refresh at the configured interval and append every
+/// tick to `path` until interrupted.
+fn run_record(path: &Path, max_bytes: u64) -> Result<()> {
+    let mut app = App::new();
+    let cfg = config::PstopConfig::load();
+    cfg.apply_to(&mut app);
+
+    let mut collector = Collector::new();
+    let mut recorder = record::Recorder::create(path, max_bytes)?;
+    eprintln!(
+        "Recording to {} every {}ms (Ctrl+C to stop)",
+        path.display(),
+        app.update_interval_ms,
+    );
+
+    loop {
+        let started = Instant::now();
+        collector.refresh(&mut app);
+        let snapshot = record::Snapshot::capture(&app, chrono::Utc::now().timestamp_millis());
+        recorder.write(&snapshot)?;
+
+        let interval = Duration::from_millis(app.update_interval_ms);
+        std::thread::sleep(interval.saturating_sub(started.elapsed()));
+    }
+}
+
 /// Benchmark startup time: initialize everything, render one full frame, and print timing.
 fn run_benchmark() -> Result<()> {
     let args: Vec<String> = std::env::args().collect();
@@ -301,8 +582,9 @@ fn run_benchmark() -> Result<()> {
 
     if verbose {
         // Detailed sub-component timing for Collector::new()
+        let backend = crate::system::backend::native();
         let tc0 = Instant::now();
-        let boot_handle = std::thread::spawn(|| crate::system::winapi::get_real_boot_time());
+        let boot_handle = std::thread::spawn(move || backend.boot_time());
         let tc_boot_spawn = tc0.elapsed();
 
         let mut sys = sysinfo::System::new();
@@ -344,20 +626,20 @@ fn run_benchmark() -> Result<()> {
         let mut collector = Collector::new();
         let tr0 = Instant::now();
 
-        let pids = crate::system::winapi::quick_enumerate_pids();
+        let pids = backend.enumerate_pids();
         let tr_enum = tr0.elapsed();
 
         let pids2 = pids.clone();
-        let io_h = std::thread::spawn(move || crate::system::winapi::batch_io_counters(&pids2));
+        let io_h = std::thread::spawn(move || backend.io_counters(&pids2));
         let pids3 = pids.clone();
-        let data_h = std::thread::spawn(move || crate::system::winapi::collect_process_data(&pids3));
+        let data_h = std::thread::spawn(move || backend.process_data(&pids3));
         let pids4 = pids.clone();
-        let users_h = std::thread::spawn(move || crate::system::winapi::batch_process_users(&pids4));
+        let users_h = std::thread::spawn(move || backend.process_users(&pids4));
         let pids5 = pids;
-        let times_h = std::thread::spawn(move || crate::system::winapi::batch_process_times(&pids5));
+        let times_h = std::thread::spawn(move || backend.process_times(&pids5));
         let tr_spawn = tr0.elapsed();
 
-        // These run while Win32 threads are in-flight
+        // These run while backend threads are in-flight
         collector.sys.refresh_cpu_all();
         let tr_cpu = tr0.elapsed();
         collector.sys.refresh_memory();
@@ -366,6 +648,7 @@ fn run_benchmark() -> Result<()> {
             sysinfo::ProcessesToUpdate::All,
             true,
             sysinfo::ProcessRefreshKind::nothing()
+                .without_tasks()
                 .with_cpu()
                 .with_memory()
                 .with_cmd(sysinfo::UpdateKind::OnlyIfNotSet),
@@ -383,7 +666,7 @@ fn run_benchmark() -> Result<()> {
 
         println!("  refresh() breakdown:");
         println!("    quick_enumerate:    {:>6.1}ms", tr_enum.as_secs_f64() * 1000.0);
-        println!("    spawn Win32 thrds:  {:>6.1}ms", (tr_spawn - tr_enum).as_secs_f64() * 1000.0);
+        println!("    spawn native thrds: {:>6.1}ms", (tr_spawn - tr_enum).as_secs_f64() * 1000.0);
         println!("    refresh_cpu_all:    {:>6.1}ms", (tr_cpu - tr_spawn).as_secs_f64() * 1000.0);
         println!("    refresh_memory:     {:>6.1}ms", (tr_mem2 - tr_cpu).as_secs_f64() * 1000.0);
         println!("    refresh_processes:  {:>6.1}ms", (tr_procs - tr_mem2).as_secs_f64() * 1000.0);
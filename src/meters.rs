@@ -0,0 +1,225 @@
+//! Header meters as configured in Setup → Meters: a meter name plus the
+//! htop display mode (Bar, Text, Graph or LED), and the rolling history the
+//! Graph mode draws from.
+//!
+//! In the config file a meter keeps its plain name in Bar mode and gets a
+//! `:mode` suffix otherwise (`left_meters=AllCPUs;Memory:graph;Swap`), so
+//! files written before modes existed load unchanged.
+
+use std::collections::VecDeque;
+
+use crate::system::cpu::CpuInfo;
+use crate::system::disk::DiskIoInfo;
+use crate::system::memory::MemoryInfo;
+use crate::system::network::NetworkInfo;
+
+/// Samples kept per graph: enough for a full-width meter on a wide terminal
+pub const GRAPH_SAMPLES: usize = 256;
+
+/// How a meter is drawn (htop's meter modes)
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum MeterMode {
+    #[default]
+    Bar,
+    Text,
+    Graph,
+    Led,
+}
+
+impl MeterMode {
+    pub fn name(&self) -> &'static str {
+        match self {
+            Self::Bar => "Bar",
+            Self::Text => "Text",
+            Self::Graph => "Graph",
+            Self::Led => "LED",
+        }
+    }
+
+    pub fn parse(s: &str) -> Option<Self> {
+        match s.to_ascii_lowercase().as_str() {
+            "bar" => Some(Self::Bar),
+            "text" => Some(Self::Text),
+            "graph" => Some(Self::Graph),
+            "led" => Some(Self::Led),
+            _ => None,
+        }
+    }
+
+    /// Header rows the mode takes for a single meter
+    pub fn height(&self) -> usize {
+        match self {
+            Self::Bar | Self::Text => 1,
+            Self::Graph => 4,
+            Self::Led => 3,
+        }
+    }
+}
+
+/// One entry of `left_meters` / `right_meters`
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Meter {
+    pub name: String,
+    pub mode: MeterMode,
+}
+
+impl Meter {
+    pub fn new(name: &str) -> Self {
+        Self { name: name.to_string(), mode: Self::modes(name)[0] }
+    }
+
+    /// Modes this meter can be switched to, default first. The CPU grids
+    /// draw one row per core, which leaves no room for LED digits.
+    pub fn modes(name: &str) -> &'static [MeterMode] {
+        use MeterMode::*;
+        match name {
+            "CPU average" | "Memory" | "Swap" | "Network" | "DiskIO" | "GPU" => &[Bar, Text, Graph, Led],
+            "VMem" | "Disk usage" => &[Bar, Text, Led],
+            "Tasks" | "Load average" | "Uptime" | "Clock" => &[Text, Led],
+            "Hostname" => &[Text],
+            "Blank" => &[Bar],
+            _ if is_cpu_meter(name) => &[Bar, Text, Graph],
+            _ => &[Bar],
+        }
+    }
+
+    /// Switch to the next mode this meter supports
+    pub fn cycle_mode(&mut self) {
+        let modes = Self::modes(&self.name);
+        let next = modes.iter().position(|m| *m == self.mode).map_or(0, |i| (i + 1) % modes.len());
+        self.mode = modes[next];
+    }
+
+    /// Header rows this meter takes (CPU grids are sized separately)
+    pub fn height(&self) -> usize {
+        self.mode.height()
+    }
+
+    /// `Name` or `Name:mode` for the config file
+    pub fn config_value(&self) -> String {
+        if self.mode == Self::modes(&self.name)[0] {
+            self.name.clone()
+        } else {
+            format!("{}:{}", self.name, self.mode.name().to_ascii_lowercase())
+        }
+    }
+
+    /// Read a config entry; an unknown or unsupported mode falls back to the
+    /// meter's default
+    pub fn from_config(value: &str) -> Self {
+        let (name, mode) = match value.rsplit_once(':') {
+            Some((name, mode)) => (name.trim(), MeterMode::parse(mode.trim())),
+            None => (value.trim(), None),
+        };
+        let mut meter = Self::new(name);
+        if let Some(mode) = mode.filter(|m| Self::modes(name).contains(m)) {
+            meter.mode = mode;
+        }
+        meter
+    }
+}
+
+/// Does a meter name stand for the per-core CPU bars?
+pub fn is_cpu_meter(name: &str) -> bool {
+    matches!(name, "AllCPUs" | "AllCPUs2" | "AllCPUs4" | "AllCPUs8") || name.starts_with("CPUs")
+}
+
+/// `["AllCPUs", "Memory"]` → Bar-mode meters
+pub fn meters(names: &[&str]) -> Vec<Meter> {
+    names.iter().map(|n| Meter::new(n)).collect()
+}
+
+// ── Graph history ───────────────────────────────────────────────────────
+
+/// Recent values of the graphable meters, oldest first. CPU, memory, swap,
+/// disk busy time and GPU are fractions of 1; network is bytes/sec (graphs
+/// scale it to the busiest tick on screen).
+#[derive(Debug, Clone, Default)]
+pub struct MeterHistory {
+    pub cpu: VecDeque<f64>,
+    pub cores: Vec<VecDeque<f64>>,
+    pub memory: VecDeque<f64>,
+    pub swap: VecDeque<f64>,
+    pub network: VecDeque<f64>,
+    pub disk: VecDeque<f64>,
+    pub gpu: VecDeque<f64>,
+}
+
+impl MeterHistory {
+    pub fn record(&mut self, cpu: &CpuInfo, memory: &MemoryInfo, network: &NetworkInfo, disk: &DiskIoInfo, gpu_usage: f64) {
+        let fraction = |part: u64, total: u64| if total > 0 { part as f64 / total as f64 } else { 0.0 };
+
+        let n = cpu.cores.len().max(1) as f64;
+        let avg = cpu.cores.iter().map(|c| c.usage_percent as f64).sum::<f64>() / n;
+        push(&mut self.cpu, avg / 100.0);
+        self.cores.resize_with(cpu.cores.len(), VecDeque::new);
+        for (ring, core) in self.cores.iter_mut().zip(&cpu.cores) {
+            push(ring, core.usage_percent as f64 / 100.0);
+        }
+        push(&mut self.memory, fraction(memory.used_mem, memory.total_mem));
+        push(&mut self.swap, fraction(memory.used_swap, memory.total_swap));
+        push(&mut self.network, network.rx_bytes_per_sec + network.tx_bytes_per_sec);
+        push(&mut self.disk, (disk.busy_percent / 100.0).clamp(0.0, 1.0));
+        push(&mut self.gpu, (gpu_usage / 100.0).clamp(0.0, 1.0));
+    }
+
+    pub fn clear(&mut self) {
+        *self = Self::default();
+    }
+}
+
+fn push(ring: &mut VecDeque<f64>, value: f64) {
+    if ring.len() == GRAPH_SAMPLES {
+        ring.pop_front();
+    }
+    ring.push_back(if value.is_finite() { value.max(0.0) } else { 0.0 });
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn config_round_trip_and_cycling() {
+        let mut mem = Meter::from_config("Memory:graph");
+        assert_eq!(mem.mode, MeterMode::Graph);
+        assert_eq!(mem.config_value(), "Memory:graph");
+        mem.cycle_mode();
+        assert_eq!(mem.mode, MeterMode::Led);
+        mem.cycle_mode();
+        assert_eq!(mem.config_value(), "Memory");
+
+        // Text-only meters default to Text and never become a bar
+        let mut tasks = Meter::new("Tasks");
+        assert_eq!(tasks.mode, MeterMode::Text);
+        assert_eq!(tasks.config_value(), "Tasks");
+        tasks.cycle_mode();
+        tasks.cycle_mode();
+        assert_eq!(tasks.mode, MeterMode::Text);
+
+        // Unknown or unsupported modes fall back to the default
+        assert_eq!(Meter::from_config("AllCPUs:led").mode, MeterMode::Bar);
+        assert_eq!(Meter::from_config("Swap:sparkles"), Meter::new("Swap"));
+        assert_eq!(Meter::from_config("Load average:LED").mode, MeterMode::Led);
+    }
+
+    #[test]
+    fn history_is_bounded() {
+        let mut history = MeterHistory::default();
+        let mut cpu = CpuInfo::default();
+        cpu.cores.resize_with(2, Default::default);
+        cpu.cores[1].usage_percent = 50.0;
+        let memory = MemoryInfo { total_mem: 4, used_mem: 1, ..MemoryInfo::new() };
+        let disk = DiskIoInfo { busy_percent: 40.0, ..Default::default() };
+        for _ in 0..GRAPH_SAMPLES + 5 {
+            history.record(&cpu, &memory, &NetworkInfo::default(), &disk, 150.0);
+        }
+        assert_eq!(history.cpu.len(), GRAPH_SAMPLES);
+        assert_eq!(history.cpu.back(), Some(&0.25));
+        assert_eq!(history.cores[1].back(), Some(&0.5));
+        assert_eq!(history.memory.back(), Some(&0.25));
+        assert_eq!(history.swap.back(), Some(&0.0));
+        assert_eq!(history.disk.back(), Some(&0.4));
+        assert_eq!(history.gpu.back(), Some(&1.0));
+    }
+}
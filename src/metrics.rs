@@ -0,0 +1,391 @@
+//! `pstop --serve-metrics <addr>`: an OpenMetrics endpoint for scrapers.
+//!
+//! A sampler thread refreshes a `Collector` every update interval and renders
+//! the exposition text once per sample; the listener thread hands the latest
+//! text to every `GET /metrics`. Plain `std::net` with a short-lived thread
+//! per connection, so a slow or idle client can't hold up other scrapers.
+//!
+//! Exposed families (all gauges unless noted):
+//!
+//! ```text
+//! pstop_cpu_usage_ratio{cpu}                 per core, 0..1 ("total" = all cores)
+//! pstop_cpu_time_fraction{cpu,mode}          mode = user|kernel|dpc|irq
+//! pstop_cpu_frequency_hertz{cpu}
+//! pstop_memory_{total,used,free,cached,buffered}_bytes
+//! pstop_swap_{total,used,free}_bytes
+//! pstop_network_{receive,transmit}_bytes_per_second
+//! pstop_network_{receive,transmit}_bytes     counter (_total)
+//! pstop_load1 / pstop_load5 / pstop_load15
+//! pstop_tasks{state}                         state = total|running|sleeping
+//! pstop_threads
+//! pstop_uptime_seconds
+//! pstop_process_cpu_usage_ratio{pid,name,user}              top N by CPU
+//! pstop_process_resident_memory_bytes{pid,name,user}        top N by RSS
+//! pstop_process_io_{read,write}_bytes_per_second{pid,name,user}  top N by I/O
+//! ```
+
+use std::fmt::Write as _;
+use std::io::{BufRead, BufReader, Read, Write};
+use std::net::{TcpListener, TcpStream};
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+
+use anyhow::{Context, Result};
+
+use crate::app::App;
+use crate::config::PstopConfig;
+use crate::system::collector::Collector;
+use crate::system::process::ProcessInfo;
+
+/// Processes per top-N family unless `--top` says otherwise
+pub const DEFAULT_TOP: usize = 10;
+
+/// Connections served at once; more are closed right away
+const MAX_CONNECTIONS: usize = 32;
+
+/// Longest request head (request line and headers) read from a client
+const MAX_REQUEST_BYTES: u64 = 8192;
+
+const CONTENT_TYPE: &str = "application/openmetrics-text; version=1.0.0; charset=utf-8";
+
+/// Command-line settings for the exporter
+pub struct MetricsOptions {
+    pub addr: String,
+    /// Processes listed in each per-process family
+    pub top: usize,
+    /// Sampling interval; None = the saved update interval
+    pub interval_ms: Option<u64>,
+}
+
+/// Bind `addr`, then serve scrapes until killed
+pub fn run(opts: &MetricsOptions) -> Result<()> {
+    let listener = TcpListener::bind(&opts.addr).with_context(|| format!("cannot listen on {}", opts.addr))?;
+
+    let mut app = App::new();
+    PstopConfig::load().apply_to(&mut app);
+    let delay = Duration::from_millis(opts.interval_ms.unwrap_or(app.update_interval_ms));
+
+    // Prime once so the first scrape already has CPU deltas
+    let mut collector = Collector::new();
+    collector.refresh(&mut app);
+    std::thread::sleep(delay);
+    collector.refresh(&mut app);
+    let latest = Arc::new(Mutex::new(render(&app, opts.top)));
+
+    let top = opts.top;
+    let shared = Arc::clone(&latest);
+    std::thread::spawn(move || {
+        let mut last = Instant::now();
+        loop {
+            std::thread::sleep(delay.saturating_sub(last.elapsed()));
+            last = Instant::now();
+            collector.refresh(&mut app);
+            let text = render(&app, top);
+            *shared.lock().unwrap_or_else(|e| e.into_inner()) = text;
+        }
+    });
+
+    eprintln!("pstop: serving OpenMetrics on http://{}/metrics", listener.local_addr()?);
+    accept(listener, latest);
+    Ok(())
+}
+
+// ── HTTP ────────────────────────────────────────────────────────────────
+
+/// Serve every connection on its own thread, up to `MAX_CONNECTIONS` at once
+fn accept(listener: TcpListener, latest: Arc<Mutex<String>>) {
+    let active = Arc::new(AtomicUsize::new(0));
+    for stream in listener.incoming() {
+        let Ok(stream) = stream else { continue };
+        if active.fetch_add(1, Ordering::SeqCst) >= MAX_CONNECTIONS {
+            active.fetch_sub(1, Ordering::SeqCst);
+            continue;
+        }
+        let latest = Arc::clone(&latest);
+        let active = Arc::clone(&active);
+        std::thread::spawn(move || {
+            // A misbehaving client only costs itself the scrape
+            let _ = serve(stream, &latest);
+            active.fetch_sub(1, Ordering::SeqCst);
+        });
+    }
+}
+
+/// Answer one request on `stream` with the latest exposition
+fn serve(mut stream: TcpStream, latest: &Mutex<String>) -> std::io::Result<()> {
+    stream.set_read_timeout(Some(Duration::from_secs(5)))?;
+    stream.set_write_timeout(Some(Duration::from_secs(5)))?;
+
+    let request_line = read_request_line(&stream)?;
+    let mut parts = request_line.as_deref().unwrap_or("").split_whitespace();
+    let (method, target) = (parts.next().unwrap_or(""), parts.next().unwrap_or(""));
+    let path = target.split('?').next().unwrap_or("");
+    let (status, content_type, body) = match (method, path) {
+        _ if request_line.is_none() => ("400 Bad Request", "text/plain; charset=utf-8", "request too large\n".to_string()),
+        ("GET" | "HEAD", "/metrics") => {
+            let text = latest.lock().unwrap_or_else(|e| e.into_inner()).clone();
+            ("200 OK", CONTENT_TYPE, text)
+        }
+        ("GET" | "HEAD", "/") => ("200 OK", "text/plain; charset=utf-8", "pstop exporter: see /metrics\n".to_string()),
+        ("GET" | "HEAD", _) => ("404 Not Found", "text/plain; charset=utf-8", "not found\n".to_string()),
+        _ => ("405 Method Not Allowed", "text/plain; charset=utf-8", "method not allowed\n".to_string()),
+    };
+
+    let mut response = format!(
+        "HTTP/1.1 {}\r\nContent-Type: {}\r\nContent-Length: {}\r\nConnection: close\r\n\r\n",
+        status,
+        content_type,
+        body.len(),
+    );
+    if method != "HEAD" {
+        response.push_str(&body);
+    }
+    stream.write_all(response.as_bytes())?;
+    stream.flush()
+}
+
+/// Request line of the request on `input`, after draining its headers
+/// (there is no body to read for GET); None if the request line and headers
+/// run past MAX_REQUEST_BYTES
+fn read_request_line(input: impl Read) -> std::io::Result<Option<String>> {
+    let mut reader = BufReader::new(input.take(MAX_REQUEST_BYTES));
+    let mut request_line = String::new();
+    reader.read_line(&mut request_line)?;
+    let mut header = String::new();
+    while request_line.ends_with('\n') && reader.read_line(&mut header)? > 0 && header.trim_end() != "" {
+        header.clear();
+    }
+    let cut_short = reader.get_ref().limit() == 0 && !header.ends_with('\n');
+    Ok((!cut_short).then_some(request_line))
+}
+
+// ── Exposition ──────────────────────────────────────────────────────────
+
+/// Label value with OpenMetrics escapes
+fn label(s: &str) -> String {
+    s.replace('\\', "\\\\").replace('"', "\\\"").replace('\n', "\\n")
+}
+
+/// Sample value; NaN and infinities have their own spellings. Like
+/// `dump::json_num`, f32 values print at f32 precision.
+fn value<T: Into<f64> + std::fmt::Display + Copy>(v: T) -> String {
+    let f: f64 = v.into();
+    if f.is_nan() {
+        "NaN".to_string()
+    } else if f.is_infinite() {
+        if f > 0.0 { "+Inf" } else { "-Inf" }.to_string()
+    } else {
+        format!("{}", v)
+    }
+}
+
+/// Writes metric families to a string
+struct Exposition {
+    out: String,
+}
+
+impl Exposition {
+    fn family(&mut self, name: &str, kind: &str, unit: Option<&str>, help: &str) {
+        let _ = writeln!(self.out, "# TYPE {} {}", name, kind);
+        if let Some(unit) = unit {
+            let _ = writeln!(self.out, "# UNIT {} {}", name, unit);
+        }
+        let _ = writeln!(self.out, "# HELP {} {}", name, help);
+    }
+
+    fn sample<T: Into<f64> + std::fmt::Display + Copy>(&mut self, name: &str, labels: &[(&str, &str)], v: T) {
+        self.out.push_str(name);
+        if !labels.is_empty() {
+            let pairs: Vec<String> = labels.iter().map(|(k, v)| format!("{}=\"{}\"", k, label(v))).collect();
+            let _ = write!(self.out, "{{{}}}", pairs.join(","));
+        }
+        let _ = writeln!(self.out, " {}", value(v));
+    }
+
+    fn gauge(&mut self, name: &str, unit: Option<&str>, help: &str, v: f64) {
+        self.family(name, "gauge", unit, help);
+        self.sample(name, &[], v);
+    }
+
+    /// One gauge per process, for the `top` processes with the largest `key`
+    fn top_processes<T: Into<f64> + std::fmt::Display + Copy>(
+        &mut self,
+        app: &App,
+        top: usize,
+        name: &str,
+        unit: Option<&str>,
+        help: &str,
+        key: impl Fn(&ProcessInfo) -> T,
+    ) {
+        let mut procs: Vec<&ProcessInfo> = app.processes.iter().collect();
+        procs.sort_by(|a, b| key(b).into().total_cmp(&key(a).into()).then(a.pid.cmp(&b.pid)));
+        self.family(name, "gauge", unit, help);
+        for p in procs.into_iter().take(top) {
+            let pid = p.pid.to_string();
+            self.sample(name, &[("pid", &pid), ("name", &p.name), ("user", &p.user)], key(p));
+        }
+    }
+}
+
+/// The whole exposition for one sample, `# EOF` included
+pub fn render(app: &App, top: usize) -> String {
+    let mut e = Exposition { out: String::new() };
+    let cpu = &app.cpu_info;
+
+    e.family("pstop_cpu_usage_ratio", "gauge", Some("ratio"), "CPU busy time, per core and for all cores.");
+    e.sample("pstop_cpu_usage_ratio", &[("cpu", "total")], cpu.total_usage / 100.0);
+    for core in &cpu.cores {
+        let id = core.id.to_string();
+        e.sample("pstop_cpu_usage_ratio", &[("cpu", &id)], core.usage_percent / 100.0);
+    }
+
+    e.family("pstop_cpu_time_fraction", "gauge", None, "Share of CPU time spent per mode.");
+    for core in &cpu.cores {
+        let id = core.id.to_string();
+        for (mode, frac) in [
+            ("user", core.user_frac),
+            ("kernel", core.kernel_frac),
+            ("dpc", core.dpc_frac),
+            ("irq", core.interrupt_frac),
+        ] {
+            e.sample("pstop_cpu_time_fraction", &[("cpu", &id), ("mode", mode)], frac);
+        }
+    }
+
+    e.family("pstop_cpu_frequency_hertz", "gauge", Some("hertz"), "Current core frequency.");
+    for core in &cpu.cores {
+        let id = core.id.to_string();
+        e.sample("pstop_cpu_frequency_hertz", &[("cpu", &id)], core.frequency_mhz as f64 * 1e6);
+    }
+
+    let m = &app.memory_info;
+    for (name, help, bytes) in [
+        ("pstop_memory_total_bytes", "Physical memory.", m.total_mem),
+        ("pstop_memory_used_bytes", "Physical memory in use.", m.used_mem),
+        ("pstop_memory_free_bytes", "Physical memory free.", m.free_mem),
+        ("pstop_memory_cached_bytes", "Physical memory used for the file cache.", m.cached_mem),
+        ("pstop_memory_buffered_bytes", "Physical memory used for buffers.", m.buffered_mem),
+        ("pstop_swap_total_bytes", "Swap space.", m.total_swap),
+        ("pstop_swap_used_bytes", "Swap space in use.", m.used_swap),
+        ("pstop_swap_free_bytes", "Swap space free.", m.free_swap),
+    ] {
+        e.gauge(name, Some("bytes"), help, bytes as f64);
+    }
+
+    let n = &app.network_info;
+    e.gauge("pstop_network_receive_bytes_per_second", None, "Bytes received per second, all interfaces.", n.rx_bytes_per_sec);
+    e.gauge("pstop_network_transmit_bytes_per_second", None, "Bytes sent per second, all interfaces.", n.tx_bytes_per_sec);
+    e.family("pstop_network_receive_bytes", "counter", Some("bytes"), "Bytes received, all interfaces.");
+    e.sample("pstop_network_receive_bytes_total", &[], n.total_rx as f64);
+    e.family("pstop_network_transmit_bytes", "counter", Some("bytes"), "Bytes sent, all interfaces.");
+    e.sample("pstop_network_transmit_bytes_total", &[], n.total_tx as f64);
+
+    e.gauge("pstop_load1", None, "1-minute load average.", app.load_avg_1);
+    e.gauge("pstop_load5", None, "5-minute load average.", app.load_avg_5);
+    e.gauge("pstop_load15", None, "15-minute load average.", app.load_avg_15);
+
+    e.family("pstop_tasks", "gauge", None, "Processes by state.");
+    e.sample("pstop_tasks", &[("state", "total")], app.total_tasks as f64);
+    e.sample("pstop_tasks", &[("state", "running")], app.running_tasks as f64);
+    e.sample("pstop_tasks", &[("state", "sleeping")], app.sleeping_tasks as f64);
+    e.gauge("pstop_threads", None, "Threads in all processes.", app.total_threads as f64);
+    e.gauge("pstop_uptime_seconds", Some("seconds"), "System uptime.", app.uptime_seconds as f64);
+
+    e.top_processes(app, top, "pstop_process_cpu_usage_ratio", Some("ratio"), "CPU usage of the busiest processes (1 = one core).", |p| {
+        p.cpu_usage / 100.0
+    });
+    e.top_processes(app, top, "pstop_process_resident_memory_bytes", Some("bytes"), "Resident memory of the largest processes.", |p| {
+        p.resident_mem as f64
+    });
+    e.top_processes(app, top, "pstop_process_io_read_bytes_per_second", None, "Disk read rate of the busiest readers.", |p| {
+        p.io_read_rate
+    });
+    e.top_processes(app, top, "pstop_process_io_write_bytes_per_second", None, "Disk write rate of the busiest writers.", |p| {
+        p.io_write_rate
+    });
+
+    e.out.push_str("# EOF\n");
+    e.out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::system::fake::FakeSource;
+
+    #[test]
+    fn exposition_covers_system_and_top_processes() {
+        let mut app = FakeSource::basic_app();
+        app.processes[0].name = "odd \"name\"\\".to_string();
+        app.processes[0].cpu_usage = 999.0;
+        let text = render(&app, 3);
+
+        assert!(text.ends_with("# EOF\n"));
+        assert!(text.contains("# TYPE pstop_cpu_usage_ratio gauge\n# UNIT pstop_cpu_usage_ratio ratio\n"));
+        assert_eq!(text.matches("pstop_cpu_usage_ratio{cpu=").count(), 1 + app.cpu_info.cores.len());
+        assert_eq!(text.matches("pstop_cpu_time_fraction{cpu=").count(), 4 * app.cpu_info.cores.len());
+        assert!(text.contains("pstop_cpu_time_fraction{cpu=\"0\",mode=\"dpc\"} "));
+        assert!(text.contains(&format!("\npstop_memory_total_bytes {}\n", app.memory_info.total_mem)));
+        assert!(text.contains("\n# TYPE pstop_network_receive_bytes counter\n"));
+        assert!(text.contains("\npstop_network_receive_bytes_total "));
+        assert!(text.contains(&format!("\npstop_tasks{{state=\"running\"}} {}\n", app.running_tasks)));
+
+        // Top N per family, biggest first, labels escaped
+        assert_eq!(text.matches("\npstop_process_cpu_usage_ratio{").count(), 3);
+        assert_eq!(text.matches("\npstop_process_resident_memory_bytes{").count(), 3);
+        let first = &app.processes[0];
+        assert!(text.contains(&format!(
+            "\npstop_process_cpu_usage_ratio{{pid=\"{}\",name=\"odd \\\"name\\\"\\\\\",user=\"{}\"}} 9.99",
+            first.pid, first.user
+        )));
+        let biggest = app.processes.iter().map(|p| p.resident_mem).max().unwrap();
+        let rss = text.lines().find(|l| l.starts_with("pstop_process_resident_memory_bytes{")).unwrap();
+        assert!(rss.ends_with(&format!(" {}", biggest)));
+
+        // Every non-comment line is `name{labels} value` with a parseable value
+        for line in text.lines().filter(|l| !l.starts_with('#')) {
+            let v = line.rsplit(' ').next().unwrap();
+            assert!(v.parse::<f64>().is_ok() || v == "NaN", "{}", line);
+        }
+    }
+
+    #[test]
+    fn request_heads_are_capped() {
+        let line = |request: &str| read_request_line(request.as_bytes()).unwrap();
+        assert_eq!(line("GET /metrics HTTP/1.1\r\nHost: x\r\n\r\n").as_deref(), Some("GET /metrics HTTP/1.1\r\n"));
+        let padded = |n: usize| format!("GET / HTTP/1.1\r\nX-Pad: {}\r\n\r\n", "a".repeat(n));
+        let fits = padded(0).len();
+        assert!(line(&padded(MAX_REQUEST_BYTES as usize - fits)).is_some());
+        assert_eq!(line(&padded(MAX_REQUEST_BYTES as usize - fits + 1)), None);
+        assert_eq!(line(&format!("GET /{} HTTP/1.1\r\n\r\n", "a".repeat(10_000))), None);
+    }
+
+    #[test]
+    fn serves_metrics_over_http() {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let addr = listener.local_addr().unwrap();
+        let latest = Arc::new(Mutex::new(render(&FakeSource::basic_app(), 5)));
+        let shared = Arc::clone(&latest);
+        std::thread::spawn(move || accept(listener, shared));
+
+        // A client that connects and says nothing doesn't hold up the others
+        let _idle = TcpStream::connect(addr).unwrap();
+        let started = Instant::now();
+
+        let get = |request: &str| {
+            let mut conn = TcpStream::connect(addr).unwrap();
+            conn.write_all(request.as_bytes()).unwrap();
+            let mut response = String::new();
+            conn.read_to_string(&mut response).unwrap();
+            response
+        };
+        let ok = get("GET /metrics HTTP/1.1\r\nHost: localhost\r\nAccept: */*\r\n\r\n");
+        assert!(ok.starts_with("HTTP/1.1 200 OK\r\n"));
+        assert!(ok.contains(&format!("Content-Type: {}\r\n", CONTENT_TYPE)));
+        assert!(ok.ends_with(latest.lock().unwrap().as_str()));
+        assert!(get("GET /nope HTTP/1.1\r\n\r\n").starts_with("HTTP/1.1 404"));
+        assert!(get("POST /metrics HTTP/1.1\r\n\r\n").starts_with("HTTP/1.1 405"));
+        assert!(started.elapsed() < Duration::from_secs(2));
+    }
+}
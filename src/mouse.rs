@@ -3,7 +3,7 @@ use crossterm::event::{MouseEvent, MouseEventKind, MouseButton};
 use crate::app::{App, AppMode, ProcessTab};
 use crate::system::process::ProcessSortField;
 use crate::ui;
-use crate::ui::process_table::{HEADERS, IO_HEADERS, NET_HEADERS, GPU_HEADERS, compute_display_columns};
+use crate::ui::process_table::{HEADERS, IO_HEADERS, NET_HEADERS, GPU_HEADERS, PORTS_HEADERS, compute_display_columns};
 
 /// Handle a mouse event.
 /// Requires the terminal size (columns, rows) to compute layout areas.
@@ -55,8 +55,9 @@ pub fn handle_mouse(app: &mut App, mouse: MouseEvent, term_width: u16, term_heig
 
 // ── Tab bar click ────────────────────────────────────────────────────
 
-/// Tab bar layout: " " (1) + " Main " (6) + " " (1) + " I/O " (5) + " " (1) + " Net " (5) + " " (1) + " GPU " (5)
-/// Main: x in [1..7), I/O: x in [8..13), Net: x in [14..18), GPU: x in [19..23)
+/// Tab bar layout: " " (1) + " Main " (6) + " " (1) + " I/O " (5) + " " (1) + " Net " (5) + " " (1) + " GPU " (5),
+/// then " " (1) + " Ports " (7)
+/// Main: x in [1..7), I/O: x in [8..13), Net: x in [14..18), GPU: x in [19..23), Ports: x in [26..33)
 fn handle_tab_bar_click(app: &mut App, x: u16) {
     if (1..7).contains(&x) {
         app.active_tab = ProcessTab::Main;
@@ -66,6 +67,8 @@ fn handle_tab_bar_click(app: &mut App, x: u16) {
         app.active_tab = ProcessTab::Net;
     } else if (20..25).contains(&x) {
         app.active_tab = ProcessTab::Gpu;
+    } else if (26..33).contains(&x) {
+        app.active_tab = ProcessTab::Ports;
     }
 }
 
@@ -77,6 +80,7 @@ fn handle_header_click(app: &mut App, x: u16, term_width: u16) {
         ProcessTab::Io   => IO_HEADERS,
         ProcessTab::Net  => NET_HEADERS,
         ProcessTab::Gpu  => GPU_HEADERS,
+        ProcessTab::Ports => PORTS_HEADERS,
     };
 
     // Compute display columns (same logic as rendering, so clicks match)
@@ -136,6 +140,12 @@ fn handle_row_click(app: &mut App, y: u16, data_start_y: u16) {
                 app.gpu_selected_index = target_index;
             }
         }
+        ProcessTab::Ports => {
+            let target_index = app.ports_scroll_offset + row_offset;
+            if target_index < app.ports.len() {
+                app.ports_selected_index = target_index;
+            }
+        }
     }
 }
 
@@ -184,7 +194,7 @@ fn handle_footer_click(app: &mut App, x: u16) {
 }
 
 fn execute_fkey_action(app: &mut App, action: FkeyAction) {
-    use crate::system::winapi;
+    use crate::system::backend;
 
     match action {
         FkeyAction::Help => {
@@ -211,14 +221,16 @@ fn execute_fkey_action(app: &mut App, action: FkeyAction) {
             app.sort_menu_index = app.active_sort_field().index();
             app.mode = AppMode::SortSelect;
         }
+        // Recorded PIDs may belong to unrelated live processes by now
+        FkeyAction::NiceMinus | FkeyAction::NicePlus | FkeyAction::Kill if app.viewing_history() => {}
         FkeyAction::NiceMinus => {
-            if let Some(proc) = app.selected_process() {
-                let _ok = winapi::raise_priority(proc.pid);
+            if let Some(proc) = app.selected_live_process() {
+                let _ok = backend::native().raise_priority(proc.pid);
             }
         }
         FkeyAction::NicePlus => {
-            if let Some(proc) = app.selected_process() {
-                let _ok = winapi::lower_priority(proc.pid);
+            if let Some(proc) = app.selected_live_process() {
+                let _ok = backend::native().lower_priority(proc.pid);
             }
         }
         FkeyAction::Kill => {
@@ -0,0 +1,1262 @@
+//! Record and replay of refresh ticks.
+//!
+//! `pstop --record <file>` runs the collector headless and appends every tick
+//! to a recording; `pstop --replay <file>` feeds those ticks back into `App`
+//! in place of the live collector, so sorting, filtering, tree view and the
+//! tabs all work on historical data.
+//!
+//! The file format is line-oriented text, one tab-separated record per line:
+//!
+//! ```text
+//! PSTOPREC  2                                    format header (start of every segment)
+//! T  <unix ms>  key|delta  <uptime> <tasks> <running> <sleeping> <threads>
+//! C  <physical cores>  <brand>                   CPU identity (when changed)
+//! U  <total%> <user> <kernel> <dpc> <irq>        CPU split (every tick)
+//! K  <usage,user,kernel,dpc,irq,mhz> ...         per-core samples (every tick)
+//! M  <8 memory/swap counters>                    (when changed)
+//! N  <rx/s> <tx/s> <total rx> <total tx>         (when changed)
+//! L  <load 1> <load 5> <load 15>                 (when changed)
+//! P  <pid> <ppid> <name> <cmd> <user>           process identity (new or changed)
+//! S  <pid> <state> <pri> <ni> ... <threads> ...  process counters (when changed)
+//! H  <tid> <wait reason> <start address|-> <csw>  after a thread row's `S` line
+//! X  <pid> ...                                   (exited processes)
+//! W  then  w <pid> <name> <rx/s> <tx/s> <conns>  Net tab rows (when changed)
+//! V  then  v <pid> <name> <gpu%> <ded> <shr> <engine>  GPU tab rows (when changed)
+//! D  then  d <name> <r/s> <w/s> <r iops> <w iops> <queue> <util%>  block devices (when changed)
+//! F  then  f <mount> <device> <type> <total> <used> <avail> <inodes used/total|-> <ro>  filesystems (when changed)
+//! G  <usage%> <dedicated> <shared> <adapter>     GPU totals (when changed)
+//! ```
+//!
+//! Version 1 had no `W`/`V`/`G`/`D`/`F` lines. It is still read, and replays
+//! with empty Net and GPU tabs and disk views. Readers skip tags they don't
+//! know, so adding a tag only needs a bump when older readers would misread it.
+//!
+//! Ticks are delta-compressed against the previous tick; a `key` tick resets
+//! the decoder and carries everything, and is written every
+//! `KEYFRAME_INTERVAL` ticks and at the start of every rotated segment, so
+//! any tick can be rebuilt from the nearest keyframe before it.
+
+use std::collections::HashMap;
+use std::fs::{self, File, OpenOptions};
+use std::io::{BufWriter, Read, Write};
+use std::path::{Path, PathBuf};
+use std::time::Duration;
+
+use anyhow::{bail, Context, Result};
+
+use crate::app::App;
+use crate::system::cpu::{CpuCore, CpuInfo};
+use crate::system::disk::{DiskDevice, DiskIoInfo};
+use crate::system::filesystem::FilesystemInfo;
+use crate::system::gpu::GpuProcessInfo;
+use crate::system::memory::MemoryInfo;
+use crate::system::netstat::ProcessNetBandwidth;
+use crate::system::network::NetworkInfo;
+use crate::system::process::{ProcessInfo, ProcessStatus};
+
+/// First word of every segment
+const MAGIC: &str = "PSTOPREC";
+
+/// Bump when the line layout changes (v2: Net, GPU and disk lines)
+pub const FORMAT_VERSION: u32 = 2;
+
+/// A keyframe every N ticks bounds the cost of seeking
+const KEYFRAME_INTERVAL: u32 = 60;
+
+/// Default rotation threshold for `--max-size`
+pub const DEFAULT_MAX_BYTES: u64 = 64 * 1024 * 1024;
+
+/// Rotated segments kept next to the live file (`<file>.1` … `<file>.N`)
+const ROTATE_KEEP: usize = 5;
+
+/// Fastest replay multiplier (speeds double from 1x)
+pub const MAX_SPEED: u32 = 64;
+
+/// Longest real-time wait between replayed ticks (gaps between sessions)
+const MAX_REPLAY_GAP_MS: u64 = 5000;
+
+// ── Snapshot ────────────────────────────────────────────────────────────
+
+/// Everything one refresh tick puts into `App`
+#[derive(Debug, Clone, Default)]
+pub struct Snapshot {
+    pub timestamp_ms: i64,
+    pub cpu_info: CpuInfo,
+    pub cpu_user_frac: f64,
+    pub cpu_kernel_frac: f64,
+    pub cpu_dpc_frac: f64,
+    pub cpu_interrupt_frac: f64,
+    pub memory_info: MemoryInfo,
+    pub network_info: NetworkInfo,
+    pub disks: Vec<DiskDevice>,
+    pub filesystems: Vec<FilesystemInfo>,
+    pub load_avg: (f64, f64, f64),
+    pub uptime_seconds: u64,
+    pub total_tasks: usize,
+    pub running_tasks: usize,
+    pub sleeping_tasks: usize,
+    pub total_threads: usize,
+    pub processes: Vec<ProcessInfo>,
+    pub net_processes: Vec<ProcessNetBandwidth>,
+    pub gpu_processes: Vec<GpuProcessInfo>,
+    pub gpu_adapter_name: String,
+    pub gpu_overall_usage: f64,
+    pub gpu_dedicated_mem: u64,
+    pub gpu_shared_mem: u64,
+}
+
+impl Snapshot {
+    /// Take the data the collector just wrote into the App
+    pub fn capture(app: &App, timestamp_ms: i64) -> Self {
+        Self {
+            timestamp_ms,
+            cpu_info: app.cpu_info.clone(),
+            cpu_user_frac: app.cpu_user_frac,
+            cpu_kernel_frac: app.cpu_kernel_frac,
+            cpu_dpc_frac: app.cpu_dpc_frac,
+            cpu_interrupt_frac: app.cpu_interrupt_frac,
+            memory_info: app.memory_info.clone(),
+            network_info: app.network_info.clone(),
+            disks: app.disks.clone(),
+            filesystems: app.filesystems.clone(),
+            load_avg: (app.load_avg_1, app.load_avg_5, app.load_avg_15),
+            uptime_seconds: app.uptime_seconds,
+            total_tasks: app.total_tasks,
+            running_tasks: app.running_tasks,
+            sleeping_tasks: app.sleeping_tasks,
+            total_threads: app.total_threads,
+            processes: app.processes.clone(),
+            net_processes: app.net_processes.clone(),
+            gpu_processes: app.gpu_processes.clone(),
+            gpu_adapter_name: app.gpu_adapter_name.clone(),
+            gpu_overall_usage: app.gpu_overall_usage,
+            gpu_dedicated_mem: app.gpu_dedicated_mem,
+            gpu_shared_mem: app.gpu_shared_mem,
+        }
+    }
+
+    /// Put the snapshot into the App the same way `Collector::refresh` does,
+    /// keeping the user's sort, filter, tree and selection state.
+    pub fn apply(&self, app: &mut App) {
+        app.cpu_info = self.cpu_info.clone();
+        app.cpu_user_frac = self.cpu_user_frac;
+        app.cpu_kernel_frac = self.cpu_kernel_frac;
+        app.cpu_dpc_frac = self.cpu_dpc_frac;
+        app.cpu_interrupt_frac = self.cpu_interrupt_frac;
+        app.memory_info = self.memory_info.clone();
+        app.network_info = self.network_info.clone();
+        app.disks = self.disks.clone();
+        app.disk_io_info = DiskIoInfo::from_devices(&self.disks);
+        app.filesystems = self.filesystems.clone();
+        (app.load_avg_1, app.load_avg_5, app.load_avg_15) = self.load_avg;
+        app.uptime_seconds = self.uptime_seconds;
+        app.total_tasks = self.total_tasks;
+        app.running_tasks = self.running_tasks;
+        app.sleeping_tasks = self.sleeping_tasks;
+        app.total_threads = self.total_threads;
+        app.processes = self.processes.clone();
+        app.net_processes = self.net_processes.clone();
+        app.gpu_processes = self.gpu_processes.clone();
+        app.gpu_adapter_name = self.gpu_adapter_name.clone();
+        app.gpu_overall_usage = self.gpu_overall_usage;
+        app.gpu_dedicated_mem = self.gpu_dedicated_mem;
+        app.gpu_shared_mem = self.gpu_shared_mem;
+
+        app.collect_users();
+        app.apply_filter();
+        app.sort_processes();
+        if app.tree_view {
+            app.build_tree_view();
+        }
+        app.sort_net_processes();
+        app.sort_gpu_processes();
+        app.follow_process();
+        app.clamp_selection();
+        app.tick += 1;
+    }
+}
+
+// ── Encoding ────────────────────────────────────────────────────────────
+
+/// Escape a free-text field so it can't break the tab/newline framing
+fn escape(s: &str) -> String {
+    let mut out = String::with_capacity(s.len());
+    for c in s.chars() {
+        match c {
+            '\\' => out.push_str("\\\\"),
+            '\t' => out.push_str("\\t"),
+            '\n' => out.push_str("\\n"),
+            '\r' => out.push_str("\\r"),
+            _ => out.push(c),
+        }
+    }
+    out
+}
+
+fn unescape(s: &str) -> String {
+    let mut out = String::with_capacity(s.len());
+    let mut chars = s.chars();
+    while let Some(c) = chars.next() {
+        if c != '\\' {
+            out.push(c);
+            continue;
+        }
+        match chars.next() {
+            Some('t') => out.push('\t'),
+            Some('n') => out.push('\n'),
+            Some('r') => out.push('\r'),
+            Some(other) => out.push(other),
+            None => {}
+        }
+    }
+    out
+}
+
+fn status_from_symbol(s: &str) -> ProcessStatus {
+    match s {
+        "R" => ProcessStatus::Running,
+        "S" => ProcessStatus::Sleeping,
+        "D" => ProcessStatus::DiskSleep,
+        "T" => ProcessStatus::Stopped,
+        "Z" => ProcessStatus::Zombie,
+        _ => ProcessStatus::Unknown,
+    }
+}
+
+/// Encode a process's identity as a `P` line (without the trailing newline)
+fn identity_line(p: &ProcessInfo) -> String {
+    format!(
+        "P\t{}\t{}\t{}\t{}\t{}",
+        p.pid,
+        p.ppid,
+        escape(&p.name),
+        escape(&p.command),
+        escape(&p.user),
+    )
+}
+
+/// Encode a process's counters as an `S` line. Run time is stored as the
+/// start offset from boot, which stays constant while the process lives.
+fn stats_line(p: &ProcessInfo, uptime: u64) -> String {
+    format!(
+        "S\t{}\t{}\t{}\t{}\t{}\t{}\t{}\t{}\t{}\t{}\t{}\t{}\t{}\t{}\t{}",
+        p.pid,
+        p.status.symbol(),
+        p.priority,
+        p.nice,
+        p.virtual_mem,
+        p.resident_mem,
+        p.shared_mem,
+        p.cpu_usage,
+        p.mem_usage,
+        uptime.saturating_sub(p.run_time),
+        p.cpu_time_100ns,
+        p.threads,
+        p.io_read_rate,
+        p.io_write_rate,
+        p.depth,
+    )
+}
+
+/// Encode what marks a thread row (H) as an `H` line
+fn thread_line(p: &ProcessInfo) -> String {
+    format!(
+        "H\t{}\t{}\t{}\t{}",
+        p.pid,
+        escape(&p.wait_reason),
+        p.start_address.map_or("-".to_string(), |a| a.to_string()),
+        p.context_switches,
+    )
+}
+
+/// Encode the Net tab list as a `W` block (header line, then one `w` per row)
+fn net_block(rows: &[ProcessNetBandwidth]) -> String {
+    let mut out = String::from("W");
+    for r in rows {
+        out.push_str(&format!(
+            "\nw\t{}\t{}\t{}\t{}\t{}",
+            r.pid,
+            escape(&r.name),
+            r.recv_bytes_per_sec,
+            r.send_bytes_per_sec,
+            r.connection_count,
+        ));
+    }
+    out
+}
+
+/// Encode the block devices as a `D` block (header line, then one `d` per device)
+fn disk_block(rows: &[DiskDevice]) -> String {
+    let mut out = String::from("D");
+    for d in rows {
+        out.push_str(&format!(
+            "\nd\t{}\t{}\t{}\t{}\t{}\t{}\t{}",
+            escape(&d.name),
+            d.read_bytes_per_sec,
+            d.write_bytes_per_sec,
+            d.reads_per_sec,
+            d.writes_per_sec,
+            d.queue_depth,
+            d.utilization,
+        ));
+    }
+    out
+}
+
+/// Encode the mounted filesystems as an `F` block (header line, then one `f` each)
+fn filesystem_block(rows: &[FilesystemInfo]) -> String {
+    let mut out = String::from("F");
+    for fs in rows {
+        let inodes = fs.inodes.map_or("-".to_string(), |(used, total)| format!("{}/{}", used, total));
+        out.push_str(&format!(
+            "\nf\t{}\t{}\t{}\t{}\t{}\t{}\t{}\t{}",
+            escape(&fs.mount_point),
+            escape(&fs.device),
+            escape(&fs.fs_type),
+            fs.total,
+            fs.used,
+            fs.available,
+            inodes,
+            fs.read_only as u8,
+        ));
+    }
+    out
+}
+
+/// Encode the GPU tab list as a `V` block (header line, then one `v` per row)
+fn gpu_block(rows: &[GpuProcessInfo]) -> String {
+    let mut out = String::from("V");
+    for r in rows {
+        out.push_str(&format!(
+            "\nv\t{}\t{}\t{}\t{}\t{}\t{}",
+            r.pid,
+            escape(&r.name),
+            r.gpu_usage,
+            r.dedicated_mem,
+            r.shared_mem,
+            escape(&r.engine_type),
+        ));
+    }
+    out
+}
+
+/// Turns snapshots into delta-compressed tick records
+pub struct Encoder {
+    /// Last emitted C/M/N/L/G lines and W/V/D/F blocks, to skip unchanged ones
+    prev_lines: HashMap<char, String>,
+    /// Last emitted `P` and `S` lines per PID
+    prev_rows: HashMap<u32, (String, String)>,
+    /// Ticks since the last keyframe (None = next tick must be a keyframe)
+    since_keyframe: Option<u32>,
+    keyframe_interval: u32,
+}
+
+impl Default for Encoder {
+    fn default() -> Self {
+        Self::with_keyframe_interval(KEYFRAME_INTERVAL)
+    }
+}
+
+impl Encoder {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Encoder writing a keyframe every `interval` ticks
+    pub fn with_keyframe_interval(interval: u32) -> Self {
+        Self {
+            prev_lines: HashMap::new(),
+            prev_rows: HashMap::new(),
+            since_keyframe: None,
+            keyframe_interval: interval.max(1),
+        }
+    }
+
+    /// Make the next encoded tick a keyframe
+    pub fn force_keyframe(&mut self) {
+        self.since_keyframe = None;
+    }
+
+    /// Encode one tick, returning the lines to append (newline-terminated)
+    pub fn encode(&mut self, snap: &Snapshot) -> String {
+        let keyframe = match self.since_keyframe {
+            Some(n) if n < self.keyframe_interval => {
+                self.since_keyframe = Some(n + 1);
+                false
+            }
+            _ => {
+                self.since_keyframe = Some(1);
+                self.prev_lines.clear();
+                self.prev_rows.clear();
+                true
+            }
+        };
+
+        let mut out = String::new();
+        out.push_str(&format!(
+            "T\t{}\t{}\t{}\t{}\t{}\t{}\t{}\n",
+            snap.timestamp_ms,
+            if keyframe { "key" } else { "delta" },
+            snap.uptime_seconds,
+            snap.total_tasks,
+            snap.running_tasks,
+            snap.sleeping_tasks,
+            snap.total_threads,
+        ));
+
+        let cpu = &snap.cpu_info;
+        self.push_if_changed(&mut out, 'C', format!("C\t{}\t{}", cpu.physical_cores, escape(&cpu.brand)));
+        out.push_str(&format!(
+            "U\t{}\t{}\t{}\t{}\t{}\n",
+            cpu.total_usage, snap.cpu_user_frac, snap.cpu_kernel_frac, snap.cpu_dpc_frac, snap.cpu_interrupt_frac,
+        ));
+        out.push('K');
+        for c in &cpu.cores {
+            out.push_str(&format!(
+                "\t{},{},{},{},{},{}",
+                c.usage_percent, c.user_frac, c.kernel_frac, c.dpc_frac, c.interrupt_frac, c.frequency_mhz,
+            ));
+        }
+        out.push('\n');
+
+        let m = &snap.memory_info;
+        self.push_if_changed(&mut out, 'M', format!(
+            "M\t{}\t{}\t{}\t{}\t{}\t{}\t{}\t{}",
+            m.total_mem, m.used_mem, m.free_mem, m.cached_mem, m.buffered_mem, m.total_swap, m.used_swap, m.free_swap,
+        ));
+        let n = &snap.network_info;
+        self.push_if_changed(&mut out, 'N', format!(
+            "N\t{}\t{}\t{}\t{}",
+            n.rx_bytes_per_sec, n.tx_bytes_per_sec, n.total_rx, n.total_tx,
+        ));
+        let (l1, l5, l15) = snap.load_avg;
+        self.push_if_changed(&mut out, 'L', format!("L\t{}\t{}\t{}", l1, l5, l15));
+        self.push_if_changed(&mut out, 'W', net_block(&snap.net_processes));
+        self.push_if_changed(&mut out, 'V', gpu_block(&snap.gpu_processes));
+        self.push_if_changed(&mut out, 'D', disk_block(&snap.disks));
+        self.push_if_changed(&mut out, 'F', filesystem_block(&snap.filesystems));
+        self.push_if_changed(&mut out, 'G', format!(
+            "G\t{}\t{}\t{}\t{}",
+            snap.gpu_overall_usage, snap.gpu_dedicated_mem, snap.gpu_shared_mem, escape(&snap.gpu_adapter_name),
+        ));
+
+        // Processes: only new or changed rows, then the PIDs that went away
+        let mut seen = HashMap::with_capacity(snap.processes.len());
+        for p in &snap.processes {
+            let identity = identity_line(p);
+            let mut stats = stats_line(p, snap.uptime_seconds);
+            if p.is_thread {
+                stats.push('\n');
+                stats.push_str(&thread_line(p));
+            }
+            let prev = self.prev_rows.get(&p.pid);
+            if prev.map(|(i, _)| i) != Some(&identity) {
+                out.push_str(&identity);
+                out.push('\n');
+            }
+            if prev.map(|(_, s)| s) != Some(&stats) {
+                out.push_str(&stats);
+                out.push('\n');
+            }
+            seen.insert(p.pid, (identity, stats));
+        }
+        let mut exited: Vec<u32> = self.prev_rows.keys().filter(|pid| !seen.contains_key(pid)).copied().collect();
+        if !exited.is_empty() {
+            exited.sort_unstable();
+            out.push('X');
+            for pid in exited {
+                out.push_str(&format!("\t{}", pid));
+            }
+            out.push('\n');
+        }
+        self.prev_rows = seen;
+
+        out
+    }
+
+    fn push_if_changed(&mut self, out: &mut String, tag: char, line: String) {
+        if self.prev_lines.get(&tag) != Some(&line) {
+            out.push_str(&line);
+            out.push('\n');
+            self.prev_lines.insert(tag, line);
+        }
+    }
+}
+
+// ── Decoding ────────────────────────────────────────────────────────────
+
+/// A process known only by PID until its `P`/`S` lines fill it in
+fn blank_process(pid: u32) -> ProcessInfo {
+    ProcessInfo {
+        pid,
+        ppid: 0,
+        name: String::new(),
+        command: String::new(),
+        user: String::new(),
+        status: ProcessStatus::Unknown,
+        priority: 0,
+        nice: 0,
+        virtual_mem: 0,
+        resident_mem: 0,
+        shared_mem: 0,
+        cpu_usage: 0.0,
+        mem_usage: 0.0,
+        run_time: 0,
+        cpu_time_100ns: 0,
+        threads: 0,
+        io_read_rate: 0.0,
+        io_write_rate: 0.0,
+        depth: 0,
+        is_last_child: false,
+        is_thread: false,
+        wait_reason: String::new(),
+        start_address: None,
+        context_switches: 0,
+    }
+}
+
+/// Rebuilds snapshots from tick records
+#[derive(Default)]
+pub struct Decoder {
+    snap: Snapshot,
+    procs: HashMap<u32, ProcessInfo>,
+}
+
+impl Decoder {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Apply one tick's lines. Unknown or malformed lines are skipped so a
+    /// truncated last line (recorder killed mid-write) doesn't poison replay.
+    pub fn apply(&mut self, tick: &str) {
+        for line in tick.lines() {
+            let mut fields = line.split('\t');
+            let tag = fields.next().unwrap_or("");
+            let fields: Vec<&str> = fields.collect();
+            let num = |i: usize| fields.get(i).and_then(|v| v.parse::<f64>().ok()).unwrap_or(0.0);
+            let int = |i: usize| fields.get(i).and_then(|v| v.parse::<u64>().ok()).unwrap_or(0);
+            match tag {
+                "T" => {
+                    if fields.get(1) == Some(&"key") {
+                        self.snap = Snapshot::default();
+                        self.procs.clear();
+                    }
+                    self.snap.timestamp_ms = fields.first().and_then(|v| v.parse().ok()).unwrap_or(0);
+                    self.snap.uptime_seconds = int(2);
+                    self.snap.total_tasks = int(3) as usize;
+                    self.snap.running_tasks = int(4) as usize;
+                    self.snap.sleeping_tasks = int(5) as usize;
+                    self.snap.total_threads = int(6) as usize;
+                }
+                "C" => {
+                    self.snap.cpu_info.physical_cores = int(0) as usize;
+                    self.snap.cpu_info.brand = unescape(fields.get(1).unwrap_or(&""));
+                }
+                "U" => {
+                    self.snap.cpu_info.total_usage = num(0) as f32;
+                    self.snap.cpu_user_frac = num(1);
+                    self.snap.cpu_kernel_frac = num(2);
+                    self.snap.cpu_dpc_frac = num(3);
+                    self.snap.cpu_interrupt_frac = num(4);
+                }
+                "K" => {
+                    let cores: Vec<CpuCore> = fields
+                        .iter()
+                        .enumerate()
+                        .map(|(id, f)| {
+                            let v: Vec<f64> = f.split(',').map(|x| x.parse().unwrap_or(0.0)).collect();
+                            let at = |i: usize| v.get(i).copied().unwrap_or(0.0);
+                            CpuCore {
+                                id,
+                                usage_percent: at(0) as f32,
+                                user_frac: at(1) as f32,
+                                kernel_frac: at(2) as f32,
+                                dpc_frac: at(3) as f32,
+                                interrupt_frac: at(4) as f32,
+                                frequency_mhz: at(5) as u64,
+                            }
+                        })
+                        .collect();
+                    self.snap.cpu_info.logical_cores = cores.len();
+                    self.snap.cpu_info.cores = cores;
+                }
+                "M" => {
+                    self.snap.memory_info = MemoryInfo {
+                        total_mem: int(0),
+                        used_mem: int(1),
+                        free_mem: int(2),
+                        cached_mem: int(3),
+                        buffered_mem: int(4),
+                        total_swap: int(5),
+                        used_swap: int(6),
+                        free_swap: int(7),
+                    };
+                }
+                "N" => {
+                    self.snap.network_info = NetworkInfo {
+                        rx_bytes_per_sec: num(0),
+                        tx_bytes_per_sec: num(1),
+                        total_rx: int(2),
+                        total_tx: int(3),
+                    };
+                }
+                "L" => self.snap.load_avg = (num(0), num(1), num(2)),
+                "W" => self.snap.net_processes.clear(),
+                "w" if fields.len() >= 5 => {
+                    self.snap.net_processes.push(ProcessNetBandwidth {
+                        pid: int(0) as u32,
+                        name: unescape(fields[1]),
+                        recv_bytes_per_sec: num(2),
+                        send_bytes_per_sec: num(3),
+                        connection_count: int(4) as u32,
+                    });
+                }
+                "V" => self.snap.gpu_processes.clear(),
+                "v" if fields.len() >= 6 => {
+                    self.snap.gpu_processes.push(GpuProcessInfo {
+                        pid: int(0) as u32,
+                        name: unescape(fields[1]),
+                        gpu_usage: num(2),
+                        dedicated_mem: int(3),
+                        shared_mem: int(4),
+                        engine_type: unescape(fields[5]),
+                    });
+                }
+                "D" => self.snap.disks.clear(),
+                "d" if fields.len() >= 7 => {
+                    self.snap.disks.push(DiskDevice {
+                        name: unescape(fields[0]),
+                        read_bytes_per_sec: num(1),
+                        write_bytes_per_sec: num(2),
+                        reads_per_sec: num(3),
+                        writes_per_sec: num(4),
+                        queue_depth: int(5),
+                        utilization: num(6),
+                    });
+                }
+                "F" => self.snap.filesystems.clear(),
+                "f" if fields.len() >= 8 => {
+                    let inodes = fields[6].split_once('/').and_then(|(used, total)| Some((used.parse().ok()?, total.parse().ok()?)));
+                    self.snap.filesystems.push(FilesystemInfo {
+                        mount_point: unescape(fields[0]),
+                        device: unescape(fields[1]),
+                        fs_type: unescape(fields[2]),
+                        total: int(3),
+                        used: int(4),
+                        available: int(5),
+                        inodes,
+                        read_only: int(7) != 0,
+                    });
+                }
+                "G" => {
+                    self.snap.gpu_overall_usage = num(0);
+                    self.snap.gpu_dedicated_mem = int(1);
+                    self.snap.gpu_shared_mem = int(2);
+                    self.snap.gpu_adapter_name = unescape(fields.get(3).unwrap_or(&""));
+                }
+                "P" if fields.len() >= 5 => {
+                    let pid = int(0) as u32;
+                    let p = self.procs.entry(pid).or_insert_with(|| blank_process(pid));
+                    p.ppid = int(1) as u32;
+                    p.name = unescape(fields[2]);
+                    p.command = unescape(fields[3]);
+                    p.user = unescape(fields[4]);
+                }
+                "S" if fields.len() >= 15 => {
+                    let uptime = self.snap.uptime_seconds;
+                    if let Some(p) = self.procs.get_mut(&(int(0) as u32)) {
+                        p.status = status_from_symbol(fields[1]);
+                        p.priority = num(2) as i32;
+                        p.nice = num(3) as i32;
+                        p.virtual_mem = int(4);
+                        p.resident_mem = int(5);
+                        p.shared_mem = int(6);
+                        p.cpu_usage = num(7) as f32;
+                        p.mem_usage = num(8) as f32;
+                        p.run_time = uptime.saturating_sub(int(9));
+                        p.cpu_time_100ns = int(10);
+                        p.threads = int(11) as u32;
+                        p.io_read_rate = num(12);
+                        p.io_write_rate = num(13);
+                        p.depth = int(14) as usize;
+                    }
+                }
+                "H" if fields.len() >= 4 => {
+                    if let Some(p) = self.procs.get_mut(&(int(0) as u32)) {
+                        p.is_thread = true;
+                        p.wait_reason = unescape(fields[1]);
+                        p.start_address = fields[2].parse().ok();
+                        p.context_switches = int(3);
+                    }
+                }
+                "X" => {
+                    for pid in fields.iter().filter_map(|v| v.parse::<u32>().ok()) {
+                        self.procs.remove(&pid);
+                    }
+                }
+                _ => {}
+            }
+        }
+    }
+
+    /// The state after the last applied tick (processes ordered by PID)
+    pub fn snapshot(&self) -> Snapshot {
+        let mut snap = self.snap.clone();
+        let mut processes: Vec<ProcessInfo> = self.procs.values().cloned().collect();
+        processes.sort_by_key(|p| p.pid);
+        snap.processes = processes;
+        snap
+    }
+}
+
+// ── Recorder ────────────────────────────────────────────────────────────
+
+/// Path of rotated segment `n` (`<file>.n`)
+fn segment_path(path: &Path, n: usize) -> PathBuf {
+    let mut s = path.as_os_str().to_owned();
+    s.push(format!(".{}", n));
+    PathBuf::from(s)
+}
+
+/// Appends ticks to a recording, rotating by size
+pub struct Recorder {
+    path: PathBuf,
+    file: BufWriter<File>,
+    bytes_written: u64,
+    max_bytes: u64,
+    encoder: Encoder,
+}
+
+impl Recorder {
+    /// Open (or continue) a recording. An existing recording is appended to;
+    /// the first tick written is always a keyframe so the new session stands
+    /// alone. A recording in another format version is rotated away first, so
+    /// every segment's header matches its lines. Any other non-empty file is
+    /// refused rather than corrupted.
+    pub fn create(path: &Path, max_bytes: u64) -> Result<Self> {
+        let version = Self::existing_version(path)?;
+        let (file, bytes_written) = Self::open_segment(path)?;
+        let mut recorder = Self {
+            path: path.to_path_buf(),
+            file,
+            bytes_written,
+            max_bytes: max_bytes.max(4096),
+            encoder: Encoder::new(),
+        };
+        if version.is_some_and(|v| v != FORMAT_VERSION) {
+            recorder.rotate()?;
+        }
+        Ok(recorder)
+    }
+
+    /// Format version in the header of an existing file (None = missing or empty)
+    fn existing_version(path: &Path) -> Result<Option<u32>> {
+        let mut head = Vec::new();
+        match File::open(path) {
+            Ok(file) => {
+                file.take(64)
+                    .read_to_end(&mut head)
+                    .with_context(|| format!("cannot read {}", path.display()))?;
+            }
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => return Ok(None),
+            Err(e) => return Err(e).with_context(|| format!("cannot open {}", path.display())),
+        }
+        if head.is_empty() {
+            return Ok(None);
+        }
+        let head = String::from_utf8_lossy(&head);
+        let mut parts = head.lines().next().unwrap_or("").split('\t');
+        match (parts.next(), parts.next().and_then(|v| v.parse::<u32>().ok())) {
+            (Some(MAGIC), Some(version)) => Ok(Some(version)),
+            _ => bail!("{} is not a pstop recording; refusing to append to it", path.display()),
+        }
+    }
+
+    fn open_segment(path: &Path) -> Result<(BufWriter<File>, u64)> {
+        let existing = fs::metadata(path).map(|m| m.len()).unwrap_or(0);
+        let file = OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(path)
+            .with_context(|| format!("cannot open {}", path.display()))?;
+        let mut file = BufWriter::new(file);
+        let mut bytes = existing;
+        if existing == 0 {
+            let header = format!("{}\t{}\n", MAGIC, FORMAT_VERSION);
+            file.write_all(header.as_bytes())?;
+            bytes += header.len() as u64;
+        }
+        Ok((file, bytes))
+    }
+
+    /// Append one tick and flush it, so a killed recorder loses nothing
+    pub fn write(&mut self, snap: &Snapshot) -> Result<()> {
+        if self.bytes_written >= self.max_bytes {
+            self.rotate()?;
+        }
+        let record = self.encoder.encode(snap);
+        self.file.write_all(record.as_bytes())?;
+        self.file.flush()?;
+        self.bytes_written += record.len() as u64;
+        Ok(())
+    }
+
+    /// `<file>` → `<file>.1` → … → `<file>.N` (oldest dropped), then start fresh
+    fn rotate(&mut self) -> Result<()> {
+        self.file.flush()?;
+        let _ = fs::remove_file(segment_path(&self.path, ROTATE_KEEP));
+        for n in (1..ROTATE_KEEP).rev() {
+            let _ = fs::rename(segment_path(&self.path, n), segment_path(&self.path, n + 1));
+        }
+        fs::rename(&self.path, segment_path(&self.path, 1))
+            .with_context(|| format!("cannot rotate {}", self.path.display()))?;
+        let (file, bytes) = Self::open_segment(&self.path)?;
+        self.file = file;
+        self.bytes_written = bytes;
+        self.encoder.force_keyframe();
+        Ok(())
+    }
+}
+
+// ── Player ──────────────────────────────────────────────────────────────
+
+/// Replay controls requested by key handlers, carried out by the main loop
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PlaybackCommand {
+    Step(i64),    // ±N ticks (pauses playback)
+    Seek(i64),    // ±N seconds of recorded time
+    Faster,
+    Slower,
+    Live,         // leave rewind and show the newest tick
+}
+
+/// What the header shows while viewing recorded data
+#[derive(Debug, Clone, Default)]
+pub struct PlaybackStatus {
+    pub timestamp_ms: i64,  // wall-clock time of the displayed tick
+    pub position: usize,    // index of the displayed tick
+    pub total: usize,       // ticks available
+    pub speed: u32,         // replay multiplier (1..=MAX_SPEED)
+    pub live_offset_ms: Option<i64>, // rewind: how far behind live (None = --replay)
+}
+
+/// Byte range and time of one tick inside the loaded recording
+struct TickIndex {
+    start: usize,
+    end: usize,
+    timestamp_ms: i64,
+    keyframe: bool,
+}
+
+/// Feeds recorded ticks into the App
+pub struct Player {
+    text: String,
+    ticks: Vec<TickIndex>,
+    position: usize,
+    speed: u32,
+    decoder: Decoder,
+    /// Tick the decoder currently holds
+    decoded: Option<usize>,
+}
+
+impl Player {
+    /// Load a recording, including any rotated segments next to it
+    /// (`<file>.N` … `<file>.1`, oldest first).
+    pub fn open(path: &Path) -> Result<Self> {
+        let mut text = String::new();
+        for n in (1..=ROTATE_KEEP).rev() {
+            if let Ok(segment) = fs::read_to_string(segment_path(path, n)) {
+                Self::check_header(&segment, &segment_path(path, n))?;
+                text.push_str(&segment);
+            }
+        }
+        let live = fs::read_to_string(path).with_context(|| format!("cannot read {}", path.display()))?;
+        Self::check_header(&live, path)?;
+        text.push_str(&live);
+
+        let player = Self::from_text(text);
+        if player.ticks.is_empty() {
+            bail!("{} contains no recorded ticks", path.display());
+        }
+        Ok(player)
+    }
+
+    fn check_header(text: &str, path: &Path) -> Result<()> {
+        let first = text.lines().next().unwrap_or("");
+        let mut parts = first.split('\t');
+        if parts.next() != Some(MAGIC) {
+            bail!("{} is not a pstop recording", path.display());
+        }
+        let version: u32 = parts.next().and_then(|v| v.parse().ok()).unwrap_or(0);
+        if version == 0 || version > FORMAT_VERSION {
+            bail!(
+                "{} uses recording format v{}; this pstop reads up to v{}",
+                path.display(),
+                version,
+                FORMAT_VERSION,
+            );
+        }
+        Ok(())
+    }
+
+    /// Index an already-loaded recording
+    pub fn from_text(text: String) -> Self {
+        let mut ticks: Vec<TickIndex> = Vec::new();
+        let mut offset = 0;
+        for line in text.split_inclusive('\n') {
+            if let Some(rest) = line.strip_prefix("T\t") {
+                if let Some(last) = ticks.last_mut() {
+                    last.end = offset;
+                }
+                let mut fields = rest.split('\t');
+                let timestamp_ms = fields.next().and_then(|v| v.trim().parse().ok()).unwrap_or(0);
+                let keyframe = fields.next() == Some("key");
+                // A delta before any keyframe has nothing to apply to
+                if keyframe || !ticks.is_empty() {
+                    ticks.push(TickIndex { start: offset, end: text.len(), timestamp_ms, keyframe });
+                }
+            }
+            offset += line.len();
+        }
+        Self {
+            text,
+            ticks,
+            position: 0,
+            speed: 1,
+            decoder: Decoder::new(),
+            decoded: None,
+        }
+    }
+
+    pub fn len(&self) -> usize {
+        self.ticks.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.ticks.is_empty()
+    }
+
+    pub fn position(&self) -> usize {
+        self.position
+    }
+
+    /// Rebuild tick `index` (from the nearest keyframe, or incrementally
+    /// when moving forward) and put it into the App
+    pub fn seek(&mut self, index: usize, app: &mut App) {
+        if self.ticks.is_empty() {
+            return;
+        }
+        let index = index.min(self.ticks.len() - 1);
+        let forward = self.decoded.is_some() && index == self.position + 1;
+        let keyframe = (0..=index).rev().find(|&i| self.ticks[i].keyframe).unwrap_or(0);
+        let start = match self.decoded {
+            Some(d) if d <= index && d >= keyframe => d + 1,
+            _ => keyframe,
+        };
+        for tick in &self.ticks[start..=index] {
+            self.decoder.apply(&self.text[tick.start..tick.end]);
+        }
+        self.decoded = Some(index);
+        self.position = index;
+        // Before recording, so the history goes by the recorded clock
+        self.update_status(app);
+
+        self.decoder.snapshot().apply(app);
+
+        // Process history only makes sense tick by tick; a jump starts it over
+        if !forward {
+            app.clear_history();
+        }
+        app.record_history();
+        // Ghost rows and history sorts depend on what was just recorded
+        if app.highlight_changes || app.sort_field.is_history() {
+            app.apply_filter();
+            app.sort_processes();
+            if app.tree_view {
+                app.build_tree_view();
+            }
+            app.clamp_selection();
+        }
+    }
+
+    /// Play the next tick. At the end of the recording playback pauses.
+    pub fn advance(&mut self, app: &mut App) {
+        if self.position + 1 < self.ticks.len() {
+            self.seek(self.position + 1, app);
+        }
+        if self.position + 1 >= self.ticks.len() {
+            app.paused = true;
+        }
+    }
+
+    /// Real time to wait before the next tick, scaled by the replay speed
+    pub fn interval(&self, app: &App) -> Duration {
+        let recorded = match (self.ticks.get(self.position), self.ticks.get(self.position + 1)) {
+            (Some(cur), Some(next)) => (next.timestamp_ms - cur.timestamp_ms).max(0) as u64,
+            _ => app.update_interval_ms,
+        };
+        Duration::from_millis((recorded / self.speed as u64).clamp(10, MAX_REPLAY_GAP_MS))
+    }
+
+    /// Carry out a key-requested playback command
+    pub fn handle(&mut self, cmd: PlaybackCommand, app: &mut App) {
+        match cmd {
+            PlaybackCommand::Step(n) => {
+                app.paused = true;
+                let target = (self.position as i64 + n).max(0) as usize;
+                self.seek(target, app);
+            }
+            PlaybackCommand::Seek(secs) => {
+                let Some(cur) = self.ticks.get(self.position) else { return };
+                let target_ms = cur.timestamp_ms + secs * 1000;
+                let mut target = self.ticks.partition_point(|t| t.timestamp_ms < target_ms);
+                if secs < 0 {
+                    // Land on the last tick at or before the target time
+                    target = self.ticks.partition_point(|t| t.timestamp_ms <= target_ms).saturating_sub(1);
+                }
+                self.seek(target, app);
+            }
+            PlaybackCommand::Faster => {
+                self.speed = (self.speed * 2).min(MAX_SPEED);
+                self.update_status(app);
+            }
+            PlaybackCommand::Slower => {
+                self.speed = (self.speed / 2).max(1);
+                self.update_status(app);
+            }
+            PlaybackCommand::Live => {}
+        }
+    }
+
+    fn update_status(&self, app: &mut App) {
+        app.playback = Some(PlaybackStatus {
+            timestamp_ms: self.ticks.get(self.position).map(|t| t.timestamp_ms).unwrap_or(0),
+            position: self.position,
+            total: self.ticks.len(),
+            speed: self.speed,
+            live_offset_ms: None,
+        });
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::system::fake::FakeSource;
+
+    fn fixture_snapshots() -> Vec<Snapshot> {
+        let mut source = FakeSource::basic();
+        let mut app = App::new();
+        (0..source.frame_count())
+            .map(|i| {
+                source.refresh(&mut app);
+                Snapshot::capture(&app, 1_700_000_000_000 + i as i64 * 1500)
+            })
+            .collect()
+    }
+
+    fn text_of(app: &App) -> Vec<String> {
+        app.filtered_processes
+            .iter()
+            .map(|p| format!("{} {}", identity_line(p), stats_line(p, 0)))
+            .collect()
+    }
+
+    #[test]
+    fn round_trip_through_deltas() {
+        let mut snaps = fixture_snapshots();
+        // Processes age with the clock between ticks
+        let age = snaps[1].uptime_seconds - snaps[0].uptime_seconds;
+        for p in &mut snaps[1].processes {
+            p.run_time += age;
+        }
+        let mut encoder = Encoder::new();
+        let mut text = format!("{}\t{}\n", MAGIC, FORMAT_VERSION);
+        let first = encoder.encode(&snaps[0]);
+        let second = encoder.encode(&snaps[1]);
+        // Unchanged processes, memory and load are not repeated
+        assert!(!second.contains("\nP\t"));
+        assert!(!second.contains("\nS\t"));
+        assert!(!second.contains("\nM\t"));
+        assert!(second.len() < first.len() / 4);
+        text.push_str(&first);
+        text.push_str(&second);
+
+        let mut player = Player::from_text(text);
+        assert_eq!(player.len(), 2);
+        for (i, snap) in snaps.iter().enumerate() {
+            let mut expected = App::new();
+            snap.apply(&mut expected);
+            let mut replayed = App::new();
+            player.seek(i, &mut replayed);
+            assert_eq!(text_of(&replayed), text_of(&expected));
+            assert_eq!(replayed.cpu_info.cores.len(), snap.cpu_info.cores.len());
+            assert_eq!(replayed.cpu_info.cores[7].usage_percent, snap.cpu_info.cores[7].usage_percent);
+            assert_eq!(replayed.memory_info.used_mem, snap.memory_info.used_mem);
+            assert_eq!(replayed.uptime_seconds, snap.uptime_seconds);
+            assert_eq!(replayed.cpu_info.brand, snap.cpu_info.brand);
+            assert_eq!(replayed.net_processes.len(), snap.net_processes.len());
+            assert_eq!(replayed.gpu_processes.len(), snap.gpu_processes.len());
+            assert_eq!(replayed.gpu_overall_usage, snap.gpu_overall_usage);
+            assert_eq!(replayed.disks, snap.disks);
+            assert_eq!(replayed.filesystems, snap.filesystems);
+        }
+    }
+
+    #[test]
+    fn exited_processes_and_escaping() {
+        let mut snap = fixture_snapshots().remove(0);
+        snap.processes[0].command = "tab\there\\and\nnewline".to_string();
+        let mut encoder = Encoder::new();
+        let mut text = encoder.encode(&snap);
+        let gone = snap.processes.pop().unwrap().pid;
+        snap.timestamp_ms += 1000;
+        text.push_str(&encoder.encode(&snap));
+
+        let mut player = Player::from_text(text);
+        let mut app = App::new();
+        player.seek(1, &mut app);
+        assert!(app.processes.iter().all(|p| p.pid != gone));
+        assert_eq!(app.processes.len(), snap.processes.len());
+        let cmd = &app.processes.iter().find(|p| p.pid == snap.processes[0].pid).unwrap().command;
+        assert_eq!(cmd, "tab\there\\and\nnewline");
+    }
+
+    #[test]
+    fn thread_rows_keep_their_details() {
+        let mut snap = fixture_snapshots().remove(0);
+        let owner = snap.processes[0].clone();
+        let mut thread = owner.clone();
+        thread.pid = 60_001;
+        thread.ppid = owner.pid;
+        thread.command = String::new();
+        thread.is_thread = true;
+        thread.wait_reason = "futex wait".to_string();
+        thread.context_switches = 4242;
+        let mut windows_thread = thread.clone();
+        windows_thread.pid = 60_002;
+        windows_thread.start_address = Some(0x7ff6_1a2b_0000);
+        snap.processes.extend([thread.clone(), windows_thread.clone()]);
+
+        let mut encoder = Encoder::new();
+        let mut player = Player::from_text(encoder.encode(&snap));
+        let mut app = App::new();
+        player.seek(0, &mut app);
+        let find = |pid: u32| app.processes.iter().find(|p| p.pid == pid).unwrap();
+        for expected in [&thread, &windows_thread] {
+            let p = find(expected.pid);
+            assert!(p.is_thread);
+            assert_eq!(p.thread_detail(), expected.thread_detail());
+        }
+        assert_eq!(find(60_002).thread_detail(), "wait:futex wait start:0x7ff61a2b0000 csw:4242");
+        assert!(!find(owner.pid).is_thread);
+    }
+
+    #[test]
+    fn seek_and_speed() {
+        let snap = fixture_snapshots().remove(0);
+        let mut encoder = Encoder::new();
+        let mut text = String::new();
+        for i in 0..200 {
+            let mut s = snap.clone();
+            s.timestamp_ms = i * 1000;
+            s.uptime_seconds = i as u64;
+            text.push_str(&encoder.encode(&s));
+        }
+        let mut player = Player::from_text(text);
+        let mut app = App::new();
+        player.seek(0, &mut app);
+
+        player.handle(PlaybackCommand::Seek(90), &mut app);
+        assert_eq!(app.uptime_seconds, 90);
+        player.handle(PlaybackCommand::Step(-1), &mut app);
+        assert_eq!(app.uptime_seconds, 89);
+        assert!(app.paused);
+        player.handle(PlaybackCommand::Seek(-30), &mut app);
+        assert_eq!(app.uptime_seconds, 59);
+
+        for _ in 0..10 {
+            player.handle(PlaybackCommand::Faster, &mut app);
+        }
+        assert_eq!(app.playback.as_ref().unwrap().speed, MAX_SPEED);
+        assert_eq!(player.interval(&app), Duration::from_millis(15));
+
+        player.seek(199, &mut app);
+        app.paused = false;
+        player.advance(&mut app);
+        assert_eq!(player.position(), 199);
+        assert!(app.paused);
+    }
+
+    #[test]
+    fn rotation_keeps_segments_replayable() {
+        let dir = std::env::temp_dir().join(format!("pstop-rec-test-{}", std::process::id()));
+        let _ = fs::remove_dir_all(&dir);
+        fs::create_dir_all(&dir).unwrap();
+        let path = dir.join("session.rec");
+
+        let snap = fixture_snapshots().remove(0);
+        let mut recorder = Recorder::create(&path, 4096).unwrap();
+        for i in 0..12 {
+            let mut s = snap.clone();
+            s.timestamp_ms = i * 1000;
+            s.uptime_seconds = i as u64;
+            recorder.write(&s).unwrap();
+        }
+        drop(recorder);
+        assert!(segment_path(&path, 1).exists());
+
+        let mut player = Player::open(&path).unwrap();
+        assert_eq!(player.len(), 12);
+        let mut app = App::new();
+        for i in 0..12 {
+            player.seek(i, &mut app);
+            assert_eq!(app.uptime_seconds, i as u64);
+            assert_eq!(app.processes.len(), snap.processes.len());
+        }
+
+        fs::write(&path, "PSTOPREC\t99\n").unwrap();
+        assert!(Player::open(&path).is_err());
+        let _ = fs::remove_dir_all(&dir);
+    }
+
+    #[test]
+    fn recorder_refuses_other_files_and_rotates_old_versions() {
+        let dir = std::env::temp_dir().join(format!("pstop-rec-append-{}", std::process::id()));
+        let _ = fs::remove_dir_all(&dir);
+        fs::create_dir_all(&dir).unwrap();
+        let path = dir.join("notes.txt");
+
+        fs::write(&path, "not a recording\n").unwrap();
+        assert!(Recorder::create(&path, DEFAULT_MAX_BYTES).is_err());
+        assert_eq!(fs::read_to_string(&path).unwrap(), "not a recording\n");
+
+        // A v1 recording has no Net/GPU lines but still replays, and new
+        // ticks go to a fresh v2 segment next to it
+        let snap = fixture_snapshots().remove(0);
+        let v1: String = Encoder::new()
+            .encode(&snap)
+            .lines()
+            .filter(|l| !l.starts_with(['W', 'w', 'V', 'v', 'G', 'D', 'd', 'F', 'f']))
+            .map(|l| format!("{}\n", l))
+            .collect();
+        fs::write(&path, format!("{}\t1\n{}", MAGIC, v1)).unwrap();
+        let mut app = App::new();
+        Player::open(&path).unwrap().seek(0, &mut app);
+        assert_eq!(app.processes.len(), snap.processes.len());
+        assert!(app.net_processes.is_empty());
+
+        let mut later = snap.clone();
+        later.timestamp_ms += 1000;
+        Recorder::create(&path, DEFAULT_MAX_BYTES).unwrap().write(&later).unwrap();
+        assert!(fs::read_to_string(segment_path(&path, 1)).unwrap().starts_with("PSTOPREC\t1\n"));
+        assert!(fs::read_to_string(&path).unwrap().starts_with(&format!("{}\t{}\n", MAGIC, FORMAT_VERSION)));
+        let mut player = Player::open(&path).unwrap();
+        assert_eq!(player.len(), 2);
+        player.seek(1, &mut app);
+        assert!(!app.net_processes.is_empty());
+        assert_eq!(app.net_processes.len(), snap.net_processes.len());
+        let _ = fs::remove_dir_all(&dir);
+    }
+}
@@ -0,0 +1,289 @@
+//! Live rewind: an in-memory ring of the last few minutes of ticks.
+//!
+//! Every live refresh is encoded with the recording format from `record`
+//! (delta-compressed, keyframe every `KEYFRAME_INTERVAL` ticks) and kept in a
+//! ring bounded by age and size. `[`/`]` step through the ring while the
+//! collector keeps filling it; Esc (or `]` past the newest tick) returns to
+//! live data.
+
+use std::collections::VecDeque;
+
+use crate::app::App;
+use crate::record::{Decoder, Encoder, PlaybackCommand, PlaybackStatus, Snapshot};
+
+/// Default history kept, in minutes (`rewind_minutes`, 0 disables rewind)
+pub const DEFAULT_MINUTES: u64 = 5;
+
+/// Default memory cap for the encoded ticks (`rewind_max_mb`)
+pub const DEFAULT_MAX_MB: u64 = 16;
+
+/// Shorter than the on-disk interval: stepping back decodes at most this many ticks
+const KEYFRAME_INTERVAL: u32 = 30;
+
+struct Tick {
+    timestamp_ms: i64,
+    keyframe: bool,
+    data: String,
+}
+
+/// Recent ticks plus the rewind cursor
+pub struct RewindBuffer {
+    ticks: VecDeque<Tick>,
+    /// Sequence number of `ticks[0]` (stays valid across eviction)
+    first_seq: u64,
+    encoder: Encoder,
+    bytes: usize,
+    max_bytes: usize,
+    max_age_ms: i64,
+    /// Tick being shown (None = live)
+    cursor: Option<u64>,
+    decoder: Decoder,
+    /// Tick the decoder currently holds
+    decoded: Option<u64>,
+    /// Newest live tick, put back into the App before each collector refresh
+    latest: Option<Snapshot>,
+}
+
+impl RewindBuffer {
+    /// Ring holding up to `minutes` of history in about `max_mb` MiB.
+    /// Returns None when rewind is disabled (`minutes == 0`).
+    pub fn new(minutes: u64, max_mb: u64) -> Option<Self> {
+        if minutes == 0 {
+            return None;
+        }
+        Some(Self {
+            ticks: VecDeque::new(),
+            first_seq: 0,
+            encoder: Encoder::with_keyframe_interval(KEYFRAME_INTERVAL),
+            bytes: 0,
+            max_bytes: (max_mb.max(1) * 1024 * 1024) as usize,
+            max_age_ms: (minutes * 60_000) as i64,
+            cursor: None,
+            decoder: Decoder::new(),
+            decoded: None,
+            latest: None,
+        })
+    }
+
+    pub fn is_rewound(&self) -> bool {
+        self.cursor.is_some()
+    }
+
+    pub fn len(&self) -> usize {
+        self.ticks.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.ticks.is_empty()
+    }
+
+    /// Encoded size of the ring
+    pub fn bytes(&self) -> usize {
+        self.bytes
+    }
+
+    fn newest_seq(&self) -> Option<u64> {
+        (!self.ticks.is_empty()).then(|| self.first_seq + self.ticks.len() as u64 - 1)
+    }
+
+    /// While rewound the App shows old data; put the live state back so the
+    /// collector (which looks up names in `app.processes`) works on it.
+    pub fn restore_live(&self, app: &mut App) {
+        if let (Some(_), Some(latest)) = (self.cursor, &self.latest) {
+            latest.apply(app);
+        }
+    }
+
+    /// Store the tick the collector just produced. When rewound, the App is
+    /// switched back to the tick being viewed afterwards.
+    pub fn record(&mut self, app: &mut App, timestamp_ms: i64) {
+        let snap = Snapshot::capture(app, timestamp_ms);
+        let data = self.encoder.encode(&snap);
+        // `T <ts> key|delta ...` leads every tick
+        let keyframe = data.split('\t').nth(2) == Some("key");
+        self.bytes += data.len();
+        self.ticks.push_back(Tick { timestamp_ms, keyframe, data });
+        self.latest = Some(snap);
+        self.evict(timestamp_ms);
+
+        if let Some(seq) = self.cursor {
+            self.show(seq.max(self.first_seq), app);
+        }
+    }
+
+    /// Drop whole keyframe groups from the front while the ring is over its
+    /// size or age limit, so the oldest kept tick is always a keyframe.
+    /// The ring can exceed the limits by at most one group.
+    fn evict(&mut self, now_ms: i64) {
+        let cutoff = now_ms - self.max_age_ms;
+        loop {
+            let Some(next_key) = self.ticks.iter().skip(1).position(|t| t.keyframe).map(|i| i + 1) else {
+                return;
+            };
+            let too_big = self.bytes > self.max_bytes;
+            let too_old = self.ticks[next_key].timestamp_ms <= cutoff;
+            if !too_big && !too_old {
+                return;
+            }
+            for tick in self.ticks.drain(..next_key) {
+                self.bytes -= tick.data.len();
+            }
+            self.first_seq += next_key as u64;
+            if self.decoded.is_some_and(|d| d < self.first_seq) {
+                self.decoded = None;
+            }
+        }
+    }
+
+    /// Rebuild tick `seq` (from the nearest keyframe, or incrementally when
+    /// moving forward) and put it into the App
+    fn show(&mut self, seq: u64, app: &mut App) {
+        let Some(newest) = self.newest_seq() else { return };
+        let seq = seq.clamp(self.first_seq, newest);
+        let index = (seq - self.first_seq) as usize;
+        let keyframe = (0..=index).rev().find(|&i| self.ticks[i].keyframe).unwrap_or(0);
+        let start = match self.decoded.map(|d| (d - self.first_seq) as usize) {
+            Some(d) if d <= index && d >= keyframe => d + 1,
+            _ => keyframe,
+        };
+        for tick in self.ticks.range(start..=index) {
+            self.decoder.apply(&tick.data);
+        }
+        self.decoded = Some(seq);
+        self.cursor = Some(seq);
+
+        self.decoder.snapshot().apply(app);
+        let shown = &self.ticks[index];
+        let newest_ms = self.ticks.back().map(|t| t.timestamp_ms).unwrap_or(shown.timestamp_ms);
+        app.playback = Some(PlaybackStatus {
+            timestamp_ms: shown.timestamp_ms,
+            position: index,
+            total: self.ticks.len(),
+            speed: 1,
+            live_offset_ms: Some(newest_ms - shown.timestamp_ms),
+        });
+    }
+
+    fn go_live(&mut self, app: &mut App) {
+        if self.cursor.take().is_some() {
+            if let Some(latest) = &self.latest {
+                latest.apply(app);
+            }
+        }
+        app.playback = None;
+    }
+
+    /// Carry out a key-requested rewind command
+    pub fn handle(&mut self, cmd: PlaybackCommand, app: &mut App) {
+        let Some(newest) = self.newest_seq() else { return };
+        match cmd {
+            PlaybackCommand::Step(n) => {
+                let target = self.cursor.unwrap_or(newest) as i64 + n;
+                if target >= newest as i64 {
+                    self.go_live(app);
+                } else {
+                    self.show(target.max(self.first_seq as i64) as u64, app);
+                }
+            }
+            PlaybackCommand::Seek(secs) => {
+                let cur = (self.cursor.unwrap_or(newest) - self.first_seq) as usize;
+                let target_ms = self.ticks[cur].timestamp_ms + secs * 1000;
+                let newest_ms = self.ticks[self.ticks.len() - 1].timestamp_ms;
+                if target_ms >= newest_ms {
+                    self.go_live(app);
+                    return;
+                }
+                // Last tick at or before the target time
+                let index = self.ticks.partition_point(|t| t.timestamp_ms <= target_ms).saturating_sub(1);
+                self.show(self.first_seq + index as u64, app);
+            }
+            PlaybackCommand::Live => self.go_live(app),
+            PlaybackCommand::Faster | PlaybackCommand::Slower => {}
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::system::fake::FakeSource;
+
+    /// App on the fixture's first frame, with `uptime` standing in for the tick number
+    fn tick_app(source: &mut FakeSource, app: &mut App, uptime: u64) {
+        source.refresh(app);
+        app.uptime_seconds = uptime;
+    }
+
+    #[test]
+    fn step_back_and_return_to_live() {
+        let mut source = FakeSource::basic();
+        let mut app = App::new();
+        let mut ring = RewindBuffer::new(5, 16).unwrap();
+        for i in 0..100 {
+            tick_app(&mut source, &mut app, i);
+            ring.record(&mut app, i as i64 * 1000);
+        }
+        assert_eq!(ring.len(), 100);
+
+        ring.handle(PlaybackCommand::Step(-1), &mut app);
+        assert!(ring.is_rewound());
+        assert_eq!(app.uptime_seconds, 98);
+        ring.handle(PlaybackCommand::Seek(-42), &mut app);
+        assert_eq!(app.uptime_seconds, 56);
+        assert_eq!(app.playback.as_ref().unwrap().live_offset_ms, Some(43_000));
+        assert_eq!(app.net_processes.len(), 5);
+        assert!(app.viewing_history());
+
+        // Collection carries on underneath; the view stays on the same tick
+        ring.restore_live(&mut app);
+        assert_eq!(app.uptime_seconds, 99);
+        tick_app(&mut source, &mut app, 100);
+        ring.record(&mut app, 100_000);
+        assert_eq!(app.uptime_seconds, 56);
+        assert_eq!(app.playback.as_ref().unwrap().live_offset_ms, Some(44_000));
+
+        ring.handle(PlaybackCommand::Step(1), &mut app);
+        assert_eq!(app.uptime_seconds, 57);
+        ring.handle(PlaybackCommand::Live, &mut app);
+        assert!(!ring.is_rewound());
+        assert!(app.playback.is_none());
+        assert_eq!(app.uptime_seconds, 100);
+
+        // Stepping forward past the newest tick also returns to live
+        ring.handle(PlaybackCommand::Step(-2), &mut app);
+        ring.handle(PlaybackCommand::Step(2), &mut app);
+        assert!(!ring.is_rewound());
+    }
+
+    #[test]
+    fn eviction_respects_age_and_size() {
+        let mut source = FakeSource::basic();
+        let mut app = App::new();
+
+        // One minute at one tick per second: ~60 ticks, give or take a group
+        let mut ring = RewindBuffer::new(1, 16).unwrap();
+        for i in 0..300 {
+            tick_app(&mut source, &mut app, i);
+            ring.record(&mut app, i as i64 * 1000);
+        }
+        assert!(ring.len() >= 60 && ring.len() <= 60 + KEYFRAME_INTERVAL as usize);
+        assert!(ring.ticks[0].keyframe);
+
+        // Oldest reachable tick is the front of the ring
+        ring.handle(PlaybackCommand::Seek(-3600), &mut app);
+        assert_eq!(app.uptime_seconds, 300 - ring.len() as u64);
+
+        // A tiny cap keeps about one keyframe group
+        let mut ring = RewindBuffer::new(60, 1).unwrap();
+        ring.max_bytes = 4096;
+        for i in 0..300 {
+            tick_app(&mut source, &mut app, i);
+            ring.record(&mut app, i as i64 * 1000);
+        }
+        assert!(ring.len() <= 2 * KEYFRAME_INTERVAL as usize);
+        assert!(ring.ticks[0].keyframe);
+        assert_eq!(ring.bytes(), ring.ticks.iter().map(|t| t.data.len()).sum::<usize>());
+
+        assert!(RewindBuffer::new(0, 16).is_none());
+    }
+}
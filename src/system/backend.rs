@@ -0,0 +1,278 @@
+//! Platform abstraction for the native queries `Collector` can't get from sysinfo:
+//! - Fast PID enumeration (used to prefetch data in parallel with sysinfo)
+//! - Per-process enrichment (PRI/NI, thread count, private memory, owner, CPU time)
+//! - Per-process I/O counters
+//! - Per-core CPU sampling with user/kernel/irq breakdown
+//! - Priority and CPU affinity changes
+//! - Environment block, working directory and executable of one process
+//! - Virtual memory map of one process
+//!
+//! Win32 lives in `winapi`, Linux reads `/proc` in `linux`. `native()` returns
+//! the backend for the platform we were compiled for.
+
+use std::collections::HashMap;
+
+use crate::system::process::ProcessStatus;
+
+/// Per-process data collected natively (cached every N ticks)
+#[derive(Debug, Clone, Default)]
+pub struct ProcessData {
+    pub priority: i32,   // Base priority level (PRI column)
+    pub nice: i32,       // Nice value (NI column)
+    pub thread_count: u32,
+    pub private_working_set: u64, // Private bytes (for shared_mem = resident - private)
+}
+
+/// Thread info for show_threads feature
+#[derive(Debug, Clone)]
+pub struct ThreadInfo {
+    pub thread_id: u32,
+    pub owner_pid: u32,
+    pub base_priority: i32,
+    pub name: String,
+    /// User + kernel CPU time in 100-nanosecond units
+    pub cpu_time_100ns: u64,
+    pub status: ProcessStatus,
+    /// What a blocked thread waits for: the kernel wait channel on Linux,
+    /// the KWAIT_REASON on Windows. Empty while it runs.
+    pub wait_reason: String,
+    /// Win32 start address, when names were asked for (Windows only)
+    pub start_address: Option<u64>,
+    /// Voluntary + involuntary context switches since the thread started
+    pub context_switches: u64,
+}
+
+/// Handle information for display in lsof-style viewer
+#[derive(Debug, Clone, Default)]
+pub struct HandleInfo {
+    /// fd number on Linux, handle value on Windows (None for loaded modules)
+    pub id: Option<u64>,
+    pub handle_type: String,
+    pub name: String,
+    /// "r", "w", "rw", or "" when unknown
+    pub access: String,
+    /// Locks held through this descriptor, e.g. "POSIX WRITE" ("" = none)
+    pub lock: String,
+}
+
+/// Why a single-process query came back empty
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum QueryError {
+    /// Another user's process (or a protected one) and we aren't privileged
+    AccessDenied,
+    /// The process exited, or has no such thing (kernel threads have no executable)
+    Unavailable,
+    Other(String),
+}
+
+impl QueryError {
+    pub fn from_io(e: &std::io::Error) -> Self {
+        match e.kind() {
+            std::io::ErrorKind::PermissionDenied => Self::AccessDenied,
+            std::io::ErrorKind::NotFound => Self::Unavailable,
+            _ => Self::Other(e.to_string()),
+        }
+    }
+}
+
+impl std::fmt::Display for QueryError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::AccessDenied => write!(f, "access denied"),
+            Self::Unavailable => write!(f, "not available"),
+            Self::Other(e) => write!(f, "{}", e),
+        }
+    }
+}
+
+/// Environment viewer data for one process, read when the viewer opens.
+/// Each part can fail on its own: the start time is public, the rest
+/// usually needs the process's owner or root/Administrator.
+#[derive(Debug, Clone)]
+pub struct ProcessEnvironment {
+    pub exe: Result<String, QueryError>,
+    pub cwd: Result<String, QueryError>,
+    /// NAME=value pairs in the order the process holds them
+    pub vars: Result<Vec<(String, String)>, QueryError>,
+    /// Unix timestamp
+    pub start_time: Option<i64>,
+}
+
+/// Split a `NAME=value` entry. Windows keeps per-drive directories as
+/// `=C:=C:\dir`, so a leading '=' belongs to the name.
+pub fn split_env_entry(entry: &str) -> (String, String) {
+    match entry.char_indices().skip(1).find(|&(_, c)| c == '=') {
+        Some((i, _)) => (entry[..i].to_string(), entry[i + 1..].to_string()),
+        None => (entry.to_string(), String::new()),
+    }
+}
+
+/// What backs a memory region
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum RegionKind {
+    /// Executables and libraries (and the kernel's vdso)
+    Image,
+    /// Other file mappings and shared memory
+    Mapped,
+    #[default]
+    Private,
+    Heap,
+    Stack,
+}
+
+impl RegionKind {
+    pub fn label(&self) -> &'static str {
+        match self {
+            Self::Image => "image",
+            Self::Mapped => "mapped",
+            Self::Private => "private",
+            Self::Heap => "heap",
+            Self::Stack => "stack",
+        }
+    }
+}
+
+/// One region of a process's virtual address space (pmap-style viewer)
+#[derive(Debug, Clone, Default)]
+pub struct MemoryRegion {
+    pub start: u64,
+    pub end: u64,
+    /// Resident bytes
+    pub rss: u64,
+    /// `rwxp` style: read, write, execute, then p(rivate) or s(hared)
+    pub protection: String,
+    pub kind: RegionKind,
+    /// Backing file, or a `[heap]`-style pseudo name ("" for anonymous memory)
+    pub path: String,
+}
+
+impl MemoryRegion {
+    pub fn size(&self) -> u64 {
+        self.end - self.start
+    }
+}
+
+/// Regions of a memory map summed per backing file
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct MappingTotal {
+    /// The file, or `[private]`-style for anonymous memory of one type
+    pub name: String,
+    pub regions: usize,
+    pub size: u64,
+    pub rss: u64,
+}
+
+/// Per-file totals of a memory map, most resident first
+pub fn mapping_totals(regions: &[MemoryRegion]) -> Vec<MappingTotal> {
+    let mut totals: Vec<MappingTotal> = Vec::new();
+    let mut index: HashMap<String, usize> = HashMap::new();
+    for region in regions {
+        let name = if region.path.is_empty() { format!("[{}]", region.kind.label()) } else { region.path.clone() };
+        let i = *index.entry(name.clone()).or_insert_with(|| {
+            totals.push(MappingTotal { name, regions: 0, size: 0, rss: 0 });
+            totals.len() - 1
+        });
+        totals[i].regions += 1;
+        totals[i].size += region.size();
+        totals[i].rss += region.rss;
+    }
+    totals.sort_by(|a, b| b.rss.cmp(&a.rss).then(b.size.cmp(&a.size)).then(a.name.cmp(&b.name)));
+    totals
+}
+
+/// Per-core CPU time sample with breakdown into user/kernel/dpc/interrupt.
+/// Fractions are of total time (including idle), so they sum to usage%/100.
+pub struct CpuCoreSample {
+    pub usage_percent: f32,
+    pub user_frac: f32,      // fraction of total time in user mode
+    pub kernel_frac: f32,    // fraction in pure kernel (excl idle, dpc, interrupt)
+    pub dpc_frac: f32,       // fraction in DPC (≈ Linux softirq)
+    pub interrupt_frac: f32, // fraction in interrupt (≈ Linux irq)
+}
+
+impl CpuCoreSample {
+    pub fn idle() -> Self {
+        Self {
+            usage_percent: 0.0, user_frac: 0.0, kernel_frac: 0.0,
+            dpc_frac: 0.0, interrupt_frac: 0.0,
+        }
+    }
+}
+
+/// Stateful CPU sampler: takes one snapshot on creation, then every call
+/// returns the deltas since the previous one.
+pub trait CpuMonitor: Send {
+    /// CPU brand string (cached)
+    fn brand(&self) -> &str;
+    /// CPU frequency in MHz (cached, 0 if unknown)
+    fn frequency_mhz(&self) -> u64;
+    /// Per-core usage with time breakdown
+    fn sample(&mut self) -> Vec<CpuCoreSample>;
+    /// System-wide (user_fraction, kernel_fraction) of total CPU time
+    fn sample_split(&mut self) -> (f64, f64);
+}
+
+/// Owner shown when `process_users` has nothing for a process. On Windows
+/// that's nearly always a protected system process; /proc only hides the
+/// owner of a process that already exited.
+pub const UNKNOWN_USER: &str = if cfg!(windows) { "SYSTEM" } else { "?" };
+
+/// Native per-process queries. Implementations are stateless so they can be
+/// shared with the prefetch threads spawned by `Collector::refresh`.
+pub trait SystemBackend: Send + Sync {
+    /// Fast PID enumeration (< 1ms), used to prefetch before sysinfo's refresh
+    fn enumerate_pids(&self) -> Vec<u32>;
+    /// Batch-collect priority, nice, thread count and private memory
+    fn process_data(&self, pids: &[u32]) -> HashMap<u32, ProcessData>;
+    /// Batch-resolve process owners
+    fn process_users(&self, pids: &[u32]) -> HashMap<u32, String>;
+    /// Batch-collect cumulative CPU time (user + kernel) in 100ns units, for TIME+
+    fn process_times(&self, pids: &[u32]) -> HashMap<u32, u64>;
+    /// Batch-collect cumulative (read_bytes, write_bytes)
+    fn io_counters(&self, pids: &[u32]) -> HashMap<u32, (u64, u64)>;
+    /// Threads of a single process with CPU time, state and context
+    /// switches, optionally with their names
+    fn threads(&self, pid: u32, get_names: bool) -> Vec<ThreadInfo>;
+    /// Open handles / files of a single process
+    fn handles(&self, pid: u32) -> Vec<HandleInfo>;
+    /// Environment block, working directory, executable and start time of a single process
+    fn environment(&self, pid: u32) -> ProcessEnvironment;
+    /// Virtual memory regions of a single process, in address order
+    fn memory_map(&self, pid: u32) -> Result<Vec<MemoryRegion>, QueryError>;
+    /// Boot time as a Unix timestamp. May be slow; called on a background thread.
+    fn boot_time(&self) -> Option<i64>;
+    /// Create a per-core CPU sampler
+    fn cpu_monitor(&self) -> Box<dyn CpuMonitor>;
+
+    /// Raise priority one step (F7 = Nice-)
+    fn raise_priority(&self, pid: u32) -> bool;
+    /// Lower priority one step (F8 = Nice+)
+    fn lower_priority(&self, pid: u32) -> bool;
+    /// Send a signal from `KILL_SIGNALS` (by index). Must not block the TUI.
+    /// Ok means the signal was delivered (or a graceful close was started),
+    /// not that the process has exited.
+    fn kill(&self, pid: u32, signal_index: usize) -> std::io::Result<()>;
+
+    /// Number of logical CPUs
+    fn cpu_count(&self) -> usize {
+        std::thread::available_parallelism()
+            .map(|n| n.get())
+            .unwrap_or(1)
+    }
+    /// Returns (process_affinity, system_affinity, success)
+    fn get_affinity(&self, pid: u32) -> (usize, usize, bool);
+    /// Set affinity mask (bit 0 = CPU 0, bit 1 = CPU 1, etc.)
+    fn set_affinity(&self, pid: u32, mask: usize) -> bool;
+}
+
+/// The backend for the platform we were compiled for.
+pub fn native() -> &'static dyn SystemBackend {
+    #[cfg(windows)]
+    {
+        &crate::system::winapi::WinBackend
+    }
+    #[cfg(not(windows))]
+    {
+        &crate::system::linux::LinuxBackend
+    }
+}
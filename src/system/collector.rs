@@ -1,32 +1,45 @@
 use std::collections::HashMap;
 
-use sysinfo::{System, ProcessStatus as SysProcessStatus, ProcessesToUpdate, ProcessRefreshKind, UpdateKind, Networks};
+use sysinfo::{System, ProcessStatus as SysProcessStatus, ProcessesToUpdate, ProcessRefreshKind, UpdateKind, Networks, Disks};
 
 use crate::app::App;
 use crate::system::cpu::{CpuCore, CpuInfo};
 use crate::system::gpu::GpuCollector;
 use crate::system::memory::MemoryInfo;
 use crate::system::network::NetworkInfo;
+use crate::system::disk::{self, DiskCounters, DiskDevice, DiskIoInfo};
+use crate::system::filesystem;
 use crate::system::process::{ProcessInfo, ProcessStatus};
-use crate::system::winapi;
+use crate::system::backend::{self, CpuMonitor, ProcessData, SystemBackend};
 use crate::system::netstat;
 
+/// sysinfo's view of one process: pid, ppid, name, command, status, virt,
+/// resident, cpu%, mem%, run time
+type RawProcess = (u32, u32, String, String, SysProcessStatus, u64, u64, f32, f32, u64);
+
 /// System data collector using the `sysinfo` crate for process data,
-/// with native Win32 APIs for CPU monitoring and per-process enrichment.
+/// with a native `SystemBackend` (Win32 or /proc) for CPU monitoring and
+/// per-process enrichment.
 pub struct Collector {
     pub(crate) sys: System,
     networks: Option<Networks>,
+    /// Mounted filesystems (lazy-initialized like networks)
+    filesystems: Option<Disks>,
+    /// Native per-process queries for this platform
+    backend: &'static dyn SystemBackend,
     /// Native per-core CPU monitor (replaces sysinfo PDH, saves ~155ms init)
-    cpu_monitor: winapi::NativeCpuMonitor,
-    /// Cache: PID -> resolved user name (via Win32 token lookup)
+    cpu_monitor: Box<dyn CpuMonitor>,
+    /// Cache: PID -> resolved user name (via Win32 token lookup / UID)
     user_name_cache: HashMap<u32, String>,
-    /// Cache: Win32 process data (priority, threads) - updated every 3 ticks
-    win_data_cache: HashMap<u32, winapi::WinProcessData>,
+    /// Cache: native process data (priority, threads) - updated every 3 ticks
+    win_data_cache: HashMap<u32, ProcessData>,
     win_data_cache_ticks: u64,
     /// Cache: per-process CPU times for TIME+ (updated every 3 ticks)
     process_times_cache: HashMap<u32, u64>,
     /// Previous I/O counters for rate calculation: PID -> (read_bytes, write_bytes, timestamp)
     prev_io_counters: HashMap<u32, (u64, u64, std::time::Instant)>,
+    /// Previous per-thread CPU times for thread CPU%: TID -> (cpu_time_100ns, timestamp)
+    prev_thread_times: HashMap<u32, (u64, std::time::Instant)>,
     /// Pre-fetched I/O counters from parallel thread (consumed by collect_processes)
     prefetched_io: HashMap<u32, (u64, u64)>,
     /// Cache: PID -> (name, command) — used when update_process_names is OFF
@@ -35,18 +48,18 @@ pub struct Collector {
     prev_net_rx: u64,
     prev_net_tx: u64,
     prev_net_time: Option<std::time::Instant>,
+    /// Previous block device counters for disk I/O rates
+    prev_disks: Option<(std::time::Instant, Vec<DiskCounters>)>,
     /// Exponential moving averages for load approximation
     load_samples_1: f64,
     load_samples_5: f64,
     load_samples_15: f64,
-    /// Real boot time (Unix timestamp) from Windows Event Log.
+    /// Real boot time (Unix timestamp) from Windows Event Log / /proc/stat btime.
     /// Accounts for Fast Startup, which causes GetTickCount64() to report
     /// inflated uptime because the kernel hibernates instead of rebooting.
     boot_time_unix: Option<i64>,
     /// Pending boot time query (runs on background thread)
     boot_time_pending: Option<std::thread::JoinHandle<Option<i64>>>,
-    /// CPU user/kernel time split tracker (via GetSystemTimes)
-    cpu_time_split: winapi::CpuTimeSplit,
     /// Last sampled CPU user/kernel fractions
     pub cpu_user_frac: f64,
     pub cpu_kernel_frac: f64,
@@ -56,13 +69,17 @@ pub struct Collector {
 
 impl Collector {
     pub fn new() -> Self {
-        // Spawn boot time query on background thread (it shells out to wevtutil ~200ms)
+        Self::with_backend(backend::native())
+    }
+
+    pub fn with_backend(backend: &'static dyn SystemBackend) -> Self {
+        // Spawn boot time query on background thread (on Windows it shells out to wevtutil ~200ms)
         // Don't block — we'll check for completion on first refresh
-        let boot_time_handle = std::thread::spawn(|| winapi::get_real_boot_time());
+        let boot_time_handle = std::thread::spawn(move || backend.boot_time());
 
-        // Use native NtQuerySystemInformation for CPU monitoring (<1ms)
+        // Use native per-core times (NtQuerySystemInformation / /proc/stat, <1ms)
         // instead of sysinfo's PDH-based approach (~155ms initialization).
-        let cpu_monitor = winapi::NativeCpuMonitor::new();
+        let cpu_monitor = backend.cpu_monitor();
 
         let sys = System::new();
         // No refresh_cpu_all needed! CPU monitoring is handled by NativeCpuMonitor.
@@ -73,23 +90,26 @@ impl Collector {
         Self {
             sys,
             networks: None,
+            filesystems: None,
+            backend,
             cpu_monitor,
             user_name_cache: HashMap::new(),
             win_data_cache: HashMap::new(),
             win_data_cache_ticks: 0,
             process_times_cache: HashMap::new(),
             prev_io_counters: HashMap::new(),
+            prev_thread_times: HashMap::new(),
             prefetched_io: HashMap::new(),
             process_name_cache: HashMap::new(),
             prev_net_rx: 0,
             prev_net_tx: 0,
             prev_net_time: None,
+            prev_disks: None,
             load_samples_1: 0.0,
             load_samples_5: 0.0,
             load_samples_15: 0.0,
             boot_time_unix: None,
             boot_time_pending: Some(boot_time_handle),
-            cpu_time_split: winapi::CpuTimeSplit::new(),
             cpu_user_frac: 0.7,
             cpu_kernel_frac: 0.3,
             gpu_collector: GpuCollector::new(),
@@ -102,7 +122,7 @@ impl Collector {
         self.sys.refresh_memory();
         self.collect_cpu(app);
         self.collect_memory(app);
-        let (user_frac, kernel_frac) = self.cpu_time_split.sample();
+        let (user_frac, kernel_frac) = self.cpu_monitor.sample_split();
         self.cpu_user_frac = user_frac;
         self.cpu_kernel_frac = kernel_frac;
         app.cpu_user_frac = self.cpu_user_frac;
@@ -126,28 +146,30 @@ impl Collector {
             }
         }
 
-        // ── Prefetch Win32 data in parallel with sysinfo refresh ──
-        // EnumProcesses gives PID list in <1ms, then we launch Win32 batch threads
-        // that run concurrently with sysinfo's slower refresh_processes (~100ms).
-        let refresh_win_data = self.win_data_cache_ticks == 0 || self.win_data_cache_ticks % 3 == 0;
-        let refresh_times = self.win_data_cache_ticks % 3 == 0;
+        // ── Prefetch native data in parallel with sysinfo refresh ──
+        // EnumProcesses / readdir(/proc) gives the PID list in <1ms, then we launch
+        // backend batch threads that run concurrently with sysinfo's slower
+        // refresh_processes (~100ms).
+        let refresh_win_data = self.win_data_cache_ticks == 0 || self.win_data_cache_ticks.is_multiple_of(3);
+        let refresh_times = self.win_data_cache_ticks.is_multiple_of(3);
 
-        // Pre-enumerate PIDs via EnumProcesses (<1ms) for parallel Win32 batch calls.
+        // Pre-enumerate PIDs (<1ms) for parallel backend batch calls.
         // I/O counters are fetched every tick; data/users/times only every 3 ticks.
-        let pids = winapi::quick_enumerate_pids();
+        let backend = self.backend;
+        let pids = backend.enumerate_pids();
 
         // Always launch I/O counters in parallel with sysinfo refresh
         let pids_for_io = pids.clone();
-        let io_handle = std::thread::spawn(move || winapi::batch_io_counters(&pids_for_io));
+        let io_handle = std::thread::spawn(move || backend.io_counters(&pids_for_io));
 
         let prefetch_handles = if refresh_win_data {
             let pids_for_data = pids.clone();
             let pids_for_users = pids.clone();
-            let data_handle = std::thread::spawn(move || winapi::collect_process_data(&pids_for_data));
-            let users_handle = std::thread::spawn(move || winapi::batch_process_users(&pids_for_users));
+            let data_handle = std::thread::spawn(move || backend.process_data(&pids_for_data));
+            let users_handle = std::thread::spawn(move || backend.process_users(&pids_for_users));
             let times_handle = if refresh_times {
                 let pids_for_times = pids;
-                Some(std::thread::spawn(move || winapi::batch_process_times(&pids_for_times)))
+                Some(std::thread::spawn(move || backend.process_times(&pids_for_times)))
             } else {
                 None
             };
@@ -156,13 +178,16 @@ impl Collector {
             None
         };
 
-        // Refresh sysinfo data (runs concurrently with Win32 prefetch threads above)
-        // CPU monitoring is handled natively by NativeCpuMonitor, not sysinfo.
+        // Refresh sysinfo data (runs concurrently with backend prefetch threads above)
+        // CPU monitoring is handled natively by the backend's CpuMonitor, not sysinfo.
+        // Threads come from the backend when show_threads is on, so sysinfo
+        // doesn't list Linux tasks as processes of their own.
         self.sys.refresh_memory();
         self.sys.refresh_processes_specifics(
             ProcessesToUpdate::All,
             true,
             ProcessRefreshKind::nothing()
+                .without_tasks()
                 .with_cpu()
                 .with_memory()
                 .with_cmd(UpdateKind::OnlyIfNotSet),
@@ -180,17 +205,20 @@ impl Collector {
             }
         }
 
-        // Sample real CPU user/kernel split via GetSystemTimes
-        let (user_frac, kernel_frac) = self.cpu_time_split.sample();
+        // Sample real CPU user/kernel split (GetSystemTimes / /proc/stat)
+        let (user_frac, kernel_frac) = self.cpu_monitor.sample_split();
         self.cpu_user_frac = user_frac;
         self.cpu_kernel_frac = kernel_frac;
 
         self.collect_cpu(app);
         self.collect_memory(app);
         self.collect_network(app);
+        self.collect_disks(app);
+        self.collect_filesystems(app);
         self.collect_processes(app);
         self.collect_uptime(app);
         self.compute_load_average(app);
+        self.collect_gpu(app);
 
         // Pass CPU user/kernel split to app for header rendering
         app.cpu_user_frac = self.cpu_user_frac;
@@ -204,7 +232,12 @@ impl Collector {
             app.cpu_interrupt_frac = cores.iter().map(|c| c.interrupt_frac as f64).sum::<f64>() / n;
         }
 
+        app.record_history();
         app.collect_users();
+        // Sockets for the Ports tab, before filtering picks its rows
+        if matches!(app.active_tab, crate::app::ProcessTab::Ports) {
+            app.connections = netstat::connections();
+        }
         app.apply_filter();
         app.sort_processes();
 
@@ -216,7 +249,9 @@ impl Collector {
         // ── Network bandwidth (Net tab) ──
         // Only collect when on the Net tab (avoid overhead otherwise)
         if matches!(app.active_tab, crate::app::ProcessTab::Net) {
-            let conn_counts = netstat::count_connections_per_pid();
+            let connections = netstat::connections();
+            let conn_counts = netstat::count_connections_per_pid(&connections);
+            app.connections = connections;
 
             // Collect raw I/O rates for processes with connections
             let raw: Vec<(u32, String, f64, f64, u32)> = conn_counts
@@ -272,9 +307,23 @@ impl Collector {
             app.sort_net_processes();
         }
 
-        // ── GPU per-process data (GPU tab) ──
+        app.follow_process();
+        app.clamp_selection();
+        app.tick += 1;
+        app.check_alerts();
+        app.run_governor();
+    }
+
+    /// GPU totals every tick, so the header meter and its history stay
+    /// current on every tab; the per-process rows only for the GPU tab
+    fn collect_gpu(&mut self, app: &mut App) {
+        let mut gpu_procs = self.gpu_collector.collect();
+        let info = &self.gpu_collector.adapter_info;
+        app.gpu_overall_usage = info.overall_usage;
+        app.gpu_dedicated_mem = info.total_dedicated_mem;
+        app.gpu_shared_mem = info.total_shared_mem;
+
         if matches!(app.active_tab, crate::app::ProcessTab::Gpu) {
-            let mut gpu_procs = self.gpu_collector.collect();
             // Populate process names from sysinfo process list
             for gp in &mut gpu_procs {
                 if let Some(proc) = app.processes.iter().find(|p| p.pid == gp.pid) {
@@ -284,25 +333,16 @@ impl Collector {
             app.gpu_processes = gpu_procs;
             // Sort by user's selected GPU sort field
             app.sort_gpu_processes();
-
-            // Populate overall GPU stats for header meters
-            let info = &self.gpu_collector.adapter_info;
-            app.gpu_overall_usage = info.overall_usage;
-            app.gpu_dedicated_mem = info.total_dedicated_mem;
-            app.gpu_shared_mem = info.total_shared_mem;
+            // Slow (wmic on Windows), so only once the tab is looked at
             if app.gpu_adapter_name.is_empty() {
                 app.gpu_adapter_name = crate::system::gpu::detect_gpu_adapter_name();
             }
         }
-
-        app.follow_process();
-        app.clamp_selection();
-        app.tick += 1;
     }
 
     fn collect_cpu(&mut self, app: &mut App) {
         let samples = self.cpu_monitor.sample();
-        let freq = self.cpu_monitor.frequency;
+        let freq = self.cpu_monitor.frequency_mhz();
 
         let cores: Vec<CpuCore> = samples
             .iter()
@@ -328,7 +368,7 @@ impl Collector {
             physical_cores: sysinfo::System::physical_core_count().unwrap_or(cores.len()),
             logical_cores: cores.len(),
             total_usage,
-            brand: self.cpu_monitor.brand.clone(),
+            brand: self.cpu_monitor.brand().to_string(),
             cores,
         };
     }
@@ -395,6 +435,43 @@ impl Collector {
         };
     }
 
+    fn collect_disks(&mut self, app: &mut App) {
+        let now = std::time::Instant::now();
+        let counters = disk::read_counters();
+
+        // The first read only sets the baseline; devices that appear later
+        // show up on the refresh after
+        let devices: Vec<DiskDevice> = match &self.prev_disks {
+            Some((prev_time, prev)) => {
+                let elapsed = now.duration_since(*prev_time).as_secs_f64();
+                counters
+                    .iter()
+                    .filter_map(|c| {
+                        let p = prev.iter().find(|p| p.name == c.name)?;
+                        Some(DiskDevice::between(p, c, elapsed))
+                    })
+                    .collect()
+            }
+            None => Vec::new(),
+        };
+
+        self.prev_disks = Some((now, counters));
+        app.disk_io_info = DiskIoInfo::from_devices(&devices);
+        app.disks = devices;
+    }
+
+    /// Only while the Disk usage meter or the d overlay shows them: a hung
+    /// network mount would stall every tick otherwise
+    fn collect_filesystems(&mut self, app: &mut App) {
+        let overlay = app.mode == crate::app::AppMode::Filesystems;
+        if !overlay && !app.has_meter("Disk usage") {
+            app.filesystems.clear();
+            return;
+        }
+        let disks = self.filesystems.get_or_insert_with(Disks::new);
+        app.filesystems = filesystem::read_filesystems(disks, overlay);
+    }
+
     fn collect_processes(&mut self, app: &mut App) {
         let total_mem = self.sys.total_memory();
         let uptime = self.real_uptime();
@@ -404,7 +481,7 @@ impl Collector {
         let update_names = app.update_process_names;
 
         // Collect raw process data first (no &mut self needed)
-        let raw_procs: Vec<(u32, u32, String, String, SysProcessStatus, u64, u64, f32, f32, u64)> = self.sys.processes()
+        let raw_procs: Vec<RawProcess> = self.sys.processes()
             .iter()
             .map(|(&pid, proc_info)| {
                 let resident = proc_info.memory();
@@ -460,7 +537,7 @@ impl Collector {
             })
             .collect();
 
-        // Win32 data (priority, users, times, I/O) is pre-fetched in refresh() via parallel threads.
+        // Native data (priority, users, times, I/O) is pre-fetched in refresh() via parallel threads.
         let all_pids: Vec<u32> = raw_procs.iter().map(|(pid, ..)| *pid).collect();
         let io_counters = std::mem::take(&mut self.prefetched_io);
 
@@ -469,7 +546,7 @@ impl Collector {
         // Build a set of current PIDs for dead PID cleanup
         let current_pids: std::collections::HashSet<u32> = all_pids.iter().copied().collect();
 
-        // Merge Win32 data into process list — access caches by reference, no cloning
+        // Merge native data into process list — access caches by reference, no cloning
         let processes: Vec<ProcessInfo> = raw_procs.into_iter()
             .map(|(pid, ppid, name, command, sys_status, virt, resident, cpu_usage, mem_pct, run_time)| {
                 let status = match sys_status {
@@ -481,6 +558,7 @@ impl Collector {
                         sleeping += 1;
                         ProcessStatus::Sleeping
                     }
+                    SysProcessStatus::UninterruptibleDiskSleep => ProcessStatus::DiskSleep,
                     SysProcessStatus::Stop => ProcessStatus::Stopped,
                     SysProcessStatus::Zombie => ProcessStatus::Zombie,
                     _ => {
@@ -489,9 +567,9 @@ impl Collector {
                     }
                 };
 
-                let user_name = self.user_name_cache.get(&pid).cloned().unwrap_or_else(|| "SYSTEM".to_string());
+                let user_name = self.user_name_cache.get(&pid).cloned().unwrap_or_else(|| backend::UNKNOWN_USER.to_string());
 
-                // Get Win32 data (priority, nice, thread count)
+                // Get native data (priority, nice, thread count)
                 let wd = self.win_data_cache.get(&pid);
                 let priority = wd.map(|d| d.priority).unwrap_or(8);
                 let nice = wd.map(|d| d.nice).unwrap_or(0);
@@ -546,6 +624,10 @@ impl Collector {
                     io_write_rate,
                     depth: 0,
                     is_last_child: false,
+                    is_thread: false,
+                    wait_reason: String::new(),
+                    start_address: None,
+                    context_switches: 0,
                 }
             })
             .collect();
@@ -557,11 +639,25 @@ impl Collector {
         // If show_threads is enabled, enumerate individual threads and add as sub-entries
         if app.show_threads {
             let mut expanded = Vec::with_capacity(processes.len() * 2);
+            let mut seen_tids = std::collections::HashSet::new();
             for proc in processes {
                 let pid = proc.pid;
-                let threads_info = winapi::enumerate_threads(pid, app.show_thread_names);
+                let user = proc.user.clone();
+                let threads_info = self.backend.threads(pid, app.show_thread_names);
                 expanded.push(proc);
                 for ti in threads_info {
+                    // CPU% from the CPU time used since the previous tick, like a process
+                    let now = std::time::Instant::now();
+                    let cpu_usage = match self.prev_thread_times.get(&ti.thread_id) {
+                        Some((prev, at)) if now > *at => {
+                            let used = ti.cpu_time_100ns.saturating_sub(*prev) as f64 / 10_000_000.0;
+                            (used / now.duration_since(*at).as_secs_f64() * 100.0) as f32
+                        }
+                        _ => 0.0,
+                    };
+                    self.prev_thread_times.insert(ti.thread_id, (ti.cpu_time_100ns, now));
+                    seen_tids.insert(ti.thread_id);
+
                     let thread_name = if !ti.name.is_empty() {
                         ti.name
                     } else {
@@ -572,27 +668,33 @@ impl Collector {
                         ppid: pid,           // Parent is the owning process
                         name: thread_name,
                         command: String::new(),
-                        user: String::new(),
-                        status: ProcessStatus::Running,
+                        user: user.clone(),
+                        status: ti.status,
                         priority: ti.base_priority,
                         nice: 0,
                         virtual_mem: 0,
                         resident_mem: 0,
                         shared_mem: 0,
-                        cpu_usage: 0.0,
+                        cpu_usage,
                         mem_usage: 0.0,
                         run_time: 0,
-                        cpu_time_100ns: 0,
+                        cpu_time_100ns: ti.cpu_time_100ns,
                         threads: 0,
                         io_read_rate: 0.0,
                         io_write_rate: 0.0,
                         depth: 1,
                         is_last_child: false,
+                        is_thread: true,
+                        wait_reason: ti.wait_reason,
+                        start_address: ti.start_address,
+                        context_switches: ti.context_switches,
                     });
                 }
             }
+            self.prev_thread_times.retain(|tid, _| seen_tids.contains(tid));
             app.processes = expanded;
         } else {
+            self.prev_thread_times.clear();
             app.processes = processes;
         }
 
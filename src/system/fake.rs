@@ -0,0 +1,388 @@
+//! Deterministic fake data source for tests.
+//!
+//! Replaces `Collector` with a scripted fixture so every screen can be
+//! rendered from known data. A fixture is a line-based script; `tick`
+//! separates frames, and each frame only needs to list what changed:
+//!
+//! ```text
+//! # comment
+//! cpu brand="Fake CPU" physical=4
+//! core 12.5 user=0.08 kernel=0.04     # one line per core
+//! mem total=16G used=6G cached=3G
+//! swap total=4G used=512M
+//! net rx=125K tx=40K
+//! disk name=sda read=12M write=3M riops=120 wiops=40 queue=2 util=35   (one per device)
+//! fs mount=/ dev=/dev/sda2 type=ext4 size=500G used=412G avail=63G inodes=32M iused=4M ro=0
+//! uptime 93784
+//! load 0.52 0.48 0.40
+//! proc pid=1 ppid=0 user=root name=init cmd="/sbin/init" state=S cpu=0.3 mem=0.1
+//!      virt=160M res=12M shr=8M pri=20 ni=0 thr=1 time=1234 io_r=0 io_w=0   (one line)
+//! thread pid=12 ppid=1 name=worker state=S cpu=0.5 wait=futex_wait_queue start=0x7ff6 csw=20
+//!                                     # a thread row (H); takes the proc keys too
+//! netproc pid=1 name=init rx=1K tx=2K conns=3
+//! gpu adapter="Fake GPU" usage=35 dedicated=1G shared=256M
+//! gpuproc pid=1 name=init gpu=12.5 ded=128M shr=16M engine=3D
+//! tick
+//! ```
+//!
+//! Sizes accept htop-style K/M/G/T suffixes (1024-based). `time` is in
+//! hundredths of a second, like htop's TIME+.
+
+use std::collections::HashMap;
+
+use crate::app::App;
+use crate::system::cpu::{CpuCore, CpuInfo};
+use crate::system::disk::{DiskDevice, DiskIoInfo};
+use crate::system::filesystem::FilesystemInfo;
+use crate::system::gpu::GpuProcessInfo;
+use crate::system::memory::MemoryInfo;
+use crate::system::netstat::ProcessNetBandwidth;
+use crate::system::network::NetworkInfo;
+use crate::system::process::{ProcessInfo, ProcessStatus};
+
+/// `tests/fixtures/basic.fixture`, the data most tests run against
+pub const BASIC_FIXTURE: &str = include_str!("../../tests/fixtures/basic.fixture");
+
+/// One frame of fixture data
+#[derive(Clone, Default)]
+struct Frame {
+    cpu_brand: String,
+    physical_cores: usize,
+    cores: Vec<CpuCore>,
+    memory: MemoryInfo,
+    network: NetworkInfo,
+    disks: Vec<DiskDevice>,
+    filesystems: Vec<FilesystemInfo>,
+    uptime: u64,
+    load: (f64, f64, f64),
+    processes: Vec<ProcessInfo>,
+    net_processes: Vec<ProcessNetBandwidth>,
+    gpu_processes: Vec<GpuProcessInfo>,
+    gpu_adapter: String,
+    gpu_usage: f64,
+    gpu_dedicated: u64,
+    gpu_shared: u64,
+}
+
+/// Scripted replacement for `Collector`
+pub struct FakeSource {
+    frames: Vec<Frame>,
+    next: usize,
+}
+
+impl FakeSource {
+    /// Parse a fixture script. Panics with the line number on malformed input
+    /// (fixtures are test data, so failing loudly is what we want).
+    pub fn from_script(script: &str) -> Self {
+        let mut frames = Vec::new();
+        let mut frame = Frame::default();
+        // Lists are replaced (not appended) the first time they appear in a frame
+        let mut seen: Vec<&str> = Vec::new();
+        // Whether the current frame has anything since the last `tick`
+        let mut dirty = false;
+
+        for (lineno, raw) in script.lines().enumerate() {
+            let line = raw.split(" #").next().unwrap_or("").trim();
+            if line.is_empty() || line.starts_with('#') {
+                continue;
+            }
+            let (directive, rest) = line.split_once(' ').unwrap_or((line, ""));
+            let (positional, kv) = parse_args(rest);
+            let err = |what: &str| -> ! {
+                panic!("fixture line {}: {}: {}", lineno + 1, what, raw)
+            };
+            let num = |key: &str| -> f64 {
+                kv.get(key).map(|v| v.parse().unwrap_or_else(|_| err(key))).unwrap_or(0.0)
+            };
+            let size = |key: &str| -> u64 {
+                kv.get(key).map(|v| parse_size(v).unwrap_or_else(|| err(key))).unwrap_or(0)
+            };
+            let text = |key: &str| -> String { kv.get(key).cloned().unwrap_or_default() };
+            let pos = |i: usize| -> f64 {
+                positional.get(i).map(|v| v.parse().unwrap_or_else(|_| err("number"))).unwrap_or(0.0)
+            };
+
+            let list = match directive {
+                "core" | "disk" | "fs" | "proc" | "netproc" | "gpuproc" => directive,
+                "thread" => "proc",
+                _ => "",
+            };
+            if !list.is_empty() && !seen.contains(&list) {
+                seen.push(list);
+                match list {
+                    "core" => frame.cores.clear(),
+                    "disk" => frame.disks.clear(),
+                    "fs" => frame.filesystems.clear(),
+                    "proc" => frame.processes.clear(),
+                    "netproc" => frame.net_processes.clear(),
+                    _ => frame.gpu_processes.clear(),
+                }
+            }
+
+            dirty = directive != "tick";
+            match directive {
+                "tick" => {
+                    frames.push(frame.clone());
+                    seen.clear();
+                }
+                "cpu" => {
+                    frame.cpu_brand = text("brand");
+                    frame.physical_cores = num("physical") as usize;
+                }
+                "core" => {
+                    let usage = pos(0) as f32;
+                    let user = kv.get("user").map(|_| num("user") as f32).unwrap_or(usage / 100.0 * 0.7);
+                    let kernel = kv.get("kernel").map(|_| num("kernel") as f32).unwrap_or(usage / 100.0 * 0.3);
+                    frame.cores.push(CpuCore {
+                        id: frame.cores.len(),
+                        usage_percent: usage,
+                        frequency_mhz: num("mhz") as u64,
+                        user_frac: user,
+                        kernel_frac: kernel,
+                        dpc_frac: num("dpc") as f32,
+                        interrupt_frac: num("irq") as f32,
+                    });
+                }
+                "mem" => {
+                    let total = size("total");
+                    let used = size("used");
+                    frame.memory.total_mem = total;
+                    frame.memory.used_mem = used;
+                    frame.memory.free_mem = total.saturating_sub(used);
+                    frame.memory.cached_mem = size("cached");
+                    frame.memory.buffered_mem = size("buffers");
+                }
+                "swap" => {
+                    let total = size("total");
+                    let used = size("used");
+                    frame.memory.total_swap = total;
+                    frame.memory.used_swap = used;
+                    frame.memory.free_swap = total.saturating_sub(used);
+                }
+                "net" => {
+                    frame.network.rx_bytes_per_sec = size("rx") as f64;
+                    frame.network.tx_bytes_per_sec = size("tx") as f64;
+                    frame.network.total_rx += size("rx");
+                    frame.network.total_tx += size("tx");
+                }
+                "disk" => frame.disks.push(DiskDevice {
+                    name: text("name"),
+                    read_bytes_per_sec: size("read") as f64,
+                    write_bytes_per_sec: size("write") as f64,
+                    reads_per_sec: num("riops"),
+                    writes_per_sec: num("wiops"),
+                    queue_depth: num("queue") as u64,
+                    utilization: num("util"),
+                }),
+                "fs" => frame.filesystems.push(FilesystemInfo {
+                    mount_point: text("mount"),
+                    device: text("dev"),
+                    fs_type: text("type"),
+                    total: size("size"),
+                    used: size("used"),
+                    available: size("avail"),
+                    inodes: kv.get("inodes").map(|_| (size("iused"), size("inodes"))),
+                    read_only: num("ro") != 0.0,
+                }),
+                "uptime" => frame.uptime = pos(0) as u64,
+                "load" => frame.load = (pos(0), pos(1), pos(2)),
+                "proc" | "thread" => {
+                    let is_thread = directive == "thread";
+                    let name = text("name");
+                    let command = kv.get("cmd").cloned().unwrap_or_else(|| if is_thread { String::new() } else { name.clone() });
+                    let status = match text("state").as_str() {
+                        "R" => ProcessStatus::Running,
+                        "S" | "" => ProcessStatus::Sleeping,
+                        "D" => ProcessStatus::DiskSleep,
+                        "T" => ProcessStatus::Stopped,
+                        "Z" => ProcessStatus::Zombie,
+                        _ => ProcessStatus::Unknown,
+                    };
+                    let time_hundredths = num("time") as u64;
+                    frame.processes.push(ProcessInfo {
+                        pid: num("pid") as u32,
+                        ppid: num("ppid") as u32,
+                        name,
+                        command,
+                        user: text("user"),
+                        status,
+                        priority: kv.get("pri").map(|_| num("pri") as i32).unwrap_or(8),
+                        nice: num("ni") as i32,
+                        virtual_mem: size("virt"),
+                        resident_mem: size("res"),
+                        shared_mem: size("shr"),
+                        cpu_usage: num("cpu") as f32,
+                        mem_usage: num("mem") as f32,
+                        run_time: time_hundredths / 100,
+                        cpu_time_100ns: time_hundredths * 100_000,
+                        threads: kv.get("thr").map(|_| num("thr") as u32).unwrap_or(if is_thread { 0 } else { 1 }),
+                        io_read_rate: size("io_r") as f64,
+                        io_write_rate: size("io_w") as f64,
+                        depth: 0,
+                        is_last_child: false,
+                        is_thread,
+                        wait_reason: text("wait"),
+                        start_address: kv.get("start").map(|v| {
+                            u64::from_str_radix(v.trim_start_matches("0x"), 16).unwrap_or_else(|_| err("start"))
+                        }),
+                        context_switches: num("csw") as u64,
+                    });
+                }
+                "netproc" => frame.net_processes.push(ProcessNetBandwidth {
+                    pid: num("pid") as u32,
+                    name: text("name"),
+                    recv_bytes_per_sec: size("rx") as f64,
+                    send_bytes_per_sec: size("tx") as f64,
+                    connection_count: num("conns") as u32,
+                }),
+                "gpu" => {
+                    frame.gpu_adapter = text("adapter");
+                    frame.gpu_usage = num("usage");
+                    frame.gpu_dedicated = size("dedicated");
+                    frame.gpu_shared = size("shared");
+                }
+                "gpuproc" => frame.gpu_processes.push(GpuProcessInfo {
+                    pid: num("pid") as u32,
+                    name: text("name"),
+                    gpu_usage: num("gpu"),
+                    dedicated_mem: size("ded"),
+                    shared_mem: size("shr"),
+                    engine_type: text("engine"),
+                }),
+                _ => err("unknown directive"),
+            }
+        }
+
+        // A trailing frame without `tick` still counts
+        if dirty || frames.is_empty() {
+            frames.push(frame);
+        }
+
+        Self { frames, next: 0 }
+    }
+
+    /// Source scripted with `BASIC_FIXTURE`
+    pub fn basic() -> Self {
+        Self::from_script(BASIC_FIXTURE)
+    }
+
+    /// Default App with the first frame of `BASIC_FIXTURE` applied
+    pub fn basic_app() -> App {
+        let mut app = App::new();
+        Self::basic().refresh(&mut app);
+        app
+    }
+
+    /// Number of frames in the script
+    pub fn frame_count(&self) -> usize {
+        self.frames.len()
+    }
+
+    /// Apply the next frame to the App, the same way `Collector::refresh` does.
+    /// Stays on the last frame once the script is exhausted.
+    pub fn refresh(&mut self, app: &mut App) {
+        if app.paused {
+            return;
+        }
+        let frame = &self.frames[self.next.min(self.frames.len() - 1)];
+        self.next += 1;
+
+        let cores = frame.cores.clone();
+        let total_usage = if cores.is_empty() {
+            0.0
+        } else {
+            cores.iter().map(|c| c.usage_percent).sum::<f32>() / cores.len() as f32
+        };
+        app.cpu_info = CpuInfo {
+            physical_cores: if frame.physical_cores > 0 { frame.physical_cores } else { cores.len() },
+            logical_cores: cores.len(),
+            total_usage,
+            brand: frame.cpu_brand.clone(),
+            cores,
+        };
+        let n = app.cpu_info.cores.len().max(1) as f64;
+        app.cpu_user_frac = app.cpu_info.cores.iter().map(|c| c.user_frac as f64).sum::<f64>() / n;
+        app.cpu_kernel_frac = app.cpu_info.cores.iter().map(|c| c.kernel_frac as f64).sum::<f64>() / n;
+        app.cpu_dpc_frac = app.cpu_info.cores.iter().map(|c| c.dpc_frac as f64).sum::<f64>() / n;
+        app.cpu_interrupt_frac = app.cpu_info.cores.iter().map(|c| c.interrupt_frac as f64).sum::<f64>() / n;
+
+        app.memory_info = frame.memory.clone();
+        app.network_info = frame.network.clone();
+        app.disks = frame.disks.clone();
+        app.disk_io_info = DiskIoInfo::from_devices(&app.disks);
+        app.filesystems = frame.filesystems.clone();
+        app.uptime_seconds = frame.uptime;
+        (app.load_avg_1, app.load_avg_5, app.load_avg_15) = frame.load;
+
+        app.processes = frame.processes.clone();
+        app.total_tasks = app.processes.len();
+        app.running_tasks = app.processes.iter().filter(|p| p.status == ProcessStatus::Running).count();
+        app.sleeping_tasks = app.processes.iter().filter(|p| p.status == ProcessStatus::Sleeping).count();
+        app.total_threads = app.processes.iter().map(|p| p.threads as usize).sum();
+
+        app.record_history();
+        app.collect_users();
+        app.apply_filter();
+        app.sort_processes();
+        if app.tree_view {
+            app.build_tree_view();
+        }
+
+        app.net_processes = frame.net_processes.clone();
+        app.sort_net_processes();
+        app.gpu_processes = frame.gpu_processes.clone();
+        app.sort_gpu_processes();
+        app.gpu_adapter_name = frame.gpu_adapter.clone();
+        app.gpu_overall_usage = frame.gpu_usage;
+        app.gpu_dedicated_mem = frame.gpu_dedicated;
+        app.gpu_shared_mem = frame.gpu_shared;
+
+        app.follow_process();
+        app.clamp_selection();
+        app.tick += 1;
+    }
+}
+
+/// Split `a b key=value key="quoted value"` into positional args and key/value pairs
+fn parse_args(rest: &str) -> (Vec<String>, HashMap<String, String>) {
+    let mut positional = Vec::new();
+    let mut kv = HashMap::new();
+    let mut tokens = Vec::new();
+    let mut current = String::new();
+    let mut in_quotes = false;
+    for c in rest.chars() {
+        match c {
+            '"' => in_quotes = !in_quotes,
+            ' ' | '\t' if !in_quotes => {
+                if !current.is_empty() {
+                    tokens.push(std::mem::take(&mut current));
+                }
+            }
+            _ => current.push(c),
+        }
+    }
+    if !current.is_empty() {
+        tokens.push(current);
+    }
+    for token in tokens {
+        match token.split_once('=') {
+            Some((k, v)) => {
+                kv.insert(k.to_string(), v.to_string());
+            }
+            None => positional.push(token),
+        }
+    }
+    (positional, kv)
+}
+
+/// Parse `512`, `4K`, `1.5M`, `16G`, `2T` (1024-based)
+fn parse_size(s: &str) -> Option<u64> {
+    let (digits, mult) = match s.chars().last()? {
+        'K' | 'k' => (&s[..s.len() - 1], 1024.0),
+        'M' | 'm' => (&s[..s.len() - 1], 1024.0 * 1024.0),
+        'G' | 'g' => (&s[..s.len() - 1], 1024.0 * 1024.0 * 1024.0),
+        'T' | 't' => (&s[..s.len() - 1], 1024.0 * 1024.0 * 1024.0 * 1024.0),
+        _ => (s, 1.0),
+    };
+    digits.parse::<f64>().ok().map(|v| (v * mult) as u64)
+}
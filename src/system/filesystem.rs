@@ -0,0 +1,136 @@
+//! Mounted filesystems: size, used and available space, and inode usage.
+//!
+//! The mount list and space come from sysinfo's `Disks`. Inode counts come
+//! from statvfs on Unix, and only while the `d` overlay shows them; Windows
+//! filesystems have no fixed inode table, so they have none. Reading a mount
+//! can block on a hung network share, so the collector only asks while the
+//! Disk usage meter or the overlay is on screen.
+
+use sysinfo::Disks;
+
+/// One mounted filesystem (Disk usage meter, df overlay)
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct FilesystemInfo {
+    pub mount_point: String,
+    /// Device or volume name
+    pub device: String,
+    pub fs_type: String,
+    pub total: u64,
+    pub used: u64,
+    /// Space an unprivileged user can still write
+    pub available: u64,
+    /// None when the filesystem doesn't report inodes
+    pub inodes: Option<(u64, u64)>, // (used, total)
+    pub read_only: bool,
+}
+
+impl FilesystemInfo {
+    /// Share in use (0-100) of `used + available`. sysinfo counts space
+    /// reserved for root as used
+    pub fn used_percent(&self) -> f64 {
+        let usable = self.used + self.available;
+        if usable > 0 { self.used as f64 / usable as f64 * 100.0 } else { 0.0 }
+    }
+
+    pub fn inode_percent(&self) -> Option<f64> {
+        self.inodes.filter(|(_, total)| *total > 0).map(|(used, total)| used as f64 / total as f64 * 100.0)
+    }
+
+    /// Can fill up: read-only images (squashfs, ISOs) are always 100% used
+    pub fn is_writable(&self) -> bool {
+        !self.read_only && self.total > 0
+    }
+}
+
+/// The fullest writable filesystem, the one the Disk usage meter shows
+pub fn fullest(filesystems: &[FilesystemInfo]) -> Option<&FilesystemInfo> {
+    filesystems
+        .iter()
+        .filter(|f| f.is_writable())
+        .max_by(|a, b| a.used_percent().total_cmp(&b.used_percent()))
+}
+
+/// Every mounted filesystem sysinfo lists, by mount point. `disks` is
+/// refreshed in place, re-reading the mount list. Inode counts cost another
+/// statvfs per mount, so they're only read `with_inodes`.
+pub fn read_filesystems(disks: &mut Disks, with_inodes: bool) -> Vec<FilesystemInfo> {
+    disks.refresh(true);
+    let mut filesystems: Vec<FilesystemInfo> = disks
+        .list()
+        .iter()
+        .map(|d| {
+            let mount_point = d.mount_point().to_string_lossy().into_owned();
+            let total = d.total_space();
+            let available = d.available_space();
+            FilesystemInfo {
+                device: d.name().to_string_lossy().into_owned(),
+                fs_type: d.file_system().to_string_lossy().into_owned(),
+                total,
+                used: total.saturating_sub(available),
+                available,
+                inodes: if with_inodes { inodes(&mount_point) } else { None },
+                read_only: d.is_read_only(),
+                mount_point,
+            }
+        })
+        .collect();
+    filesystems.sort_by(|a, b| a.mount_point.cmp(&b.mount_point));
+    // Bind mounts and overlays list the same mount point more than once
+    filesystems.dedup_by(|a, b| a.mount_point == b.mount_point);
+    filesystems
+}
+
+/// (used, total) inodes from statvfs, which sysinfo doesn't report
+#[cfg(unix)]
+fn inodes(mount_point: &str) -> Option<(u64, u64)> {
+    let path = std::ffi::CString::new(mount_point).ok()?;
+    let mut st: libc::statvfs = unsafe { std::mem::zeroed() };
+    if unsafe { libc::statvfs(path.as_ptr(), &mut st) } != 0 {
+        return None;
+    }
+    // Filesystems without an inode table (btrfs, vfat) report zero
+    let files = st.f_files as u64;
+    (files > 0).then(|| (files.saturating_sub(st.f_ffree as u64), files))
+}
+
+#[cfg(windows)]
+fn inodes(_mount_point: &str) -> Option<(u64, u64)> {
+    None
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn fs(mount_point: &str, total: u64, used: u64, available: u64, read_only: bool) -> FilesystemInfo {
+        FilesystemInfo { mount_point: mount_point.to_string(), total, used, available, read_only, ..Default::default() }
+    }
+
+    #[test]
+    fn used_percent_of_usable_space() {
+        assert_eq!(fs("/", 100, 25, 75, false).used_percent(), 25.0);
+        // Space neither used nor available (an ext4 root reserve) is left out
+        assert_eq!(fs("/", 100, 45, 45, false).used_percent(), 50.0);
+        assert_eq!(fs("/empty", 0, 0, 0, false).used_percent(), 0.0);
+
+        let mut with_inodes = fs("/", 100, 25, 75, false);
+        with_inodes.inodes = Some((250, 1000));
+        assert_eq!(with_inodes.inode_percent(), Some(25.0));
+        with_inodes.inodes = Some((0, 0));
+        assert_eq!(with_inodes.inode_percent(), None);
+    }
+
+    #[test]
+    fn fullest_skips_read_only_and_empty() {
+        let filesystems = [
+            fs("/", 100, 60, 40, false),
+            fs("/snap/core/1", 50, 50, 0, true),
+            fs("/proc", 0, 0, 0, false),
+            fs("/home", 200, 180, 20, false),
+            fs("/boot", 10, 5, 5, false),
+        ];
+        assert_eq!(fullest(&filesystems).map(|f| f.mount_point.as_str()), Some("/home"));
+        assert!(fullest(&filesystems[1..3]).is_none());
+        assert!(fullest(&[]).is_none());
+    }
+}
@@ -6,9 +6,14 @@
 //!   \GPU Engine(pid_N_*)\Utilization Percentage   — per engine per process
 //!   \GPU Process Memory(pid_N_*)\Dedicated Usage  — dedicated GPU memory per process
 //!   \GPU Process Memory(pid_N_*)\Shared Usage     — shared GPU memory per process
+//!
+//! PDH is Windows-only; on other platforms `GpuCollector` returns no data.
 
+#[cfg(windows)]
 use std::collections::HashMap;
+#[cfg(windows)]
 use std::ffi::c_void;
+#[cfg(windows)]
 use std::os::windows::process::CommandExt;
 
 // ─── Types ───────────────────────────────────────────────────────────────────
@@ -35,13 +40,19 @@ pub struct GpuAdapterInfo {
 
 // ─── PDH FFI ─────────────────────────────────────────────────────────────────
 
+#[cfg(windows)]
 type PdhQueryHandle = isize;
+#[cfg(windows)]
 type PdhCounterHandle = isize;
 
+#[cfg(windows)]
 const PDH_FMT_DOUBLE: u32 = 0x00000200;
+#[cfg(windows)]
 const PDH_FMT_LARGE: u32 = 0x00000400;
+#[cfg(windows)]
 const PDH_MORE_DATA: u32 = 0x800007D2;
 
+#[cfg(windows)]
 #[repr(C)]
 #[allow(non_snake_case)]
 struct PDH_FMT_COUNTERVALUE_ITEM_DOUBLE {
@@ -49,6 +60,7 @@ struct PDH_FMT_COUNTERVALUE_ITEM_DOUBLE {
     value: PDH_FMT_COUNTERVALUE_DOUBLE,
 }
 
+#[cfg(windows)]
 #[repr(C)]
 #[derive(Copy, Clone)]
 #[allow(non_snake_case)]
@@ -57,6 +69,7 @@ struct PDH_FMT_COUNTERVALUE_DOUBLE {
     doubleValue: f64,
 }
 
+#[cfg(windows)]
 #[repr(C)]
 #[allow(non_snake_case)]
 struct PDH_FMT_COUNTERVALUE_ITEM_LARGE {
@@ -64,6 +77,7 @@ struct PDH_FMT_COUNTERVALUE_ITEM_LARGE {
     value: PDH_FMT_COUNTERVALUE_LARGE,
 }
 
+#[cfg(windows)]
 #[repr(C)]
 #[derive(Copy, Clone)]
 #[allow(non_snake_case)]
@@ -72,6 +86,7 @@ struct PDH_FMT_COUNTERVALUE_LARGE {
     largeValue: i64,
 }
 
+#[cfg(windows)]
 #[link(name = "pdh")]
 extern "system" {
     fn PdhOpenQueryW(
@@ -104,6 +119,7 @@ extern "system" {
 
 /// Persistent GPU data collector using PDH performance counters.
 /// Must be kept alive across ticks to compute utilization deltas.
+#[cfg(windows)]
 pub struct GpuCollector {
     query: PdhQueryHandle,
     engine_counter: PdhCounterHandle,
@@ -115,6 +131,7 @@ pub struct GpuCollector {
     pub adapter_info: GpuAdapterInfo,
 }
 
+#[cfg(windows)]
 impl GpuCollector {
     pub fn new() -> Self {
         // Lazy initialization: don't set up PDH queries until GPU tab is first accessed.
@@ -330,6 +347,7 @@ impl GpuCollector {
     }
 }
 
+#[cfg(windows)]
 impl Drop for GpuCollector {
     fn drop(&mut self) {
         if self.query != 0 {
@@ -347,6 +365,7 @@ impl Drop for GpuCollector {
 // Memory instances look like:
 //   "pid_1234_luid_0x00_0x0000ABCD_phys_0"
 
+#[cfg(windows)]
 fn parse_engine_instance(name: &str) -> Option<(u32, String)> {
     // Extract PID: look for "pid_" prefix
     let pid_start = name.find("pid_")? + 4;
@@ -363,6 +382,7 @@ fn parse_engine_instance(name: &str) -> Option<(u32, String)> {
     Some((pid, eng_type))
 }
 
+#[cfg(windows)]
 fn parse_memory_instance(name: &str) -> Option<u32> {
     let pid_start = name.find("pid_")? + 4;
     let pid_end = name[pid_start..].find('_').map(|i| pid_start + i).unwrap_or(name.len());
@@ -371,10 +391,12 @@ fn parse_memory_instance(name: &str) -> Option<u32> {
 
 // ─── Helpers ─────────────────────────────────────────────────────────────────
 
+#[cfg(windows)]
 fn to_wide(s: &str) -> Vec<u16> {
     s.encode_utf16().chain(std::iter::once(0)).collect()
 }
 
+#[cfg(windows)]
 unsafe fn read_wide_ptr(ptr: *mut u16) -> String {
     if ptr.is_null() {
         return String::new();
@@ -387,6 +409,7 @@ unsafe fn read_wide_ptr(ptr: *mut u16) -> String {
 }
 
 /// Detect GPU adapter name via DXGI (best-effort, returns first adapter name)
+#[cfg(windows)]
 pub fn detect_gpu_adapter_name() -> String {
     // Use WMI via command line as a simple fallback
     // DXGI COM initialization adds complexity — use simple Win32 registry approach
@@ -412,3 +435,30 @@ pub fn detect_gpu_adapter_name() -> String {
         Err(_) => "Unknown GPU".to_string(),
     }
 }
+
+// ─── Non-Windows fallback ────────────────────────────────────────────────────
+
+/// No PDH outside Windows: the GPU tab stays empty.
+#[cfg(not(windows))]
+pub struct GpuCollector {
+    /// Cached adapter info
+    pub adapter_info: GpuAdapterInfo,
+}
+
+#[cfg(not(windows))]
+impl GpuCollector {
+    pub fn new() -> Self {
+        GpuCollector {
+            adapter_info: GpuAdapterInfo::default(),
+        }
+    }
+
+    pub fn collect(&mut self) -> Vec<GpuProcessInfo> {
+        Vec::new()
+    }
+}
+
+#[cfg(not(windows))]
+pub fn detect_gpu_adapter_name() -> String {
+    "Unknown GPU".to_string()
+}
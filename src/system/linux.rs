@@ -0,0 +1,725 @@
+//! Linux `/proc` backend for the data sysinfo doesn't expose:
+//! - PRI / NI / thread count from /proc/[pid]/stat
+//! - Private resident memory (RssAnon) and owner UID from /proc/[pid]/status
+//! - TIME+ from utime + stime in /proc/[pid]/stat
+//! - I/O counters from /proc/[pid]/io
+//! - Per-core CPU times (user/system/irq/softirq) from /proc/stat
+//! - Priority changes via setpriority, affinity via Cpus_allowed + sched_setaffinity
+//! - Environment, cwd and executable from /proc/[pid]/environ, cwd and exe
+//! - Open files from /proc/[pid]/fd, with access mode and locks from fdinfo
+//! - Memory map from /proc/[pid]/smaps
+//! - Per-thread CPU time and state from /proc/[pid]/task/[tid]/stat, wait
+//!   channel from wchan, context switches from status
+
+use std::collections::HashMap;
+use std::fs;
+
+use crate::app::KILL_SIGNALS;
+use crate::system::backend::{
+    split_env_entry, CpuCoreSample, CpuMonitor, HandleInfo, MemoryRegion, ProcessData,
+    ProcessEnvironment, QueryError, RegionKind, SystemBackend, ThreadInfo,
+};
+use crate::system::process::ProcessStatus;
+
+/// Clock ticks per second used by /proc/[pid]/stat (USER_HZ)
+fn user_hz() -> u64 {
+    match unsafe { libc::sysconf(libc::_SC_CLK_TCK) } {
+        hz if hz > 0 => hz as u64,
+        _ => 100,
+    }
+}
+
+/// Linux implementation of `SystemBackend`
+pub struct LinuxBackend;
+
+impl SystemBackend for LinuxBackend {
+    fn enumerate_pids(&self) -> Vec<u32> {
+        list_numeric_dirs("/proc")
+    }
+
+    fn process_data(&self, pids: &[u32]) -> HashMap<u32, ProcessData> {
+        let mut result = HashMap::with_capacity(pids.len());
+        for &pid in pids {
+            let stat = match read_stat(&format!("/proc/{}/stat", pid)) {
+                Some(s) => s,
+                None => continue,
+            };
+            let status = read_status(pid);
+            result.insert(pid, ProcessData {
+                priority: stat.priority,
+                nice: stat.nice,
+                thread_count: stat.num_threads,
+                // resident - RssAnon = RssFile + RssShmem, which is htop's SHR
+                private_working_set: status.rss_anon_kb * 1024,
+            });
+        }
+        result
+    }
+
+    fn process_users(&self, pids: &[u32]) -> HashMap<u32, String> {
+        let names = read_passwd();
+        let mut result = HashMap::with_capacity(pids.len());
+        for &pid in pids {
+            if let Some(uid) = read_status(pid).uid {
+                let name = names.get(&uid).cloned().unwrap_or_else(|| uid.to_string());
+                result.insert(pid, name);
+            }
+        }
+        result
+    }
+
+    fn process_times(&self, pids: &[u32]) -> HashMap<u32, u64> {
+        let mut result = HashMap::with_capacity(pids.len());
+        for &pid in pids {
+            if let Some(stat) = read_stat(&format!("/proc/{}/stat", pid)) {
+                // clock ticks → 100ns units (same unit as Win32 FILETIME)
+                let ticks = stat.utime + stat.stime;
+                result.insert(pid, ticks * (10_000_000 / user_hz()));
+            }
+        }
+        result
+    }
+
+    fn io_counters(&self, pids: &[u32]) -> HashMap<u32, (u64, u64)> {
+        let mut result = HashMap::with_capacity(pids.len());
+        for &pid in pids {
+            // rchar/wchar count all read()/write() traffic (disk, pipes, sockets),
+            // matching GetProcessIoCounters — the Net tab relies on that.
+            // /proc/[pid]/io is only readable for our own processes without root.
+            let content = match fs::read_to_string(format!("/proc/{}/io", pid)) {
+                Ok(c) => c,
+                Err(_) => continue,
+            };
+            let mut read = 0u64;
+            let mut write = 0u64;
+            for line in content.lines() {
+                if let Some(v) = line.strip_prefix("rchar:") {
+                    read = v.trim().parse().unwrap_or(0);
+                } else if let Some(v) = line.strip_prefix("wchar:") {
+                    write = v.trim().parse().unwrap_or(0);
+                }
+            }
+            result.insert(pid, (read, write));
+        }
+        result
+    }
+
+    fn threads(&self, pid: u32, get_names: bool) -> Vec<ThreadInfo> {
+        let mut threads = Vec::new();
+        for tid in list_numeric_dirs(&format!("/proc/{}/task", pid)) {
+            // The main thread is the process row itself
+            if tid == pid {
+                continue;
+            }
+            let task = format!("/proc/{}/task/{}", pid, tid);
+            let stat = match read_stat(&format!("{}/stat", task)) {
+                Some(s) => s,
+                None => continue,
+            };
+            let status = thread_status(stat.state);
+            // The kernel function a blocked thread sleeps in ("0" when running)
+            let wait_reason = match status {
+                ProcessStatus::Running => String::new(),
+                _ => fs::read_to_string(format!("{}/wchan", task))
+                    .map(|w| w.trim().to_string())
+                    .unwrap_or_default(),
+            };
+            threads.push(ThreadInfo {
+                thread_id: tid,
+                owner_pid: pid,
+                base_priority: stat.priority,
+                name: if get_names { stat.comm } else { String::new() },
+                cpu_time_100ns: (stat.utime + stat.stime) * (10_000_000 / user_hz()),
+                status,
+                wait_reason: if wait_reason == "0" { String::new() } else { wait_reason },
+                start_address: None,
+                context_switches: read_context_switches(&format!("{}/status", task)),
+            });
+        }
+        threads
+    }
+
+    fn handles(&self, pid: u32) -> Vec<HandleInfo> {
+        let entries = match fs::read_dir(format!("/proc/{}/fd", pid)) {
+            Ok(e) => e,
+            Err(_) => return Vec::new(),
+        };
+        let mut fds: Vec<(u32, String)> = entries
+            .filter_map(|e| e.ok())
+            .filter_map(|e| {
+                let fd: u32 = e.file_name().to_str()?.parse().ok()?;
+                let target = fs::read_link(e.path()).ok()?;
+                Some((fd, target.to_string_lossy().to_string()))
+            })
+            .collect();
+        fds.sort_by_key(|(fd, _)| *fd);
+
+        fds.into_iter()
+            .map(|(fd, target)| {
+                let handle_type = if target.starts_with("socket:") {
+                    "Socket"
+                } else if target.starts_with("pipe:") {
+                    "Pipe"
+                } else if target.starts_with("anon_inode:") {
+                    "Anon"
+                } else if target.starts_with("/dev/") {
+                    "Device"
+                } else if fs::metadata(format!("/proc/{}/fd/{}", pid, fd)).is_ok_and(|m| m.is_dir()) {
+                    "Dir"
+                } else {
+                    "File"
+                };
+                let fdinfo = fs::read_to_string(format!("/proc/{}/fdinfo/{}", pid, fd)).unwrap_or_default();
+                let (access, lock) = parse_fdinfo(&fdinfo);
+                HandleInfo {
+                    id: Some(fd as u64),
+                    handle_type: handle_type.to_string(),
+                    name: target,
+                    access,
+                    lock,
+                }
+            })
+            .collect()
+    }
+
+    fn environment(&self, pid: u32) -> ProcessEnvironment {
+        // All three need ptrace-level access: same user (and not setuid) or root
+        let link = |name: &str| {
+            fs::read_link(format!("/proc/{}/{}", pid, name))
+                .map(|p| p.to_string_lossy().to_string())
+                .map_err(|e| QueryError::from_io(&e))
+        };
+        let vars = fs::read(format!("/proc/{}/environ", pid))
+            .map(|bytes| {
+                bytes
+                    .split(|&b| b == 0)
+                    .filter(|entry| !entry.is_empty())
+                    .map(|entry| split_env_entry(&String::from_utf8_lossy(entry)))
+                    .collect()
+            })
+            .map_err(|e| QueryError::from_io(&e));
+        let start_time = read_stat(&format!("/proc/{}/stat", pid))
+            .zip(self.boot_time())
+            .map(|(stat, boot)| boot + (stat.start_ticks / user_hz()) as i64);
+        ProcessEnvironment { exe: link("exe"), cwd: link("cwd"), vars, start_time }
+    }
+
+    fn memory_map(&self, pid: u32) -> Result<Vec<MemoryRegion>, QueryError> {
+        // smaps is the maps listing plus per-region statistics; reading it
+        // needs the same access as environ
+        let content = fs::read_to_string(format!("/proc/{}/smaps", pid)).map_err(|e| QueryError::from_io(&e))?;
+        Ok(parse_smaps(&content))
+    }
+
+    fn boot_time(&self) -> Option<i64> {
+        let content = fs::read_to_string("/proc/stat").ok()?;
+        content
+            .lines()
+            .find_map(|l| l.strip_prefix("btime "))
+            .and_then(|v| v.trim().parse().ok())
+    }
+
+    fn cpu_monitor(&self) -> Box<dyn CpuMonitor> {
+        let (brand, frequency) = read_cpu_info();
+        let (total, cores) = read_proc_stat_cpus();
+        Box::new(ProcStatCpuMonitor {
+            prev_total: total,
+            prev: cores,
+            brand,
+            frequency,
+        })
+    }
+
+    fn raise_priority(&self, pid: u32) -> bool {
+        change_nice(pid, -1)
+    }
+
+    fn lower_priority(&self, pid: u32) -> bool {
+        change_nice(pid, 1)
+    }
+
+    fn kill(&self, pid: u32, signal_index: usize) -> std::io::Result<()> {
+        let signal = KILL_SIGNALS
+            .get(signal_index)
+            .and_then(|(num, _)| num.parse().ok())
+            .unwrap_or(libc::SIGTERM);
+        if unsafe { libc::kill(pid as libc::pid_t, signal) } == 0 {
+            Ok(())
+        } else {
+            Err(std::io::Error::last_os_error())
+        }
+    }
+
+    fn get_affinity(&self, pid: u32) -> (usize, usize, bool) {
+        let cpu_count = self.cpu_count();
+        let system_mask = if cpu_count >= usize::BITS as usize {
+            usize::MAX
+        } else {
+            (1usize << cpu_count) - 1
+        };
+        match read_status(pid).cpus_allowed {
+            Some(mask) => (mask & system_mask, system_mask, true),
+            None => (0, 0, false),
+        }
+    }
+
+    fn set_affinity(&self, pid: u32, mask: usize) -> bool {
+        if mask == 0 {
+            return false;
+        }
+        unsafe {
+            let mut set: libc::cpu_set_t = std::mem::zeroed();
+            for cpu in (0..usize::BITS as usize).filter(|cpu| mask & (1 << cpu) != 0) {
+                libc::CPU_SET(cpu, &mut set);
+            }
+            libc::sched_setaffinity(pid as libc::pid_t, std::mem::size_of::<libc::cpu_set_t>(), &set) == 0
+        }
+    }
+}
+
+// ─── /proc parsing ─────────────────────────────────────────────────────────
+
+/// Fields we use from /proc/[pid]/stat (or /proc/[pid]/task/[tid]/stat)
+struct ProcStat {
+    comm: String,
+    state: char,
+    utime: u64,
+    stime: u64,
+    priority: i32,
+    nice: i32,
+    num_threads: u32,
+    start_ticks: u64, // clock ticks after boot
+}
+
+fn read_stat(path: &str) -> Option<ProcStat> {
+    parse_stat(&fs::read_to_string(path).ok()?)
+}
+
+/// Parse a stat file. The comm field is parenthesised and may contain spaces
+/// or ')' itself, so split at the LAST ')'.
+fn parse_stat(content: &str) -> Option<ProcStat> {
+    let open = content.find('(')?;
+    let close = content.rfind(')')?;
+    let comm = content.get(open + 1..close)?.to_string();
+    // Fields after comm, starting at field 3 (state)
+    let fields: Vec<&str> = content.get(close + 1..)?.split_whitespace().collect();
+    let field = |n: usize| fields.get(n - 3).copied();
+
+    Some(ProcStat {
+        comm,
+        state: field(3)?.chars().next()?,
+        utime: field(14)?.parse().ok()?,
+        stime: field(15)?.parse().ok()?,
+        priority: field(18)?.parse().ok()?,
+        nice: field(19)?.parse().ok()?,
+        num_threads: field(20)?.parse().ok()?,
+        start_ticks: field(22)?.parse().ok()?,
+    })
+}
+
+/// Fields we use from /proc/[pid]/status
+#[derive(Default)]
+struct ProcStatus {
+    uid: Option<u32>,
+    rss_anon_kb: u64,
+    cpus_allowed: Option<usize>,
+}
+
+fn read_status(pid: u32) -> ProcStatus {
+    let mut status = ProcStatus::default();
+    let content = match fs::read_to_string(format!("/proc/{}/status", pid)) {
+        Ok(c) => c,
+        Err(_) => return status,
+    };
+    for line in content.lines() {
+        if let Some(v) = line.strip_prefix("Uid:") {
+            // Real, effective, saved, filesystem — htop shows the real UID
+            status.uid = v.split_whitespace().next().and_then(|u| u.parse().ok());
+        } else if let Some(v) = line.strip_prefix("RssAnon:") {
+            status.rss_anon_kb = v.trim().trim_end_matches("kB").trim().parse().unwrap_or(0);
+        } else if let Some(v) = line.strip_prefix("Cpus_allowed:") {
+            // Comma-separated 32-bit hex groups, most significant first
+            let hex: String = v.trim().chars().filter(|c| *c != ',').collect();
+            let keep = (usize::BITS / 4) as usize;
+            let low = &hex[hex.len().saturating_sub(keep)..];
+            status.cpus_allowed = usize::from_str_radix(low, 16).ok();
+        }
+    }
+    status
+}
+
+/// State letter from a stat file as a status
+fn thread_status(state: char) -> ProcessStatus {
+    match state {
+        'R' => ProcessStatus::Running,
+        'S' | 'I' => ProcessStatus::Sleeping,
+        'D' => ProcessStatus::DiskSleep,
+        'T' | 't' => ProcessStatus::Stopped,
+        'Z' | 'X' => ProcessStatus::Zombie,
+        _ => ProcessStatus::Unknown,
+    }
+}
+
+fn read_context_switches(path: &str) -> u64 {
+    parse_context_switches(&fs::read_to_string(path).unwrap_or_default())
+}
+
+/// voluntary_ctxt_switches + nonvoluntary_ctxt_switches from a status file
+fn parse_context_switches(content: &str) -> u64 {
+    content
+        .lines()
+        .filter_map(|line| {
+            line.strip_prefix("voluntary_ctxt_switches:")
+                .or_else(|| line.strip_prefix("nonvoluntary_ctxt_switches:"))
+        })
+        .filter_map(|v| v.trim().parse::<u64>().ok())
+        .sum()
+}
+
+/// Access mode and locks held from /proc/[pid]/fdinfo/[fd]:
+///   flags:  0100002       (octal open flags; the low two bits are O_ACCMODE)
+///   lock:   1: POSIX  ADVISORY  WRITE 4242 fd:01:1234 0 EOF
+fn parse_fdinfo(content: &str) -> (String, String) {
+    let mut access = "";
+    let mut locks = Vec::new();
+    for line in content.lines() {
+        if let Some(v) = line.strip_prefix("flags:") {
+            access = match u32::from_str_radix(v.trim(), 8).map(|f| f & 3) {
+                Ok(0) => "r",
+                Ok(1) => "w",
+                Ok(2) => "rw",
+                _ => "",
+            };
+        } else if let Some(v) = line.strip_prefix("lock:") {
+            // Kind (FLOCK, POSIX, OFDLCK, LEASE) and READ/WRITE
+            let words: Vec<&str> = v.split_whitespace().collect();
+            if let (Some(kind), Some(mode)) = (words.get(1), words.get(3)) {
+                locks.push(format!("{} {}", kind, mode));
+            }
+        }
+    }
+    (access.to_string(), locks.join(", "))
+}
+
+/// Regions from /proc/[pid]/smaps: a maps line per region
+///   7f3a1c000000-7f3a1c021000 rw-p 00000000 00:00 0      [heap]
+/// followed by `Key:   value kB` lines, of which we use Rss
+fn parse_smaps(content: &str) -> Vec<MemoryRegion> {
+    let mut regions: Vec<MemoryRegion> = Vec::new();
+    for line in content.lines() {
+        if let Some(v) = line.strip_prefix("Rss:") {
+            if let Some(region) = regions.last_mut() {
+                region.rss = v.trim().trim_end_matches("kB").trim().parse::<u64>().unwrap_or(0) * 1024;
+            }
+            continue;
+        }
+        // The first five fields are single-space separated, the path is padded
+        let mut fields = line.splitn(6, ' ');
+        let range = fields.next().and_then(|r| r.split_once('-')).and_then(|(start, end)| {
+            Some((u64::from_str_radix(start, 16).ok()?, u64::from_str_radix(end, 16).ok()?))
+        });
+        let (Some((start, end)), Some(perms)) = (range, fields.next()) else {
+            continue;
+        };
+        let path = fields.nth(3).unwrap_or("").trim_start().to_string();
+        regions.push(MemoryRegion { start, end, rss: 0, protection: perms.to_string(), kind: RegionKind::Private, path });
+    }
+
+    // A file mapped executable anywhere is a program or library; its data
+    // segments count as part of the image too
+    let images: std::collections::HashSet<String> = regions
+        .iter()
+        .filter(|r| r.path.starts_with('/') && r.protection.contains('x'))
+        .map(|r| r.path.clone())
+        .collect();
+    for region in &mut regions {
+        let path = region.path.as_str();
+        region.kind = if path == "[heap]" {
+            RegionKind::Heap
+        } else if path.starts_with("[stack") {
+            RegionKind::Stack
+        } else if matches!(path, "[vdso]" | "[vvar]" | "[vsyscall]") || images.contains(path) {
+            RegionKind::Image
+        } else if path.starts_with('/') || region.protection.ends_with('s') {
+            RegionKind::Mapped
+        } else {
+            RegionKind::Private
+        };
+    }
+    regions
+}
+
+/// UID → user name map from /etc/passwd
+fn read_passwd() -> HashMap<u32, String> {
+    let mut names = HashMap::new();
+    if let Ok(content) = fs::read_to_string("/etc/passwd") {
+        for line in content.lines() {
+            let mut parts = line.split(':');
+            let name = parts.next();
+            let uid = parts.nth(1).and_then(|u| u.parse::<u32>().ok());
+            if let (Some(name), Some(uid)) = (name, uid) {
+                names.entry(uid).or_insert_with(|| name.to_string());
+            }
+        }
+    }
+    names
+}
+
+/// All numeric entries of a directory (PIDs in /proc, TIDs in /proc/[pid]/task)
+fn list_numeric_dirs(path: &str) -> Vec<u32> {
+    match fs::read_dir(path) {
+        Ok(entries) => entries
+            .filter_map(|e| e.ok())
+            .filter_map(|e| e.file_name().to_str()?.parse().ok())
+            .collect(),
+        Err(_) => Vec::new(),
+    }
+}
+
+/// Step the nice value by `delta` (F7 = -1, F8 = +1, as in htop).
+/// Lowering nice below its current value requires root.
+fn change_nice(pid: u32, delta: i32) -> bool {
+    let current = match read_stat(&format!("/proc/{}/stat", pid)) {
+        Some(s) => s.nice,
+        None => return false,
+    };
+    let new_nice = (current + delta).clamp(-20, 19);
+    if new_nice == current {
+        return false;
+    }
+    unsafe { libc::setpriority(libc::PRIO_PROCESS, pid as libc::id_t, new_nice) == 0 }
+}
+
+// ─── Per-core CPU monitor via /proc/stat ───────────────────────────────────
+
+/// Cumulative jiffies for one `cpu` line of /proc/stat
+#[derive(Clone, Copy, Default)]
+struct CpuTimes {
+    user: u64,    // user + nice
+    system: u64,
+    idle: u64,    // idle + iowait
+    irq: u64,
+    softirq: u64,
+    steal: u64,
+}
+
+impl CpuTimes {
+    fn total(&self) -> u64 {
+        self.user + self.system + self.idle + self.irq + self.softirq + self.steal
+    }
+
+    fn delta(&self, prev: &CpuTimes) -> CpuTimes {
+        CpuTimes {
+            user: self.user.saturating_sub(prev.user),
+            system: self.system.saturating_sub(prev.system),
+            idle: self.idle.saturating_sub(prev.idle),
+            irq: self.irq.saturating_sub(prev.irq),
+            softirq: self.softirq.saturating_sub(prev.softirq),
+            steal: self.steal.saturating_sub(prev.steal),
+        }
+    }
+}
+
+/// Read the aggregate `cpu` line and every `cpuN` line
+fn read_proc_stat_cpus() -> (CpuTimes, Vec<CpuTimes>) {
+    let mut total = CpuTimes::default();
+    let mut cores = Vec::new();
+    let content = match fs::read_to_string("/proc/stat") {
+        Ok(c) => c,
+        Err(_) => return (total, cores),
+    };
+    for line in content.lines() {
+        let mut parts = line.split_whitespace();
+        let label = match parts.next() {
+            Some(l) if l.starts_with("cpu") => l,
+            _ => continue,
+        };
+        let v: Vec<u64> = parts.map(|p| p.parse().unwrap_or(0)).collect();
+        let get = |i: usize| v.get(i).copied().unwrap_or(0);
+        // user nice system idle iowait irq softirq steal
+        let times = CpuTimes {
+            user: get(0) + get(1),
+            system: get(2),
+            idle: get(3) + get(4),
+            irq: get(5),
+            softirq: get(6),
+            steal: get(7),
+        };
+        if label == "cpu" {
+            total = times;
+        } else {
+            cores.push(times);
+        }
+    }
+    (total, cores)
+}
+
+/// CPU brand + MHz from /proc/cpuinfo (first processor entry)
+fn read_cpu_info() -> (String, u64) {
+    let content = fs::read_to_string("/proc/cpuinfo").unwrap_or_default();
+    let value = |key: &str| {
+        content
+            .lines()
+            .find(|l| l.starts_with(key))
+            .and_then(|l| l.split_once(':'))
+            .map(|(_, v)| v.trim().to_string())
+    };
+    let brand = value("model name").unwrap_or_else(|| "Unknown CPU".to_string());
+    let frequency = value("cpu MHz")
+        .and_then(|v| v.parse::<f64>().ok())
+        .map(|mhz| mhz as u64)
+        .unwrap_or(0);
+    (brand, frequency)
+}
+
+struct ProcStatCpuMonitor {
+    prev_total: CpuTimes,
+    prev: Vec<CpuTimes>,
+    brand: String,
+    frequency: u64,
+}
+
+impl CpuMonitor for ProcStatCpuMonitor {
+    fn brand(&self) -> &str {
+        &self.brand
+    }
+
+    fn frequency_mhz(&self) -> u64 {
+        self.frequency
+    }
+
+    fn sample(&mut self) -> Vec<CpuCoreSample> {
+        let (_, current) = read_proc_stat_cpus();
+        let samples = current
+            .iter()
+            .enumerate()
+            .map(|(i, now)| {
+                let d = match self.prev.get(i) {
+                    Some(prev) => now.delta(prev),
+                    None => return CpuCoreSample::idle(),
+                };
+                let total = d.total();
+                if total == 0 {
+                    return CpuCoreSample::idle();
+                }
+                let tf = total as f64;
+                let active = total - d.idle;
+                CpuCoreSample {
+                    usage_percent: (active as f64 / tf * 100.0).clamp(0.0, 100.0) as f32,
+                    user_frac: (d.user as f64 / tf) as f32,
+                    kernel_frac: (d.system as f64 / tf) as f32,
+                    dpc_frac: (d.softirq as f64 / tf) as f32,
+                    interrupt_frac: (d.irq as f64 / tf) as f32,
+                }
+            })
+            .collect();
+        self.prev = current;
+        samples
+    }
+
+    fn sample_split(&mut self) -> (f64, f64) {
+        let (now, _) = read_proc_stat_cpus();
+        let d = now.delta(&self.prev_total);
+        self.prev_total = now;
+
+        let total = d.total();
+        if total == 0 {
+            return (0.0, 0.0);
+        }
+        let kernel = d.system + d.irq + d.softirq;
+        (d.user as f64 / total as f64, kernel as f64 / total as f64)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn fdinfo_access_and_locks() {
+        let content = "pos:\t0\nflags:\t0100002\nmnt_id:\t29\nino:\t1234\n\
+            lock:\t1: POSIX  ADVISORY  WRITE 4242 fd:01:1234 0 EOF\n\
+            lock:\t2: FLOCK  ADVISORY  READ  4242 fd:01:1234 0 EOF\n";
+        assert_eq!(parse_fdinfo(content), ("rw".to_string(), "POSIX WRITE, FLOCK READ".to_string()));
+        // O_WRONLY|O_APPEND|O_CLOEXEC, no locks
+        assert_eq!(parse_fdinfo("pos:\t0\nflags:\t02002001\n"), ("w".to_string(), String::new()));
+        assert_eq!(parse_fdinfo("flags:\t0100000\n").0, "r");
+        // No flags line (the fd closed under us), or a lock line cut short
+        assert_eq!(parse_fdinfo("lock:\t1: POSIX\n"), (String::new(), String::new()));
+    }
+
+    #[test]
+    fn smaps_regions_and_kinds() {
+        let content = "\
+5581a2c00000-5581a2c28000 r--p 00000000 fd:01 1311    /usr/bin/bash
+Size:                160 kB
+Rss:                 160 kB
+5581a2c28000-5581a2cf5000 r-xp 00028000 fd:01 1311    /usr/bin/bash
+Rss:                 620 kB
+5581a2d0a000-5581a2d0e000 rw-p 0010a000 fd:01 1311    /usr/bin/bash
+Rss:                  16 kB
+5581a3f1e000-5581a409c000 rw-p 00000000 00:00 0       [heap]
+Rss:                1476 kB
+7f3a1c000000-7f3a1c021000 rw-p 00000000 00:00 0 
+Rss:                   8 kB
+7f3a1e400000-7f3a1e600000 r--p 00000000 fd:01 2048    /usr/share/locale/locale-archive
+Rss:                 104 kB
+7f3a1e800000-7f3a1e810000 rw-s 00000000 00:05 4711    /SYSV00000000 (deleted)
+7ffd4b1e2000-7ffd4b203000 rw-p 00000000 00:00 0       [stack]
+Rss:                  24 kB
+7ffd4b3f1000-7ffd4b3f3000 r-xp 00000000 00:00 0       [vdso]
+Rss:                   4 kB
+VmFlags: rd ex mr mw me de sd
+";
+        let regions = parse_smaps(content);
+        let summary: Vec<(u64, u64, &str, RegionKind, &str)> = regions
+            .iter()
+            .map(|r| (r.size(), r.rss, r.protection.as_str(), r.kind, r.path.as_str()))
+            .collect();
+        assert_eq!(
+            summary,
+            [
+                (0x28000, 160 * 1024, "r--p", RegionKind::Image, "/usr/bin/bash"),
+                (0xcd000, 620 * 1024, "r-xp", RegionKind::Image, "/usr/bin/bash"),
+                (0x4000, 16 * 1024, "rw-p", RegionKind::Image, "/usr/bin/bash"),
+                (0x17e000, 1476 * 1024, "rw-p", RegionKind::Heap, "[heap]"),
+                (0x21000, 8 * 1024, "rw-p", RegionKind::Private, ""),
+                (0x200000, 104 * 1024, "r--p", RegionKind::Mapped, "/usr/share/locale/locale-archive"),
+                (0x10000, 0, "rw-s", RegionKind::Mapped, "/SYSV00000000 (deleted)"),
+                (0x21000, 24 * 1024, "rw-p", RegionKind::Stack, "[stack]"),
+                (0x2000, 4 * 1024, "r-xp", RegionKind::Image, "[vdso]"),
+            ]
+        );
+        assert_eq!(regions[0].start, 0x5581a2c00000);
+        assert!(parse_smaps("").is_empty());
+    }
+
+    #[test]
+    fn stat_with_odd_comm() {
+        // comm may hold spaces and parentheses; fields count from the last ')'
+        let content = "4242 (tmux: server) (1)) S 1 4242 4242 0 -1 4194560 2210 0 0 0 \
+            1520 380 0 0 20 0 3 0 98765 23080960 1187 18446744073709551615\n";
+        let stat = parse_stat(content).unwrap();
+        assert_eq!(stat.comm, "tmux: server) (1)");
+        assert_eq!(stat.state, 'S');
+        assert_eq!((stat.utime, stat.stime), (1520, 380));
+        assert_eq!((stat.priority, stat.nice), (20, 0));
+        assert_eq!(stat.num_threads, 3);
+        assert_eq!(stat.start_ticks, 98765);
+        // Cut short (a zombie's stat can't be, but a truncated read can)
+        assert!(parse_stat("4242 (sh) S 1 4242").is_none());
+        assert!(parse_stat("").is_none());
+    }
+
+    #[test]
+    fn thread_states_and_context_switches() {
+        let states: Vec<ProcessStatus> = "RSIDTtZXW".chars().map(thread_status).collect();
+        use ProcessStatus::*;
+        assert_eq!(states, [Running, Sleeping, Sleeping, DiskSleep, Stopped, Stopped, Zombie, Zombie, Unknown]);
+
+        let status = "Name:\tworker\nState:\tS (sleeping)\n\
+            voluntary_ctxt_switches:\t5100\nnonvoluntary_ctxt_switches:\t20\n";
+        assert_eq!(parse_context_switches(status), 5120);
+        assert_eq!(parse_context_switches(""), 0);
+    }
+}
@@ -1,8 +1,16 @@
+pub mod backend;
 pub mod cpu;
+pub mod disk;
+pub mod filesystem;
 pub mod gpu;
 pub mod memory;
 pub mod netstat;
 pub mod network;
 pub mod process;
 pub mod collector;
+#[cfg(not(windows))]
+pub mod linux;
+#[cfg(windows)]
 pub mod winapi;
+#[cfg(test)]
+pub mod fake;
@@ -1,14 +1,19 @@
 //! Per-process network connection enumeration and bandwidth tracking.
 //!
-//! Enumerates TCP/UDP connections via GetExtendedTcpTable / GetExtendedUdpTable
-//! (iphlpapi.dll) to count active connections per PID. Bandwidth (download/upload)
+//! Lists TCP/UDP v4/v6 sockets with their endpoints, TCP state and owning
+//! PID: on Windows via GetExtendedTcpTable / GetExtendedUdpTable
+//! (iphlpapi.dll), on Linux from /proc/net/{tcp,tcp6,udp,udp6} with socket
+//! inodes mapped to PIDs through /proc/[pid]/fd. Bandwidth (download/upload)
 //! is derived from per-process I/O counters (GetProcessIoCounters) which captures
 //! all I/O including network. Processes with active connections will show their
 //! total I/O throughput as a reasonable bandwidth proxy.
 //!
-//! This approach works WITHOUT admin, unlike GetPerTcpConnectionEStats.
+//! This approach works WITHOUT admin, unlike GetPerTcpConnectionEStats. On
+//! Linux, sockets of other users' processes have no known owner unless run
+//! as root.
 
 use std::collections::HashMap;
+use std::net::{IpAddr, SocketAddr};
 
 // ═══════════════════════════════════════════════════════════════════════════════
 //  Public types
@@ -24,30 +29,194 @@ pub struct ProcessNetBandwidth {
     pub connection_count: u32,     // active TCP + UDP endpoints
 }
 
+/// Transport protocol of a socket
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum Protocol {
+    Tcp,
+    Udp,
+}
+
+/// TCP connection state, as netstat names them
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum TcpState {
+    Established,
+    SynSent,
+    SynReceived,
+    FinWait1,
+    FinWait2,
+    TimeWait,
+    Closed,
+    CloseWait,
+    LastAck,
+    Listen,
+    Closing,
+    DeleteTcb,
+    Unknown,
+}
+
+impl TcpState {
+    pub const ALL: [TcpState; 13] = [
+        Self::Established, Self::SynSent, Self::SynReceived, Self::FinWait1, Self::FinWait2,
+        Self::TimeWait, Self::Closed, Self::CloseWait, Self::LastAck, Self::Listen,
+        Self::Closing, Self::DeleteTcb, Self::Unknown,
+    ];
+
+    pub fn label(&self) -> &'static str {
+        match self {
+            Self::Established => "ESTABLISHED",
+            Self::SynSent => "SYN_SENT",
+            Self::SynReceived => "SYN_RECV",
+            Self::FinWait1 => "FIN_WAIT1",
+            Self::FinWait2 => "FIN_WAIT2",
+            Self::TimeWait => "TIME_WAIT",
+            Self::Closed => "CLOSE",
+            Self::CloseWait => "CLOSE_WAIT",
+            Self::LastAck => "LAST_ACK",
+            Self::Listen => "LISTEN",
+            Self::Closing => "CLOSING",
+            Self::DeleteTcb => "DELETE_TCB",
+            Self::Unknown => "UNKNOWN",
+        }
+    }
+
+    /// State numbers from include/net/tcp_states.h
+    #[cfg(not(windows))]
+    fn from_linux(state: u32) -> Self {
+        match state {
+            1 => Self::Established,
+            2 => Self::SynSent,
+            3 | 12 => Self::SynReceived, // 12: TCP_NEW_SYN_RECV
+            4 => Self::FinWait1,
+            5 => Self::FinWait2,
+            6 => Self::TimeWait,
+            7 => Self::Closed,
+            8 => Self::CloseWait,
+            9 => Self::LastAck,
+            10 => Self::Listen,
+            11 => Self::Closing,
+            _ => Self::Unknown,
+        }
+    }
+
+    /// MIB_TCP_STATE values
+    #[cfg(windows)]
+    fn from_mib(state: u32) -> Self {
+        match state {
+            1 => Self::Closed,
+            2 => Self::Listen,
+            3 => Self::SynSent,
+            4 => Self::SynReceived,
+            5 => Self::Established,
+            6 => Self::FinWait1,
+            7 => Self::FinWait2,
+            8 => Self::CloseWait,
+            9 => Self::Closing,
+            10 => Self::LastAck,
+            11 => Self::TimeWait,
+            12 => Self::DeleteTcb,
+            _ => Self::Unknown,
+        }
+    }
+}
+
+/// One socket: its endpoints, TCP state and owner
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Connection {
+    pub protocol: Protocol,
+    pub local: SocketAddr,
+    /// Unspecified (`0.0.0.0:0` / `[::]:0`) for listening and unconnected sockets
+    pub remote: SocketAddr,
+    /// None for UDP
+    pub state: Option<TcpState>,
+    /// 0 when the owner isn't known
+    pub pid: u32,
+}
+
+impl Connection {
+    /// "tcp", "tcp6", "udp" or "udp6", like netstat
+    pub fn protocol_label(&self) -> &'static str {
+        match (self.protocol, self.local.is_ipv6()) {
+            (Protocol::Tcp, false) => "tcp",
+            (Protocol::Tcp, true) => "tcp6",
+            (Protocol::Udp, false) => "udp",
+            (Protocol::Udp, true) => "udp6",
+        }
+    }
+
+    /// The remote endpoint, or "*" when there is none
+    pub fn remote_label(&self) -> String {
+        if self.remote.ip().is_unspecified() && self.remote.port() == 0 {
+            "*".to_string()
+        } else {
+            self.remote.to_string()
+        }
+    }
+
+    /// State shown for the socket: the TCP state, empty for UDP
+    pub fn state_label(&self) -> &'static str {
+        self.state.map(|s| s.label()).unwrap_or("")
+    }
+
+    /// Waiting for peers: a listening TCP socket, or a UDP socket bound
+    /// without a connected remote
+    pub fn is_listening(&self) -> bool {
+        match self.protocol {
+            Protocol::Tcp => self.state == Some(TcpState::Listen),
+            Protocol::Udp => self.remote.ip().is_unspecified() && self.remote.port() == 0,
+        }
+    }
+}
+
+/// A listening socket with its owner's details (Ports tab)
+#[derive(Debug, Clone)]
+pub struct ListeningPort {
+    pub socket: Connection,
+    /// Empty when the owner isn't known
+    pub name: String,
+    pub user: String,
+    pub command: String,
+}
+
 // ═══════════════════════════════════════════════════════════════════════════════
 //  Public API
 // ═══════════════════════════════════════════════════════════════════════════════
 
-/// Count active TCP + UDP connections per PID.
-/// Returns HashMap<pid, connection_count>. No admin required.
-pub fn count_connections_per_pid() -> HashMap<u32, u32> {
-    let mut counts: HashMap<u32, u32> = HashMap::new();
+/// All TCP + UDP sockets (v4 and v6) with their owning PID. No admin required.
+#[cfg(windows)]
+pub fn connections() -> Vec<Connection> {
+    let mut connections = Vec::new();
+    connections.extend(enum_tcp_v4());
+    connections.extend(enum_tcp_v6());
+    connections.extend(enum_udp_v4());
+    connections.extend(enum_udp_v6());
+    connections
+}
 
-    // TCP v4
-    for pid in enum_tcp_v4_pids() {
-        *counts.entry(pid).or_insert(0) += 1;
-    }
-    // TCP v6
-    for pid in enum_tcp_v6_pids() {
-        *counts.entry(pid).or_insert(0) += 1;
-    }
-    // UDP v4
-    for pid in enum_udp_v4_pids() {
-        *counts.entry(pid).or_insert(0) += 1;
+/// All TCP + UDP sockets (v4 and v6) from /proc/net, with the owning PID
+/// where its fds are readable.
+#[cfg(not(windows))]
+pub fn connections() -> Vec<Connection> {
+    let mut with_inodes = Vec::new();
+    for (file, protocol) in [("tcp", Protocol::Tcp), ("tcp6", Protocol::Tcp), ("udp", Protocol::Udp), ("udp6", Protocol::Udp)] {
+        if let Ok(content) = std::fs::read_to_string(format!("/proc/net/{}", file)) {
+            with_inodes.extend(parse_proc_net(&content, protocol));
+        }
     }
-    // UDP v6
-    for pid in enum_udp_v6_pids() {
-        *counts.entry(pid).or_insert(0) += 1;
+    let owners = socket_owners();
+    with_inodes
+        .into_iter()
+        .map(|(mut connection, inode)| {
+            connection.pid = owners.get(&inode).copied().unwrap_or(0);
+            connection
+        })
+        .collect()
+}
+
+/// Count TCP + UDP sockets per PID (owner unknown / System Idle left out).
+pub fn count_connections_per_pid(connections: &[Connection]) -> HashMap<u32, u32> {
+    let mut counts: HashMap<u32, u32> = HashMap::new();
+    for connection in connections {
+        *counts.entry(connection.pid).or_insert(0) += 1;
     }
 
     // Remove system idle process
@@ -56,15 +225,104 @@ pub fn count_connections_per_pid() -> HashMap<u32, u32> {
     counts
 }
 
+// ═══════════════════════════════════════════════════════════════════════════════
+//  /proc/net parsing (Linux)
+// ═══════════════════════════════════════════════════════════════════════════════
+
+/// Rows of /proc/net/{tcp,udp}[6] with their socket inode:
+///   sl  local_address rem_address   st tx_queue:rx_queue tr:tm->when retrnsmt  uid timeout inode
+///    0: 0100007F:0277 00000000:0000 0A 00000000:00000000 00:00000000 00000000    0       0 41234
+#[cfg(not(windows))]
+fn parse_proc_net(content: &str, protocol: Protocol) -> Vec<(Connection, u64)> {
+    content
+        .lines()
+        .skip(1)
+        .filter_map(|line| {
+            let fields: Vec<&str> = line.split_whitespace().collect();
+            let local = parse_proc_endpoint(fields.get(1)?)?;
+            let remote = parse_proc_endpoint(fields.get(2)?)?;
+            let state = u32::from_str_radix(fields.get(3)?, 16).ok()?;
+            let inode = fields.get(9)?.parse().ok()?;
+            let connection = Connection {
+                protocol,
+                local,
+                remote,
+                state: (protocol == Protocol::Tcp).then(|| TcpState::from_linux(state)),
+                pid: 0,
+            };
+            Some((connection, inode))
+        })
+        .collect()
+}
+
+/// `ADDR:PORT` in hex. The address is printed as 32-bit words in host byte
+/// order (one word for IPv4, four for IPv6), the port as a plain number.
+#[cfg(not(windows))]
+fn parse_proc_endpoint(s: &str) -> Option<SocketAddr> {
+    let (addr, port) = s.split_once(':')?;
+    let port = u16::from_str_radix(port, 16).ok()?;
+    let mut bytes = Vec::with_capacity(16);
+    for i in (0..addr.len()).step_by(8) {
+        let word = u32::from_str_radix(addr.get(i..i + 8)?, 16).ok()?;
+        bytes.extend_from_slice(&word.to_ne_bytes());
+    }
+    let ip = match bytes.len() {
+        4 => IpAddr::from(<[u8; 4]>::try_from(bytes).ok()?),
+        16 => IpAddr::from(<[u8; 16]>::try_from(bytes).ok()?),
+        _ => return None,
+    };
+    Some(SocketAddr::new(ip, port))
+}
+
+/// Socket inode -> owning PID, from the `socket:[inode]` links in
+/// /proc/[pid]/fd (only readable for our own processes unless root)
+#[cfg(not(windows))]
+fn socket_owners() -> HashMap<u64, u32> {
+    socket_owners_in(std::path::Path::new("/proc"))
+}
+
+/// `socket_owners` under another proc root
+#[cfg(not(windows))]
+fn socket_owners_in(proc_root: &std::path::Path) -> HashMap<u64, u32> {
+    let mut owners = HashMap::new();
+    let Ok(procs) = std::fs::read_dir(proc_root) else {
+        return owners;
+    };
+    for entry in procs.filter_map(|e| e.ok()) {
+        let Some(pid) = entry.file_name().to_str().and_then(|n| n.parse::<u32>().ok()) else {
+            continue;
+        };
+        let Ok(fds) = std::fs::read_dir(entry.path().join("fd")) else {
+            continue;
+        };
+        for fd in fds.filter_map(|e| e.ok()) {
+            if let Ok(target) = std::fs::read_link(fd.path()) {
+                let target = target.to_string_lossy();
+                if let Some(inode) = target.strip_prefix("socket:[").and_then(|t| t.strip_suffix(']')) {
+                    if let Ok(inode) = inode.parse() {
+                        owners.insert(inode, pid);
+                    }
+                }
+            }
+        }
+    }
+    owners
+}
+
 // ═══════════════════════════════════════════════════════════════════════════════
 //  Win32 FFI types
 // ═══════════════════════════════════════════════════════════════════════════════
 
+#[cfg(windows)]
 const AF_INET: u32 = 2;
+#[cfg(windows)]
 const AF_INET6: u32 = 23;
+#[cfg(windows)]
 const TCP_TABLE_OWNER_PID_ALL: u32 = 5;
+#[cfg(windows)]
 const UDP_TABLE_OWNER_PID: u32 = 1;
 
+#[cfg(windows)]
 #[repr(C)]
 #[allow(non_snake_case, non_camel_case_types)]
 struct MIB_TCPROW_OWNER_PID {
@@ -76,6 +334,7 @@ struct MIB_TCPROW_OWNER_PID {
     dwOwningPid: u32,
 }
 
+#[cfg(windows)]
 #[repr(C)]
 #[allow(non_snake_case, non_camel_case_types)]
 struct MIB_TCPTABLE_OWNER_PID {
@@ -83,6 +342,7 @@ struct MIB_TCPTABLE_OWNER_PID {
     table: [MIB_TCPROW_OWNER_PID; 1],
 }
 
+#[cfg(windows)]
 #[repr(C)]
 #[allow(non_snake_case, non_camel_case_types)]
 struct MIB_TCP6ROW_OWNER_PID {
@@ -96,6 +356,7 @@ struct MIB_TCP6ROW_OWNER_PID {
     dwOwningPid: u32,
 }
 
+#[cfg(windows)]
 #[repr(C)]
 #[allow(non_snake_case, non_camel_case_types)]
 struct MIB_TCP6TABLE_OWNER_PID {
@@ -103,6 +364,7 @@ struct MIB_TCP6TABLE_OWNER_PID {
     table: [MIB_TCP6ROW_OWNER_PID; 1],
 }
 
+#[cfg(windows)]
 #[repr(C)]
 #[allow(non_snake_case, non_camel_case_types)]
 struct MIB_UDPROW_OWNER_PID {
@@ -111,6 +373,7 @@ struct MIB_UDPROW_OWNER_PID {
     dwOwningPid: u32,
 }
 
+#[cfg(windows)]
 #[repr(C)]
 #[allow(non_snake_case, non_camel_case_types)]
 struct MIB_UDPTABLE_OWNER_PID {
@@ -118,6 +381,7 @@ struct MIB_UDPTABLE_OWNER_PID {
     table: [MIB_UDPROW_OWNER_PID; 1],
 }
 
+#[cfg(windows)]
 #[repr(C)]
 #[allow(non_snake_case, non_camel_case_types)]
 struct MIB_UDP6ROW_OWNER_PID {
@@ -127,6 +391,7 @@ struct MIB_UDP6ROW_OWNER_PID {
     dwOwningPid: u32,
 }
 
+#[cfg(windows)]
 #[repr(C)]
 #[allow(non_snake_case, non_camel_case_types)]
 struct MIB_UDP6TABLE_OWNER_PID {
@@ -138,6 +403,7 @@ struct MIB_UDP6TABLE_OWNER_PID {
 //  FFI declarations (iphlpapi.dll)
 // ═══════════════════════════════════════════════════════════════════════════════
 
+#[cfg(windows)]
 #[link(name = "iphlpapi")]
 extern "system" {
     fn GetExtendedTcpTable(
@@ -152,105 +418,197 @@ extern "system" {
 }
 
 // ═══════════════════════════════════════════════════════════════════════════════
-//  Connection enumeration (returns PIDs only, no details needed)
+//  Connection enumeration (Windows)
 // ═══════════════════════════════════════════════════════════════════════════════
 
-fn enum_tcp_v4_pids() -> Vec<u32> {
-    unsafe {
-        let mut size: u32 = 0;
-        GetExtendedTcpTable(
-            std::ptr::null_mut(), &mut size, 0,
-            AF_INET, TCP_TABLE_OWNER_PID_ALL, 0,
-        );
-        if size == 0 { return Vec::new(); }
-
-        let mut buf = vec![0u8; size as usize];
-        if GetExtendedTcpTable(
-            buf.as_mut_ptr(), &mut size, 0,
-            AF_INET, TCP_TABLE_OWNER_PID_ALL, 0,
-        ) != 0 {
-            return Vec::new();
+/// Ports in the owner tables are in network byte order in the low 16 bits
+#[cfg(windows)]
+fn mib_port(port: u32) -> u16 {
+    u16::from_be((port & 0xFFFF) as u16)
+}
+
+/// Fetch a GetExtendedTcpTable / GetExtendedUdpTable table into a buffer
+/// aligned for its rows
+#[cfg(windows)]
+fn extended_table(tcp: bool, af: u32) -> Option<Vec<u32>> {
+    let query = |buf: *mut u8, size: &mut u32| unsafe {
+        if tcp {
+            GetExtendedTcpTable(buf, size, 0, af, TCP_TABLE_OWNER_PID_ALL, 0)
+        } else {
+            GetExtendedUdpTable(buf, size, 0, af, UDP_TABLE_OWNER_PID, 0)
         }
+    };
+    let mut size: u32 = 0;
+    query(std::ptr::null_mut(), &mut size);
+    if size == 0 { return None; }
+
+    let mut buf = vec![0u32; (size as usize).div_ceil(4)];
+    if query(buf.as_mut_ptr() as *mut u8, &mut size) != 0 {
+        return None;
+    }
+    Some(buf)
+}
 
+#[cfg(windows)]
+fn enum_tcp_v4() -> Vec<Connection> {
+    let Some(buf) = extended_table(true, AF_INET) else { return Vec::new() };
+    unsafe {
         let table = &*(buf.as_ptr() as *const MIB_TCPTABLE_OWNER_PID);
         let rows = std::slice::from_raw_parts(
             table.table.as_ptr(), table.dwNumEntries as usize,
         );
-        rows.iter().map(|r| r.dwOwningPid).collect()
+        rows.iter()
+            .map(|r| Connection {
+                protocol: Protocol::Tcp,
+                local: SocketAddr::new(IpAddr::from(r.dwLocalAddr.to_ne_bytes()), mib_port(r.dwLocalPort)),
+                remote: SocketAddr::new(IpAddr::from(r.dwRemoteAddr.to_ne_bytes()), mib_port(r.dwRemotePort)),
+                state: Some(TcpState::from_mib(r.dwState)),
+                pid: r.dwOwningPid,
+            })
+            .collect()
     }
 }
 
-fn enum_tcp_v6_pids() -> Vec<u32> {
+#[cfg(windows)]
+fn enum_tcp_v6() -> Vec<Connection> {
+    let Some(buf) = extended_table(true, AF_INET6) else { return Vec::new() };
     unsafe {
-        let mut size: u32 = 0;
-        GetExtendedTcpTable(
-            std::ptr::null_mut(), &mut size, 0,
-            AF_INET6, TCP_TABLE_OWNER_PID_ALL, 0,
-        );
-        if size == 0 { return Vec::new(); }
-
-        let mut buf = vec![0u8; size as usize];
-        if GetExtendedTcpTable(
-            buf.as_mut_ptr(), &mut size, 0,
-            AF_INET6, TCP_TABLE_OWNER_PID_ALL, 0,
-        ) != 0 {
-            return Vec::new();
-        }
-
         let table = &*(buf.as_ptr() as *const MIB_TCP6TABLE_OWNER_PID);
         let rows = std::slice::from_raw_parts(
             table.table.as_ptr(), table.dwNumEntries as usize,
         );
-        rows.iter().map(|r| r.dwOwningPid).collect()
+        rows.iter()
+            .map(|r| Connection {
+                protocol: Protocol::Tcp,
+                local: SocketAddr::new(IpAddr::from(r.ucLocalAddr), mib_port(r.dwLocalPort)),
+                remote: SocketAddr::new(IpAddr::from(r.ucRemoteAddr), mib_port(r.dwRemotePort)),
+                state: Some(TcpState::from_mib(r.dwState)),
+                pid: r.dwOwningPid,
+            })
+            .collect()
     }
 }
 
-fn enum_udp_v4_pids() -> Vec<u32> {
+#[cfg(windows)]
+fn enum_udp_v4() -> Vec<Connection> {
+    let Some(buf) = extended_table(false, AF_INET) else { return Vec::new() };
     unsafe {
-        let mut size: u32 = 0;
-        GetExtendedUdpTable(
-            std::ptr::null_mut(), &mut size, 0,
-            AF_INET, UDP_TABLE_OWNER_PID, 0,
-        );
-        if size == 0 { return Vec::new(); }
-
-        let mut buf = vec![0u8; size as usize];
-        if GetExtendedUdpTable(
-            buf.as_mut_ptr(), &mut size, 0,
-            AF_INET, UDP_TABLE_OWNER_PID, 0,
-        ) != 0 {
-            return Vec::new();
-        }
-
         let table = &*(buf.as_ptr() as *const MIB_UDPTABLE_OWNER_PID);
         let rows = std::slice::from_raw_parts(
             table.table.as_ptr(), table.dwNumEntries as usize,
         );
-        rows.iter().map(|r| r.dwOwningPid).collect()
+        rows.iter()
+            .map(|r| Connection {
+                protocol: Protocol::Udp,
+                local: SocketAddr::new(IpAddr::from(r.dwLocalAddr.to_ne_bytes()), mib_port(r.dwLocalPort)),
+                remote: SocketAddr::from(([0, 0, 0, 0], 0)),
+                state: None,
+                pid: r.dwOwningPid,
+            })
+            .collect()
     }
 }
 
-fn enum_udp_v6_pids() -> Vec<u32> {
+#[cfg(windows)]
+fn enum_udp_v6() -> Vec<Connection> {
+    let Some(buf) = extended_table(false, AF_INET6) else { return Vec::new() };
     unsafe {
-        let mut size: u32 = 0;
-        GetExtendedUdpTable(
-            std::ptr::null_mut(), &mut size, 0,
-            AF_INET6, UDP_TABLE_OWNER_PID, 0,
-        );
-        if size == 0 { return Vec::new(); }
-
-        let mut buf = vec![0u8; size as usize];
-        if GetExtendedUdpTable(
-            buf.as_mut_ptr(), &mut size, 0,
-            AF_INET6, UDP_TABLE_OWNER_PID, 0,
-        ) != 0 {
-            return Vec::new();
-        }
-
         let table = &*(buf.as_ptr() as *const MIB_UDP6TABLE_OWNER_PID);
         let rows = std::slice::from_raw_parts(
             table.table.as_ptr(), table.dwNumEntries as usize,
         );
-        rows.iter().map(|r| r.dwOwningPid).collect()
+        rows.iter()
+            .map(|r| Connection {
+                protocol: Protocol::Udp,
+                local: SocketAddr::new(IpAddr::from(r.ucLocalAddr), mib_port(r.dwLocalPort)),
+                remote: SocketAddr::from(([0u16; 8], 0)),
+                state: None,
+                pid: r.dwOwningPid,
+            })
+            .collect()
+    }
+}
+
+#[cfg(all(test, not(windows)))]
+mod tests {
+    use super::*;
+
+    // Sample rows from a little-endian (x86-64) box; the kernel prints the
+    // address words in host byte order
+    #[test]
+    #[cfg(target_endian = "little")]
+    fn proc_net_rows() {
+        let tcp = "  sl  local_address rem_address   st tx_queue rx_queue tr tm->when retrnsmt   uid  timeout inode
+   0: 0100007F:0277 00000000:0000 0A 00000000:00000000 00:00000000 00000000     0        0 41234 1 0000000000000000 100 0 0 10 0
+   1: 0F02000A:A2C4 22D8B85D:01BB 01 00000000:00000000 02:000A6B1B 00000000  1000        0 52011 2 0000000000000000 20 4 30 10 -1
+   2: 0F02000A:A2B0 22D8B85D:01BB 06 00000000:00000000 03:00000F39 00000000     0        0 0 3 0000000000000000
+   3: garbage
+";
+        let rows = parse_proc_net(tcp, Protocol::Tcp);
+        let summary: Vec<(String, String, Option<TcpState>, u64)> =
+            rows.iter().map(|(c, inode)| (c.local.to_string(), c.remote_label(), c.state, *inode)).collect();
+        assert_eq!(
+            summary,
+            [
+                ("127.0.0.1:631".to_string(), "*".to_string(), Some(TcpState::Listen), 41234),
+                ("10.0.2.15:41668".to_string(), "93.184.216.34:443".to_string(), Some(TcpState::Established), 52011),
+                // TIME_WAIT sockets belong to no process any more: inode 0
+                ("10.0.2.15:41648".to_string(), "93.184.216.34:443".to_string(), Some(TcpState::TimeWait), 0),
+            ]
+        );
+        assert!(rows[0].0.is_listening());
+
+        let udp6 = "  sl  local_address                         remote_address                        st tx_queue rx_queue tr tm->when retrnsmt   uid  timeout inode ref pointer drops
+  0: 00000000000000000000000000000000:14E9 00000000000000000000000000000000:0000 07 00000000:00000000 00:00000000 00000000   105        0 20871 2 0000000000000000 0
+";
+        let rows = parse_proc_net(udp6, Protocol::Udp);
+        assert_eq!(rows.len(), 1);
+        assert_eq!(rows[0].0.protocol_label(), "udp6");
+        assert_eq!(rows[0].0.local.to_string(), "[::]:5353");
+        assert_eq!(rows[0].0.state, None);
+        assert!(rows[0].0.is_listening());
+    }
+
+    #[test]
+    #[cfg(target_endian = "little")]
+    fn proc_net_endpoints() {
+        let parse = |s: &str| parse_proc_endpoint(s).map(|a| a.to_string());
+        assert_eq!(parse("0100007F:0050").as_deref(), Some("127.0.0.1:80"));
+        // IPv6: four words, each in host order, most significant word first
+        assert_eq!(parse("00000000000000000000000001000000:0277").as_deref(), Some("[::1]:631"));
+        assert_eq!(parse("B80D0120000000000000000001000000:01BB").as_deref(), Some("[2001:db8::1]:443"));
+        assert_eq!(parse("0000000000000000FFFF00000100007F:1F90").as_deref(), Some("[::ffff:127.0.0.1]:8080"));
+        // Neither 4 nor 16 bytes, bad hex, no port
+        assert_eq!(parse("0100007F01:0050"), None);
+        assert_eq!(parse("0100007G:0050"), None);
+        assert_eq!(parse("0100007F"), None);
+    }
+
+    #[test]
+    fn socket_owners_from_fd_links() {
+        use std::os::unix::fs::symlink;
+        let root = std::env::temp_dir().join(format!("pstop-netstat-{}", std::process::id()));
+        let _ = std::fs::remove_dir_all(&root);
+        for (pid, links) in [
+            ("812", &[("3", "socket:[41234]"), ("4", "/dev/null"), ("5", "socket:[52011]")][..]),
+            ("1400", &[("0", "pipe:[9001]"), ("7", "socket:[60001]")]),
+            // Not a process directory
+            ("self", &[("9", "socket:[77777]")]),
+        ] {
+            let fd = root.join(pid).join("fd");
+            std::fs::create_dir_all(&fd).unwrap();
+            for (name, target) in links {
+                symlink(target, fd.join(name)).unwrap();
+            }
+        }
+        // A process whose fds can't be read
+        std::fs::create_dir_all(root.join("1500")).unwrap();
+
+        let owners = socket_owners_in(&root);
+        let _ = std::fs::remove_dir_all(&root);
+        let mut owners: Vec<(u64, u32)> = owners.into_iter().collect();
+        owners.sort_unstable();
+        assert_eq!(owners, [(41234, 812), (52011, 812), (60001, 1400)]);
+        assert!(socket_owners_in(std::path::Path::new("/nonexistent")).is_empty());
     }
 }
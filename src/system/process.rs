@@ -19,6 +19,12 @@ pub enum ProcessSortField {
     IoReadRate,
     IoWriteRate,
     IoRate,
+    /// Average CPU% over the per-process history
+    CpuHist,
+    CpuPeak,
+    /// Average RES over the per-process history
+    ResHist,
+    ResPeak,
 }
 
 impl ProcessSortField {
@@ -42,6 +48,10 @@ impl ProcessSortField {
             Self::IoReadRate => "DISK READ",
             Self::IoWriteRate => "DISK WRITE",
             Self::IoRate => "DISK R/W",
+            Self::CpuHist => "CPU HIST",
+            Self::CpuPeak => "CPU PEAK",
+            Self::ResHist => "RES HIST",
+            Self::ResPeak => "RES PEAK",
         }
     }
 
@@ -65,6 +75,10 @@ impl ProcessSortField {
             Self::IoReadRate => "IO_READ_RATE",
             Self::IoWriteRate => "IO_WRITE_RATE",
             Self::IoRate => "IO_RATE",
+            Self::CpuHist => "CPU_AVG",
+            Self::CpuPeak => "CPU_PEAK",
+            Self::ResHist => "M_RESIDENT_AVG",
+            Self::ResPeak => "M_RESIDENT_PEAK",
         }
     }
 
@@ -88,9 +102,19 @@ impl ProcessSortField {
             Self::IoWriteRate,
             Self::IoRate,
             Self::Command,
+            // Added later; kept at the end so saved column indices stay valid
+            Self::CpuHist,
+            Self::CpuPeak,
+            Self::ResHist,
+            Self::ResPeak,
         ]
     }
 
+    /// Fields computed from `App::history` rather than the current sample
+    pub fn is_history(&self) -> bool {
+        matches!(self, Self::CpuHist | Self::CpuPeak | Self::ResHist | Self::ResPeak)
+    }
+
     /// Get index in `all()` list
     pub fn index(&self) -> usize {
         Self::all().iter().position(|f| f == self).unwrap_or(0)
@@ -152,9 +176,28 @@ pub struct ProcessInfo {
     // For tree view
     pub depth: usize,
     pub is_last_child: bool,
+    // Thread rows (H): a thread listed under its process
+    pub is_thread: bool,
+    pub wait_reason: String,        // what a blocked thread waits for ("" while it runs)
+    pub start_address: Option<u64>, // Win32 start address (Windows only)
+    pub context_switches: u64,
 }
 
 impl ProcessInfo {
+    /// What a thread row shows after its name: wait reason, start address
+    /// and context switches
+    pub fn thread_detail(&self) -> String {
+        let mut parts = Vec::new();
+        if !self.wait_reason.is_empty() {
+            parts.push(format!("wait:{}", self.wait_reason));
+        }
+        if let Some(address) = self.start_address {
+            parts.push(format!("start:{:#x}", address));
+        }
+        parts.push(format!("csw:{}", self.context_switches));
+        parts.join(" ")
+    }
+
     /// Format run time as h:MM:SS or M:SS.cc (hundredths) — matches htop TIME+
     /// Uses cpu_time_100ns for sub-second precision when available.
     /// Output is always ≤ 9 chars to fit within the column width.
@@ -162,7 +205,7 @@ impl ProcessInfo {
         // Use high-precision CPU time if available
         if self.cpu_time_100ns > 0 {
             let total_hundredths = self.cpu_time_100ns / 100_000; // 100ns → hundredths of a second
-            let hundredths = (total_hundredths % 100) as u64;
+            let hundredths = total_hundredths % 100;
             let total_seconds = total_hundredths / 100;
             let hours = total_seconds / 3600;
             let minutes = (total_seconds % 3600) / 60;
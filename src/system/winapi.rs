@@ -6,11 +6,13 @@
 //! - Real boot time (via Event Log, accounts for Fast Startup)
 //! - System CPU kernel/user time split (via GetSystemTimes)
 //! - Per-process CPU time with sub-second precision (via GetProcessTimes)
+//! - Environment block and working directory (read from the target's PEB)
+//! - Memory map (VirtualQueryEx regions, resident pages from the working set)
+//! - Per-thread CPU time, state, wait reason and context switches
+//!   (SystemProcessInformation), start address (NtQueryInformationThread)
 
 use std::collections::HashMap;
 use std::mem;
-
-#[cfg(windows)]
 use std::os::windows::process::CommandExt;
 
 use windows::Win32::Foundation::{CloseHandle, MAX_PATH, HMODULE, HANDLE, FILETIME};
@@ -30,78 +32,255 @@ use windows::Win32::System::Threading::{
     HIGH_PRIORITY_CLASS, IDLE_PRIORITY_CLASS, NORMAL_PRIORITY_CLASS,
     REALTIME_PRIORITY_CLASS, PROCESS_QUERY_INFORMATION, PROCESS_SET_INFORMATION,
     PROCESS_QUERY_LIMITED_INFORMATION, IO_COUNTERS,
+    QueryFullProcessImageNameW, PROCESS_NAME_WIN32, PROCESS_VM_READ,
+    GetCurrentProcess, PROCESS_DUP_HANDLE,
+};
+use windows::Win32::Foundation::{DuplicateHandle, DUPLICATE_SAME_ACCESS};
+use windows::Win32::Storage::FileSystem::{
+    GetFileType, GetFinalPathNameByHandleW, FILE_NAME_NORMALIZED, FILE_TYPE_CHAR, FILE_TYPE_DISK,
+    FILE_TYPE_PIPE,
 };
 use windows::Win32::Security::{
     GetTokenInformation, LookupAccountSidW, TokenUser, TOKEN_QUERY, TOKEN_USER,
     SID_NAME_USE,
 };
 use windows::Win32::System::Threading::OpenThread;
-use windows::Win32::System::Threading::THREAD_QUERY_LIMITED_INFORMATION;
-
-/// Per-process data collected via Windows API (cached every N ticks)
-#[derive(Debug, Clone, Default)]
-pub struct WinProcessData {
-    pub priority: i32,   // Base priority level (PRI column)
-    pub nice: i32,       // Nice-equivalent mapping (NI column)
-    pub thread_count: u32,
-    pub private_working_set: u64, // Private bytes (for shared_mem = resident - private)
+use windows::Win32::System::Threading::{THREAD_QUERY_INFORMATION, THREAD_QUERY_LIMITED_INFORMATION};
+
+use crate::system::backend::{
+    split_env_entry, CpuCoreSample, CpuMonitor, HandleInfo, MemoryRegion, ProcessData,
+    ProcessEnvironment, QueryError, RegionKind, SystemBackend, ThreadInfo,
+};
+use crate::system::process::ProcessStatus;
+
+/// Win32 implementation of `SystemBackend`
+pub struct WinBackend;
+
+impl SystemBackend for WinBackend {
+    fn enumerate_pids(&self) -> Vec<u32> {
+        quick_enumerate_pids()
+    }
+
+    fn process_data(&self, pids: &[u32]) -> HashMap<u32, ProcessData> {
+        collect_process_data(pids)
+    }
+
+    fn process_users(&self, pids: &[u32]) -> HashMap<u32, String> {
+        batch_process_users(pids)
+    }
+
+    fn process_times(&self, pids: &[u32]) -> HashMap<u32, u64> {
+        batch_process_times(pids)
+    }
+
+    fn io_counters(&self, pids: &[u32]) -> HashMap<u32, (u64, u64)> {
+        batch_io_counters(pids)
+    }
+
+    fn threads(&self, pid: u32, get_names: bool) -> Vec<ThreadInfo> {
+        enumerate_threads(pid, get_names)
+    }
+
+    fn handles(&self, pid: u32) -> Vec<HandleInfo> {
+        get_process_handles(pid)
+    }
+
+    fn environment(&self, pid: u32) -> ProcessEnvironment {
+        get_process_environment(pid)
+    }
+
+    fn memory_map(&self, pid: u32) -> Result<Vec<MemoryRegion>, QueryError> {
+        get_memory_map(pid)
+    }
+
+    fn boot_time(&self) -> Option<i64> {
+        get_real_boot_time()
+    }
+
+    fn cpu_monitor(&self) -> Box<dyn CpuMonitor> {
+        Box::new(WinCpuMonitor {
+            cores: NativeCpuMonitor::new(),
+            split: CpuTimeSplit::new(),
+        })
+    }
+
+    fn raise_priority(&self, pid: u32) -> bool {
+        raise_priority(pid)
+    }
+
+    fn lower_priority(&self, pid: u32) -> bool {
+        lower_priority(pid)
+    }
+
+    fn kill(&self, pid: u32, signal_index: usize) -> std::io::Result<()> {
+        kill_process_with_signal(pid, signal_index)
+    }
+
+    fn cpu_count(&self) -> usize {
+        get_cpu_count()
+    }
+
+    fn get_affinity(&self, pid: u32) -> (usize, usize, bool) {
+        get_process_affinity(pid)
+    }
+
+    fn set_affinity(&self, pid: u32, mask: usize) -> bool {
+        set_process_affinity(pid, mask)
+    }
 }
 
-/// Thread info for show_threads feature
-#[derive(Debug, Clone)]
-pub struct ThreadInfo {
-    pub thread_id: u32,
-    pub owner_pid: u32,
-    pub base_priority: i32,
-    pub name: String,
+/// Per-core monitor (NtQuerySystemInformation) + system-wide split (GetSystemTimes)
+struct WinCpuMonitor {
+    cores: NativeCpuMonitor,
+    split: CpuTimeSplit,
+}
+
+impl CpuMonitor for WinCpuMonitor {
+    fn brand(&self) -> &str {
+        &self.cores.brand
+    }
+
+    fn frequency_mhz(&self) -> u64 {
+        self.cores.frequency
+    }
+
+    fn sample(&mut self) -> Vec<CpuCoreSample> {
+        self.cores.sample()
+    }
+
+    fn sample_split(&mut self) -> (f64, f64) {
+        self.split.sample()
+    }
 }
 
-/// Enumerate all threads for a given process, optionally getting thread names.
-/// Returns a Vec of ThreadInfo for the given PID.
+/// Enumerate all threads for a given process, optionally getting thread names
+/// and start addresses. Times, state, wait reason and context switches come
+/// from the process's SystemProcessInformation entry.
 pub fn enumerate_threads(pid: u32, get_names: bool) -> Vec<ThreadInfo> {
+    use ntapi::ntexapi::{
+        NtQuerySystemInformation, SystemProcessInformation, SYSTEM_PROCESS_INFORMATION,
+    };
+    use ntapi::ntkeapi::{Initialized, Suspended, Terminated, Waiting, WrSuspended};
+
     let mut threads = Vec::new();
 
     unsafe {
-        let snapshot = CreateToolhelp32Snapshot(TH32CS_SNAPTHREAD, 0);
-        let snapshot = match snapshot {
-            Ok(h) => h,
-            Err(_) => return threads,
-        };
-
-        let mut entry: THREADENTRY32 = mem::zeroed();
-        entry.dwSize = mem::size_of::<THREADENTRY32>() as u32;
+        // Grow the buffer until the snapshot of all processes fits
+        let mut buf_size: usize = 512 * 1024;
+        let mut buffer: Vec<u64>;
+        loop {
+            buffer = vec![0u64; buf_size / 8];
+            let mut return_length: u32 = 0;
+            let status = NtQuerySystemInformation(
+                SystemProcessInformation,
+                buffer.as_mut_ptr() as *mut _,
+                buf_size as u32,
+                &mut return_length,
+            );
+            // STATUS_INFO_LENGTH_MISMATCH = 0xC0000004
+            if status == 0xC0000004_u32 as i32 {
+                buf_size = (return_length as usize + 64 * 1024).max(buf_size * 2);
+                if buf_size > 64 * 1024 * 1024 {
+                    return threads;
+                }
+                continue;
+            }
+            if status < 0 {
+                return threads;
+            }
+            break;
+        }
 
-        if Thread32First(snapshot, &mut entry).is_ok() {
-            loop {
-                if entry.th32OwnerProcessID == pid {
-                    let name = if get_names {
-                        get_thread_name(entry.th32ThreadID)
+        // Entries are chained by NextEntryOffset, threads follow each header
+        let base = buffer.as_ptr() as *const u8;
+        let mut offset = 0usize;
+        loop {
+            let entry = &*(base.add(offset) as *const SYSTEM_PROCESS_INFORMATION);
+            if entry.UniqueProcessId as usize == pid as usize {
+                let infos = std::slice::from_raw_parts(entry.Threads.as_ptr(), entry.NumberOfThreads as usize);
+                for info in infos {
+                    let thread_id = info.ClientId.UniqueThread as usize as u32;
+                    let (name, start_address) = if get_names {
+                        (get_thread_name(thread_id), thread_start_address(thread_id))
+                    } else {
+                        (String::new(), None)
+                    };
+                    let waiting = info.ThreadState == Waiting;
+                    let status = if waiting && (info.WaitReason == Suspended || info.WaitReason == WrSuspended) {
+                        ProcessStatus::Stopped
+                    } else if waiting {
+                        ProcessStatus::Sleeping
+                    } else if info.ThreadState == Terminated {
+                        ProcessStatus::Zombie
+                    } else if info.ThreadState == Initialized {
+                        ProcessStatus::Unknown
                     } else {
-                        String::new()
+                        // Ready, Running, Standby, Transition, DeferredReady
+                        ProcessStatus::Running
                     };
                     threads.push(ThreadInfo {
-                        thread_id: entry.th32ThreadID,
+                        thread_id,
                         owner_pid: pid,
-                        base_priority: entry.tpBasePri,
+                        base_priority: info.BasePriority,
                         name,
+                        cpu_time_100ns: (*info.KernelTime.QuadPart() + *info.UserTime.QuadPart()) as u64,
+                        status,
+                        wait_reason: if waiting { wait_reason_name(info.WaitReason).to_string() } else { String::new() },
+                        start_address,
+                        context_switches: info.ContextSwitches as u64,
                     });
                 }
-
-                let mut next_entry: THREADENTRY32 = mem::zeroed();
-                next_entry.dwSize = mem::size_of::<THREADENTRY32>() as u32;
-                if Thread32Next(snapshot, &mut next_entry).is_err() {
-                    break;
-                }
-                entry = next_entry;
+                break;
             }
+            if entry.NextEntryOffset == 0 {
+                break;
+            }
+            offset += entry.NextEntryOffset as usize;
         }
-
-        let _ = CloseHandle(snapshot);
     }
 
     threads
 }
 
+/// KWAIT_REASON as named in the WDK
+fn wait_reason_name(reason: u32) -> &'static str {
+    const NAMES: [&str; 42] = [
+        "Executive", "FreePage", "PageIn", "PoolAllocation", "DelayExecution", "Suspended",
+        "UserRequest", "WrExecutive", "WrFreePage", "WrPageIn", "WrPoolAllocation",
+        "WrDelayExecution", "WrSuspended", "WrUserRequest", "WrEventPair", "WrQueue",
+        "WrLpcReceive", "WrLpcReply", "WrVirtualMemory", "WrPageOut", "WrRendezvous",
+        "WrKeyedEvent", "WrTerminated", "WrProcessInSwap", "WrCpuRateControl", "WrCalloutStack",
+        "WrKernel", "WrResource", "WrPushLock", "WrMutex", "WrQuantumEnd", "WrDispatchInt",
+        "WrPreempted", "WrYieldExecution", "WrFastMutex", "WrGuardedMutex", "WrRundown",
+        "WrAlertByThreadId", "WrDeferredPreempt", "WrPhysicalFault", "WrIoRing", "WrMdlCache",
+    ];
+    NAMES.get(reason as usize).copied().unwrap_or("Unknown")
+}
+
+/// Address the thread was started at (what CreateThread was given), via
+/// NtQueryInformationThread(ThreadQuerySetWin32StartAddress)
+fn thread_start_address(thread_id: u32) -> Option<u64> {
+    use ntapi::ntpsapi::{NtQueryInformationThread, ThreadQuerySetWin32StartAddress};
+
+    unsafe {
+        let handle = OpenThread(THREAD_QUERY_INFORMATION, false, thread_id).ok()?;
+        let mut address: usize = 0;
+        let status = NtQueryInformationThread(
+            handle.0 as *mut _,
+            ThreadQuerySetWin32StartAddress,
+            &mut address as *mut usize as *mut _,
+            mem::size_of::<usize>() as u32,
+            std::ptr::null_mut(),
+        );
+        let _ = CloseHandle(handle);
+        if status < 0 || address == 0 {
+            None
+        } else {
+            Some(address as u64)
+        }
+    }
+}
+
 /// Get thread description (name) via GetThreadDescription (Windows 10 1607+).
 /// Falls back to empty string on older systems or access denied.
 fn get_thread_name(thread_id: u32) -> String {
@@ -163,7 +342,7 @@ fn get_thread_name(thread_id: u32) -> String {
 /// Batch-collect Windows-specific process data for all running processes.
 /// This is efficient: takes one thread snapshot for all threads, then queries
 /// each process for priority individually.
-pub fn collect_process_data(pids: &[u32]) -> HashMap<u32, WinProcessData> {
+pub fn collect_process_data(pids: &[u32]) -> HashMap<u32, ProcessData> {
     let thread_counts = count_all_threads();
     let mut result = HashMap::with_capacity(pids.len());
 
@@ -171,7 +350,7 @@ pub fn collect_process_data(pids: &[u32]) -> HashMap<u32, WinProcessData> {
         let tc = thread_counts.get(&pid).copied().unwrap_or(1);
 
         if pid == 0 || pid == 4 {
-            result.insert(pid, WinProcessData {
+            result.insert(pid, ProcessData {
                 priority: 0,
                 nice: 0,
                 thread_count: tc,
@@ -195,7 +374,7 @@ pub fn collect_process_data(pids: &[u32]) -> HashMap<u32, WinProcessData> {
                     } else {
                         0
                     };
-                    result.insert(pid, WinProcessData {
+                    result.insert(pid, ProcessData {
                         priority: 8,
                         nice: 0,
                         thread_count: tc,
@@ -211,7 +390,7 @@ pub fn collect_process_data(pids: &[u32]) -> HashMap<u32, WinProcessData> {
 
             let _ = CloseHandle(handle);
 
-            result.insert(pid, WinProcessData {
+            result.insert(pid, ProcessData {
                 priority: pri,
                 nice: ni,
                 thread_count: tc,
@@ -437,6 +616,58 @@ fn change_priority(pid: u32, raise: bool) -> bool {
     }
 }
 
+/// Kill a process by PID on Windows.
+/// signal_index: 0=SIGTERM (graceful), 1=SIGKILL (force), etc.
+///
+/// All paths are non-blocking so the TUI never freezes:
+/// - Force kill uses Win32 TerminateProcess (instant).
+/// - Graceful kill spawns taskkill in a background thread with a timeout;
+///   Ok only means it was started.
+pub fn kill_process_with_signal(pid: u32, signal_index: usize) -> std::io::Result<()> {
+    match signal_index {
+        0 => {
+            // SIGTERM equivalent: graceful close via taskkill, non-blocking
+            std::thread::spawn(move || {
+                use std::process::Command;
+                // CREATE_NO_WINDOW = 0x08000000
+                let child = Command::new("taskkill")
+                    .args(["/PID", &pid.to_string()])
+                    .creation_flags(0x08000000)
+                    .spawn();
+                if let Ok(mut child) = child {
+                    // Wait up to 5 seconds, then give up (don't block forever)
+                    let deadline = std::time::Instant::now()
+                        + std::time::Duration::from_secs(5);
+                    loop {
+                        match child.try_wait() {
+                            Ok(Some(_)) => break,
+                            Ok(None) => {
+                                if std::time::Instant::now() >= deadline {
+                                    let _ = child.kill();
+                                    break;
+                                }
+                                std::thread::sleep(std::time::Duration::from_millis(50));
+                            }
+                            Err(_) => break,
+                        }
+                    }
+                }
+            });
+            Ok(())
+        }
+        _ => {
+            // SIGKILL / force kill: use TerminateProcess directly (instant, no subprocess)
+            use windows::Win32::System::Threading::{TerminateProcess, PROCESS_TERMINATE};
+            unsafe {
+                let handle = OpenProcess(PROCESS_TERMINATE, false, pid).map_err(|_| std::io::Error::last_os_error())?;
+                let result = TerminateProcess(handle, 1).map_err(|_| std::io::Error::last_os_error());
+                let _ = CloseHandle(handle);
+                result
+            }
+        }
+    }
+}
+
 /// Get CPU affinity mask for a process
 /// Returns (process_affinity, system_affinity, success)
 /// The masks are bit arrays where each bit represents a CPU core
@@ -626,13 +857,6 @@ fn parse_event_system_time(xml: &str) -> Option<i64> {
         .map(|dt| dt.timestamp())
 }
 
-/// Handle information for display in lsof-style viewer
-#[derive(Debug, Clone)]
-pub struct HandleInfo {
-    pub handle_type: String,
-    pub name: String,
-}
-
 /// Get open handles/modules for a process (Windows lsof equivalent)
 /// Returns loaded modules (DLLs) + real file/pipe/registry handles via NtQuerySystemInformation
 pub fn get_process_handles(pid: u32) -> Vec<HandleInfo> {
@@ -659,15 +883,15 @@ pub fn get_process_handles(pid: u32) -> Vec<HandleInfo> {
         if result.is_ok() && bytes_needed > 0 {
             let module_count = (bytes_needed as usize) / mem::size_of::<HMODULE>();
 
-            for i in 0..module_count.min(modules.len()) {
-                if modules[i].0.is_null() {
+            for &module in modules.iter().take(module_count) {
+                if module.0.is_null() {
                     continue;
                 }
 
                 let mut filename = vec![0u16; MAX_PATH as usize];
                 let len = GetModuleFileNameExW(
                     Some(handle),
-                    Some(modules[i]),
+                    Some(module),
                     &mut filename,
                 );
 
@@ -676,6 +900,7 @@ pub fn get_process_handles(pid: u32) -> Vec<HandleInfo> {
                     handles.push(HandleInfo {
                         handle_type: "Module".to_string(),
                         name: path,
+                        ..Default::default()
                     });
                 }
             }
@@ -691,9 +916,14 @@ pub fn get_process_handles(pid: u32) -> Vec<HandleInfo> {
 }
 
 /// Enumerate real OS handles (files, registry keys, events, etc.) for a process
-/// using NtQuerySystemInformation(SystemHandleInformation).
+/// using NtQuerySystemInformation(SystemExtendedHandleInformation). Types and
+/// names are resolved on a duplicate of each handle, which needs
+/// PROCESS_DUP_HANDLE on the owner; without it only the raw type index is known.
 fn enumerate_real_handles(pid: u32, handles: &mut Vec<HandleInfo>) {
-    use ntapi::ntexapi::{NtQuerySystemInformation, SystemHandleInformation, SYSTEM_HANDLE_INFORMATION, SYSTEM_HANDLE_TABLE_ENTRY_INFO};
+    use ntapi::ntexapi::{
+        NtQuerySystemInformation, SystemExtendedHandleInformation, SYSTEM_HANDLE_INFORMATION_EX,
+        SYSTEM_HANDLE_TABLE_ENTRY_INFO_EX,
+    };
 
     unsafe {
         // Start with a reasonable buffer size and grow if needed
@@ -704,7 +934,7 @@ fn enumerate_real_handles(pid: u32, handles: &mut Vec<HandleInfo>) {
             buffer = vec![0u8; buf_size];
             let mut return_length: u32 = 0;
             let status = NtQuerySystemInformation(
-                SystemHandleInformation,
+                SystemExtendedHandleInformation,
                 buffer.as_mut_ptr() as *mut _,
                 buf_size as u32,
                 &mut return_length,
@@ -725,35 +955,435 @@ fn enumerate_real_handles(pid: u32, handles: &mut Vec<HandleInfo>) {
             break;
         }
 
-        let info = &*(buffer.as_ptr() as *const SYSTEM_HANDLE_INFORMATION);
-        let count = info.NumberOfHandles as usize;
-        
-        // Safety: the entries are laid out contiguously after NumberOfHandles
+        let info = &*(buffer.as_ptr() as *const SYSTEM_HANDLE_INFORMATION_EX);
+        let count = info.NumberOfHandles;
+        let header = mem::size_of::<SYSTEM_HANDLE_INFORMATION_EX>() - mem::size_of::<SYSTEM_HANDLE_TABLE_ENTRY_INFO_EX>();
+
+        // Safety: the entries are laid out contiguously after the header
         let entries = std::slice::from_raw_parts(
             info.Handles.as_ptr(),
-            count.min((buffer.len() - std::mem::size_of::<u32>()) / std::mem::size_of::<SYSTEM_HANDLE_TABLE_ENTRY_INFO>()),
+            count.min((buffer.len() - header) / mem::size_of::<SYSTEM_HANDLE_TABLE_ENTRY_INFO_EX>()),
         );
 
-        let mut type_counts: HashMap<u8, u32> = HashMap::new();
-        for entry in entries {
-            if entry.UniqueProcessId as u32 == pid {
-                *type_counts.entry(entry.ObjectTypeIndex).or_insert(0) += 1;
+        let owner = OpenProcess(PROCESS_DUP_HANDLE, false, pid).ok();
+        // Type indices vary by OS version, so names are looked up once per index
+        let mut type_names: HashMap<u16, String> = HashMap::new();
+        for entry in entries.iter().filter(|e| e.UniqueProcessId == pid as usize) {
+            let mut handle_type = type_names.get(&entry.ObjectTypeIndex).cloned();
+            let mut name = String::new();
+            let mut duplicate = HANDLE::default();
+            let duplicated = owner.is_some_and(|owner| {
+                DuplicateHandle(
+                    owner,
+                    HANDLE(entry.HandleValue as _),
+                    GetCurrentProcess(),
+                    &mut duplicate,
+                    0,
+                    false,
+                    DUPLICATE_SAME_ACCESS,
+                )
+                .is_ok()
+            });
+            if duplicated {
+                let type_name = handle_type
+                    .get_or_insert_with(|| query_object_type(duplicate).unwrap_or_default())
+                    .clone();
+                type_names.insert(entry.ObjectTypeIndex, type_name.clone());
+                if type_name == "File" {
+                    // Querying a synchronous pipe's name can block forever, so
+                    // only disk files are named
+                    let file_type = GetFileType(duplicate);
+                    if file_type == FILE_TYPE_DISK {
+                        name = final_path_name(duplicate).unwrap_or_default();
+                    } else if file_type == FILE_TYPE_PIPE {
+                        handle_type = Some("Pipe".to_string());
+                    } else if file_type == FILE_TYPE_CHAR {
+                        handle_type = Some("Device".to_string());
+                    }
+                } else {
+                    name = query_object_name(duplicate).unwrap_or_default();
+                }
+                let _ = CloseHandle(duplicate);
             }
-        }
 
-        // Map common Windows object type indices to names
-        // (indices vary by OS version, but these are common)
-        for (&type_idx, &count) in &type_counts {
-            let type_name = match type_idx {
-                // Common type indices on Windows 10/11
-                _ => format!("Type_{}", type_idx),
+            // FILE_READ_DATA / FILE_WRITE_DATA | FILE_APPEND_DATA
+            let access = if handle_type.as_deref() == Some("File") {
+                match (entry.GrantedAccess & 0x1 != 0, entry.GrantedAccess & 0x6 != 0) {
+                    (true, true) => "rw",
+                    (true, false) => "r",
+                    (false, true) => "w",
+                    (false, false) => "",
+                }
+            } else {
+                ""
             };
             handles.push(HandleInfo {
-                handle_type: format!("Handle({})", type_name),
-                name: format!("{} handle(s)", count),
+                id: Some(entry.HandleValue as u64),
+                handle_type: handle_type.unwrap_or_else(|| format!("Type #{}", entry.ObjectTypeIndex)),
+                name,
+                access: access.to_string(),
+                lock: String::new(),
             });
         }
+        if let Some(owner) = owner {
+            let _ = CloseHandle(owner);
+        }
+    }
+}
+
+/// NtQueryObject into a buffer big enough for a name, or None
+unsafe fn query_object(handle: HANDLE, class: ntapi::ntobapi::OBJECT_INFORMATION_CLASS) -> Option<Vec<u64>> {
+    use ntapi::ntobapi::NtQueryObject;
+
+    // u64 elements keep the buffer aligned for the structs read out of it
+    let mut buffer = vec![0u64; 1024];
+    let mut needed = 0u32;
+    let mut status = NtQueryObject(handle.0 as _, class, buffer.as_mut_ptr() as *mut _, (buffer.len() * 8) as u32, &mut needed);
+    if status == 0xC0000004_u32 as i32 && needed > 0 {
+        buffer = vec![0u64; (needed as usize).div_ceil(8)];
+        status = NtQueryObject(handle.0 as _, class, buffer.as_mut_ptr() as *mut _, (buffer.len() * 8) as u32, &mut needed);
+    }
+    (status >= 0).then_some(buffer)
+}
+
+unsafe fn unicode_to_string(s: &ntapi::winapi::shared::ntdef::UNICODE_STRING) -> String {
+    if s.Buffer.is_null() {
+        return String::new();
+    }
+    String::from_utf16_lossy(std::slice::from_raw_parts(s.Buffer, s.Length as usize / 2))
+}
+
+/// Object type name, e.g. "File", "Key", "Event", "Mutant"
+unsafe fn query_object_type(handle: HANDLE) -> Option<String> {
+    use ntapi::ntobapi::{ObjectTypeInformation, OBJECT_TYPE_INFORMATION};
+
+    let buffer = query_object(handle, ObjectTypeInformation)?;
+    let info = &*(buffer.as_ptr() as *const OBJECT_TYPE_INFORMATION);
+    Some(unicode_to_string(&info.TypeName))
+}
+
+/// Object manager name, e.g. `\REGISTRY\MACHINE\SOFTWARE\...` or `\Sessions\1\BaseNamedObjects\...`
+unsafe fn query_object_name(handle: HANDLE) -> Option<String> {
+    use ntapi::ntobapi::{ObjectNameInformation, OBJECT_NAME_INFORMATION};
+
+    let buffer = query_object(handle, ObjectNameInformation)?;
+    let info = &*(buffer.as_ptr() as *const OBJECT_NAME_INFORMATION);
+    Some(unicode_to_string(&info.Name))
+}
+
+/// DOS path of an open disk file, without the `\\?\` prefix
+unsafe fn final_path_name(handle: HANDLE) -> Option<String> {
+    let mut buf = vec![0u16; 32768];
+    let len = GetFinalPathNameByHandleW(handle, &mut buf, FILE_NAME_NORMALIZED) as usize;
+    if len == 0 || len > buf.len() {
+        return None;
+    }
+    let path = String::from_utf16_lossy(&buf[..len]);
+    Some(path.strip_prefix("\\\\?\\").map(str::to_string).unwrap_or(path))
+}
+
+// ─── Memory map via VirtualQueryEx ─────────────────────────────────────────
+
+/// Committed regions of a process's address space. Windows knows whether a
+/// region is an image, a file mapping or private memory, but not which
+/// private regions are heaps or stacks, so those show as private.
+pub fn get_memory_map(pid: u32) -> Result<Vec<MemoryRegion>, QueryError> {
+    use windows::Win32::System::Memory::{
+        VirtualQueryEx, MEMORY_BASIC_INFORMATION, MEM_COMMIT, MEM_IMAGE, MEM_MAPPED,
+    };
+
+    unsafe {
+        let handle = OpenProcess(PROCESS_QUERY_INFORMATION | PROCESS_VM_READ, false, pid).map_err(|e| win_error(&e))?;
+        let pages = working_set_pages(handle);
+        let devices = dos_device_names();
+
+        let mut regions = Vec::new();
+        let mut address = 0usize;
+        loop {
+            let mut mbi = MEMORY_BASIC_INFORMATION::default();
+            let written = VirtualQueryEx(
+                handle,
+                Some(address as *const std::ffi::c_void),
+                &mut mbi,
+                mem::size_of::<MEMORY_BASIC_INFORMATION>(),
+            );
+            if written == 0 || mbi.RegionSize == 0 {
+                break;
+            }
+            let start = mbi.BaseAddress as usize;
+            let end = start + mbi.RegionSize;
+            if mbi.State == MEM_COMMIT {
+                let kind = if mbi.Type == MEM_IMAGE {
+                    RegionKind::Image
+                } else if mbi.Type == MEM_MAPPED {
+                    RegionKind::Mapped
+                } else {
+                    RegionKind::Private
+                };
+                let path = if kind == RegionKind::Private {
+                    String::new()
+                } else {
+                    mapped_file_name(handle, mbi.BaseAddress, &devices)
+                };
+                // Resident pages inside [start, end)
+                let first = pages.partition_point(|&p| p < start);
+                let last = pages.partition_point(|&p| p < end);
+                regions.push(MemoryRegion {
+                    start: start as u64,
+                    end: end as u64,
+                    rss: ((last - first) * 4096) as u64,
+                    protection: protection_string(mbi.Protect.0, kind),
+                    kind,
+                    path,
+                });
+            }
+            match end.checked_sub(address) {
+                Some(step) if step > 0 => address = end,
+                _ => break,
+            }
+        }
+        let _ = CloseHandle(handle);
+        Ok(regions)
+    }
+}
+
+/// `rwxp`-style protection. Copy-on-write pages are writable and private.
+fn protection_string(protect: u32, kind: RegionKind) -> String {
+    let (read, write, exec, copy) = match protect & 0xff {
+        0x02 => (true, false, false, false), // PAGE_READONLY
+        0x04 => (true, true, false, false),  // PAGE_READWRITE
+        0x08 => (true, true, false, true),   // PAGE_WRITECOPY
+        0x10 => (false, false, true, false), // PAGE_EXECUTE
+        0x20 => (true, false, true, false),  // PAGE_EXECUTE_READ
+        0x40 => (true, true, true, false),   // PAGE_EXECUTE_READWRITE
+        0x80 => (true, true, true, true),    // PAGE_EXECUTE_WRITECOPY
+        _ => (false, false, false, false),   // PAGE_NOACCESS
+    };
+    let shared = kind != RegionKind::Private && !copy;
+    format!(
+        "{}{}{}{}",
+        if read { 'r' } else { '-' },
+        if write { 'w' } else { '-' },
+        if exec { 'x' } else { '-' },
+        if shared { 's' } else { 'p' }
+    )
+}
+
+/// Start addresses of the process's resident pages, sorted
+unsafe fn working_set_pages(handle: HANDLE) -> Vec<usize> {
+    use windows::Win32::System::ProcessStatus::{QueryWorkingSet, PSAPI_WORKING_SET_INFORMATION};
+
+    // NumberOfEntries followed by one usize block per page; grow until it fits
+    let mut entries = 64 * 1024;
+    loop {
+        let mut buffer = vec![0usize; entries + 1];
+        let ok = QueryWorkingSet(
+            handle,
+            buffer.as_mut_ptr() as *mut _,
+            (buffer.len() * mem::size_of::<usize>()) as u32,
+        );
+        let info = &*(buffer.as_ptr() as *const PSAPI_WORKING_SET_INFORMATION);
+        let count = info.NumberOfEntries;
+        if ok.is_ok() {
+            // Flags hold the virtual page number in the bits above 12
+            let mut pages: Vec<usize> = buffer[1..=count.min(entries)].iter().map(|b| b & !0xfff).collect();
+            pages.sort_unstable();
+            return pages;
+        }
+        if count <= entries || entries >= 64 * 1024 * 1024 {
+            return Vec::new();
+        }
+        entries = count + count / 8;
+    }
+}
+
+/// `\Device\HarddiskVolume3` → `C:` for every drive letter in use
+fn dos_device_names() -> Vec<(String, String)> {
+    use windows::Win32::Storage::FileSystem::QueryDosDeviceW;
+
+    let mut names = Vec::new();
+    for letter in b'A'..=b'Z' {
+        let drive = format!("{}:", letter as char);
+        let wide: Vec<u16> = drive.encode_utf16().chain(std::iter::once(0)).collect();
+        let mut buf = vec![0u16; 1024];
+        let len = unsafe { QueryDosDeviceW(windows::core::PCWSTR(wide.as_ptr()), Some(&mut buf)) } as usize;
+        if len > 0 {
+            // Multi-string: the first entry is the current target
+            let device: String = String::from_utf16_lossy(&buf[..len]).split('\0').next().unwrap_or("").to_string();
+            if !device.is_empty() {
+                names.push((device, drive));
+            }
+        }
     }
+    names
+}
+
+/// File behind an image or mapped region, with its NT device path turned
+/// into a drive letter where possible
+unsafe fn mapped_file_name(handle: HANDLE, address: *mut std::ffi::c_void, devices: &[(String, String)]) -> String {
+    use windows::Win32::System::ProcessStatus::GetMappedFileNameW;
+
+    let mut buf = vec![0u16; 32768];
+    let len = GetMappedFileNameW(handle, address, &mut buf) as usize;
+    if len == 0 {
+        return String::new();
+    }
+    let path = String::from_utf16_lossy(&buf[..len]);
+    for (device, drive) in devices {
+        if let Some(rest) = path.strip_prefix(device.as_str()) {
+            if rest.starts_with('\\') {
+                return format!("{}{}", drive, rest);
+            }
+        }
+    }
+    path
+}
+
+// ─── Environment via the PEB ───────────────────────────────────────────────
+
+/// Executable path, start time, working directory and environment block of a
+/// process. The last two live in the RTL_USER_PROCESS_PARAMETERS its PEB
+/// points at, copied out with NtReadVirtualMemory (needs PROCESS_VM_READ,
+/// so other users' processes need Administrator).
+pub fn get_process_environment(pid: u32) -> ProcessEnvironment {
+    let (cwd, vars) = match read_process_parameters(pid) {
+        Ok((cwd, vars)) => (Ok(cwd), Ok(vars)),
+        Err(e) => (Err(e.clone()), Err(e)),
+    };
+    ProcessEnvironment {
+        exe: process_image_path(pid),
+        cwd,
+        vars,
+        start_time: process_start_time(pid),
+    }
+}
+
+fn win_error(e: &windows::core::Error) -> QueryError {
+    match e.code().0 as u32 {
+        0x8007_0005 => QueryError::AccessDenied, // E_ACCESSDENIED
+        0x8007_0057 => QueryError::Unavailable,  // invalid parameter: no such PID
+        _ => QueryError::Other(e.to_string()),
+    }
+}
+
+fn nt_error(status: i32) -> QueryError {
+    match status as u32 {
+        0xC000_0022 => QueryError::AccessDenied, // STATUS_ACCESS_DENIED
+        s => QueryError::Other(format!("NTSTATUS {:#010x}", s)),
+    }
+}
+
+fn process_image_path(pid: u32) -> Result<String, QueryError> {
+    unsafe {
+        let handle = OpenProcess(PROCESS_QUERY_LIMITED_INFORMATION, false, pid).map_err(|e| win_error(&e))?;
+        let mut buf = vec![0u16; 32768];
+        let mut len = buf.len() as u32;
+        let result = QueryFullProcessImageNameW(handle, PROCESS_NAME_WIN32, windows::core::PWSTR(buf.as_mut_ptr()), &mut len);
+        let _ = CloseHandle(handle);
+        result.map_err(|e| win_error(&e))?;
+        Ok(String::from_utf16_lossy(&buf[..len as usize]))
+    }
+}
+
+/// Creation time as a Unix timestamp
+fn process_start_time(pid: u32) -> Option<i64> {
+    // FILETIME epoch (1601) → Unix epoch, in 100ns units
+    const UNIX_EPOCH_100NS: u64 = 116_444_736_000_000_000;
+    unsafe {
+        let handle = OpenProcess(PROCESS_QUERY_LIMITED_INFORMATION, false, pid).ok()?;
+        let mut creation = FILETIME::default();
+        let mut exit = FILETIME::default();
+        let mut kernel = FILETIME::default();
+        let mut user = FILETIME::default();
+        let ok = GetProcessTimes(handle, &mut creation, &mut exit, &mut kernel, &mut user);
+        let _ = CloseHandle(handle);
+        ok.ok()?;
+        let created = filetime_to_u64(&creation).checked_sub(UNIX_EPOCH_100NS)?;
+        Some((created / 10_000_000) as i64)
+    }
+}
+
+/// Largest environment block read from another process; real ones are a
+/// few KiB
+const MAX_ENVIRONMENT_BYTES: usize = 1 << 20;
+
+fn read_process_parameters(pid: u32) -> Result<(String, Vec<(String, String)>), QueryError> {
+    unsafe {
+        let handle = OpenProcess(PROCESS_QUERY_LIMITED_INFORMATION | PROCESS_VM_READ, false, pid)
+            .map_err(|e| win_error(&e))?;
+        let result = read_parameters_from(handle);
+        let _ = CloseHandle(handle);
+        result
+    }
+}
+
+unsafe fn read_parameters_from(handle: HANDLE) -> Result<(String, Vec<(String, String)>), QueryError> {
+    use ntapi::ntmmapi::NtReadVirtualMemory;
+    use ntapi::ntpebteb::PEB;
+    use ntapi::ntpsapi::{NtQueryInformationProcess, ProcessBasicInformation, PROCESS_BASIC_INFORMATION};
+    use ntapi::ntrtl::RTL_USER_PROCESS_PARAMETERS;
+
+    let raw = handle.0 as _;
+    // Copy `size` bytes at `address` in the target into `out`
+    let read = |address: *mut std::ffi::c_void, out: *mut std::ffi::c_void, size: usize| {
+        if size == 0 {
+            return Ok(());
+        }
+        let status = NtReadVirtualMemory(raw, address as _, out as _, size, std::ptr::null_mut());
+        if status < 0 { Err(nt_error(status)) } else { Ok(()) }
+    };
+
+    let mut basic: PROCESS_BASIC_INFORMATION = mem::zeroed();
+    let status = NtQueryInformationProcess(
+        raw,
+        ProcessBasicInformation,
+        &mut basic as *mut _ as *mut _,
+        mem::size_of::<PROCESS_BASIC_INFORMATION>() as u32,
+        std::ptr::null_mut(),
+    );
+    if status < 0 {
+        return Err(nt_error(status));
+    }
+    if basic.PebBaseAddress.is_null() {
+        return Err(QueryError::Unavailable);
+    }
+
+    // Only the ProcessParameters pointer of the PEB is needed
+    let mut params_address: *mut std::ffi::c_void = std::ptr::null_mut();
+    read(
+        (basic.PebBaseAddress as *mut u8).add(mem::offset_of!(PEB, ProcessParameters)) as *mut _,
+        &mut params_address as *mut _ as *mut _,
+        mem::size_of::<*mut std::ffi::c_void>(),
+    )?;
+    if params_address.is_null() {
+        return Err(QueryError::Unavailable);
+    }
+    let mut params: RTL_USER_PROCESS_PARAMETERS = mem::zeroed();
+    read(params_address, &mut params as *mut _ as *mut _, mem::size_of::<RTL_USER_PROCESS_PARAMETERS>())?;
+
+    // UNICODE_STRING lengths are in bytes
+    let dir = &params.CurrentDirectory.DosPath;
+    let mut cwd = vec![0u16; dir.Length as usize / 2];
+    read(dir.Buffer as *mut _, cwd.as_mut_ptr() as *mut _, cwd.len() * 2)?;
+    let mut cwd = String::from_utf16_lossy(&cwd);
+    // Kept with a trailing backslash except at a drive root
+    if cwd.len() > 3 && cwd.ends_with('\\') {
+        cwd.pop();
+    }
+
+    // NAME=value\0NAME=value\0\0. The size comes from the target's memory,
+    // so a corrupt or hostile value mustn't make us allocate gigabytes
+    if params.EnvironmentSize > MAX_ENVIRONMENT_BYTES {
+        return Err(QueryError::Other(format!("environment block of {} bytes", params.EnvironmentSize)));
+    }
+    let mut block = vec![0u16; params.EnvironmentSize / 2];
+    read(params.Environment as *mut _, block.as_mut_ptr() as *mut _, block.len() * 2)?;
+    let vars = block
+        .split(|&c| c == 0)
+        .take_while(|entry| !entry.is_empty())
+        .map(|entry| split_env_entry(&String::from_utf16_lossy(entry)))
+        .collect();
+    Ok((cwd, vars))
 }
 
 /// System-wide CPU time split: returns (user_fraction, kernel_fraction, idle_fraction)
@@ -907,16 +1537,6 @@ struct ProcessorPerformanceInfo {
     _interrupt_count: u32,
 }
 
-/// Per-core CPU time sample with breakdown into user/kernel/dpc/interrupt.
-/// Fractions are of total time (including idle), so they sum to usage%/100.
-pub struct CpuCoreSample {
-    pub usage_percent: f32,
-    pub user_frac: f32,      // fraction of total time in user mode
-    pub kernel_frac: f32,    // fraction in pure kernel (excl idle, dpc, interrupt)
-    pub dpc_frac: f32,       // fraction in DPC (≈ Linux softirq)
-    pub interrupt_frac: f32, // fraction in interrupt (≈ Linux irq)
-}
-
 /// Per-core CPU usage tracker using NtQuerySystemInformation.
 /// Takes one snapshot on creation, then computes deltas on each `sample()` call.
 pub struct NativeCpuMonitor {
@@ -975,16 +1595,10 @@ impl NativeCpuMonitor {
                         interrupt_frac: (d_interrupt as f64 / tf) as f32,
                     });
                 } else {
-                    samples.push(CpuCoreSample {
-                        usage_percent: 0.0, user_frac: 0.0, kernel_frac: 0.0,
-                        dpc_frac: 0.0, interrupt_frac: 0.0,
-                    });
+                    samples.push(CpuCoreSample::idle());
                 }
             } else {
-                samples.push(CpuCoreSample {
-                    usage_percent: 0.0, user_frac: 0.0, kernel_frac: 0.0,
-                    dpc_frac: 0.0, interrupt_frac: 0.0,
-                });
+                samples.push(CpuCoreSample::idle());
             }
         }
 
@@ -0,0 +1,204 @@
+use ratatui::Frame;
+use ratatui::layout::{Alignment, Constraint, Rect};
+use ratatui::style::{Color, Modifier, Style};
+use ratatui::text::{Line, Span};
+use ratatui::widgets::{Block, Borders, Clear, Paragraph};
+
+use crate::app::App;
+use crate::system::netstat::{Connection, TcpState};
+
+/// Draw the connections viewer (Enter on a Net tab row - netstat
+/// equivalent): every socket of the process, or of all processes, with its
+/// endpoints and TCP state. Refreshed every tick while open.
+pub fn draw_connections_view(f: &mut Frame, app: &App) {
+    let area = centered_rect(90, 85, f.area());
+    f.render_widget(Clear, area);
+
+    let label = Style::default().fg(Color::Yellow).add_modifier(Modifier::BOLD);
+    let dim = Style::default().fg(Color::DarkGray);
+    let name_of = |pid: u32| {
+        app.net_processes
+            .iter()
+            .find(|p| p.pid == pid)
+            .map(|p| p.name.clone())
+            .or_else(|| app.processes.iter().find(|p| p.pid == pid).map(|p| p.name.clone()))
+            .unwrap_or_else(|| if pid == 0 { "?".to_string() } else { format!("PID:{}", pid) })
+    };
+
+    let owned: Vec<&Connection> = app
+        .connections
+        .iter()
+        .filter(|c| app.connections_all || c.pid == app.connections_pid)
+        .collect();
+    let shown = app.shown_connections();
+
+    let mut status = if app.connections_all {
+        vec![Span::styled(" All processes ", Style::default().fg(Color::Cyan).add_modifier(Modifier::BOLD))]
+    } else {
+        vec![
+            Span::styled(format!(" {} ", name_of(app.connections_pid)), Style::default().fg(Color::Cyan).add_modifier(Modifier::BOLD)),
+            Span::styled(" PID ", label),
+            Span::raw(app.connections_pid.to_string()),
+        ]
+    };
+    status.extend([
+        Span::styled("   Connections: ", label),
+        Span::raw(if shown.len() == owned.len() { owned.len().to_string() } else { format!("{} of {}", shown.len(), owned.len()) }),
+        Span::styled("   Filter: ", label),
+        Span::raw(app.connections_filter.clone()),
+    ]);
+    if app.connections_filter_editing {
+        status.push(Span::styled("_", Style::default().fg(Color::Cyan).add_modifier(Modifier::SLOW_BLINK)));
+    }
+
+    // Counts per TCP state, then UDP sockets
+    let mut summary = vec![Span::raw(" ")];
+    for state in TcpState::ALL {
+        let n = owned.iter().filter(|c| c.state == Some(state)).count();
+        if n > 0 {
+            summary.push(Span::styled(format!(" {} ", state.label()), state_style(Some(state))));
+            summary.push(Span::raw(format!("{} ", n)));
+        }
+    }
+    let udp = owned.iter().filter(|c| c.state.is_none()).count();
+    if udp > 0 {
+        summary.push(Span::styled(" UDP ", state_style(None)));
+        summary.push(Span::raw(format!("{} ", udp)));
+    }
+
+    // Endpoint columns as wide as the longest shown; when they don't fit,
+    // the longer one gives way. The owner's name gets what's left.
+    let inner_width = area.width.saturating_sub(2) as usize;
+    let fixed = 2 + 5 + 1 + 1 + 1 + 11 + if app.connections_all { 1 + 7 + 1 + 8 } else { 0 };
+    let available = inner_width.saturating_sub(fixed);
+    let mut local_width = shown.iter().map(|c| c.local.to_string().len()).max().unwrap_or(0).max(13);
+    let mut remote_width = shown.iter().map(|c| c.remote_label().len()).max().unwrap_or(0).max(14);
+    if local_width + remote_width > available {
+        let half = available / 2;
+        if local_width > half && remote_width > half {
+            local_width = half;
+            remote_width = available - half;
+        } else if local_width > half {
+            local_width = available.saturating_sub(remote_width);
+        } else {
+            remote_width = available.saturating_sub(local_width);
+        }
+    }
+    let name_width = inner_width.saturating_sub(2 + 5 + 1 + local_width + 1 + remote_width + 1 + 11 + 1 + 7 + 1);
+
+    let mut header = format!("  {:<5} {:<lw$} {:<rw$} {:<11}", "PROTO", "LOCAL ADDRESS", "REMOTE ADDRESS", "STATE", lw = local_width, rw = remote_width);
+    if app.connections_all {
+        header.push_str(&format!(" {:>7} {}", "PID", "NAME"));
+    }
+    let mut lines = vec![
+        Line::from(status),
+        Line::from(summary),
+        Line::from(""),
+        Line::from(Span::styled(header, label)),
+    ];
+
+    // Rows left inside the border, above the hint line
+    let room = (area.height as usize).saturating_sub(lines.len() + 4);
+    let scroll = app.connections_scroll.min(shown.len().saturating_sub(room));
+    if app.viewing_history() {
+        lines.push(Line::from(Span::styled("  Connections are only listed for live processes", dim)));
+    } else if owned.is_empty() {
+        lines.push(Line::from(Span::styled("  No sockets open", dim)));
+    } else if shown.is_empty() {
+        lines.push(Line::from(Span::styled("  No connections match", dim)));
+    }
+    if !app.viewing_history() {
+        for c in shown.iter().skip(scroll).take(room) {
+            let mut spans = vec![
+                Span::styled(format!("  {:<5} ", c.protocol_label()), Style::default().fg(Color::Cyan)),
+                Span::raw(format!("{:<w$} ", truncate_start(&c.local.to_string(), local_width), w = local_width)),
+                Span::styled(format!("{:<w$} ", truncate_start(&c.remote_label(), remote_width), w = remote_width), Style::default().fg(Color::White).add_modifier(Modifier::BOLD)),
+                Span::styled(format!("{:<11}", c.state_label()), state_style(c.state)),
+            ];
+            if app.connections_all {
+                let pid = if c.pid == 0 { "-".to_string() } else { c.pid.to_string() };
+                spans.push(Span::styled(format!(" {:>7} ", pid), dim));
+                spans.push(Span::raw(truncate_str(&name_of(c.pid), name_width)));
+            }
+            lines.push(Line::from(spans));
+        }
+    }
+
+    lines.push(Line::from(""));
+    let more = if shown.len() > room {
+        format!("  ({}-{} of {})", scroll + 1, (scroll + room).min(shown.len()), shown.len())
+    } else {
+        String::new()
+    };
+    lines.push(Line::from(Span::styled(
+        format!(" / filter by state, port or address  Tab process/all  ↑/↓ scroll  Esc close{} ", more),
+        dim,
+    )));
+
+    let paragraph = Paragraph::new(lines)
+        .block(
+            Block::default()
+                .borders(Borders::ALL)
+                .title(" Connections (netstat) ")
+                .title_alignment(Alignment::Center)
+                .border_style(Style::default().fg(Color::Cyan)),
+        )
+        .style(Style::default().fg(Color::White).bg(Color::Black));
+
+    f.render_widget(paragraph, area);
+}
+
+fn state_style(state: Option<TcpState>) -> Style {
+    match state {
+        Some(TcpState::Established) => Style::default().fg(Color::Green),
+        Some(TcpState::Listen) => Style::default().fg(Color::Cyan),
+        Some(TcpState::SynSent) | Some(TcpState::SynReceived) => Style::default().fg(Color::Yellow),
+        Some(TcpState::CloseWait) => Style::default().fg(Color::Red),
+        Some(_) => Style::default().fg(Color::DarkGray),
+        None => Style::default().fg(Color::Magenta),
+    }
+}
+
+/// Endpoints keep their port: cut from the front
+fn truncate_start(s: &str, max: usize) -> String {
+    let count = s.chars().count();
+    if count > max {
+        let mut truncated = String::from("...");
+        truncated.extend(s.chars().skip(count - max.saturating_sub(3)));
+        truncated
+    } else {
+        s.to_string()
+    }
+}
+
+fn truncate_str(s: &str, max: usize) -> String {
+    if s.chars().count() > max {
+        let mut truncated: String = s.chars().take(max.saturating_sub(3)).collect();
+        truncated.push_str("...");
+        truncated
+    } else {
+        s.to_string()
+    }
+}
+
+fn centered_rect(percent_x: u16, percent_y: u16, area: Rect) -> Rect {
+    use ratatui::layout::{Direction, Layout};
+
+    let popup_layout = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([
+            Constraint::Percentage((100 - percent_y) / 2),
+            Constraint::Percentage(percent_y),
+            Constraint::Percentage((100 - percent_y) / 2),
+        ])
+        .split(area);
+
+    Layout::default()
+        .direction(Direction::Horizontal)
+        .constraints([
+            Constraint::Percentage((100 - percent_x) / 2),
+            Constraint::Percentage(percent_x),
+            Constraint::Percentage((100 - percent_x) / 2),
+        ])
+        .split(popup_layout[1])[1]
+}
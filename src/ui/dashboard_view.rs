@@ -0,0 +1,161 @@
+use ratatui::Frame;
+use ratatui::layout::{Alignment, Constraint, Direction, Layout, Rect};
+use ratatui::style::{Color, Modifier, Style};
+use ratatui::text::{Line, Span};
+use ratatui::widgets::{Block, Borders, Clear, Paragraph};
+
+use crate::app::App;
+use crate::dashboard::{ancestry, child_counts, DashSample, Dashboard};
+use crate::history::graph_rows;
+use crate::system::memory::format_bytes;
+use crate::ui::header::format_rate;
+
+/// Draw the full-screen process dashboard ('D' key)
+pub fn draw_dashboard_view(f: &mut Frame, app: &App) {
+    let Some(dash) = &app.dashboard else { return };
+    let area = f.area();
+    f.render_widget(Clear, area);
+
+    let block = Block::default()
+        .borders(Borders::ALL)
+        .title(" Process Dashboard ")
+        .title_alignment(Alignment::Center)
+        .border_style(Style::default().fg(Color::Cyan))
+        .style(Style::default().fg(Color::White).bg(Color::Black));
+    let inner = block.inner(area);
+    f.render_widget(block, area);
+
+    let rows = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([Constraint::Length(5), Constraint::Min(0), Constraint::Length(1)])
+        .split(inner);
+
+    draw_summary(f, app, dash, rows[0]);
+
+    let chart_rows = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([Constraint::Ratio(1, 3); 3])
+        .split(rows[1]);
+    let halves = |area: Rect| {
+        Layout::default()
+            .direction(Direction::Horizontal)
+            .constraints([Constraint::Ratio(1, 2); 2])
+            .split(area)
+    };
+    let top = halves(chart_rows[0]);
+    let middle = halves(chart_rows[1]);
+    let bottom = halves(chart_rows[2]);
+
+    let percent = |v: f64| format!("{:.1}%", v);
+    let bytes = |v: f64| format_bytes(v as u64);
+    let count = |v: f64| format!("{:.0}", v);
+    // CPU% can pass 100 on a multi-threaded process; never scale below it
+    let cpu_full = dash.stats(|s| s.cpu).max.max(100.0);
+
+    draw_chart(f, dash, top[0], "CPU%", Color::Green, cpu_full, |s| s.cpu, percent);
+    draw_chart(f, dash, top[1], "Threads", Color::Cyan, 0.0, |s| s.threads as f64, count);
+    draw_chart(f, dash, middle[0], "Resident (RSS)", Color::Yellow, 0.0, |s| s.resident_mem as f64, bytes);
+    draw_chart(f, dash, middle[1], "Private", Color::Yellow, 0.0, |s| s.private_mem as f64, bytes);
+    draw_chart(f, dash, bottom[0], "I/O Read", Color::Magenta, 0.0, |s| s.io_read_rate, format_rate);
+    draw_chart(f, dash, bottom[1], "I/O Write", Color::Magenta, 0.0, |s| s.io_write_rate, format_rate);
+
+    f.render_widget(
+        Paragraph::new(Line::from(Span::styled(" Press Esc, D, or q to close ", Style::default().fg(Color::DarkGray)))),
+        rows[2],
+    );
+}
+
+/// Identity, ancestry chain and children of the watched process
+fn draw_summary(f: &mut Frame, app: &App, dash: &Dashboard, area: Rect) {
+    let label = Style::default().fg(Color::Yellow).add_modifier(Modifier::BOLD);
+    let value = Style::default().fg(Color::White);
+    let current = if dash.exited { None } else { app.processes.iter().find(|p| p.pid == dash.pid) };
+
+    let mut title = vec![
+        Span::styled(format!(" PID {} ", dash.pid), Style::default().fg(Color::Cyan).add_modifier(Modifier::BOLD)),
+        Span::styled(dash.name.clone(), Style::default().fg(Color::White).add_modifier(Modifier::BOLD)),
+    ];
+    match current {
+        Some(p) => title.push(Span::styled(
+            format!("  user {}  state {}  nice {}  TIME+ {}", p.user, p.status.symbol(), p.nice, p.format_time()),
+            value,
+        )),
+        None => title.push(Span::styled("  exited", Style::default().fg(Color::Red).add_modifier(Modifier::BOLD))),
+    }
+
+    // Root on the left, the watched process last
+    let chain: Vec<String> = ancestry(&app.processes, dash.pid)
+        .iter()
+        .rev()
+        .map(|p| format!("{} ({})", p.name, p.pid))
+        .collect();
+    let chain = if chain.is_empty() { "-".to_string() } else { chain.join(" › ") };
+    let (direct, total) = child_counts(&app.processes, dash.pid);
+
+    let lines = vec![
+        Line::from(title),
+        Line::from(vec![
+            Span::styled(" Command:  ", label),
+            Span::styled(dash.command.clone(), value),
+        ]),
+        Line::from(vec![Span::styled(" Ancestry: ", label), Span::styled(chain, value)]),
+        Line::from(vec![
+            Span::styled(" Children: ", label),
+            Span::styled(format!("{} direct, {} total", direct, total), value),
+            Span::styled("   Samples: ", label),
+            Span::styled(format!("{} since opened", dash.samples().len()), value),
+        ]),
+    ];
+    f.render_widget(Paragraph::new(lines), area);
+}
+
+/// One boxed chart: the series since the dashboard opened, the current
+/// value in the title and min/avg/max on the bottom row. `full` is the top
+/// of the scale (0 = the largest value shown).
+#[allow(clippy::too_many_arguments)]
+fn draw_chart(
+    f: &mut Frame,
+    dash: &Dashboard,
+    area: Rect,
+    title: &str,
+    color: Color,
+    full: f64,
+    value: impl Fn(&DashSample) -> f64 + Copy,
+    format: impl Fn(f64) -> String,
+) {
+    let stats = dash.stats(value);
+    let now = dash.samples().back().map_or(0.0, value);
+    let block = Block::default()
+        .borders(Borders::ALL)
+        .title(Line::from(vec![
+            Span::styled(format!(" {} ", title), Style::default().fg(Color::White)),
+            Span::styled(format!("{} ", format(now)), Style::default().fg(color).add_modifier(Modifier::BOLD)),
+        ]))
+        .border_style(Style::default().fg(Color::DarkGray));
+    let inner = block.inner(area);
+    f.render_widget(block, area);
+    if inner.height == 0 || inner.width == 0 {
+        return;
+    }
+
+    // Tighter spacing when the full line wouldn't fit
+    let values = [("min", format(stats.min)), ("avg", format(stats.avg)), ("max", format(stats.max))];
+    let wide: usize = values.iter().map(|(l, v)| l.len() + 1 + v.chars().count()).sum::<usize>() + 4;
+    let gap = if wide <= inner.width as usize { "  " } else { " " };
+    let label = Style::default().fg(Color::Cyan);
+    let mut spans = Vec::new();
+    for (i, (name, v)) in values.into_iter().enumerate() {
+        let sep = if i == 0 { "" } else { gap };
+        spans.push(Span::styled(format!("{}{} ", sep, name), label));
+        spans.push(Span::raw(v));
+    }
+    let summary = Line::from(spans);
+
+    let height = inner.height as usize - 1;
+    let mut lines: Vec<Line> = graph_rows(&dash.series(value), inner.width as usize, height, full)
+        .into_iter()
+        .map(|row| Line::from(Span::styled(row, Style::default().fg(color))))
+        .collect();
+    lines.push(summary);
+    f.render_widget(Paragraph::new(lines), inner);
+}
@@ -0,0 +1,135 @@
+use ratatui::Frame;
+use ratatui::layout::{Alignment, Constraint, Rect};
+use ratatui::style::{Color, Modifier, Style};
+use ratatui::text::{Line, Span};
+use ratatui::widgets::{Block, Borders, Clear, Paragraph};
+
+use crate::app::App;
+use crate::ui::header::format_rate;
+
+/// Draw the disk I/O viewer (O - iostat equivalent): each physical block
+/// device with its throughput, IOPS, queue depth and utilisation over the
+/// last refresh. Refreshed every tick while open.
+pub fn draw_disks_view(f: &mut Frame, app: &App) {
+    let area = centered_rect(90, 85, f.area());
+    f.render_widget(Clear, area);
+
+    let label = Style::default().fg(Color::Yellow).add_modifier(Modifier::BOLD);
+    let dim = Style::default().fg(Color::DarkGray);
+    let disks = &app.disks;
+    let io = &app.disk_io_info;
+
+    let status = vec![
+        Span::styled(" Devices: ", label),
+        Span::raw(disks.len().to_string()),
+        Span::styled("   Read: ", label),
+        Span::raw(format_rate(io.read_bytes_per_sec)),
+        Span::styled("   Write: ", label),
+        Span::raw(format_rate(io.write_bytes_per_sec)),
+        Span::styled("   Busiest: ", label),
+        Span::styled(format!("{:.0}%", io.busy_percent), util_style(io.busy_percent)),
+    ];
+    let summary = vec![
+        Span::styled(" IOPS: ", label),
+        Span::raw(format!("{:.0} read, {:.0} write", disks.iter().map(|d| d.reads_per_sec).sum::<f64>(), disks.iter().map(|d| d.writes_per_sec).sum::<f64>())),
+        Span::styled("   In flight: ", label),
+        Span::raw(disks.iter().map(|d| d.queue_depth).sum::<u64>().to_string()),
+    ];
+
+    // The device name gets what the fixed columns leave, within reason
+    let inner_width = area.width.saturating_sub(2) as usize;
+    let fixed = 2 + 1 + 11 + 1 + 11 + 1 + 8 + 1 + 8 + 1 + 6 + 1 + 6 + 1;
+    let name_width = inner_width.saturating_sub(fixed).clamp(6, 20);
+
+    let header = format!(
+        "  {:<nw$} {:>11} {:>11} {:>8} {:>8} {:>6} {:>6}",
+        "DEVICE", "READ/s", "WRITE/s", "R IOPS", "W IOPS", "QUEUE", "UTIL%",
+        nw = name_width
+    );
+    let mut lines = vec![
+        Line::from(status),
+        Line::from(summary),
+        Line::from(""),
+        Line::from(Span::styled(header, label)),
+    ];
+
+    // Rows left inside the border, above the hint line
+    let room = (area.height as usize).saturating_sub(lines.len() + 4);
+    let scroll = app.disks_scroll.min(disks.len().saturating_sub(room));
+    if disks.is_empty() {
+        lines.push(Line::from(Span::styled("  No block devices found", dim)));
+    }
+    for d in disks.iter().skip(scroll).take(room) {
+        lines.push(Line::from(vec![
+            Span::styled(format!("  {:<w$} ", truncate_str(&d.name, name_width), w = name_width), Style::default().fg(Color::Cyan)),
+            Span::styled(format!("{:>11} ", format_rate(d.read_bytes_per_sec)), Style::default().fg(Color::Green)),
+            Span::styled(format!("{:>11} ", format_rate(d.write_bytes_per_sec)), Style::default().fg(Color::Magenta)),
+            Span::raw(format!("{:>8.0} {:>8.0} ", d.reads_per_sec, d.writes_per_sec)),
+            Span::raw(format!("{:>6} ", d.queue_depth)),
+            Span::styled(format!("{:>6.1}", d.utilization), util_style(d.utilization)),
+        ]));
+    }
+
+    lines.push(Line::from(""));
+    let more = if disks.len() > room {
+        format!("  ({}-{} of {})", scroll + 1, (scroll + room).min(disks.len()), disks.len())
+    } else {
+        String::new()
+    };
+    lines.push(Line::from(Span::styled(format!(" ↑/↓ scroll  Esc close{} ", more), dim)));
+
+    let paragraph = Paragraph::new(lines)
+        .block(
+            Block::default()
+                .borders(Borders::ALL)
+                .title(" Disk I/O (iostat) ")
+                .title_alignment(Alignment::Center)
+                .border_style(Style::default().fg(Color::Cyan)),
+        )
+        .style(Style::default().fg(Color::White).bg(Color::Black));
+
+    f.render_widget(paragraph, area);
+}
+
+/// Utilisation like a CPU bar: busy devices stand out
+fn util_style(percent: f64) -> Style {
+    if percent >= 90.0 {
+        Style::default().fg(Color::Red).add_modifier(Modifier::BOLD)
+    } else if percent >= 50.0 {
+        Style::default().fg(Color::Yellow)
+    } else {
+        Style::default().fg(Color::Green)
+    }
+}
+
+fn truncate_str(s: &str, max: usize) -> String {
+    if s.chars().count() > max {
+        let mut truncated: String = s.chars().take(max.saturating_sub(3)).collect();
+        truncated.push_str("...");
+        truncated
+    } else {
+        s.to_string()
+    }
+}
+
+fn centered_rect(percent_x: u16, percent_y: u16, area: Rect) -> Rect {
+    use ratatui::layout::{Direction, Layout};
+
+    let popup_layout = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([
+            Constraint::Percentage((100 - percent_y) / 2),
+            Constraint::Percentage(percent_y),
+            Constraint::Percentage((100 - percent_y) / 2),
+        ])
+        .split(area);
+
+    Layout::default()
+        .direction(Direction::Horizontal)
+        .constraints([
+            Constraint::Percentage((100 - percent_x) / 2),
+            Constraint::Percentage(percent_x),
+            Constraint::Percentage((100 - percent_x) / 2),
+        ])
+        .split(popup_layout[1])[1]
+}
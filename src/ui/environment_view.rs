@@ -2,120 +2,199 @@ use ratatui::Frame;
 use ratatui::layout::{Alignment, Constraint, Direction, Layout, Rect};
 use ratatui::style::{Color, Modifier, Style};
 use ratatui::text::{Line, Span};
-use ratatui::widgets::{Block, Borders, Clear, Paragraph, Wrap};
+use ratatui::widgets::{Block, Borders, Clear, Paragraph};
 
 use crate::app::App;
+use crate::system::backend::QueryError;
 use crate::system::memory::format_bytes;
 
-/// Draw the Environment/Process Details viewer (htop 'e')
+/// Draw the Environment/Process Details viewer (htop 'e'): a summary of the
+/// process, where it runs from, then its environment variables, searchable
+/// and scrollable, with long values wrapped
 pub fn draw_environment_view(f: &mut Frame, app: &App) {
-    let area = centered_rect(80, 85, f.area());
+    let area = centered_rect(90, 85, f.area());
     f.render_widget(Clear, area);
 
-    let proc = match app.selected_process() {
+    let proc = match app.environment_process().or_else(|| app.selected_process()) {
         Some(p) => p,
         None => return,
     };
 
-    let mut lines = vec![
-        Line::from(Span::styled(
-            format!(" Process Details - PID {} ", proc.pid),
-            Style::default().fg(Color::Cyan).add_modifier(Modifier::BOLD),
-        )),
-        Line::from(""),
-    ];
+    let label = Style::default().fg(Color::Yellow).add_modifier(Modifier::BOLD);
+    let dim = Style::default().fg(Color::DarkGray);
+    let inner_width = area.width.saturating_sub(2) as usize;
 
-    // Process information
-    lines.extend(vec![
-        Line::from(vec![
-            Span::styled("Name:         ", Style::default().fg(Color::Yellow).add_modifier(Modifier::BOLD)),
-            Span::styled(&proc.name, Style::default().fg(Color::White)),
-        ]),
-        Line::from(vec![
-            Span::styled("PID:          ", Style::default().fg(Color::Yellow).add_modifier(Modifier::BOLD)),
-            Span::styled(format!("{}", proc.pid), Style::default().fg(Color::White)),
-        ]),
-        Line::from(vec![
-            Span::styled("Parent PID:   ", Style::default().fg(Color::Yellow).add_modifier(Modifier::BOLD)),
-            Span::styled(format!("{}", proc.ppid), Style::default().fg(Color::White)),
-        ]),
-        Line::from(vec![
-            Span::styled("User:         ", Style::default().fg(Color::Yellow).add_modifier(Modifier::BOLD)),
-            Span::styled(&proc.user, Style::default().fg(Color::White)),
-        ]),
-        Line::from(vec![
-            Span::styled("Status:       ", Style::default().fg(Color::Yellow).add_modifier(Modifier::BOLD)),
-            Span::styled(format!("{}", proc.status.symbol()), Style::default().fg(Color::White)),
-        ]),
-        Line::from(vec![
-            Span::styled("Priority:     ", Style::default().fg(Color::Yellow).add_modifier(Modifier::BOLD)),
-            Span::styled(format!("{} (Nice: {})", proc.priority, proc.nice), Style::default().fg(Color::White)),
-        ]),
-        Line::from(vec![
-            Span::styled("Threads:      ", Style::default().fg(Color::Yellow).add_modifier(Modifier::BOLD)),
-            Span::styled(format!("{}", proc.threads), Style::default().fg(Color::White)),
-        ]),
-        Line::from(""),
-        Line::from(Span::styled(" Memory Usage ", Style::default().fg(Color::Cyan).add_modifier(Modifier::BOLD))),
-        Line::from(vec![
-            Span::styled("Virtual:      ", Style::default().fg(Color::Yellow).add_modifier(Modifier::BOLD)),
-            Span::styled(format_bytes(proc.virtual_mem), Style::default().fg(Color::White)),
-        ]),
-        Line::from(vec![
-            Span::styled("Resident:     ", Style::default().fg(Color::Yellow).add_modifier(Modifier::BOLD)),
-            Span::styled(format_bytes(proc.resident_mem), Style::default().fg(Color::White)),
-        ]),
-        Line::from(vec![
-            Span::styled("Shared:       ", Style::default().fg(Color::Yellow).add_modifier(Modifier::BOLD)),
-            Span::styled(format_bytes(proc.shared_mem), Style::default().fg(Color::White)),
-        ]),
-        Line::from(vec![
-            Span::styled("Memory %:     ", Style::default().fg(Color::Yellow).add_modifier(Modifier::BOLD)),
-            Span::styled(format!("{:.1}%", proc.mem_usage), Style::default().fg(Color::White)),
-        ]),
-        Line::from(""),
-        Line::from(Span::styled(" Performance ", Style::default().fg(Color::Cyan).add_modifier(Modifier::BOLD))),
-        Line::from(vec![
-            Span::styled("CPU %:        ", Style::default().fg(Color::Yellow).add_modifier(Modifier::BOLD)),
-            Span::styled(format!("{:.1}%", proc.cpu_usage), Style::default().fg(Color::White)),
-        ]),
-        Line::from(vec![
-            Span::styled("Runtime:      ", Style::default().fg(Color::Yellow).add_modifier(Modifier::BOLD)),
-            Span::styled(proc.format_time(), Style::default().fg(Color::White)),
-        ]),
+    let mut lines = vec![
         Line::from(vec![
-            Span::styled("I/O Read:     ", Style::default().fg(Color::Yellow).add_modifier(Modifier::BOLD)),
-            Span::styled(format_io_rate(proc.io_read_rate), Style::default().fg(Color::White)),
+            Span::styled(format!(" {} ", proc.name), Style::default().fg(Color::Cyan).add_modifier(Modifier::BOLD)),
+            Span::styled(" PID ", label),
+            Span::raw(proc.pid.to_string()),
+            Span::styled("  PPID ", label),
+            Span::raw(proc.ppid.to_string()),
+            Span::styled("  User ", label),
+            Span::raw(proc.user.clone()),
+            Span::styled("  State ", label),
+            Span::raw(proc.status.symbol().to_string()),
+            Span::styled("  Threads ", label),
+            Span::raw(proc.threads.to_string()),
+            Span::styled("  PRI ", label),
+            Span::raw(proc.priority.to_string()),
+            Span::styled("  NI ", label),
+            Span::raw(proc.nice.to_string()),
         ]),
         Line::from(vec![
-            Span::styled("I/O Write:    ", Style::default().fg(Color::Yellow).add_modifier(Modifier::BOLD)),
-            Span::styled(format_io_rate(proc.io_write_rate), Style::default().fg(Color::White)),
+            Span::styled(" CPU ", label),
+            Span::raw(format!("{:.1}%", proc.cpu_usage)),
+            Span::styled("  MEM ", label),
+            Span::raw(format!("{:.1}%", proc.mem_usage)),
+            Span::styled("  VIRT ", label),
+            Span::raw(format_bytes(proc.virtual_mem)),
+            Span::styled("  RES ", label),
+            Span::raw(format_bytes(proc.resident_mem)),
+            Span::styled("  SHR ", label),
+            Span::raw(format_bytes(proc.shared_mem)),
+            Span::styled("  TIME+ ", label),
+            Span::raw(proc.format_time()),
+            Span::styled("  I/O ", label),
+            Span::raw(format!("{} r, {} w", format_io_rate(proc.io_read_rate), format_io_rate(proc.io_write_rate))),
         ]),
         Line::from(""),
-        Line::from(Span::styled(" Command Line ", Style::default().fg(Color::Cyan).add_modifier(Modifier::BOLD))),
-        Line::from(Span::styled(&proc.command, Style::default().fg(Color::White))),
-        Line::from(""),
-        Line::from(""),
-        Line::from(Span::styled(
-            " Press Esc, e, or q to close ",
-            Style::default().fg(Color::DarkGray),
-        )),
-    ]);
+    ];
+
+    let env = app.environment.as_ref();
+    let started = env
+        .and_then(|e| e.start_time)
+        .and_then(|t| chrono::DateTime::from_timestamp(t, 0))
+        .map(|t| t.with_timezone(&chrono::Local).format("%Y-%m-%d %H:%M:%S").to_string())
+        .unwrap_or_else(|| "unknown".to_string());
+    let value_width = inner_width.saturating_sub(14);
+    let field = |name: &'static str, value: Option<&Result<String, QueryError>>| {
+        let value = match value {
+            Some(Ok(v)) => Span::raw(truncate_str(v, value_width)),
+            Some(Err(e)) => Span::styled(e.to_string(), error_style(e)),
+            None => Span::styled("unknown", dim),
+        };
+        Line::from(vec![Span::styled(name, label), value])
+    };
+    lines.push(field(" Executable:  ", env.map(|e| &e.exe)));
+    lines.push(field(" Working dir: ", env.map(|e| &e.cwd)));
+    lines.push(Line::from(vec![Span::styled(" Started:     ", label), Span::raw(started)]));
+    lines.push(Line::from(vec![
+        Span::styled(" Command:     ", label),
+        Span::raw(truncate_str(&proc.command, value_width)),
+    ]));
+    lines.push(Line::from(""));
+
+    // Environment variables
+    let shown = app.shown_environment();
+    let total = match env.map(|e| &e.vars) {
+        Some(Ok(vars)) => vars.len(),
+        _ => 0,
+    };
+    let mut status = vec![
+        Span::styled(" Environment: ", label),
+        Span::raw(if shown.len() == total { total.to_string() } else { format!("{} of {}", shown.len(), total) }),
+        Span::styled("   Search: ", label),
+        Span::raw(app.environment_search.clone()),
+    ];
+    if app.environment_search_editing {
+        status.push(Span::styled("_", Style::default().fg(Color::Cyan).add_modifier(Modifier::SLOW_BLINK)));
+    }
+    lines.push(Line::from(status));
+
+    // Rows left inside the border, above the hint line
+    let room = (area.height as usize).saturating_sub(lines.len() + 4);
+    let mut position = String::new();
+    match env.map(|e| &e.vars) {
+        None => lines.push(Line::from(Span::styled("  Not read: the environment is only read from live processes", dim))),
+        Some(Err(e)) => {
+            let mut spans = vec![Span::styled(format!("  {}", e), error_style(e))];
+            if *e == QueryError::AccessDenied {
+                let who = if cfg!(windows) { "Administrator" } else { "root" };
+                spans.push(Span::styled(format!(" (run pstop as {} or as {} to read it)", proc.user, who), dim));
+            }
+            lines.push(Line::from(spans));
+        }
+        Some(Ok(_)) if shown.is_empty() => {
+            let message = if total == 0 { "  No environment variables" } else { "  No variables match" };
+            lines.push(Line::from(Span::styled(message, dim)));
+        }
+        Some(Ok(_)) => {
+            // NAME=value, with wrapped values indented under the name
+            let rows: Vec<Vec<Line>> = shown.iter().map(|(name, value)| var_lines(name, value, inner_width)).collect();
+            // Don't scroll past the point where the last variable is at the bottom
+            let mut fits = rows.len();
+            let mut height = 0;
+            while fits > 0 && height + rows[fits - 1].len() <= room {
+                height += rows[fits - 1].len();
+                fits -= 1;
+            }
+            let scroll = app.environment_scroll.min(fits);
+            let mut used = 0;
+            let mut end = scroll;
+            for row in &rows[scroll..] {
+                if used + row.len() > room && used > 0 {
+                    break;
+                }
+                lines.extend(row.iter().take(room - used).cloned());
+                used += row.len();
+                end += 1;
+            }
+            if scroll > 0 || end < rows.len() {
+                position = format!("  ({}-{} of {})", scroll + 1, end, rows.len());
+            }
+        }
+    }
+
+    // Pin the hint to the bottom
+    let blank = (area.height as usize).saturating_sub(lines.len() + 3);
+    lines.extend(std::iter::repeat_n(Line::from(""), blank));
+    lines.push(Line::from(Span::styled(
+        format!(" / search  ↑/↓ scroll  r re-read  Esc close{} ", position),
+        dim,
+    )));
 
     let paragraph = Paragraph::new(lines)
         .block(
             Block::default()
                 .borders(Borders::ALL)
-                .title(" Process Details ")
+                .title(" Process Environment ")
                 .title_alignment(Alignment::Center)
                 .border_style(Style::default().fg(Color::Cyan)),
         )
-        .style(Style::default().fg(Color::White).bg(Color::Black))
-        .wrap(Wrap { trim: false });
+        .style(Style::default().fg(Color::White).bg(Color::Black));
 
     f.render_widget(paragraph, area);
 }
 
+/// One variable as display lines: `NAME=value`, the value wrapped at
+/// `width` with continuation lines indented
+fn var_lines(name: &str, value: &str, width: usize) -> Vec<Line<'static>> {
+    const INDENT: usize = 4;
+    let first = width.saturating_sub(name.chars().count() + 3).max(8);
+    let rest = width.saturating_sub(INDENT + 2).max(8);
+    let chars: Vec<char> = value.chars().collect();
+    let mut lines = vec![Line::from(vec![
+        Span::styled(format!("  {}", name), Style::default().fg(Color::Cyan)),
+        Span::styled("=", Style::default().fg(Color::DarkGray)),
+        Span::raw(chars.iter().take(first).collect::<String>()),
+    ])];
+    let mut at = first;
+    while at < chars.len() {
+        let chunk: String = chars[at..(at + rest).min(chars.len())].iter().collect();
+        lines.push(Line::from(Span::raw(format!("  {:INDENT$}{}", "", chunk))));
+        at += rest;
+    }
+    lines
+}
+
+fn error_style(e: &QueryError) -> Style {
+    match e {
+        QueryError::Unavailable => Style::default().fg(Color::DarkGray),
+        _ => Style::default().fg(Color::Red),
+    }
+}
+
 fn format_io_rate(rate: f64) -> String {
     if rate == 0.0 {
         "0 B/s".to_string()
@@ -130,6 +209,16 @@ fn format_io_rate(rate: f64) -> String {
     }
 }
 
+fn truncate_str(s: &str, max: usize) -> String {
+    if s.chars().count() > max {
+        let mut truncated: String = s.chars().take(max.saturating_sub(3)).collect();
+        truncated.push_str("...");
+        truncated
+    } else {
+        s.to_string()
+    }
+}
+
 fn centered_rect(percent_x: u16, percent_y: u16, area: Rect) -> Rect {
     let popup_layout = Layout::default()
         .direction(Direction::Vertical)
@@ -0,0 +1,142 @@
+use ratatui::Frame;
+use ratatui::layout::{Alignment, Constraint, Rect};
+use ratatui::style::{Color, Modifier, Style};
+use ratatui::text::{Line, Span};
+use ratatui::widgets::{Block, Borders, Clear, Paragraph};
+
+use crate::app::App;
+use crate::filter::Filter;
+use crate::lifecycle::{format_lifetime, EventKind};
+use crate::system::memory::format_bytes;
+
+/// Draw the process event log ('E' key): starts and exits, oldest at the
+/// top, following the newest unless scrolled back
+pub fn draw_events_view(f: &mut Frame, app: &App) {
+    let area = centered_rect(95, 85, f.area());
+    f.render_widget(Clear, area);
+
+    let label = Style::default().fg(Color::Yellow).add_modifier(Modifier::BOLD);
+    let dim = Style::default().fg(Color::DarkGray);
+    let events = app.shown_events();
+    let total = app.lifecycle.log().len();
+
+    let kind = match app.events_kind {
+        None => "all",
+        Some(EventKind::Started) => "starts",
+        Some(EventKind::Exited) => "exits",
+    };
+    let mut status = vec![
+        Span::styled(" Events: ", label),
+        Span::raw(if events.len() == total { total.to_string() } else { format!("{} of {}", events.len(), total) }),
+        Span::styled("   Show: ", label),
+        Span::raw(kind),
+        Span::styled("   Filter: ", label),
+        Span::raw(app.events_filter.clone()),
+    ];
+    if app.events_filter_editing {
+        status.push(Span::styled("_", Style::default().fg(Color::Cyan).add_modifier(Modifier::SLOW_BLINK)));
+    }
+    if let Err(e) = Filter::parse(&app.events_filter) {
+        status.push(Span::styled(format!("  {}", e), Style::default().fg(Color::Red)));
+    }
+
+    let inner_width = area.width.saturating_sub(2) as usize;
+    let fixed = 2 + 8 + 2 + 6 + 7 + 7 + 1 + 9 + 16 + 9 + 9 + 9;
+    let cmd_width = inner_width.saturating_sub(fixed);
+    let mut lines = vec![
+        Line::from(status),
+        Line::from(""),
+        Line::from(Span::styled(
+            format!(
+                "  {:<8}  {:<5} {:>6} {:>6} {:<8} {:<15} {:>8} {:>8} {:>8}  {}",
+                "TIME", "EVENT", "PID", "PPID", "USER", "NAME", "LIFETIME", "PEAK CPU", "PEAK RES", "COMMAND"
+            ),
+            label,
+        )),
+    ];
+
+    // Rows left inside the border, above the hint line
+    let room = (area.height as usize).saturating_sub(lines.len() + 4);
+    let scroll = app.events_scroll.min(events.len().saturating_sub(room));
+    let end = events.len() - scroll;
+    let start = end.saturating_sub(room);
+    if events.is_empty() {
+        let message = if total == 0 { "  No starts or exits seen yet" } else { "  No events match" };
+        lines.push(Line::from(Span::styled(message, dim)));
+    }
+    for event in &events[start..end] {
+        let p = &event.process;
+        let time = chrono::DateTime::from_timestamp_millis(event.at_ms)
+            .map(|t| t.with_timezone(&chrono::Local).format("%H:%M:%S").to_string())
+            .unwrap_or_default();
+        let (kind_style, lifetime, peak_cpu, peak_res) = match event.kind {
+            EventKind::Started => (Style::default().fg(Color::Green), String::new(), String::new(), String::new()),
+            EventKind::Exited => (
+                Style::default().fg(Color::Red),
+                format_lifetime(event.lifetime_secs),
+                format!("{:.1}", event.peak_cpu),
+                format_bytes(event.peak_res),
+            ),
+        };
+        lines.push(Line::from(vec![
+            Span::styled(format!("  {:<8}  ", time), dim),
+            Span::styled(format!("{:<5} ", event.kind.label()), kind_style.add_modifier(Modifier::BOLD)),
+            Span::styled(format!("{:>6} {:>6} ", p.pid, p.ppid), Style::default().fg(Color::Cyan)),
+            Span::raw(format!("{:<8} ", truncate_str(&p.user, 8))),
+            Span::styled(format!("{:<15} ", truncate_str(&p.name, 15)), Style::default().add_modifier(Modifier::BOLD)),
+            Span::raw(format!("{:>8} {:>8} {:>8}  ", lifetime, peak_cpu, peak_res)),
+            Span::styled(truncate_str(&p.command, cmd_width), dim),
+        ]));
+    }
+
+    lines.push(Line::from(""));
+    let position = if scroll > 0 { format!("  ({} newer below)", scroll) } else { String::new() };
+    lines.push(Line::from(Span::styled(
+        format!(" / filter  Tab starts/exits  w append to file  ↑/↓ scroll  Esc close{} ", position),
+        dim,
+    )));
+
+    let paragraph = Paragraph::new(lines)
+        .block(
+            Block::default()
+                .borders(Borders::ALL)
+                .title(" Process Events ")
+                .title_alignment(Alignment::Center)
+                .border_style(Style::default().fg(Color::Cyan)),
+        )
+        .style(Style::default().fg(Color::White).bg(Color::Black));
+
+    f.render_widget(paragraph, area);
+}
+
+fn truncate_str(s: &str, max: usize) -> String {
+    if s.chars().count() > max {
+        let mut truncated: String = s.chars().take(max.saturating_sub(3)).collect();
+        truncated.push_str("...");
+        truncated
+    } else {
+        s.to_string()
+    }
+}
+
+fn centered_rect(percent_x: u16, percent_y: u16, area: Rect) -> Rect {
+    use ratatui::layout::{Direction, Layout};
+
+    let popup_layout = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([
+            Constraint::Percentage((100 - percent_y) / 2),
+            Constraint::Percentage(percent_y),
+            Constraint::Percentage((100 - percent_y) / 2),
+        ])
+        .split(area);
+
+    Layout::default()
+        .direction(Direction::Horizontal)
+        .constraints([
+            Constraint::Percentage((100 - percent_x) / 2),
+            Constraint::Percentage(percent_x),
+            Constraint::Percentage((100 - percent_x) / 2),
+        ])
+        .split(popup_layout[1])[1]
+}
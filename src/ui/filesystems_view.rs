@@ -0,0 +1,167 @@
+use ratatui::Frame;
+use ratatui::layout::{Alignment, Constraint, Rect};
+use ratatui::style::{Color, Modifier, Style};
+use ratatui::text::{Line, Span};
+use ratatui::widgets::{Block, Borders, Clear, Paragraph};
+
+use crate::app::App;
+use crate::system::filesystem::fullest;
+use crate::system::memory::format_bytes;
+use crate::ui::header::draw_htop_bar;
+
+/// Draw the filesystems viewer (d - df equivalent): each mounted
+/// filesystem with its type, size, used and available space, a usage bar
+/// and inode usage. Refreshed every tick while open.
+pub fn draw_filesystems_view(f: &mut Frame, app: &App) {
+    let area = centered_rect(90, 85, f.area());
+    f.render_widget(Clear, area);
+
+    let cs = &app.color_scheme;
+    let label = Style::default().fg(Color::Yellow).add_modifier(Modifier::BOLD);
+    let dim = Style::default().fg(Color::DarkGray);
+    let filesystems = &app.filesystems;
+
+    let mut status = vec![
+        Span::styled(" Filesystems: ", label),
+        Span::raw(filesystems.len().to_string()),
+    ];
+    if let Some(fs) = fullest(filesystems) {
+        status.extend([
+            Span::styled("   Fullest: ", label),
+            Span::raw(format!("{} ", fs.mount_point)),
+            Span::styled(format!("{:.0}%", fs.used_percent()), Style::default().fg(cs.fill_color(fs.used_percent()))),
+        ]);
+    }
+    let read_only = filesystems.iter().filter(|fs| fs.read_only).count();
+    if read_only > 0 {
+        status.extend([Span::styled("   Read-only: ", label), Span::raw(read_only.to_string())]);
+    }
+    let writable: Vec<_> = filesystems.iter().filter(|fs| fs.is_writable()).collect();
+    let summary = vec![
+        Span::styled(" Writable: ", label),
+        Span::raw(format!(
+            "{} used, {} available of {}",
+            format_bytes(writable.iter().map(|fs| fs.used).sum()),
+            format_bytes(writable.iter().map(|fs| fs.available).sum()),
+            format_bytes(writable.iter().map(|fs| fs.total).sum()),
+        )),
+    ];
+
+    // Mount points get what the fixed columns leave, the usage bar the rest
+    // (up to a point); devices only when there is room
+    let inner_width = area.width.saturating_sub(2) as usize;
+    let show_device = inner_width >= 110;
+    let device_width = if show_device { 17 } else { 0 };
+    let fixed = 2 + 1 + 8 + 1 + 7 + 1 + 7 + 1 + 7 + 1 + 6 + 1 + device_width;
+    let mount_width = inner_width.saturating_sub(fixed + 16).clamp(10, 32);
+    let bar_width = inner_width.saturating_sub(fixed + mount_width + 1).min(40);
+
+    let mut header = format!("  {:<w$} ", "MOUNTED ON", w = mount_width);
+    if show_device {
+        header.push_str(&format!("{:<16} ", "DEVICE"));
+    }
+    header.push_str(&format!("{:<8} {:>7} {:>7} {:>7} {:>6} USE%", "TYPE", "SIZE", "USED", "AVAIL", "INODE%"));
+    let mut lines = vec![
+        Line::from(status),
+        Line::from(summary),
+        Line::from(""),
+        Line::from(Span::styled(header, label)),
+    ];
+
+    // Rows left inside the border, above the hint line
+    let room = (area.height as usize).saturating_sub(lines.len() + 4);
+    let scroll = app.filesystems_scroll.min(filesystems.len().saturating_sub(room));
+    if filesystems.is_empty() {
+        lines.push(Line::from(Span::styled("  No mounted filesystems found", dim)));
+    }
+    let first_row = lines.len();
+    let shown: Vec<_> = filesystems.iter().skip(scroll).take(room).collect();
+    for fs in &shown {
+        let text = if fs.read_only { dim } else { Style::default() };
+        let mut spans = vec![Span::styled(
+            format!("  {:<w$} ", truncate_str(&fs.mount_point, mount_width), w = mount_width),
+            if fs.read_only { dim } else { Style::default().fg(Color::Cyan) },
+        )];
+        if show_device {
+            spans.push(Span::styled(format!("{:<16} ", truncate_str(&fs.device, 16)), dim));
+        }
+        spans.extend([
+            Span::styled(format!("{:<8} ", truncate_str(&fs.fs_type, 8)), text),
+            Span::styled(format!("{:>7} {:>7} {:>7} ", format_bytes(fs.total), format_bytes(fs.used), format_bytes(fs.available)), text),
+            match fs.inode_percent() {
+                Some(p) => Span::styled(format!("{:>5.0}% ", p), Style::default().fg(cs.fill_color(p))),
+                None => Span::styled(format!("{:>6} ", "-"), dim),
+            },
+        ]);
+        lines.push(Line::from(spans));
+    }
+
+    lines.push(Line::from(""));
+    let more = if filesystems.len() > room {
+        format!("  ({}-{} of {})", scroll + 1, (scroll + room).min(filesystems.len()), filesystems.len())
+    } else {
+        String::new()
+    };
+    lines.push(Line::from(Span::styled(format!(" ↑/↓ scroll  Esc close{} ", more), dim)));
+
+    let paragraph = Paragraph::new(lines)
+        .block(
+            Block::default()
+                .borders(Borders::ALL)
+                .title(" Filesystems (df) ")
+                .title_alignment(Alignment::Center)
+                .border_style(Style::default().fg(Color::Cyan)),
+        )
+        .style(Style::default().fg(Color::White).bg(Color::Black));
+
+    f.render_widget(paragraph, area);
+
+    // Usage bars over the end of each row, drawn like the header meters;
+    // read-only images are always full, so theirs is greyed out
+    if bar_width < 8 {
+        return;
+    }
+    for (i, fs) in shown.iter().enumerate() {
+        let percent = fs.used_percent();
+        let color = if fs.read_only { Color::DarkGray } else { cs.fill_color(percent) };
+        let row = Rect {
+            x: area.x + 1 + (fixed + mount_width) as u16,
+            y: area.y + 1 + (first_row + i) as u16,
+            width: bar_width as u16,
+            height: 1,
+        };
+        draw_htop_bar(f, "", &[(percent / 100.0, color)], &format!("{:.1}%", percent), cs.cpu_label, cs.cpu_bar_bg, row);
+    }
+}
+
+fn truncate_str(s: &str, max: usize) -> String {
+    if s.chars().count() > max {
+        let mut truncated: String = s.chars().take(max.saturating_sub(3)).collect();
+        truncated.push_str("...");
+        truncated
+    } else {
+        s.to_string()
+    }
+}
+
+fn centered_rect(percent_x: u16, percent_y: u16, area: Rect) -> Rect {
+    use ratatui::layout::{Direction, Layout};
+
+    let popup_layout = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([
+            Constraint::Percentage((100 - percent_y) / 2),
+            Constraint::Percentage(percent_y),
+            Constraint::Percentage((100 - percent_y) / 2),
+        ])
+        .split(area);
+
+    Layout::default()
+        .direction(Direction::Horizontal)
+        .constraints([
+            Constraint::Percentage((100 - percent_x) / 2),
+            Constraint::Percentage(percent_x),
+            Constraint::Percentage((100 - percent_x) / 2),
+        ])
+        .split(popup_layout[1])[1]
+}
@@ -1,5 +1,7 @@
 use ratatui::Frame;
 use ratatui::layout::Rect;
+use std::time::Duration;
+
 use ratatui::style::{Modifier, Style};
 use ratatui::text::{Line, Span};
 use ratatui::widgets::Paragraph;
@@ -47,6 +49,9 @@ const FKEYS_FILTER: &[(&str, &str)] = &[
     ("F10", "Quit "),
 ];
 
+/// How long a footer message (e.g. Ctrl-S "Saved …") replaces the F-keys
+const MESSAGE_DURATION: Duration = Duration::from_secs(4);
+
 /// Draw the bottom F-key bar (exact htop styling)
 /// htop packs F-key buttons left-aligned with no extra padding.
 /// Each button: Fn key in black-on-cyan, label in black-on-blue (default scheme).
@@ -59,6 +64,21 @@ pub fn draw_footer(f: &mut Frame, app: &App, area: Rect) {
         area,
     );
 
+    if let Some((message, shown_at)) = &app.footer_message {
+        // The event log overlay leaves the footer visible for its `w` confirmation
+        if matches!(app.mode, AppMode::Normal | AppMode::Events) && shown_at.elapsed() < MESSAGE_DURATION {
+            let line = Line::from(Span::styled(
+                format!(" {} ", message),
+                Style::default()
+                    .fg(cs.footer_key_fg)
+                    .bg(cs.footer_key_bg)
+                    .add_modifier(Modifier::BOLD),
+            ));
+            f.render_widget(Paragraph::new(line), area);
+            return;
+        }
+    }
+
     let fkeys = match app.mode {
         AppMode::Search => FKEYS_SEARCH,
         AppMode::Filter => FKEYS_FILTER,
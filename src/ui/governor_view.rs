@@ -0,0 +1,137 @@
+use ratatui::Frame;
+use ratatui::layout::{Alignment, Constraint, Rect};
+use ratatui::style::{Color, Modifier, Style};
+use ratatui::text::{Line, Span};
+use ratatui::widgets::{Block, Borders, Clear, Paragraph};
+
+use crate::app::App;
+use crate::governor::Outcome;
+
+/// Draw the governor overlay ('A' key): policies, then the action history
+/// newest first
+pub fn draw_governor_view(f: &mut Frame, app: &App) {
+    let area = centered_rect(90, 80, f.area());
+    f.render_widget(Clear, area);
+
+    let governor = &app.governor;
+    let label = Style::default().fg(Color::Yellow).add_modifier(Modifier::BOLD);
+    let dim = Style::default().fg(Color::DarkGray);
+
+    let mut lines = vec![Line::from(vec![
+        Span::styled(" Policies: ", label),
+        Span::raw(governor.policies.len().to_string()),
+        Span::styled("   Mode: ", label),
+        if !governor.enabled {
+            Span::styled("off (governor_enabled=1 in pstoprc turns it on)", dim)
+        } else if governor.dry_run {
+            Span::styled("dry run (nothing is changed)", Style::default().fg(Color::Magenta))
+        } else {
+            Span::styled("live", Style::default().fg(Color::Green))
+        },
+    ])];
+    for policy in &governor.policies {
+        let mut spans = vec![
+            Span::styled(format!("  {:<14} ", truncate_str(&policy.name, 14)), Style::default().fg(Color::Cyan)),
+        ];
+        match policy.error() {
+            Some(error) => spans.push(Span::styled(error.to_string(), Style::default().fg(Color::Red))),
+            None => {
+                let mut when = policy.matches.clone();
+                if policy.sustain_secs > 0 {
+                    when.push_str(&format!(" for {}s", policy.sustain_secs));
+                }
+                spans.push(Span::raw(format!("{} → {}", when, policy.action().map(|a| a.describe()).unwrap_or_default())));
+                if policy.dry_run {
+                    spans.push(Span::styled("  (dry run)", Style::default().fg(Color::Magenta)));
+                }
+            }
+        }
+        lines.push(Line::from(spans));
+    }
+    if governor.policies.is_empty() {
+        lines.push(Line::from(Span::styled("  No policies; add policy.<name>.match/action lines to pstoprc", dim)));
+    }
+
+    lines.push(Line::from(""));
+    lines.push(Line::from(Span::styled(
+        format!("  {:<8}  {:<14} {:>7}  {:<16} {:<22} {}", "TIME", "POLICY", "PID", "PROCESS", "ACTION", "RESULT"),
+        label,
+    )));
+
+    // Rows left for the history inside the border, above the hint line
+    let room = (area.height as usize).saturating_sub(lines.len() + 4);
+    let total = governor.history.len();
+    let scroll = app.governor_scroll.min(total.saturating_sub(room));
+    if total == 0 {
+        lines.push(Line::from(Span::styled("  No actions yet", dim)));
+    }
+    for record in governor.history.iter().rev().skip(scroll).take(room) {
+        let time = chrono::DateTime::from_timestamp_millis(record.at_ms)
+            .map(|t| t.with_timezone(&chrono::Local).format("%H:%M:%S").to_string())
+            .unwrap_or_default();
+        let outcome = match record.outcome {
+            Outcome::Done | Outcome::Sent => Style::default().fg(Color::Green),
+            Outcome::Failed => Style::default().fg(Color::Red).add_modifier(Modifier::BOLD),
+            Outcome::DryRun => Style::default().fg(Color::Magenta),
+        };
+        lines.push(Line::from(vec![
+            Span::styled(format!("  {:<8}  ", time), dim),
+            Span::styled(format!("{:<14} ", truncate_str(&record.policy, 14)), Style::default().fg(Color::Cyan)),
+            Span::raw(format!(
+                "{:>7}  {:<16} {:<22} ",
+                record.pid,
+                truncate_str(&record.name, 16),
+                truncate_str(&record.action, 22)
+            )),
+            Span::styled(record.outcome.label(), outcome),
+        ]));
+    }
+
+    lines.push(Line::from(""));
+    let more = if total > room { format!(" ({}-{} of {})", scroll + 1, (scroll + room).min(total), total) } else { String::new() };
+    lines.push(Line::from(Span::styled(format!(" ↑/↓ scroll  Press Esc, A, or q to close{} ", more), dim)));
+
+    let paragraph = Paragraph::new(lines)
+        .block(
+            Block::default()
+                .borders(Borders::ALL)
+                .title(" Governor ")
+                .title_alignment(Alignment::Center)
+                .border_style(Style::default().fg(Color::Cyan)),
+        )
+        .style(Style::default().fg(Color::White).bg(Color::Black));
+
+    f.render_widget(paragraph, area);
+}
+
+fn truncate_str(s: &str, max: usize) -> String {
+    if s.chars().count() > max {
+        let mut truncated: String = s.chars().take(max.saturating_sub(3)).collect();
+        truncated.push_str("...");
+        truncated
+    } else {
+        s.to_string()
+    }
+}
+
+fn centered_rect(percent_x: u16, percent_y: u16, area: Rect) -> Rect {
+    use ratatui::layout::{Direction, Layout};
+
+    let popup_layout = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([
+            Constraint::Percentage((100 - percent_y) / 2),
+            Constraint::Percentage(percent_y),
+            Constraint::Percentage((100 - percent_y) / 2),
+        ])
+        .split(area);
+
+    Layout::default()
+        .direction(Direction::Horizontal)
+        .constraints([
+            Constraint::Percentage((100 - percent_x) / 2),
+            Constraint::Percentage(percent_x),
+            Constraint::Percentage((100 - percent_x) / 2),
+        ])
+        .split(popup_layout[1])[1]
+}
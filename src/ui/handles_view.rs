@@ -2,83 +2,113 @@ use ratatui::Frame;
 use ratatui::layout::{Alignment, Constraint, Rect};
 use ratatui::style::{Color, Modifier, Style};
 use ratatui::text::{Line, Span};
-use ratatui::widgets::{Block, Borders, Clear, Paragraph, Wrap};
+use ratatui::widgets::{Block, Borders, Clear, Paragraph};
 
 use crate::app::App;
 
 /// Draw open files/handles viewer (htop 'l' key - lsof equivalent)
-/// Shows handles opened by the selected process
+/// Shows handles opened by the selected process, with counts per type,
+/// filterable, sortable and scrollable
 pub fn draw_handles_view(f: &mut Frame, app: &App) {
-    let proc = match app.selected_process() {
+    let proc = match app.processes.iter().find(|p| p.pid == app.handles_pid).or_else(|| app.selected_process()) {
         Some(p) => p,
         None => return,
     };
 
-    let area = centered_rect(80, 80, f.area());
+    let area = centered_rect(90, 85, f.area());
     f.render_widget(Clear, area);
 
-    // Get handle information for this process
-    let handle_info = crate::system::winapi::get_process_handles(proc.pid);
-    
+    let label = Style::default().fg(Color::Yellow).add_modifier(Modifier::BOLD);
+    let dim = Style::default().fg(Color::DarkGray);
+    // Handle snapshot taken when the viewer was opened
+    let handle_info = &app.handles;
+    let shown = app.shown_handles();
+
+    let mut status = vec![
+        Span::styled(format!(" {} ", proc.name), Style::default().fg(Color::Cyan).add_modifier(Modifier::BOLD)),
+        Span::styled(" PID ", label),
+        Span::raw(proc.pid.to_string()),
+        Span::styled("   Handles: ", label),
+        Span::raw(if shown.len() == handle_info.len() {
+            handle_info.len().to_string()
+        } else {
+            format!("{} of {}", shown.len(), handle_info.len())
+        }),
+        Span::styled("   Sort: ", label),
+        Span::raw(app.handles_sort.label()),
+        Span::styled("   Filter: ", label),
+        Span::raw(app.handles_filter.clone()),
+    ];
+    if app.handles_filter_editing {
+        status.push(Span::styled("_", Style::default().fg(Color::Cyan).add_modifier(Modifier::SLOW_BLINK)));
+    }
+
+    // Counts per type over all handles, most common first
+    let mut counts: Vec<(&str, usize)> = Vec::new();
+    for handle in handle_info {
+        match counts.iter_mut().find(|(t, _)| *t == handle.handle_type) {
+            Some((_, n)) => *n += 1,
+            None => counts.push((&handle.handle_type, 1)),
+        }
+    }
+    counts.sort_by(|a, b| b.1.cmp(&a.1).then(a.0.cmp(b.0)));
+    let mut summary = vec![Span::raw(" ")];
+    for (handle_type, n) in &counts {
+        summary.push(Span::styled(format!(" {} ", handle_type), Style::default().fg(Color::Cyan)));
+        summary.push(Span::raw(format!("{} ", n)));
+    }
+
+    let id_header = if cfg!(windows) { "HANDLE" } else { "FD" };
     let mut lines = vec![
+        Line::from(status),
+        Line::from(summary),
+        Line::from(""),
         Line::from(Span::styled(
-            format!(" Open Files/Handles - {} (PID: {}) ", proc.name, proc.pid),
-            Style::default().fg(Color::Cyan).add_modifier(Modifier::BOLD),
+            format!("  {:>8}  {:<12} {:<6} {:<14} {}", id_header, "TYPE", "ACCESS", "LOCK", "NAME"),
+            label,
         )),
-        Line::from(""),
     ];
 
+    // Rows left inside the border, above the hint line
+    let room = (area.height as usize).saturating_sub(lines.len() + 4);
+    let scroll = app.handles_scroll.min(shown.len().saturating_sub(room));
+    let name_width = (area.width as usize).saturating_sub(2 + 2 + 8 + 2 + 13 + 7 + 15);
     if handle_info.is_empty() {
         lines.push(Line::from(Span::styled(
             "  Unable to enumerate handles for this process",
             Style::default().fg(Color::Yellow),
         )));
-        lines.push(Line::from(""));
         lines.push(Line::from(Span::styled(
-            "  (Requires elevation for most processes)",
-            Style::default().fg(Color::DarkGray),
+            "  (Another user's process requires root or elevation)",
+            dim,
         )));
-    } else {
-        lines.push(Line::from(Span::styled(
-            format!("  Total Handles: {}", handle_info.len()),
-            Style::default().fg(Color::Green).add_modifier(Modifier::BOLD),
-        )));
-        lines.push(Line::from(""));
-        lines.push(Line::from(Span::styled(
-            "  TYPE       PATH",
-            Style::default().fg(Color::Yellow).add_modifier(Modifier::BOLD),
-        )));
-        lines.push(Line::from(Span::styled(
-            "  ─────────  ───────────────────────────────────────────",
-            Style::default().fg(Color::DarkGray),
-        )));
-
-        // Show first 100 handles (to avoid overwhelming the display)
-        for handle in handle_info.iter().take(100) {
-            let type_str = format!("{:<10}", truncate_str(&handle.handle_type, 10));
-            let path_str = truncate_str(&handle.name, 70);
-            
-            lines.push(Line::from(vec![
-                Span::styled("  ", Style::default()),
-                Span::styled(type_str, Style::default().fg(Color::Cyan)),
-                Span::raw(" "),
-                Span::styled(path_str, Style::default().fg(Color::White)),
-            ]));
-        }
-
-        if handle_info.len() > 100 {
-            lines.push(Line::from(""));
-            lines.push(Line::from(Span::styled(
-                format!("  ... and {} more handles", handle_info.len() - 100),
-                Style::default().fg(Color::DarkGray),
-            )));
-        }
+    } else if shown.is_empty() {
+        lines.push(Line::from(Span::styled("  No handles match", dim)));
+    }
+    for handle in shown.iter().skip(scroll).take(room) {
+        let id = match handle.id {
+            Some(id) if cfg!(windows) => format!("{:#x}", id),
+            Some(id) => id.to_string(),
+            None => "-".to_string(),
+        };
+        lines.push(Line::from(vec![
+            Span::styled(format!("  {:>8}  ", id), dim),
+            Span::styled(format!("{:<12} ", truncate_str(&handle.handle_type, 12)), Style::default().fg(Color::Cyan)),
+            Span::raw(format!("{:<6} ", handle.access)),
+            Span::styled(format!("{:<14} ", truncate_str(&handle.lock, 14)), Style::default().fg(Color::Red).add_modifier(Modifier::BOLD)),
+            Span::styled(truncate_str(&handle.name, name_width), Style::default().fg(Color::White)),
+        ]));
     }
 
     lines.push(Line::from(""));
+    let more = if shown.len() > room {
+        format!("  ({}-{} of {})", scroll + 1, (scroll + room).min(shown.len()), shown.len())
+    } else {
+        String::new()
+    };
     lines.push(Line::from(Span::styled(
-        " Press Esc or l to close ",
-        Style::default().fg(Color::DarkGray),
+        format!(" / filter  s sort  r re-read  ↑/↓ scroll  Esc close{} ", more),
+        dim,
     )));
 
     let paragraph = Paragraph::new(lines)
@@ -89,8 +119,7 @@ pub fn draw_handles_view(f: &mut Frame, app: &App) {
                 .title_alignment(Alignment::Center)
                 .border_style(Style::default().fg(Color::Cyan)),
         )
-        .style(Style::default().fg(Color::White).bg(Color::Black))
-        .wrap(Wrap { trim: false });
+        .style(Style::default().fg(Color::White).bg(Color::Black));
 
     f.render_widget(paragraph, area);
 }
@@ -4,7 +4,12 @@ use ratatui::style::{Color, Modifier, Style};
 use ratatui::text::{Line, Span};
 use ratatui::widgets::Paragraph;
 
+use std::collections::VecDeque;
+
 use crate::app::{App, ProcessTab};
+use crate::history::graph_rows;
+use crate::meters::{is_cpu_meter, Meter, MeterMode};
+use crate::system::filesystem::fullest;
 use crate::system::memory::format_bytes;
 
 /// Draw the complete header area in htop's exact layout:
@@ -26,6 +31,14 @@ use crate::system::memory::format_bytes;
 ///   GPU[||||||||       45.2%]
 ///   VMem[||||      2.1G used]
 pub fn draw_header(f: &mut Frame, app: &App, area: Rect, term_height: u16, term_width: u16) {
+    // Replay: recorded time on its own row above the meters
+    let area = if app.playback.is_some() {
+        draw_playback_line(f, app, Rect { height: 1, ..area });
+        Rect { y: area.y + 1, height: area.height.saturating_sub(1), ..area }
+    } else {
+        area
+    };
+
     // Compact mode: single aggregate CPU bar + memory bar
     if app.compact_mode {
         draw_compact_header(f, app, area);
@@ -51,17 +64,15 @@ pub fn draw_header(f: &mut Frame, app: &App, area: Rect, term_height: u16, term_
     };
 
     // Determine which panels have CPU bars based on configured meters
-    let left_has_cpus = app.left_meters.iter().any(|m| is_cpu_meter(m));
-    let right_has_cpus = app.right_meters.iter().any(|m| is_cpu_meter(m));
+    let left_has_cpus = app.left_meters.iter().any(|m| is_cpu_meter(&m.name));
+    let right_has_cpus = app.right_meters.iter().any(|m| is_cpu_meter(&m.name));
 
     // Collect non-CPU info meters for each panel
-    let left_info: Vec<&str> = app.left_meters.iter()
-        .filter(|m| !is_cpu_meter(m))
-        .map(|s| s.as_str())
+    let left_info: Vec<&Meter> = app.left_meters.iter()
+        .filter(|m| !is_cpu_meter(&m.name))
         .collect();
-    let right_info: Vec<&str> = app.right_meters.iter()
-        .filter(|m| !is_cpu_meter(m))
-        .map(|s| s.as_str())
+    let right_info: Vec<&Meter> = app.right_meters.iter()
+        .filter(|m| !is_cpu_meter(&m.name))
         .collect();
 
     // Use the actual terminal dimensions (same as header_height) to ensure
@@ -70,20 +81,22 @@ pub fn draw_header(f: &mut Frame, app: &App, area: Rect, term_height: u16, term_
     let auto_sub_cols = (auto_cpu_cols / 2).max(1);
 
     // Check if either panel uses an explicit CPU meter variant (AllCPUs2/4/8)
-    let left_cpu_meter = app.left_meters.iter().find(|m| is_cpu_meter(m));
-    let right_cpu_meter = app.right_meters.iter().find(|m| is_cpu_meter(m));
+    let left_cpu_meter = app.left_meters.iter().find(|m| is_cpu_meter(&m.name));
+    let right_cpu_meter = app.right_meters.iter().find(|m| is_cpu_meter(&m.name));
 
     // Per-panel sub-column counts: use explicit variant if set, otherwise auto
     let left_sub_cols = left_cpu_meter
-        .and_then(|m| cpu_meter_subcols(m))
+        .and_then(|m| cpu_meter_subcols(&m.name))
         .unwrap_or(auto_sub_cols);
     let right_sub_cols = right_cpu_meter
-        .and_then(|m| cpu_meter_subcols(m))
+        .and_then(|m| cpu_meter_subcols(&m.name))
         .unwrap_or(auto_sub_cols);
+    let left_cpu_mode = left_cpu_meter.map_or(MeterMode::Bar, |m| m.mode);
+    let right_cpu_mode = right_cpu_meter.map_or(MeterMode::Bar, |m| m.mode);
 
     let (left_cores_start, left_cores_count, right_cores_start, right_cores_count) =
         if left_has_cpus && right_has_cpus {
-            let half = (core_count + 1) / 2;
+            let half = core_count.div_ceil(2);
             (0, half, half, core_count - half)
         } else if left_has_cpus {
             (0, core_count, 0, 0)
@@ -94,19 +107,16 @@ pub fn draw_header(f: &mut Frame, app: &App, area: Rect, term_height: u16, term_
         };
 
     let left_cpu_rows = if left_cores_count > 0 {
-        (left_cores_count + left_sub_cols - 1) / left_sub_cols
+        left_cores_count.div_ceil(left_sub_cols)
     } else {
         0
     };
     let right_cpu_rows = if right_cores_count > 0 {
-        (right_cores_count + right_sub_cols - 1) / right_sub_cols
+        right_cores_count.div_ceil(right_sub_cols)
     } else {
         0
     };
 
-    let left_total = left_cpu_rows + left_info.len();
-    let right_total = right_cpu_rows + right_info.len();
-
     // Split into left and right panels with 1-char separator (htop style)
     // htop Header_draw: width = COLS - 2*pad - (numCols-1), each col = width * pct / 100
     // For 2-col 50/50: left = (w-1)/2, right = (w-1) - left (right gets the extra pixel)
@@ -127,55 +137,47 @@ pub fn draw_header(f: &mut Frame, app: &App, area: Rect, term_height: u16, term_
     };
 
     // --- LEFT PANEL ---
-    if left_total > 0 {
-        let panel = left_panel;
-        let row_constraints: Vec<Constraint> = (0..left_total)
-            .map(|_| Constraint::Length(1))
-            .collect();
-        let rows = Layout::default()
-            .direction(Direction::Vertical)
-            .constraints(row_constraints)
-            .split(panel);
-
-        // CPU bars
-        render_cpu_bars(f, app, left_cores_start, left_cores_count, left_sub_cols, &rows, left_cpu_rows);
-
-        // Info meters
-        for (i, meter_name) in left_info.iter().enumerate() {
-            let row_idx = left_cpu_rows + i;
-            if row_idx < rows.len() {
-                draw_meter(f, app, meter_name, rows[row_idx]);
-            }
-        }
-    }
+    draw_panel(f, app, left_panel, left_cpu_rows, &left_info, |f, rows| {
+        render_cpu_bars(f, app, left_cores_start, left_cores_count, left_sub_cols, rows, left_cpu_rows, left_cpu_mode);
+    });
 
     // --- RIGHT PANEL ---
-    if right_total > 0 {
-        let panel = right_panel;
-        let row_constraints: Vec<Constraint> = (0..right_total)
-            .map(|_| Constraint::Length(1))
-            .collect();
-        let rows = Layout::default()
-            .direction(Direction::Vertical)
-            .constraints(row_constraints)
-            .split(panel);
-
-        // CPU bars
-        render_cpu_bars(f, app, right_cores_start, right_cores_count, right_sub_cols, &rows, right_cpu_rows);
-
-        // Info meters
-        for (i, meter_name) in right_info.iter().enumerate() {
-            let row_idx = right_cpu_rows + i;
-            if row_idx < rows.len() {
-                draw_meter(f, app, meter_name, rows[row_idx]);
-            }
-        }
-    }
+    draw_panel(f, app, right_panel, right_cpu_rows, &right_info, |f, rows| {
+        render_cpu_bars(f, app, right_cores_start, right_cores_count, right_sub_cols, rows, right_cpu_rows, right_cpu_mode);
+    });
 }
 
-/// Check if a meter name represents CPU bars
-fn is_cpu_meter(name: &str) -> bool {
-    matches!(name, "AllCPUs" | "AllCPUs2" | "AllCPUs4" | "AllCPUs8") || name.starts_with("CPUs")
+/// Lay out one header column: CPU rows first, then each info meter at the
+/// height of its mode (Graph and LED meters take several rows)
+fn draw_panel(
+    f: &mut Frame,
+    app: &App,
+    panel: Rect,
+    cpu_rows: usize,
+    info: &[&Meter],
+    draw_cpus: impl FnOnce(&mut Frame, &[Rect]),
+) {
+    if cpu_rows + info.len() == 0 {
+        return;
+    }
+    let row_constraints: Vec<Constraint> = (0..cpu_rows)
+        .map(|_| Constraint::Length(1))
+        .chain(info.iter().map(|m| Constraint::Length(m.height() as u16)))
+        .collect();
+    let rows = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints(row_constraints)
+        .split(panel);
+
+    // CPU bars
+    draw_cpus(f, &rows[..cpu_rows.min(rows.len())]);
+
+    // Info meters
+    for (i, meter) in info.iter().enumerate() {
+        if let Some(&area) = rows.get(cpu_rows + i) {
+            draw_meter(f, app, meter, area);
+        }
+    }
 }
 
 /// Get the forced sub-column count for a specific CPU meter variant.
@@ -190,6 +192,7 @@ fn cpu_meter_subcols(name: &str) -> Option<usize> {
 }
 
 /// Render CPU bars for a range of cores into the given rows
+#[allow(clippy::too_many_arguments)]
 fn render_cpu_bars(
     f: &mut Frame,
     app: &App,
@@ -198,20 +201,19 @@ fn render_cpu_bars(
     sub_cols_per_panel: usize,
     rows: &[Rect],
     max_rows: usize,
+    mode: MeterMode,
 ) {
     if cores_count == 0 || max_rows == 0 {
         return;
     }
     let cores = &app.cpu_info.cores;
-    let cs = &app.color_scheme;
-    let cores_per_sub = (cores_count + sub_cols_per_panel - 1) / sub_cols_per_panel;
+    let cores_per_sub = cores_count.div_ceil(sub_cols_per_panel);
 
     if sub_cols_per_panel == 1 {
         for i in 0..cores_count.min(max_rows) {
             let core_idx = cores_start + i;
             if core_idx < cores.len() && i < rows.len() {
-                draw_cpu_bar(f, &cores[core_idx], rows[i], cs, app.cpu_count_from_zero,
-                    app.cpu_user_frac, app.cpu_kernel_frac, app.detailed_cpu_time);
+                draw_reading(f, app, &cpu_core_reading(app, core_idx), mode, rows[i]);
             }
         }
     } else {
@@ -228,9 +230,7 @@ fn render_cpu_bars(
             for sub_i in 0..sub_cols_per_panel {
                 let core_idx = cores_start + sub_i * cores_per_sub + row_i;
                 if core_idx < cores_start + cores_count && core_idx < cores.len() && sub_i < sub_cells.len() {
-                    draw_cpu_bar(f, &cores[core_idx], sub_cells[sub_i], cs,
-                        app.cpu_count_from_zero, app.cpu_user_frac, app.cpu_kernel_frac,
-                        app.detailed_cpu_time);
+                    draw_reading(f, app, &cpu_core_reading(app, core_idx), mode, sub_cells[sub_i]);
                 }
             }
         }
@@ -238,29 +238,38 @@ fn render_cpu_bars(
 }
 
 /// Dispatch a single meter by name to its rendering function
-fn draw_meter(f: &mut Frame, app: &App, name: &str, area: Rect) {
-    match name {
-        "Memory" => draw_memory_bar(f, app, area),
-        "Swap" => {
-            if app.active_tab == ProcessTab::Gpu {
-                draw_gpu_bar(f, app, area);
-            } else {
-                draw_swap_bar(f, app, area);
-            }
-        }
-        "Network" => {
-            if app.active_tab == ProcessTab::Gpu {
-                draw_vram_bar(f, app, area);
-            } else {
-                draw_network_bar(f, app, area);
-            }
+fn draw_meter(f: &mut Frame, app: &App, meter: &Meter, area: Rect) {
+    let gpu_tab = app.active_tab == ProcessTab::Gpu;
+    let reading = match meter.name.as_str() {
+        "Memory" => memory_reading(app),
+        // On the GPU tab Swap and Network give way to GPU and VMem
+        "Swap" if gpu_tab => Some(gpu_reading(app)),
+        "Swap" => Some(swap_reading(app)),
+        "Network" if gpu_tab => Some(vram_reading(app)),
+        "Network" => Some(network_reading(app)),
+        "DiskIO" => Some(disk_io_reading(app)),
+        "GPU" => Some(gpu_reading(app)),
+        "VMem" => Some(vram_reading(app)),
+        "Disk usage" => disk_usage_reading(app),
+        "CPU average" => Some(cpu_average_reading(app)),
+        _ => None,
+    };
+    if let Some(reading) = reading {
+        draw_reading(f, app, &reading, meter.mode, area);
+        return;
+    }
+
+    if meter.mode == MeterMode::Led && area.height >= 3 {
+        if let Some((label, value)) = info_text(app, &meter.name) {
+            let cs = &app.color_scheme;
+            draw_led_meter(f, label, &value, cs.info_label, cs.info_value, area);
+            return;
         }
-        "GPU" => draw_gpu_bar(f, app, area),
-        "VMem" => draw_vram_bar(f, app, area),
+    }
+    match meter.name.as_str() {
         "Tasks" => draw_tasks_line(f, app, area),
         "Load average" => draw_load_line(f, app, area),
         "Uptime" => draw_uptime_line(f, app, area),
-        "CPU average" => draw_cpu_average_bar(f, app, area),
         "Clock" => draw_clock_line(f, app, area),
         "Hostname" => draw_hostname_line(f, app, area),
         "Blank" => {} // empty row
@@ -268,62 +277,73 @@ fn draw_meter(f: &mut Frame, app: &App, name: &str, area: Rect) {
     }
 }
 
-/// Compact header for small screens/mobile: 1 aggregate CPU bar + 1 Mem bar
+/// Compact header for small screens/mobile: 1 aggregate CPU bar + 1 Mem bar,
+/// each in the mode of the matching configured meter (one row, so Graph is
+/// a single-line graph and LED falls back to Text)
 fn draw_compact_header(f: &mut Frame, app: &App, area: Rect) {
     let rows = Layout::default()
         .direction(Direction::Vertical)
         .constraints([Constraint::Length(1), Constraint::Length(1)])
         .split(area);
 
-    // Aggregate CPU bar using averaged per-core fractions
-    let cores = &app.cpu_info.cores;
-    let cs = &app.color_scheme;
-    let n = cores.len().max(1) as f64;
+    let all_meters = || app.left_meters.iter().chain(&app.right_meters);
+    let cpu_mode = all_meters()
+        .find(|m| m.name == "CPU average" || is_cpu_meter(&m.name))
+        .map_or(MeterMode::Bar, |m| m.mode);
+    let mem_mode = all_meters().find(|m| m.name == "Memory").map_or(MeterMode::Bar, |m| m.mode);
 
-    let avg_user = cores.iter().map(|c| c.user_frac as f64).sum::<f64>() / n;
-    let avg_kernel = cores.iter().map(|c| c.kernel_frac as f64).sum::<f64>() / n;
-    let avg_dpc = cores.iter().map(|c| c.dpc_frac as f64).sum::<f64>() / n;
-    let avg_interrupt = cores.iter().map(|c| c.interrupt_frac as f64).sum::<f64>() / n;
+    draw_reading(f, app, &cpu_average_reading(app), cpu_mode, rows[0]);
+    if let Some(mem) = memory_reading(app) {
+        draw_reading(f, app, &mem, mem_mode, rows[1]);
+    }
+}
 
-    let avg_usage = cores.iter().map(|c| c.usage_percent as f64).sum::<f64>() / n;
-    let text = format!("{:.1}%", avg_usage);
+// ── Meter readings ──────────────────────────────────────────────────────────
 
-    if app.detailed_cpu_time {
-        draw_htop_bar(
-            f,
-            "CPU",
-            &[
-                (avg_user, cs.cpu_bar_normal),
-                (avg_kernel, cs.cpu_bar_system),
-                (avg_interrupt, cs.cpu_bar_irq),
-                (avg_dpc, cs.cpu_bar_softirq),
-            ],
-            &text,
-            cs.cpu_label,
-            cs.cpu_bar_bg,
-            rows[0],
-        );
-    } else {
-        let sys = avg_kernel + avg_dpc + avg_interrupt;
-        draw_htop_bar(
+/// What a bar-style meter shows, whichever mode it is drawn in
+struct Reading<'a> {
+    caption: String,
+    segments: Vec<(f64, Color)>,
+    text: String,
+    label_color: Color,
+    shadow_color: Color,
+    /// Graph mode: recent values and the value a full-height column stands
+    /// for (0 = scale to the largest value on screen)
+    graph: Option<(&'a VecDeque<f64>, f64)>,
+}
+
+/// Draw a reading as a bar, text, graph or LED digits. Modes that need more
+/// rows than the area has fall back: LED to Text, and Graph shrinks to one row.
+fn draw_reading(f: &mut Frame, app: &App, reading: &Reading, mode: MeterMode, area: Rect) {
+    let cs = &app.color_scheme;
+    match mode {
+        MeterMode::Graph if reading.graph.is_some() => draw_graph_meter(f, reading, area),
+        MeterMode::Led if area.height >= 3 => {
+            draw_led_meter(f, &reading.caption, &reading.text, reading.label_color, cs.info_value, area)
+        }
+        MeterMode::Text | MeterMode::Led => {
+            let line = Line::from(vec![
+                Span::styled(format!("{}: ", reading.caption), Style::default().fg(cs.info_label).add_modifier(Modifier::BOLD)),
+                Span::styled(reading.text.clone(), Style::default().fg(cs.info_value).add_modifier(Modifier::BOLD)),
+            ]);
+            f.render_widget(Paragraph::new(line), area);
+        }
+        _ => draw_htop_bar(
             f,
-            "CPU",
-            &[(avg_user, cs.cpu_bar_normal), (sys, cs.cpu_bar_system)],
-            &text,
-            cs.cpu_label,
-            cs.cpu_bar_bg,
-            rows[0],
-        );
+            &reading.caption,
+            &reading.segments,
+            &reading.text,
+            reading.label_color,
+            reading.shadow_color,
+            Rect { height: 1, ..area },
+        ),
     }
-
-    // Memory bar (reuse existing logic inline for compactness)
-    draw_memory_bar(f, app, rows[1]);
 }
 
 /// Render an htop-style bar meter: `Cap[||||||||       text]`
 /// Text is right-aligned inside the brackets; bar fills from left, only in spaces.
 /// Characters in the filled area get the segment color; empty area gets shadow color.
-fn draw_htop_bar(
+pub(crate) fn draw_htop_bar(
     f: &mut Frame,
     caption: &str,
     segments: &[(f64, Color)],
@@ -405,7 +425,85 @@ fn draw_htop_bar(
     f.render_widget(Paragraph::new(Line::from(spans)), area);
 }
 
-/// Draw a single CPU core usage bar with htop's multi-color scheme.
+/// Graph mode: caption on the top row, then a block-character chart of the
+/// recent values filling the rest of the area, newest on the right
+fn draw_graph_meter(f: &mut Frame, reading: &Reading, area: Rect) {
+    let Some((values, full)) = reading.graph else { return };
+    let cap_w = reading.caption.chars().count() + 1;
+    let width = (area.width as usize).saturating_sub(cap_w);
+    let height = area.height as usize;
+    if width == 0 || height == 0 {
+        return;
+    }
+
+    let shown: Vec<f64> = values.iter().rev().take(width).rev().copied().collect();
+    let color = reading.segments.first().map_or(reading.label_color, |s| s.1);
+
+    let lines: Vec<Line> = graph_rows(&shown, width, height, full)
+        .into_iter()
+        .enumerate()
+        .map(|(row, chart)| {
+            let caption = if row == 0 { reading.caption.as_str() } else { "" };
+            Line::from(vec![
+                Span::styled(
+                    format!("{:<w$}", caption, w = cap_w),
+                    Style::default().fg(reading.label_color).add_modifier(Modifier::BOLD),
+                ),
+                Span::styled(chart, Style::default().fg(color)),
+            ])
+        })
+        .collect();
+    f.render_widget(Paragraph::new(lines), area);
+}
+
+/// Seven-segment-style digits for LED mode, three rows of four columns
+const LED_DIGITS: [[&str; 3]; 10] = [
+    ["┌──┐", "│  │", "└──┘"],
+    ["  ┐ ", "  │ ", "  ╵ "],
+    ["╶──┐", "┌──┘", "└──╴"],
+    ["╶──┐", " ──┤", "╶──┘"],
+    ["╷  ╷", "└──┤", "   ╵"],
+    ["┌──╴", "└──┐", "╶──┘"],
+    ["┌──╴", "├──┐", "└──┘"],
+    ["╶──┐", "   │", "   ╵"],
+    ["┌──┐", "├──┤", "└──┘"],
+    ["┌──┐", "└──┤", "╶──┘"],
+];
+
+/// LED mode (htop): caption on the middle row, digits of the value drawn
+/// three rows tall, other characters on the middle row
+fn draw_led_meter(f: &mut Frame, caption: &str, text: &str, label_color: Color, value_color: Color, area: Rect) {
+    let cap = format!("{} ", caption);
+    let cap_blank = " ".repeat(cap.chars().count());
+    let mut rows = [String::new(), String::new(), String::new()];
+    for c in text.chars() {
+        match c.to_digit(10) {
+            Some(d) => {
+                for (row, seg) in rows.iter_mut().zip(LED_DIGITS[d as usize]) {
+                    row.push_str(seg);
+                }
+            }
+            None => {
+                rows[0].push(' ');
+                rows[1].push(c);
+                rows[2].push(' ');
+            }
+        }
+    }
+    let label = Style::default().fg(label_color).add_modifier(Modifier::BOLD);
+    let value = Style::default().fg(value_color);
+    let lines: Vec<Line> = rows
+        .into_iter()
+        .enumerate()
+        .map(|(i, row)| {
+            let cap = if i == 1 { cap.clone() } else { cap_blank.clone() };
+            Line::from(vec![Span::styled(cap, label), Span::styled(row, value)])
+        })
+        .collect();
+    f.render_widget(Paragraph::new(lines), area);
+}
+
+/// One core's reading, with htop's multi-color scheme.
 ///
 /// Basic mode (htop default):
 ///   Green  = user processes
@@ -418,58 +516,51 @@ fn draw_htop_bar(
 ///   Magenta = DPC time (≈ htop softIRQ)
 ///
 /// Uses per-core time fractions from NtQuerySystemInformation for accurate display.
-fn draw_cpu_bar(f: &mut Frame, core: &crate::system::cpu::CpuCore, area: Rect, cs: &crate::color_scheme::ColorScheme, cpu_from_zero: bool, _user_frac: f64, _kernel_frac: f64, detailed: bool) {
-    let display_id = if cpu_from_zero { core.id } else { core.id + 1 };
-    let caption = format!("{:>3}", display_id);
-    let text = format!("{:.1}%", core.usage_percent);
+fn cpu_core_reading(app: &App, index: usize) -> Reading<'_> {
+    let cs = &app.color_scheme;
+    let core = &app.cpu_info.cores[index];
+    let display_id = if app.cpu_count_from_zero { core.id } else { core.id + 1 };
 
-    if detailed {
+    let segments = if app.detailed_cpu_time {
         // Detailed mode: show user + kernel + interrupt + DPC as separate segments
         // Per-core fracs are fractions of total time (including idle)
-        draw_htop_bar(
-            f,
-            &caption,
-            &[
-                (core.user_frac as f64, cs.cpu_bar_normal),       // green: user
-                (core.kernel_frac as f64, cs.cpu_bar_system),     // red: pure kernel
-                (core.interrupt_frac as f64, cs.cpu_bar_irq),     // yellow: interrupt (≈ irq)
-                (core.dpc_frac as f64, cs.cpu_bar_softirq),       // magenta: DPC (≈ softirq)
-            ],
-            &text,
-            cs.cpu_label,
-            cs.cpu_bar_bg,
-            area,
-        );
+        vec![
+            (core.user_frac as f64, cs.cpu_bar_normal),       // green: user
+            (core.kernel_frac as f64, cs.cpu_bar_system),     // red: pure kernel
+            (core.interrupt_frac as f64, cs.cpu_bar_irq),     // yellow: interrupt (≈ irq)
+            (core.dpc_frac as f64, cs.cpu_bar_softirq),       // magenta: DPC (≈ softirq)
+        ]
     } else {
         // Basic mode: user (green) + kernel including DPC+interrupt (red)
         // This matches htop basic mode where sys = kernel + irq + softirq
         let user = core.user_frac as f64;
         let sys = core.kernel_frac as f64 + core.dpc_frac as f64 + core.interrupt_frac as f64;
-        draw_htop_bar(
-            f,
-            &caption,
-            &[
-                (user, cs.cpu_bar_normal),  // green: user
-                (sys, cs.cpu_bar_system),   // red: all kernel-mode
-            ],
-            &text,
-            cs.cpu_label,
-            cs.cpu_bar_bg,
-            area,
-        );
+        vec![
+            (user, cs.cpu_bar_normal),  // green: user
+            (sys, cs.cpu_bar_system),   // red: all kernel-mode
+        ]
+    };
+
+    Reading {
+        caption: format!("{:>3}", display_id),
+        segments,
+        text: format!("{:.1}%", core.usage_percent),
+        label_color: cs.cpu_label,
+        shadow_color: cs.cpu_bar_bg,
+        graph: app.meter_history.cores.get(index).map(|h| (h, 1.0)),
     }
 }
 
-/// Draw the memory usage bar with htop's multi-color scheme:
+/// The memory reading with htop's multi-color scheme:
 ///   Green  = used memory pages
 ///   Blue   = buffer pages
 ///   Yellow = cache pages
-fn draw_memory_bar(f: &mut Frame, app: &App, area: Rect) {
+fn memory_reading(app: &App) -> Option<Reading<'_>> {
     let mem = &app.memory_info;
     let cs = &app.color_scheme;
     let total = mem.total_mem as f64;
     if total == 0.0 {
-        return;
+        return None;
     }
 
     let used_frac = mem.used_mem as f64 / total;
@@ -478,21 +569,19 @@ fn draw_memory_bar(f: &mut Frame, app: &App, area: Rect) {
 
     let used_str = format_bytes(mem.used_mem);
     let total_str = format_bytes(mem.total_mem);
-    let text = format!("{}/{}", used_str, total_str);
 
-    draw_htop_bar(
-        f,
-        "Mem",
-        &[(used_frac, cs.mem_bar_used), (buffer_frac, cs.mem_bar_buffers), (cache_frac, cs.mem_bar_cache)],
-        &text,
-        cs.cpu_label,
-        cs.cpu_bar_bg,
-        area,
-    );
+    Some(Reading {
+        caption: "Mem".to_string(),
+        segments: vec![(used_frac, cs.mem_bar_used), (buffer_frac, cs.mem_bar_buffers), (cache_frac, cs.mem_bar_cache)],
+        text: format!("{}/{}", used_str, total_str),
+        label_color: cs.cpu_label,
+        shadow_color: cs.cpu_bar_bg,
+        graph: Some((&app.meter_history.memory, 1.0)),
+    })
 }
 
-/// Draw the swap usage bar (green only, like htop)
-fn draw_swap_bar(f: &mut Frame, app: &App, area: Rect) {
+/// The swap reading (green only, like htop)
+fn swap_reading(app: &App) -> Reading<'_> {
     let mem = &app.memory_info;
     let cs = &app.color_scheme;
     let total = mem.total_swap as f64;
@@ -500,46 +589,69 @@ fn draw_swap_bar(f: &mut Frame, app: &App, area: Rect) {
 
     let used_str = format_bytes(mem.used_swap);
     let total_str = format_bytes(mem.total_swap);
-    let text = format!("{}/{}", used_str, total_str);
 
-    draw_htop_bar(
-        f,
-        "Swp",
-        &[(usage_frac, cs.swap_bar)],
-        &text,
-        cs.cpu_label,
-        cs.cpu_bar_bg,
-        area,
-    );
+    Reading {
+        caption: "Swp".to_string(),
+        segments: vec![(usage_frac, cs.swap_bar)],
+        text: format!("{}/{}", used_str, total_str),
+        label_color: cs.cpu_label,
+        shadow_color: cs.cpu_bar_bg,
+        graph: Some((&app.meter_history.swap, 1.0)),
+    }
 }
 
-/// Draw network throughput bar: "Net[||||rx|||tx|   ↓1.2M/s ↑340K/s]"
-fn draw_network_bar(f: &mut Frame, app: &App, area: Rect) {
+/// Network throughput: "Net[||||rx|||tx|   ↓1.2M/s ↑340K/s]". The graph is
+/// rx+tx, scaled to the busiest tick on screen.
+fn network_reading(app: &App) -> Reading<'_> {
     let net = &app.network_info;
     let cs = &app.color_scheme;
 
     let rx_str = format_rate(net.rx_bytes_per_sec);
     let tx_str = format_rate(net.tx_bytes_per_sec);
-    let text = format!("↓{} ↑{}", rx_str, tx_str);
 
     // Scale bar based on 1 Gbps as visual max
     let max_rate = 125_000_000.0_f64;
     let rx_frac = if net.rx_bytes_per_sec > 0.0 { (net.rx_bytes_per_sec / max_rate).min(1.0) } else { 0.0 };
     let tx_frac = if net.tx_bytes_per_sec > 0.0 { (net.tx_bytes_per_sec / max_rate).min(1.0) } else { 0.0 };
 
-    draw_htop_bar(
-        f,
-        "Net",
-        &[(rx_frac, cs.cpu_bar_normal), (tx_frac, Color::Magenta)],
-        &text,
-        cs.cpu_label,
-        cs.cpu_bar_bg,
-        area,
-    );
+    Reading {
+        caption: "Net".to_string(),
+        segments: vec![(rx_frac, cs.cpu_bar_normal), (tx_frac, Color::Magenta)],
+        text: format!("↓{} ↑{}", rx_str, tx_str),
+        label_color: cs.cpu_label,
+        shadow_color: cs.cpu_bar_bg,
+        graph: Some((&app.meter_history.network, 0.0)),
+    }
+}
+
+/// Disk I/O: "Dsk[|||r||w|   R 1.2 M/s W 340 K/s 12%]". The bar fills to
+/// the busiest device's utilisation, split between reads and writes by
+/// bytes moved; the graph is that utilisation.
+fn disk_io_reading(app: &App) -> Reading<'_> {
+    let disk = &app.disk_io_info;
+    let cs = &app.color_scheme;
+
+    let busy = (disk.busy_percent / 100.0).clamp(0.0, 1.0);
+    let total = disk.read_bytes_per_sec + disk.write_bytes_per_sec;
+    let read_share = if total > 0.0 { disk.read_bytes_per_sec / total } else { 1.0 };
+
+    Reading {
+        caption: "Dsk".to_string(),
+        segments: vec![(busy * read_share, cs.cpu_bar_normal), (busy * (1.0 - read_share), Color::Magenta)],
+        text: format!(
+            "R {} W {} {:.0}%",
+            format_rate(disk.read_bytes_per_sec),
+            format_rate(disk.write_bytes_per_sec),
+            disk.busy_percent
+        ),
+        label_color: cs.cpu_label,
+        shadow_color: cs.cpu_bar_bg,
+        graph: Some((&app.meter_history.disk, 1.0)),
+    }
 }
 
 /// Format bytes/sec as human-readable rate
-fn format_rate(bytes_per_sec: f64) -> String {
+pub fn format_rate(bytes_per_sec: f64) -> String {
     if bytes_per_sec >= 1_073_741_824.0 {
         format!("{:.1} G/s", bytes_per_sec / 1_073_741_824.0)
     } else if bytes_per_sec >= 1_048_576.0 {
@@ -592,7 +704,7 @@ fn draw_uptime_line(f: &mut Frame, app: &App, area: Rect) {
 }
 
 /// Format uptime as DD days, HH:MM:SS (matching htop UptimeMeter.c exactly)
-fn format_uptime(seconds: u64) -> String {
+pub fn format_uptime(seconds: u64) -> String {
     let days = seconds / 86400;
     let hours = (seconds % 86400) / 3600;
     let minutes = (seconds % 3600) / 60;
@@ -609,13 +721,11 @@ fn format_uptime(seconds: u64) -> String {
     }
 }
 
-/// Draw GPU utilization bar: "GPU[||||||||       45.2%]"
-fn draw_gpu_bar(f: &mut Frame, app: &App, area: Rect) {
+/// GPU utilization: "GPU[||||||||       45.2%]"
+fn gpu_reading(app: &App) -> Reading<'_> {
     let cs = &app.color_scheme;
     let usage = app.gpu_overall_usage;
-    let usage_frac = (usage / 100.0).clamp(0.0, 1.0) as f64;
-
-    let text = format!("{:.1}%", usage);
+    let usage_frac = (usage / 100.0).clamp(0.0, 1.0);
 
     // Color the bar: green < 50%, yellow 50-80%, red > 80%
     let bar_color = if usage > 80.0 {
@@ -626,24 +736,39 @@ fn draw_gpu_bar(f: &mut Frame, app: &App, area: Rect) {
         cs.cpu_bar_normal
     };
 
-    draw_htop_bar(
-        f,
-        "GPU",
-        &[(usage_frac, bar_color)],
-        &text,
-        Color::LightCyan,
-        cs.cpu_bar_bg,
-        area,
-    );
+    Reading {
+        caption: "GPU".to_string(),
+        segments: vec![(usage_frac, bar_color)],
+        text: format!("{:.1}%", usage),
+        label_color: Color::LightCyan,
+        shadow_color: cs.cpu_bar_bg,
+        graph: Some((&app.meter_history.gpu, 1.0)),
+    }
 }
 
-/// Draw GPU VRAM bar: "VMem[||||      2.1G used]"
-fn draw_vram_bar(f: &mut Frame, app: &App, area: Rect) {
+/// The fullest writable filesystem: "Disk[||||||   /home 412G/500G]",
+/// coloured by how full it is (no graph)
+fn disk_usage_reading(app: &App) -> Option<Reading<'_>> {
+    let cs = &app.color_scheme;
+    let fs = fullest(&app.filesystems)?;
+    let percent = fs.used_percent();
+
+    Some(Reading {
+        caption: "Disk".to_string(),
+        segments: vec![(percent / 100.0, cs.fill_color(percent))],
+        text: format!("{} {}/{}", fs.mount_point, format_bytes(fs.used), format_bytes(fs.used + fs.available)),
+        label_color: cs.cpu_label,
+        shadow_color: cs.cpu_bar_bg,
+        graph: None,
+    })
+}
+
+/// GPU VRAM: "VMem[||||      2.1G used]" (no graph)
+fn vram_reading(app: &App) -> Reading<'_> {
     let cs = &app.color_scheme;
     let dedicated = app.gpu_dedicated_mem;
 
     let used_str = format_bytes(dedicated);
-    let text = format!("{} used", used_str);
 
     // Scale against a reasonable GPU VRAM max — auto-detect would be ideal,
     // but for now use 24 GB as a reasonable modern GPU ceiling.
@@ -662,19 +787,18 @@ fn draw_vram_bar(f: &mut Frame, app: &App, area: Rect) {
         Color::LightCyan
     };
 
-    draw_htop_bar(
-        f,
-        "VMem",
-        &[(usage_frac, bar_color)],
-        &text,
-        Color::LightCyan,
-        cs.cpu_bar_bg,
-        area,
-    );
+    Reading {
+        caption: "VMem".to_string(),
+        segments: vec![(usage_frac, bar_color)],
+        text: format!("{} used", used_str),
+        label_color: Color::LightCyan,
+        shadow_color: cs.cpu_bar_bg,
+        graph: None,
+    }
 }
 
-/// Draw a single aggregate CPU average bar
-fn draw_cpu_average_bar(f: &mut Frame, app: &App, area: Rect) {
+/// The aggregate CPU average (also the compact header's CPU row)
+fn cpu_average_reading(app: &App) -> Reading<'_> {
     let cores = &app.cpu_info.cores;
     let cs = &app.color_scheme;
     let n = cores.len().max(1) as f64;
@@ -685,42 +809,106 @@ fn draw_cpu_average_bar(f: &mut Frame, app: &App, area: Rect) {
     let avg_interrupt = cores.iter().map(|c| c.interrupt_frac as f64).sum::<f64>() / n;
 
     let avg_usage = cores.iter().map(|c| c.usage_percent as f64).sum::<f64>() / n;
-    let text = format!("{:.1}%", avg_usage);
 
-    if app.detailed_cpu_time {
-        draw_htop_bar(
-            f,
-            "CPU",
-            &[
-                (avg_user, cs.cpu_bar_normal),
-                (avg_kernel, cs.cpu_bar_system),
-                (avg_interrupt, cs.cpu_bar_irq),
-                (avg_dpc, cs.cpu_bar_softirq),
-            ],
-            &text,
-            cs.cpu_label,
-            cs.cpu_bar_bg,
-            area,
-        );
+    let segments = if app.detailed_cpu_time {
+        vec![
+            (avg_user, cs.cpu_bar_normal),
+            (avg_kernel, cs.cpu_bar_system),
+            (avg_interrupt, cs.cpu_bar_irq),
+            (avg_dpc, cs.cpu_bar_softirq),
+        ]
     } else {
         let sys = avg_kernel + avg_dpc + avg_interrupt;
-        draw_htop_bar(
-            f,
-            "CPU",
-            &[(avg_user, cs.cpu_bar_normal), (sys, cs.cpu_bar_system)],
-            &text,
-            cs.cpu_label,
-            cs.cpu_bar_bg,
-            area,
-        );
+        vec![(avg_user, cs.cpu_bar_normal), (sys, cs.cpu_bar_system)]
+    };
+
+    Reading {
+        caption: "CPU".to_string(),
+        segments,
+        text: format!("{:.1}%", avg_usage),
+        label_color: cs.cpu_label,
+        shadow_color: cs.cpu_bar_bg,
+        graph: Some((&app.meter_history.cpu, 1.0)),
+    }
+}
+
+/// Label and value of the text meters, for LED mode
+fn info_text(app: &App, name: &str) -> Option<(&'static str, String)> {
+    match name {
+        "Tasks" => Some(("Tasks", format!("{}, {} thr; {} running", app.total_tasks, app.total_threads, app.running_tasks))),
+        "Load average" => Some(("Load", format!("{:.2} {:.2} {:.2}", app.load_avg_1, app.load_avg_5, app.load_avg_15))),
+        "Uptime" => Some(("Uptime", format_uptime(app.uptime_seconds))),
+        "Clock" => Some(("Clock", clock_time(app).format("%H:%M:%S").to_string())),
+        _ => None,
+    }
+}
+
+/// Draw: " Replay: 2026-03-14 02:17:45  [1234/3600]  ▶ 4x",
+/// or during live rewind: " Rewind: -00:42  at 02:17:45  [/] step  Esc live"
+fn draw_playback_line(f: &mut Frame, app: &App, area: Rect) {
+    let Some(status) = &app.playback else { return };
+    let cs = &app.color_scheme;
+    if let Some(offset_ms) = status.live_offset_ms {
+        let time_str = recorded_time(status.timestamp_ms)
+            .map(|t| t.format("%H:%M:%S").to_string())
+            .unwrap_or_else(|| "--".to_string());
+        let line = Line::from(vec![
+            Span::styled(" Rewind: ", Style::default().fg(cs.info_label).add_modifier(Modifier::BOLD)),
+            Span::styled(rewind_offset(offset_ms), Style::default().fg(cs.info_value).add_modifier(Modifier::BOLD)),
+            Span::styled(format!("  at {}", time_str), Style::default().fg(cs.info_label)),
+            Span::styled("  [/] step  Esc live", Style::default().fg(cs.info_label)),
+        ]);
+        f.render_widget(Paragraph::new(line), area);
+        return;
+    }
+    let time_str = recorded_time(status.timestamp_ms)
+        .map(|t| t.format("%Y-%m-%d %H:%M:%S").to_string())
+        .unwrap_or_else(|| "--".to_string());
+    let state = if app.paused {
+        "‖ paused".to_string()
+    } else {
+        format!("▶ {}x", status.speed)
+    };
+    let line = Line::from(vec![
+        Span::styled(" Replay: ", Style::default().fg(cs.info_label).add_modifier(Modifier::BOLD)),
+        Span::styled(time_str, Style::default().fg(cs.info_value).add_modifier(Modifier::BOLD)),
+        Span::styled(
+            format!("  [{}/{}]  ", status.position + 1, status.total),
+            Style::default().fg(cs.info_label),
+        ),
+        Span::styled(state, Style::default().fg(cs.info_value).add_modifier(Modifier::BOLD)),
+    ]);
+    f.render_widget(Paragraph::new(line), area);
+}
+
+/// Distance behind live as "-MM:SS" (or "-H:MM:SS" past an hour)
+fn rewind_offset(offset_ms: i64) -> String {
+    let secs = (offset_ms.max(0) + 500) / 1000;
+    if secs >= 3600 {
+        format!("-{}:{:02}:{:02}", secs / 3600, secs / 60 % 60, secs % 60)
+    } else {
+        format!("-{:02}:{:02}", secs / 60, secs % 60)
+    }
+}
+
+/// Local time of a recorded tick
+fn recorded_time(timestamp_ms: i64) -> Option<chrono::DateTime<chrono::Local>> {
+    use chrono::TimeZone;
+    chrono::Local.timestamp_millis_opt(timestamp_ms).single()
+}
+
+/// Now, or the recorded time during replay
+fn clock_time(app: &App) -> chrono::DateTime<chrono::Local> {
+    match &app.playback {
+        Some(status) => recorded_time(status.timestamp_ms).unwrap_or_else(chrono::Local::now),
+        None => chrono::Local::now(),
     }
 }
 
-/// Draw clock: "Clock: HH:MM:SS"
+/// Draw clock: "Clock: HH:MM:SS" (the recorded time during replay)
 fn draw_clock_line(f: &mut Frame, app: &App, area: Rect) {
     let cs = &app.color_scheme;
-    let now = chrono::Local::now();
-    let time_str = now.format("%H:%M:%S").to_string();
+    let time_str = clock_time(app).format("%H:%M:%S").to_string();
     let line = Line::from(vec![
         Span::styled("Clock: ", Style::default().fg(cs.info_label).add_modifier(Modifier::BOLD)),
         Span::styled(time_str, Style::default().fg(cs.info_value).add_modifier(Modifier::BOLD)),
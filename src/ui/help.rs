@@ -19,13 +19,14 @@ pub fn draw_help(f: &mut Frame) {
         Line::from("  ↑/↓/Alt-k/j Move selection up/down"),
         Line::from("  PgUp/PgDn   Page up/down"),
         Line::from("  Home/End    Jump to first/last process"),
-        Line::from("  Tab         Switch between Main/I/O tabs"),
+        Line::from("  Tab         Switch Main/I/O/Net/GPU/Ports tabs"),
         Line::from(""),
         Line::from(Span::styled(" Function Keys ", Style::default().add_modifier(Modifier::BOLD).fg(Color::Yellow))),
         Line::from("  F1/h/?      Show this help"),
         Line::from("  F2/S        Setup - configure columns"),
         Line::from("  F3//        Search (jump to match)"),
         Line::from("  F4/\\        Filter (hide non-matching)"),
+        Line::from("              text, or e.g. cpu > 20 && name ~ chrome"),
         Line::from("  F5/t        Toggle tree view"),
         Line::from("  F6          Open sort menu"),
         Line::from("  F7          Nice - (raise priority)"),
@@ -43,9 +44,17 @@ pub fn draw_help(f: &mut Frame) {
         Line::from(""),
         Line::from(Span::styled(" Actions ", Style::default().add_modifier(Modifier::BOLD).fg(Color::Yellow))),
         Line::from("  u           Filter by user"),
+        Line::from("  v           Saved views (save/recall)"),
         Line::from("  a           Set CPU affinity"),
-        Line::from("  e           Show process details"),
+        Line::from("  e           Show process environment, cwd and executable"),
+        Line::from("  D           Process dashboard (charts since opened)"),
+        Line::from("  A           Governor policies and action history"),
+        Line::from("  E           Process start/exit log"),
         Line::from("  l           List open files/handles (lsof)"),
+        Line::from("  m           Memory map of process (pmap)"),
+        Line::from("  O           Disk I/O per device (iostat)"),
+        Line::from("  d           Filesystem space and inodes (df)"),
+        Line::from("  Enter       Net tab: connections of process"),
         Line::from("  F           Follow selected process"),
         Line::from("  Space       Tag/untag process"),
         Line::from("  c           Tag process + all children"),
@@ -54,6 +63,7 @@ pub fn draw_help(f: &mut Frame) {
         Line::from("  K           Hide kernel/system threads"),
         Line::from("  Z/z         Pause/freeze display"),
         Line::from("  Ctrl+L      Force refresh (unpause)"),
+        Line::from("  Ctrl+S      Save current view to a file"),
         Line::from("  p           Toggle full command path"),
         Line::from("  +/=         Expand tree node"),
         Line::from("  -           Collapse tree node"),
@@ -61,6 +71,16 @@ pub fn draw_help(f: &mut Frame) {
         Line::from("  0-9         Quick PID search"),
         Line::from("  Ctrl+C      Quit"),
         Line::from(""),
+        Line::from(Span::styled(" Replay (pstop --replay <file>) ", Style::default().add_modifier(Modifier::BOLD).fg(Color::Yellow))),
+        Line::from("  ←/→         Step one tick back/forward"),
+        Line::from("  Shift+←/→   Seek one minute back/forward"),
+        Line::from("  s/f         Slower/faster (1x-64x)"),
+        Line::from("  Z/z         Pause/resume playback"),
+        Line::from(""),
+        Line::from(Span::styled(" Live Rewind ", Style::default().add_modifier(Modifier::BOLD).fg(Color::Yellow))),
+        Line::from("  [ / ]       Step back/forward through recent ticks"),
+        Line::from("  Esc         Back to live"),
+        Line::from(""),
         Line::from(Span::styled(" Vim Keys (F2 > Display > enable) ", Style::default().add_modifier(Modifier::BOLD).fg(Color::Yellow))),
         Line::from("  j/k         Move down/up"),
         Line::from("  g/G         Jump to first/last process"),
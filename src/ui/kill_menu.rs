@@ -44,7 +44,7 @@ pub fn draw_kill_menu(f: &mut Frame, app: &App) {
     )));
 
     // Show which process will be targeted
-    if let Some(proc) = app.selected_process() {
+    if let Some(proc) = app.kill_target() {
         lines.push(Line::from(""));
         lines.push(Line::from(Span::styled(
             format!(" Target: PID {} ({})", proc.pid, proc.name),
@@ -0,0 +1,177 @@
+use ratatui::Frame;
+use ratatui::layout::{Alignment, Constraint, Rect};
+use ratatui::style::{Color, Modifier, Style};
+use ratatui::text::{Line, Span};
+use ratatui::widgets::{Block, Borders, Clear, Paragraph};
+
+use crate::app::App;
+use crate::system::backend::{mapping_totals, QueryError, RegionKind};
+use crate::system::memory::format_bytes;
+
+/// Draw the memory map viewer ('m' key - pmap equivalent): totals per
+/// region type, then every region in address order, or the regions summed
+/// per backing file, most resident first
+pub fn draw_memory_map_view(f: &mut Frame, app: &App) {
+    let proc = match app.processes.iter().find(|p| p.pid == app.memory_map_pid).or_else(|| app.selected_process()) {
+        Some(p) => p,
+        None => return,
+    };
+
+    let area = centered_rect(90, 85, f.area());
+    f.render_widget(Clear, area);
+
+    let label = Style::default().fg(Color::Yellow).add_modifier(Modifier::BOLD);
+    let dim = Style::default().fg(Color::DarkGray);
+    let empty = Vec::new();
+    let regions = match &app.memory_map {
+        Some(Ok(regions)) => regions,
+        _ => &empty,
+    };
+
+    let size: u64 = regions.iter().map(|r| r.size()).sum();
+    let rss: u64 = regions.iter().map(|r| r.rss).sum();
+    let mut lines = vec![Line::from(vec![
+        Span::styled(format!(" {} ", proc.name), Style::default().fg(Color::Cyan).add_modifier(Modifier::BOLD)),
+        Span::styled(" PID ", label),
+        Span::raw(proc.pid.to_string()),
+        Span::styled("   Regions: ", label),
+        Span::raw(regions.len().to_string()),
+        Span::styled("   Size: ", label),
+        Span::raw(format_bytes(size)),
+        Span::styled("   Resident: ", label),
+        Span::raw(format_bytes(rss)),
+        Span::styled("   Show: ", label),
+        Span::raw(if app.memory_map_by_file { "files" } else { "regions" }),
+    ])];
+
+    // Size and resident memory per region type
+    let mut kinds = vec![Span::styled(" Size/resident by type:", label)];
+    for kind in [RegionKind::Image, RegionKind::Mapped, RegionKind::Private, RegionKind::Heap, RegionKind::Stack] {
+        let of_kind = regions.iter().filter(|r| r.kind == kind);
+        let (size, rss) = of_kind.fold((0, 0), |(s, r), region| (s + region.size(), r + region.rss));
+        if size > 0 {
+            kinds.push(Span::styled(format!(" {} ", kind.label()), kind_style(kind)));
+            kinds.push(Span::raw(format!("{}/{} ", format_bytes(size), format_bytes(rss))));
+        }
+    }
+    lines.push(Line::from(kinds));
+    lines.push(Line::from(""));
+
+    // Addresses as wide as the highest one needs
+    let digits = regions.iter().map(|r| format!("{:x}", r.end).len()).max().unwrap_or(8).max(8);
+    let inner_width = area.width.saturating_sub(2) as usize;
+    if app.memory_map_by_file {
+        lines.push(Line::from(Span::styled(
+            format!("  {:>7} {:>8} {:>8}  {}", "REGIONS", "SIZE", "RSS", "FILE"),
+            label,
+        )));
+    } else {
+        lines.push(Line::from(Span::styled(
+            format!("  {:<w$} {:>8} {:>8} {:<4} {:<7}  {}", "ADDRESS", "SIZE", "RSS", "PERM", "TYPE", "FILE", w = digits * 2 + 1),
+            label,
+        )));
+    }
+
+    // Rows left inside the border, above the hint line
+    let room = (area.height as usize).saturating_sub(lines.len() + 4);
+    let rows = app.memory_map_rows();
+    let scroll = app.memory_map_scroll.min(rows.saturating_sub(room));
+    match &app.memory_map {
+        None => lines.push(Line::from(Span::styled("  Not read: the memory map is only read from live processes", dim))),
+        Some(Err(e)) => {
+            let mut spans = vec![Span::styled(format!("  {}", e), Style::default().fg(Color::Red))];
+            if *e == QueryError::AccessDenied {
+                let who = if cfg!(windows) { "Administrator" } else { "root" };
+                spans.push(Span::styled(format!(" (run pstop as {} or as {} to read it)", proc.user, who), dim));
+            }
+            lines.push(Line::from(spans));
+        }
+        Some(Ok(_)) if regions.is_empty() => lines.push(Line::from(Span::styled("  No memory regions", dim))),
+        Some(Ok(_)) if app.memory_map_by_file => {
+            let name_width = inner_width.saturating_sub(2 + 7 + 1 + 8 + 1 + 8 + 2);
+            for total in mapping_totals(regions).iter().skip(scroll).take(room) {
+                lines.push(Line::from(vec![
+                    Span::raw(format!("  {:>7} {:>8} ", total.regions, format_bytes(total.size))),
+                    Span::styled(format!("{:>8}  ", format_bytes(total.rss)), Style::default().add_modifier(Modifier::BOLD)),
+                    Span::styled(truncate_start(&total.name, name_width), Style::default().fg(Color::White)),
+                ]));
+            }
+        }
+        Some(Ok(_)) => {
+            let path_width = inner_width.saturating_sub(2 + digits * 2 + 1 + 1 + 8 + 1 + 8 + 1 + 4 + 1 + 7 + 2);
+            for region in regions.iter().skip(scroll).take(room) {
+                lines.push(Line::from(vec![
+                    Span::styled(format!("  {:0w$x}-{:0w$x} ", region.start, region.end, w = digits), dim),
+                    Span::raw(format!("{:>8} ", format_bytes(region.size()))),
+                    Span::styled(format!("{:>8} ", format_bytes(region.rss)), Style::default().add_modifier(Modifier::BOLD)),
+                    Span::raw(format!("{:<4} ", region.protection)),
+                    Span::styled(format!("{:<7}  ", region.kind.label()), kind_style(region.kind)),
+                    Span::styled(truncate_start(&region.path, path_width), Style::default().fg(Color::White)),
+                ]));
+            }
+        }
+    }
+
+    lines.push(Line::from(""));
+    let more = if rows > room { format!("  ({}-{} of {})", scroll + 1, (scroll + room).min(rows), rows) } else { String::new() };
+    lines.push(Line::from(Span::styled(
+        format!(" Tab regions/files  r re-read  ↑/↓ scroll  Esc close{} ", more),
+        dim,
+    )));
+
+    let paragraph = Paragraph::new(lines)
+        .block(
+            Block::default()
+                .borders(Borders::ALL)
+                .title(" Memory Map (pmap) ")
+                .title_alignment(Alignment::Center)
+                .border_style(Style::default().fg(Color::Cyan)),
+        )
+        .style(Style::default().fg(Color::White).bg(Color::Black));
+
+    f.render_widget(paragraph, area);
+}
+
+fn kind_style(kind: RegionKind) -> Style {
+    match kind {
+        RegionKind::Image => Style::default().fg(Color::Cyan),
+        RegionKind::Mapped => Style::default().fg(Color::Blue),
+        RegionKind::Private => Style::default().fg(Color::Green),
+        RegionKind::Heap => Style::default().fg(Color::Magenta),
+        RegionKind::Stack => Style::default().fg(Color::Yellow),
+    }
+}
+
+/// Paths keep their file name: cut from the front
+fn truncate_start(s: &str, max: usize) -> String {
+    let count = s.chars().count();
+    if count > max {
+        let mut truncated = String::from("...");
+        truncated.extend(s.chars().skip(count - max.saturating_sub(3)));
+        truncated
+    } else {
+        s.to_string()
+    }
+}
+
+fn centered_rect(percent_x: u16, percent_y: u16, area: Rect) -> Rect {
+    use ratatui::layout::{Direction, Layout};
+
+    let popup_layout = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([
+            Constraint::Percentage((100 - percent_y) / 2),
+            Constraint::Percentage(percent_y),
+            Constraint::Percentage((100 - percent_y) / 2),
+        ])
+        .split(area);
+
+    Layout::default()
+        .direction(Direction::Horizontal)
+        .constraints([
+            Constraint::Percentage((100 - percent_x) / 2),
+            Constraint::Percentage(percent_x),
+            Constraint::Percentage((100 - percent_x) / 2),
+        ])
+        .split(popup_layout[1])[1]
+}
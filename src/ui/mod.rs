@@ -10,11 +10,23 @@ pub mod environment_view;
 pub mod setup_menu;
 pub mod handles_view;
 pub mod tab_bar;
+pub mod views_menu;
+pub mod dashboard_view;
+pub mod governor_view;
+pub mod events_view;
+pub mod memory_map_view;
+pub mod connections_view;
+pub mod disks_view;
+pub mod filesystems_view;
+
+#[cfg(test)]
+mod tests;
 
 use ratatui::Frame;
 use ratatui::layout::{Constraint, Direction, Layout};
 
 use crate::app::{App, AppMode};
+use crate::meters::is_cpu_meter;
 
 /// Minimum width (chars) for a single CPU bar column to remain readable.
 /// At 8 chars: " 96[|99%]" — cramped but functional, matching htop on narrow terminals.
@@ -57,7 +69,7 @@ pub fn cpu_column_count(core_count: usize, term_height: u16, term_width: u16) ->
         if cols > preferred_cols {
             break;
         }
-        let rows_needed = (core_count + cols - 1) / cols;
+        let rows_needed = core_count.div_ceil(cols);
         if rows_needed <= max_cpu_rows {
             return cols;
         }
@@ -71,7 +83,7 @@ pub fn cpu_column_count(core_count: usize, term_height: u16, term_width: u16) ->
         if cols > tight_cols {
             break;
         }
-        let rows_needed = (core_count + cols - 1) / cols;
+        let rows_needed = core_count.div_ceil(cols);
         if rows_needed <= max_cpu_rows {
             return cols;
         }
@@ -79,13 +91,8 @@ pub fn cpu_column_count(core_count: usize, term_height: u16, term_width: u16) ->
 
     // Fallback: use the largest even column count that fits the width.
     // This minimizes rows even if header still exceeds preferred max height.
-    let max_even = ((tight_cols / 2) * 2).max(2).min(32);
-    max_even
-}
-
-/// Check if a meter name represents CPU bars
-fn is_cpu_meter_name(name: &str) -> bool {
-    matches!(name, "AllCPUs" | "AllCPUs2" | "AllCPUs4" | "AllCPUs8") || name.starts_with("CPUs")
+    
+    ((tight_cols / 2) * 2).clamp(2, 32)
 }
 
 /// Get forced sub-column count from meter variant name
@@ -99,8 +106,15 @@ fn meter_subcols(name: &str) -> Option<usize> {
 }
 
 /// Calculate the header height based on number of CPU cores and terminal size.
-/// htop-style: each panel flows independently, so height = max(left, right).
+/// Replay adds one row above the meters for the recorded timestamp.
 pub fn header_height(app: &App, term_height: u16, term_width: u16) -> u16 {
+    let playback_row = if app.playback.is_some() { 1 } else { 0 };
+    meters_height(app, term_height, term_width) + playback_row
+}
+
+/// Height of the meter panels alone.
+/// htop-style: each panel flows independently, so height = max(left, right).
+fn meters_height(app: &App, term_height: u16, term_width: u16) -> u16 {
     if app.compact_mode {
         return 2; // 1 aggregate CPU bar + 1 Mem bar
     }
@@ -110,10 +124,11 @@ pub fn header_height(app: &App, term_height: u16, term_width: u16) -> u16 {
     }
 
     // Count non-CPU info meters per panel
-    let left_has_cpus = app.left_meters.iter().any(|m| is_cpu_meter_name(m));
-    let right_has_cpus = app.right_meters.iter().any(|m| is_cpu_meter_name(m));
-    let left_info_count = app.left_meters.iter().filter(|m| !is_cpu_meter_name(m)).count();
-    let right_info_count = app.right_meters.iter().filter(|m| !is_cpu_meter_name(m)).count();
+    let left_has_cpus = app.left_meters.iter().any(|m| is_cpu_meter(&m.name));
+    let right_has_cpus = app.right_meters.iter().any(|m| is_cpu_meter(&m.name));
+    // Graph and LED meters take several rows each
+    let left_info_count: usize = app.left_meters.iter().filter(|m| !is_cpu_meter(&m.name)).map(|m| m.height()).sum();
+    let right_info_count: usize = app.right_meters.iter().filter(|m| !is_cpu_meter(&m.name)).map(|m| m.height()).sum();
 
     // Auto-detect column count
     let auto_cpu_cols = cpu_column_count(cores, term_height, term_width);
@@ -121,16 +136,16 @@ pub fn header_height(app: &App, term_height: u16, term_width: u16) -> u16 {
 
     // Per-panel sub-column counts (respect explicit AllCPUs2/4/8 variants)
     let left_sub_cols = app.left_meters.iter()
-        .find(|m| is_cpu_meter_name(m))
-        .and_then(|m| meter_subcols(m))
+        .find(|m| is_cpu_meter(&m.name))
+        .and_then(|m| meter_subcols(&m.name))
         .unwrap_or(auto_sub_cols);
     let right_sub_cols = app.right_meters.iter()
-        .find(|m| is_cpu_meter_name(m))
-        .and_then(|m| meter_subcols(m))
+        .find(|m| is_cpu_meter(&m.name))
+        .and_then(|m| meter_subcols(&m.name))
         .unwrap_or(auto_sub_cols);
 
     let (left_cores, right_cores) = if left_has_cpus && right_has_cpus {
-        let half = (cores + 1) / 2;
+        let half = cores.div_ceil(2);
         (half, cores - half)
     } else if left_has_cpus {
         (cores, 0)
@@ -141,12 +156,12 @@ pub fn header_height(app: &App, term_height: u16, term_width: u16) -> u16 {
     };
 
     let left_cpu_rows = if left_cores > 0 {
-        (left_cores + left_sub_cols - 1) / left_sub_cols
+        left_cores.div_ceil(left_sub_cols)
     } else {
         0
     };
     let right_cpu_rows = if right_cores > 0 {
-        (right_cores + right_sub_cols - 1) / right_sub_cols
+        right_cores.div_ceil(right_sub_cols)
     } else {
         0
     };
@@ -187,6 +202,14 @@ pub fn draw(f: &mut Frame, app: &App) {
         AppMode::Affinity => affinity_menu::draw_affinity_menu(f, app),
         AppMode::Environment => environment_view::draw_environment_view(f, app),
         AppMode::Handles => handles_view::draw_handles_view(f, app),
+        AppMode::Views => views_menu::draw_views_menu(f, app),
+        AppMode::Dashboard => dashboard_view::draw_dashboard_view(f, app),
+        AppMode::Governor => governor_view::draw_governor_view(f, app),
+        AppMode::Events => events_view::draw_events_view(f, app),
+        AppMode::MemoryMap => memory_map_view::draw_memory_map_view(f, app),
+        AppMode::Connections => connections_view::draw_connections_view(f, app),
+        AppMode::Disks => disks_view::draw_disks_view(f, app),
+        AppMode::Filesystems => filesystems_view::draw_filesystems_view(f, app),
         _ => {}
     }
 }
@@ -5,6 +5,7 @@ use ratatui::text::{Line, Span};
 use ratatui::widgets::Paragraph;
 
 use crate::app::{App, AppMode, ProcessTab};
+use crate::history::sparkline;
 use crate::system::memory::format_bytes;
 use crate::system::process::ProcessSortField;
 
@@ -26,6 +27,10 @@ pub const HEADERS: &[(&str, u16, ProcessSortField, u8)] = &[
     ("MEM%",       6,  ProcessSortField::Mem,         85),
     ("TIME+",     10,  ProcessSortField::Time,        50),
     ("THR",        4,  ProcessSortField::Threads,     25),
+    ("CPU HIST",  11,  ProcessSortField::CpuHist,     12),
+    ("CPU PEAK",   9,  ProcessSortField::CpuPeak,      6),
+    ("RES HIST",  11,  ProcessSortField::ResHist,     11),
+    ("RES PEAK",   9,  ProcessSortField::ResPeak,      5),
     ("IO_R",      10,  ProcessSortField::IoReadRate,  10),
     ("IO_W",      10,  ProcessSortField::IoWriteRate,  8),
     ("Command",    0,  ProcessSortField::Command,    100), // 0 = takes remaining space
@@ -66,6 +71,18 @@ pub const GPU_HEADERS: &[(&str, u16, ProcessSortField, u8)] = &[
     ("Total",      0,  ProcessSortField::VirtMem,     60),
 ];
 
+/// Ports tab column headers (listening TCP and bound UDP sockets)
+/// PORT sorts as the CPU% field, so `P` orders by port here
+pub const PORTS_HEADERS: &[(&str, u16, ProcessSortField, u8)] = &[
+    ("PORT",       7,  ProcessSortField::Cpu,        100),
+    ("PROTO",      6,  ProcessSortField::Status,      70),
+    ("ADDRESS",   16,  ProcessSortField::Nice,        75),
+    ("PID",        7,  ProcessSortField::Pid,         90),
+    ("USER",       9,  ProcessSortField::User,        60),
+    ("Process",   15,  ProcessSortField::Threads,     85),
+    ("Command",    0,  ProcessSortField::Command,     50),
+];
+
 /// Draw the process table
 pub fn draw_process_table(f: &mut Frame, app: &App, area: Rect) {
     if area.height < 2 {
@@ -78,6 +95,7 @@ pub fn draw_process_table(f: &mut Frame, app: &App, area: Rect) {
         ProcessTab::Io => IO_HEADERS,
         ProcessTab::Net => NET_HEADERS,
         ProcessTab::Gpu => GPU_HEADERS,
+        ProcessTab::Ports => PORTS_HEADERS,
     };
 
     // --- Column header row (full-width colored background like htop) ---
@@ -269,6 +287,45 @@ pub fn draw_process_table(f: &mut Frame, app: &App, area: Rect) {
                 f.render_widget(Paragraph::new(msg), msg_area);
             }
         }
+
+        ProcessTab::Ports => {
+            // Sockets aren't recorded: the live ones would not match the replay
+            let ports = if app.viewing_history() { &[][..] } else { &app.ports[..] };
+            let start = app.ports_scroll_offset;
+            let end = (start + visible).min(ports.len());
+
+            for (i, row_idx) in (start..end).enumerate() {
+                let is_selected = row_idx == app.ports_selected_index;
+
+                let row_area = Rect {
+                    x: proc_area.x,
+                    y: proc_area.y + i as u16,
+                    width: proc_area.width,
+                    height: 1,
+                };
+
+                let row_line = build_port_row(&ports[row_idx], row_area.width as usize, app, is_selected, &display_cols);
+                f.render_widget(Paragraph::new(row_line), row_area);
+            }
+
+            if ports.is_empty() {
+                let msg_area = Rect {
+                    x: proc_area.x,
+                    y: proc_area.y,
+                    width: proc_area.width,
+                    height: 1,
+                };
+                let text = if app.viewing_history() {
+                    "  Listening ports are only listed for live processes"
+                } else if app.filter_query.is_empty() && app.user_filter.is_none() {
+                    "  No listening sockets found"
+                } else {
+                    "  No listening sockets match the filter"
+                };
+                let msg = Line::from(Span::styled(text, Style::default().fg(Color::DarkGray)));
+                f.render_widget(Paragraph::new(msg), msg_area);
+            }
+        }
     }
 
     // Search / Filter bar
@@ -283,22 +340,87 @@ pub fn draw_process_table(f: &mut Frame, app: &App, area: Rect) {
                 spans.push(Span::styled("  Not found", Style::default().fg(Color::Red).add_modifier(Modifier::BOLD)));
             }
             Line::from(spans)
-        } else if app.mode == AppMode::Filter {
-            Line::from(vec![
-                Span::styled("Filter: ", Style::default().fg(cs.filter_label).add_modifier(Modifier::BOLD)),
-                Span::styled(app.filter_query.clone(), Style::default().fg(cs.filter_text)),
-                Span::styled("_", Style::default().fg(cs.filter_text).add_modifier(Modifier::SLOW_BLINK)),
-            ])
         } else {
-            Line::from(vec![
-                Span::styled("Filter[active]: ", Style::default().fg(cs.filter_label).add_modifier(Modifier::BOLD)),
-                Span::styled(app.filter_query.clone(), Style::default().fg(cs.filter_text)),
-            ])
+            let mut spans = if app.mode == AppMode::Filter {
+                vec![
+                    Span::styled("Filter: ", Style::default().fg(cs.filter_label).add_modifier(Modifier::BOLD)),
+                    Span::styled(app.filter_query.clone(), Style::default().fg(cs.filter_text)),
+                    Span::styled("_", Style::default().fg(cs.filter_text).add_modifier(Modifier::SLOW_BLINK)),
+                ]
+            } else {
+                vec![
+                    Span::styled("Filter[active]: ", Style::default().fg(cs.filter_label).add_modifier(Modifier::BOLD)),
+                    Span::styled(app.filter_query.clone(), Style::default().fg(cs.filter_text)),
+                ]
+            };
+            // The previous valid filter stays in effect until the query parses
+            if let Some(err) = &app.filter_error {
+                spans.push(Span::styled(format!("  ✗ {}", err), Style::default().fg(Color::Red).add_modifier(Modifier::BOLD)));
+            }
+            Line::from(spans)
         };
         f.render_widget(Paragraph::new(bar_line), bar_rect);
     }
 }
 
+/// The active tab's table as plain text (column header first), for
+/// `--batch` and Ctrl-S exports: every visible column without width-based
+/// hiding, commands untruncated, tree prefixes as on screen
+pub fn plain_text_table(app: &App) -> Vec<String> {
+    let headers = match app.active_tab {
+        ProcessTab::Main => HEADERS,
+        ProcessTab::Io => IO_HEADERS,
+        ProcessTab::Net => NET_HEADERS,
+        ProcessTab::Gpu => GPU_HEADERS,
+        ProcessTab::Ports => PORTS_HEADERS,
+    };
+    let cols: std::collections::HashSet<ProcessSortField> = match app.active_tab {
+        ProcessTab::Main => app.visible_columns.clone(),
+        _ => headers.iter().map(|(_, _, f, _)| *f).collect(),
+    };
+
+    let mut header = String::new();
+    for (name, width, field, _) in headers {
+        if !cols.contains(field) {
+            continue;
+        }
+        if *width == 0 {
+            header.push_str(name);
+        } else {
+            header.push_str(&format!("{:<width$}", name, width = *width as usize));
+        }
+    }
+
+    // Main/I/O/Ports rows give the command all remaining width; Net/GPU rows pad
+    // their last column to it, so those get none
+    let rows: Vec<Line> = match app.active_tab {
+        ProcessTab::Main => app.filtered_processes.iter()
+            .filter(|p| !app.is_ghost(p.pid))
+            .map(|p| build_process_row(p, usize::MAX, app, false, false, &cols))
+            .collect(),
+        ProcessTab::Io => app.filtered_processes.iter()
+            .filter(|p| !app.is_ghost(p.pid))
+            .map(|p| build_io_row(p, usize::MAX, app, false, false, &cols))
+            .collect(),
+        ProcessTab::Net => app.net_processes.iter()
+            .map(|p| build_net_bandwidth_row(p, 0, app, false))
+            .collect(),
+        ProcessTab::Gpu => app.gpu_processes.iter()
+            .map(|p| build_gpu_row(p, 0, app, false))
+            .collect(),
+        ProcessTab::Ports => app.ports.iter()
+            .map(|p| build_port_row(p, usize::MAX, app, false, &cols))
+            .collect(),
+    };
+
+    let mut lines = vec![header.trim_end().to_string()];
+    for row in rows {
+        let text: String = row.spans.iter().map(|s| s.content.as_ref()).collect();
+        lines.push(text.trim_end().to_string());
+    }
+    lines
+}
+
 /// Minimum width reserved for the Command column before auto-hiding other columns.
 /// When the terminal is too narrow, low-priority columns are hidden progressively
 /// (like htop) to ensure Command, CPU%, MEM%, USER, PID remain visible.
@@ -370,33 +492,39 @@ fn build_process_row(
     display_cols: &std::collections::HashSet<ProcessSortField>,
 ) -> Line<'static> {
     let cs = &app.color_scheme;
-    let bg = if selected { cs.process_selected_bg } else { cs.process_bg };
+
+    // highlight_changes: tint processes that just started; ones that just
+    // exited stay greyed out, selected or not
+    let is_ghost = app.is_ghost(proc.pid);
+    let is_new = app.highlight_changes
+        && app.lifecycle.is_new(proc.pid, app.clock_ms(), app.highlight_ms());
+    let bg = if selected { cs.process_selected_bg } else if is_new { cs.process_new_bg } else { cs.process_bg };
+    let shadow = if is_ghost { cs.process_ghost } else { cs.process_shadow };
 
     // shadow_other_users: dim processes owned by other users
-    let is_other_user = app.shadow_other_users
-        && !selected
-        && proc.user.to_lowercase() != app.current_user;
+    let is_other_user = is_ghost
+        || (app.shadow_other_users && !selected && proc.user.to_lowercase() != app.current_user);
     let default_fg = if is_other_user {
-        cs.process_shadow
+        shadow
     } else if selected {
         cs.process_selected_fg
     } else {
         cs.process_fg
     };
 
-    let pid_fg = if tagged { Color::Yellow } else if is_other_user { cs.process_shadow } else { cs.col_pid };
+    let pid_fg = if tagged { Color::Yellow } else if is_other_user { shadow } else { cs.col_pid };
 
-    let cpu_fg = if is_other_user { cs.process_shadow }
+    let cpu_fg = if is_other_user { shadow }
         else if proc.cpu_usage > 90.0 { cs.col_cpu_high }
         else if proc.cpu_usage > 50.0 { cs.col_cpu_medium }
         else { cs.col_cpu_low };
 
-    let mem_fg = if is_other_user { cs.process_shadow }
+    let mem_fg = if is_other_user { shadow }
         else if proc.mem_usage > 50.0 { cs.col_mem_high }
         else if proc.mem_usage > 20.0 { cs.col_cpu_medium }
         else { cs.col_mem_normal };
 
-    let status_fg = if is_other_user { cs.process_shadow } else { match &proc.status {
+    let status_fg = if is_other_user { shadow } else { match &proc.status {
         crate::system::process::ProcessStatus::Running => cs.col_status_running,
         crate::system::process::ProcessStatus::Sleeping => cs.col_status_sleeping,
         crate::system::process::ProcessStatus::DiskSleep => cs.col_status_disk_sleep,
@@ -421,9 +549,13 @@ fn build_process_row(
         String::new()
     };
 
-    // Command column: show_merged_command merges name + full command
+    // Command column: show_merged_command merges name + full command.
+    // Thread rows show their name followed by the wait reason and context
+    // switches
     let cmd_width = width.saturating_sub(fixed_cols_width_for(HEADERS, display_cols));
-    let cmd_text = if app.show_merged_command {
+    let cmd_text = if proc.is_thread {
+        format!("{} {}", proc.name, proc.thread_detail())
+    } else if app.show_merged_command {
         // Merged: "name command_args" (like htop's merged command)
         if proc.command != proc.name && !proc.command.is_empty() {
             format!("{} {}", proc.name, proc.command)
@@ -444,7 +576,7 @@ fn build_process_row(
     let base_style = Style::default().bg(bg);
 
     // Build spans matching htop's exact column order (only visible columns)
-    // PID PPID USER PRI NI VIRT RES SHR S CPU% MEM% TIME+ THR IO_R IO_W Command
+    // PID PPID USER PRI NI VIRT RES SHR S CPU% MEM% TIME+ THR [history] IO_R IO_W Command
     let mut spans = Vec::new();
     
     use crate::system::process::ProcessSortField;
@@ -453,27 +585,27 @@ fn build_process_row(
         spans.push(Span::styled(format!("{:>6} ", proc.pid), base_style.fg(pid_fg)));
     }
     if display_cols.contains(&ProcessSortField::Ppid) {
-        spans.push(Span::styled(format!("{:>6} ", proc.ppid), base_style.fg(if is_other_user { cs.process_shadow } else { cs.col_pid })));
+        spans.push(Span::styled(format!("{:>6} ", proc.ppid), base_style.fg(if is_other_user { shadow } else { cs.col_pid })));
     }
     if display_cols.contains(&ProcessSortField::User) {
-        spans.push(Span::styled(format!("{:<8} ", truncate_str(&proc.user, 8)), base_style.fg(if is_other_user { cs.process_shadow } else { cs.col_user })));
+        spans.push(Span::styled(format!("{:<8} ", truncate_str(&proc.user, 8)), base_style.fg(if is_other_user { shadow } else { cs.col_user })));
     }
     if display_cols.contains(&ProcessSortField::Priority) {
-        spans.push(Span::styled(format!("{:>3} ", proc.priority), base_style.fg(if is_other_user { cs.process_shadow } else { cs.col_priority })));
+        spans.push(Span::styled(format!("{:>3} ", proc.priority), base_style.fg(if is_other_user { shadow } else { cs.col_priority })));
     }
     if display_cols.contains(&ProcessSortField::Nice) {
         spans.push(Span::styled(format!("{:>3} ", proc.nice), base_style.fg(default_fg)));
     }
     if display_cols.contains(&ProcessSortField::VirtMem) {
         // highlight_megabytes: color large memory values
-        let virt_fg = if is_other_user { cs.process_shadow }
+        let virt_fg = if is_other_user { shadow }
             else if app.highlight_megabytes && proc.virtual_mem >= 1024 * 1024 * 1024 { cs.col_mem_high }
             else if app.highlight_megabytes && proc.virtual_mem >= 1024 * 1024 { cs.col_priority }
             else { default_fg };
         spans.push(Span::styled(format!("{:>6} ", format_bytes(proc.virtual_mem)), base_style.fg(virt_fg)));
     }
     if display_cols.contains(&ProcessSortField::ResMem) {
-        let res_fg = if is_other_user { cs.process_shadow }
+        let res_fg = if is_other_user { shadow }
             else if app.highlight_megabytes && proc.resident_mem >= 1024 * 1024 * 1024 { cs.col_mem_high }
             else if app.highlight_megabytes && proc.resident_mem >= 1024 * 1024 { Color::Yellow }
             else { default_fg };
@@ -496,23 +628,47 @@ fn build_process_row(
     }
     if display_cols.contains(&ProcessSortField::Threads) {
         // highlight_threads: color thread count differently
-        let thr_fg = if is_other_user { cs.process_shadow }
+        let thr_fg = if is_other_user { shadow }
             else if app.highlight_threads && proc.threads > 10 { cs.col_thread }
-            else if app.highlight_threads { cs.col_priority }
             else { cs.col_priority };
         spans.push(Span::styled(format!("{:>3} ", proc.threads), base_style.fg(thr_fg)));
     }
+    // History columns: sparklines of the last ticks, newest on the right
+    let history = app.history.get(proc.pid);
+    if display_cols.contains(&ProcessSortField::CpuHist) {
+        let line = match history {
+            Some(h) => sparkline(h.samples().iter().map(|s| s.cpu as f64), 10, h.cpu_peak().max(100.0) as f64),
+            None => " ".repeat(10),
+        };
+        spans.push(Span::styled(format!("{} ", line), base_style.fg(cpu_fg)));
+    }
+    if display_cols.contains(&ProcessSortField::CpuPeak) {
+        let peak = history.map_or(proc.cpu_usage, |h| h.cpu_peak());
+        spans.push(Span::styled(format!("{:>8.1} ", peak), base_style.fg(cpu_fg)));
+    }
+    if display_cols.contains(&ProcessSortField::ResHist) {
+        let line = match history {
+            Some(h) => sparkline(h.samples().iter().map(|s| s.resident_mem as f64), 10, h.res_peak() as f64),
+            None => " ".repeat(10),
+        };
+        let hist_fg = if is_other_user { shadow } else { cs.col_mem_normal };
+        spans.push(Span::styled(format!("{} ", line), base_style.fg(hist_fg)));
+    }
+    if display_cols.contains(&ProcessSortField::ResPeak) {
+        let peak = history.map_or(proc.resident_mem, |h| h.res_peak());
+        spans.push(Span::styled(format!("{:>8} ", format_bytes(peak)), base_style.fg(default_fg)));
+    }
     if display_cols.contains(&ProcessSortField::IoReadRate) {
-        spans.push(Span::styled(format!("{:>9} ", format_io_rate(proc.io_read_rate)), base_style.fg(if is_other_user { cs.process_shadow } else { Color::Yellow })));
+        spans.push(Span::styled(format!("{:>9} ", format_io_rate(proc.io_read_rate)), base_style.fg(if is_other_user { shadow } else { Color::Yellow })));
     }
     if display_cols.contains(&ProcessSortField::IoWriteRate) {
-        spans.push(Span::styled(format!("{:>9} ", format_io_rate(proc.io_write_rate)), base_style.fg(if is_other_user { cs.process_shadow } else { Color::Magenta })));
+        spans.push(Span::styled(format!("{:>9} ", format_io_rate(proc.io_write_rate)), base_style.fg(if is_other_user { shadow } else { Color::Magenta })));
     }
 
     // Command with basename highlighting (htop shows the process name in a different color)
     // Controlled by highlight_base_name display option
-    let cmd_fg = if is_other_user { cs.process_shadow } else { cs.col_command };
-    let cmd_base_fg = if is_other_user { cs.process_shadow } else { cs.col_command_basename };
+    let cmd_fg = if is_other_user { shadow } else { cs.col_command };
+    let cmd_base_fg = if is_other_user { shadow } else { cs.col_command_basename };
     if app.highlight_base_name {
         if let Some(pos) = command_truncated.find(base_name.as_str()) {
             let before = &command_truncated[..pos];
@@ -657,6 +813,60 @@ fn build_gpu_row(
     Line::from(spans)
 }
 
+/// Build a row for the Ports tab (one listening socket and its owner)
+/// PORT  PROTO  ADDRESS  PID  USER  Process  Command
+fn build_port_row(
+    port: &crate::system::netstat::ListeningPort,
+    width: usize,
+    app: &App,
+    selected: bool,
+    display_cols: &std::collections::HashSet<ProcessSortField>,
+) -> Line<'static> {
+    let cs = &app.color_scheme;
+    let bg = if selected { cs.process_selected_bg } else { cs.process_bg };
+    let base_style = Style::default().bg(bg);
+    let default_fg = if selected { cs.process_selected_fg } else { cs.process_fg };
+    let socket = &port.socket;
+
+    // Sockets bound to every interface stand out from loopback-only ones
+    let address = socket.local.ip();
+    let address_fg = if address.is_unspecified() { Color::Yellow }
+        else if address.is_loopback() { Color::DarkGray }
+        else { default_fg };
+    let proto_fg = match socket.protocol {
+        crate::system::netstat::Protocol::Tcp => Color::Green,
+        crate::system::netstat::Protocol::Udp => Color::Magenta,
+    };
+    let pid = if socket.pid == 0 { "-".to_string() } else { socket.pid.to_string() };
+    let cmd_width = width.saturating_sub(fixed_cols_width_for(PORTS_HEADERS, display_cols));
+
+    let mut spans = Vec::new();
+    if display_cols.contains(&ProcessSortField::Cpu) {
+        spans.push(Span::styled(format!("{:>6} ", socket.local.port()), base_style.fg(Color::Cyan).add_modifier(Modifier::BOLD)));
+    }
+    if display_cols.contains(&ProcessSortField::Status) {
+        spans.push(Span::styled(format!("{:<5} ", socket.protocol_label()), base_style.fg(proto_fg)));
+    }
+    if display_cols.contains(&ProcessSortField::Nice) {
+        spans.push(Span::styled(format!("{:<15} ", truncate_str(&address.to_string(), 15)), base_style.fg(address_fg)));
+    }
+    if display_cols.contains(&ProcessSortField::Pid) {
+        spans.push(Span::styled(format!("{:>6} ", pid), base_style.fg(cs.col_pid)));
+    }
+    if display_cols.contains(&ProcessSortField::User) {
+        spans.push(Span::styled(format!("{:<8} ", truncate_str(&port.user, 8)), base_style.fg(cs.col_user)));
+    }
+    if display_cols.contains(&ProcessSortField::Threads) {
+        spans.push(Span::styled(
+            format!("{:<14} ", truncate_str(&port.name, 14)),
+            base_style.fg(cs.col_command_basename).add_modifier(Modifier::BOLD),
+        ));
+    }
+    spans.push(Span::styled(truncate_str(&port.command, cmd_width), base_style.fg(cs.col_command)));
+
+    Line::from(spans)
+}
+
 /// Truncate a string to max characters
 fn truncate_str(s: &str, max: usize) -> String {
     if s.chars().count() > max {
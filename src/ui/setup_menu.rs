@@ -6,6 +6,7 @@ use ratatui::widgets::{Block, Borders, Clear, Paragraph};
 
 use crate::app::App;
 use crate::color_scheme::ColorSchemeId;
+use crate::meters::Meter;
 use crate::system::process::ProcessSortField;
 
 // ── Constants ───────────────────────────────────────────────────────────────
@@ -36,6 +37,7 @@ const DISPLAY_OPTIONS: &[&str] = &[
     "Show merged command",
     "Enable mouse control",
     "Vim-style keys (j/k/g/G/Ctrl-u/d)",
+    "Highlight new and old processes",
 ];
 
 // ── Main draw entry ─────────────────────────────────────────────────────────
@@ -132,6 +134,8 @@ pub const AVAILABLE_METERS: &[&str] = &[
     "Memory",
     "Swap",
     "Network",
+    "DiskIO",
+    "Disk usage",
     "GPU",
     "VMem",
     "Tasks",
@@ -178,12 +182,7 @@ fn draw_meters_panel(f: &mut Frame, app: &App, area: Rect) {
     }
     for (i, m) in app.left_meters.iter().enumerate() {
         let is_sel = left_active && i == app.setup_menu_index;
-        let bg = if is_sel { cs.popup_selected_bg } else { Color::Reset };
-        let fg = if is_sel { cs.popup_selected_fg } else { cs.popup_text };
-        left_lines.push(Line::from(Span::styled(
-            format!("  {}", m),
-            Style::default().fg(fg).bg(bg),
-        )));
+        left_lines.push(meter_line(app, m, is_sel));
     }
     f.render_widget(Paragraph::new(left_lines), cols[0]);
 
@@ -203,12 +202,7 @@ fn draw_meters_panel(f: &mut Frame, app: &App, area: Rect) {
     }
     for (i, m) in app.right_meters.iter().enumerate() {
         let is_sel = right_active && i == app.setup_menu_index;
-        let bg = if is_sel { cs.popup_selected_bg } else { Color::Reset };
-        let fg = if is_sel { cs.popup_selected_fg } else { cs.popup_text };
-        right_lines.push(Line::from(Span::styled(
-            format!("  {}", m),
-            Style::default().fg(fg).bg(bg),
-        )));
+        right_lines.push(meter_line(app, m, is_sel));
     }
     f.render_widget(Paragraph::new(right_lines), cols[1]);
 
@@ -242,6 +236,10 @@ fn draw_meters_panel(f: &mut Frame, app: &App, area: Rect) {
         "  Enter=Add  Del=Remove",
         Style::default().fg(Color::DarkGray),
     )));
+    avail_lines.push(Line::from(Span::styled(
+        "  Space=Mode (Bar/Text/Graph/LED)",
+        Style::default().fg(Color::DarkGray),
+    )));
     avail_lines.push(Line::from(Span::styled(
         "  F7=Move up  F8=Move down",
         Style::default().fg(Color::DarkGray),
@@ -249,6 +247,18 @@ fn draw_meters_panel(f: &mut Frame, app: &App, area: Rect) {
     f.render_widget(Paragraph::new(avail_lines), cols[2]);
 }
 
+/// A placed meter with its mode, e.g. "  Memory [Graph]"
+fn meter_line(app: &App, meter: &Meter, selected: bool) -> Line<'static> {
+    let cs = &app.color_scheme;
+    let bg = if selected { cs.popup_selected_bg } else { Color::Reset };
+    let fg = if selected { cs.popup_selected_fg } else { cs.popup_text };
+    let mode_fg = if selected { cs.popup_selected_fg } else { Color::DarkGray };
+    Line::from(vec![
+        Span::styled(format!("  {}", meter.name), Style::default().fg(fg).bg(bg)),
+        Span::styled(format!(" [{}]", meter.mode.name()), Style::default().fg(mode_fg).bg(bg)),
+    ])
+}
+
 // ── Display options panel (category 1) ──────────────────────────────────────
 
 fn draw_display_options(f: &mut Frame, app: &App, area: Rect) {
@@ -278,6 +288,7 @@ fn draw_display_options(f: &mut Frame, app: &App, area: Rect) {
         app.show_merged_command,
         app.enable_mouse,
         app.vim_keys,
+        app.highlight_changes,
     ];
 
     for (idx, (label, &value)) in DISPLAY_OPTIONS.iter().zip(toggle_values.iter()).enumerate() {
@@ -653,6 +664,10 @@ fn field_description(field: &ProcessSortField) -> &'static str {
         ProcessSortField::IoReadRate => "Disk read bytes/sec",
         ProcessSortField::IoWriteRate => "Disk write bytes/sec",
         ProcessSortField::IoRate => "Combined read+write I/O rate",
+        ProcessSortField::CpuHist => "Recent CPU% sparkline (sorts by average)",
+        ProcessSortField::CpuPeak => "Highest CPU% in the recent history",
+        ProcessSortField::ResHist => "Recent RES sparkline (sorts by average)",
+        ProcessSortField::ResPeak => "Highest RES in the recent history",
     }
 }
 
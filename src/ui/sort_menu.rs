@@ -24,8 +24,7 @@ pub fn draw_sort_menu(f: &mut Frame, app: &App) {
 
     let mut lines: Vec<Line> = Vec::new();
 
-    for i in scroll..end {
-        let field = &fields[i];
+    for (i, field) in fields.iter().enumerate().take(end).skip(scroll) {
         let is_highlighted = i == app.sort_menu_index;
         let is_current = *field == app.sort_field;
 
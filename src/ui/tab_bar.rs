@@ -30,22 +30,19 @@ pub fn draw_tab_bar(f: &mut Frame, app: &App, area: Rect) {
         .fg(cs.tab_inactive_fg)
         .bg(cs.tab_inactive_bg);
 
-    let (main_style, io_style, net_style, gpu_style) = match app.active_tab {
-        ProcessTab::Main => (active_style, inactive_style, inactive_style, inactive_style),
-        ProcessTab::Io => (inactive_style, active_style, inactive_style, inactive_style),
-        ProcessTab::Net => (inactive_style, inactive_style, active_style, inactive_style),
-        ProcessTab::Gpu => (inactive_style, inactive_style, inactive_style, active_style),
-    };
+    let style = |tab: ProcessTab| if app.active_tab == tab { active_style } else { inactive_style };
 
     let line = Line::from(vec![
         Span::styled(" ", Style::default().bg(cs.tab_inactive_bg)),
-        Span::styled(" Main ", main_style),
+        Span::styled(" Main ", style(ProcessTab::Main)),
         Span::styled(" ", separator_style),
-        Span::styled(" I/O ", io_style),
+        Span::styled(" I/O ", style(ProcessTab::Io)),
         Span::styled(" ", separator_style),
-        Span::styled(" Net ", net_style),
+        Span::styled(" Net ", style(ProcessTab::Net)),
         Span::styled(" ", separator_style),
-        Span::styled(" GPU ", gpu_style),
+        Span::styled(" GPU ", style(ProcessTab::Gpu)),
+        Span::styled(" ", separator_style),
+        Span::styled(" Ports ", style(ProcessTab::Ports)),
     ]);
 
     f.render_widget(Paragraph::new(line), area);
@@ -0,0 +1,994 @@
+//! Golden-buffer snapshot tests for the whole UI.
+//!
+//! Each snapshot renders `ui::draw` into a ratatui `TestBackend` with data
+//! from `tests/fixtures/basic.fixture` (via `system::fake::FakeSource`) and
+//! compares the buffer text with `tests/snapshots/<name>.txt`.
+//!
+//! After an intentional layout change, regenerate with:
+//!   UPDATE_SNAPSHOTS=1 cargo test
+
+use std::collections::HashSet;
+use std::path::PathBuf;
+
+use ratatui::backend::TestBackend;
+use ratatui::Terminal;
+
+use crate::app::{App, AppMode, ProcessTab};
+use crate::governor::{Outcome, Policy};
+use crate::meters::{Meter, MeterMode};
+use crate::record::PlaybackStatus;
+use crate::system::backend::{mapping_totals, HandleInfo, MemoryRegion, ProcessEnvironment, QueryError, RegionKind};
+use crate::system::fake::{FakeSource, BASIC_FIXTURE};
+use crate::system::netstat::{Connection, Protocol, TcpState};
+use crate::system::process::ProcessSortField;
+use crate::ui::process_table::{compute_display_columns, HEADERS};
+use crate::ui::{cpu_column_count, header_height};
+use crate::views::SavedView;
+
+/// Terminal sizes every screen is checked at: classic, roomy, wide
+const SIZES: &[(u16, u16)] = &[(80, 24), (120, 40), (200, 50)];
+
+// ── Helpers ─────────────────────────────────────────────────────────────
+
+/// App with default settings and the first frame of a fixture applied
+fn fixture_app(script: &str) -> App {
+    let mut app = App::new();
+    app.current_user = "alice".to_string();
+    FakeSource::from_script(script).refresh(&mut app);
+    app
+}
+
+/// Script with `count` cores at deterministic, varied usage
+fn many_cores_script(count: usize) -> String {
+    let mut script = String::from("cpu brand=\"Fake Server CPU\"\n");
+    for i in 0..count {
+        script.push_str(&format!("core {}\n", (i * 37) % 101));
+    }
+    script.push_str("mem total=256G used=100G cached=40G\nswap total=8G used=0\n");
+    script.push_str("proc pid=1 ppid=0 user=root name=init cmd=/sbin/init cpu=0.1 mem=0.0 virt=164M res=12M shr=8M pri=20\n");
+    script
+}
+
+/// Open files of firefox (PID 3001), one of them locked
+fn fixture_handles() -> Vec<HandleInfo> {
+    let handle = |id: u64, handle_type: &str, name: &str, access: &str, lock: &str| HandleInfo {
+        id: Some(id),
+        handle_type: handle_type.to_string(),
+        name: name.to_string(),
+        access: access.to_string(),
+        lock: lock.to_string(),
+    };
+    vec![
+        handle(0, "Device", "/dev/null", "r", ""),
+        handle(3, "Socket", "socket:[41234]", "rw", ""),
+        handle(4, "Pipe", "pipe:[41240]", "r", ""),
+        handle(5, "File", "/home/alice/.mozilla/firefox/abcd.default/.parentlock", "rw", "POSIX WRITE"),
+        handle(6, "Pipe", "pipe:[41240]", "w", ""),
+        handle(7, "File", "/var/log/app/really/long/path/to/a/log/file/that/needs/truncation/for/sure/this/time/output.log", "w", ""),
+        handle(8, "Dir", "/home/alice/.mozilla/firefox/abcd.default", "r", ""),
+    ]
+}
+
+/// What the environment viewer would read for firefox (PID 3001); the
+/// start time is left out since it's shown in local time
+fn fixture_environment() -> ProcessEnvironment {
+    let vars = [
+        ("HOME", "/home/alice"),
+        ("LANG", "en_US.UTF-8"),
+        ("PATH", "/home/alice/.cargo/bin:/home/alice/.local/bin:/usr/local/sbin:/usr/local/bin:/usr/sbin:/usr/bin:/sbin:/bin:/usr/games:/usr/local/games:/snap/bin"),
+        ("SHELL", "/bin/bash"),
+        ("MOZ_ENABLE_WAYLAND", "1"),
+        ("XDG_RUNTIME_DIR", "/run/user/1000"),
+    ];
+    ProcessEnvironment {
+        exe: Ok("/usr/lib/firefox/firefox".to_string()),
+        cwd: Ok("/home/alice".to_string()),
+        vars: Ok(vars.iter().map(|(n, v)| (n.to_string(), v.to_string())).collect()),
+        start_time: None,
+    }
+}
+
+/// Sockets of firefox (PID 3001), postgres (PID 1500) and one whose owner
+/// isn't known
+fn fixture_connections() -> Vec<Connection> {
+    let tcp = |local: &str, remote: &str, state: TcpState, pid: u32| Connection {
+        protocol: Protocol::Tcp,
+        local: local.parse().unwrap(),
+        remote: remote.parse().unwrap(),
+        state: Some(state),
+        pid,
+    };
+    let udp = |local: &str, pid: u32| Connection {
+        protocol: Protocol::Udp,
+        local: local.parse().unwrap(),
+        remote: "0.0.0.0:0".parse().unwrap(),
+        state: None,
+        pid,
+    };
+    vec![
+        tcp("192.168.1.20:51544", "10.0.4.12:443", TcpState::Established, 3001),
+        tcp("192.168.1.20:51550", "93.184.215.14:443", TcpState::Established, 3001),
+        tcp("192.168.1.20:51562", "10.0.4.12:443", TcpState::TimeWait, 3001),
+        tcp("[2001:db8::20]:40112", "[2606:4700::6810:84e5]:443", TcpState::CloseWait, 3001),
+        udp("0.0.0.0:5353", 3001),
+        tcp("127.0.0.1:5432", "0.0.0.0:0", TcpState::Listen, 1500),
+        tcp("127.0.0.1:5432", "127.0.0.1:40220", TcpState::Established, 1500),
+        tcp("10.0.4.7:22", "10.0.4.12:60001", TcpState::Established, 0),
+    ]
+}
+
+/// Render a full frame and return the buffer as text (trailing spaces trimmed)
+fn render(app: &mut App, width: u16, height: u16) -> String {
+    // Same visible_rows computation as the main loop
+    let header_h = header_height(app, height, width) as usize;
+    let bar_h = if app.mode == AppMode::Search || app.mode == AppMode::Filter || !app.filter_query.is_empty() { 1 } else { 0 };
+    app.visible_rows = (height as usize).saturating_sub(header_h + 1 + 2 + bar_h).max(5);
+
+    let mut terminal = Terminal::new(TestBackend::new(width, height)).unwrap();
+    terminal.draw(|f| super::draw(f, app)).unwrap();
+
+    let buffer = terminal.backend().buffer();
+    let mut out = String::new();
+    for y in 0..buffer.area.height {
+        let line: String = (0..buffer.area.width)
+            .map(|x| buffer[(x, y)].symbol())
+            .collect();
+        out.push_str(line.trim_end());
+        out.push('\n');
+    }
+    out
+}
+
+fn assert_snapshot(name: &str, actual: &str) {
+    let path = PathBuf::from(env!("CARGO_MANIFEST_DIR"))
+        .join("tests")
+        .join("snapshots")
+        .join(format!("{}.txt", name));
+
+    if std::env::var_os("UPDATE_SNAPSHOTS").is_some() {
+        std::fs::create_dir_all(path.parent().unwrap()).unwrap();
+        std::fs::write(&path, actual).unwrap();
+        return;
+    }
+
+    let expected = std::fs::read_to_string(&path).unwrap_or_else(|_| {
+        panic!("missing snapshot {} (run with UPDATE_SNAPSHOTS=1 to create it)", path.display())
+    });
+    if expected != actual {
+        let diff: Vec<String> = expected
+            .lines()
+            .zip(actual.lines())
+            .enumerate()
+            .filter(|(_, (e, a))| e != a)
+            .take(5)
+            .map(|(i, (e, a))| format!("  line {}:\n    expected: {:?}\n    actual:   {:?}", i + 1, e, a))
+            .collect();
+        panic!(
+            "snapshot {} differs (run with UPDATE_SNAPSHOTS=1 to accept):\n{}",
+            name,
+            diff.join("\n"),
+        );
+    }
+}
+
+// ── Screens ─────────────────────────────────────────────────────────────
+
+#[test]
+fn snapshot_each_tab() {
+    for &tab in &[ProcessTab::Main, ProcessTab::Io, ProcessTab::Net, ProcessTab::Gpu] {
+        for &(w, h) in SIZES {
+            let mut app = fixture_app(BASIC_FIXTURE);
+            app.active_tab = tab;
+            let screen = render(&mut app, w, h);
+            assert_snapshot(&format!("tab_{}_{}x{}", tab.name(), w, h), &screen);
+        }
+    }
+}
+
+#[test]
+fn snapshot_each_overlay() {
+    let overlays = [
+        (AppMode::Help, "help"),
+        (AppMode::Setup, "setup"),
+        (AppMode::SortSelect, "sort"),
+        (AppMode::Kill, "kill"),
+        (AppMode::UserFilter, "users"),
+        (AppMode::Affinity, "affinity"),
+        (AppMode::Environment, "environment"),
+        (AppMode::Handles, "handles"),
+        (AppMode::Views, "views"),
+    ];
+    for (mode, name) in overlays {
+        for &(w, h) in SIZES {
+            let mut app = fixture_app(BASIC_FIXTURE);
+            app.selected_index = 2;
+            app.affinity_cpus = (0..app.cpu_info.cores.len()).map(|i| i % 3 != 0).collect();
+            app.handles_pid = 3001;
+            app.handles = fixture_handles();
+            app.environment_pid = 3001;
+            app.environment = Some(fixture_environment());
+            let mut browsers = SavedView::named("browsers");
+            browsers.filter_query = "firefox|chrome".to_string();
+            let mut builds = SavedView::named("build agents");
+            builds.filter_query = "cpu > 20".to_string();
+            builds.user_filter = Some("svc_build".to_string());
+            app.saved_views = vec![browsers, builds, SavedView::named("everything")];
+            app.active_view = Some("build agents".to_string());
+            app.view_menu_index = 1;
+            app.mode = mode;
+            let screen = render(&mut app, w, h);
+            assert_snapshot(&format!("overlay_{}_{}x{}", name, w, h), &screen);
+        }
+    }
+}
+
+#[test]
+fn snapshot_handles_filter_and_sort() {
+    let mut app = fixture_app(BASIC_FIXTURE);
+    app.handles_pid = 3001;
+    app.handles = fixture_handles();
+    app.mode = AppMode::Handles;
+
+    // By type, then number within the type
+    app.handles_sort = crate::app::HandleSort::Type;
+    let order: Vec<Option<u64>> = app.shown_handles().iter().map(|h| h.id).collect();
+    assert_eq!(order, [Some(0), Some(8), Some(5), Some(7), Some(4), Some(6), Some(3)]);
+
+    // Finding who holds a lock: the filter also matches the lock column
+    app.handles_filter = "posix".to_string();
+    assert_eq!(app.shown_handles().len(), 1);
+    app.handles_filter = "pipe".to_string();
+    app.handles_filter_editing = true;
+    assert_snapshot("handles_filtered_80x24", &render(&mut app, 80, 24));
+}
+
+#[test]
+fn snapshot_memory_map() {
+    let region = |start: u64, end: u64, rss: u64, protection: &str, kind: RegionKind, path: &str| MemoryRegion {
+        start,
+        end,
+        rss,
+        protection: protection.to_string(),
+        kind,
+        path: path.to_string(),
+    };
+    const LIBXUL: &str = "/usr/lib/firefox/libxul.so";
+    let regions = vec![
+        region(0x5581_2a40_0000, 0x5581_2a42_1000, 0x21000, "r-xp", RegionKind::Image, "/usr/lib/firefox/firefox"),
+        region(0x5581_2b00_0000, 0x5581_3b00_0000, 0x0c00_0000, "rw-p", RegionKind::Heap, "[heap]"),
+        region(0x7f3a_0000_0000, 0x7f3a_4000_0000, 0x2000_0000, "rw-p", RegionKind::Private, ""),
+        region(0x7f3a_5000_0000, 0x7f3a_5400_0000, 0x0180_0000, "r--s", RegionKind::Mapped, "/home/alice/.mozilla/firefox/abcd.default/places.sqlite"),
+        region(0x7f3a_6000_0000, 0x7f3a_6640_0000, 0x0320_0000, "r-xp", RegionKind::Image, LIBXUL),
+        region(0x7f3a_6640_0000, 0x7f3a_6680_0000, 0x0040_0000, "rw-p", RegionKind::Image, LIBXUL),
+        region(0x7ffc_1a00_0000, 0x7ffc_1a02_1000, 0x8000, "rw-p", RegionKind::Stack, "[stack]"),
+    ];
+
+    // Files sum their regions; anonymous memory is grouped by type
+    let totals = mapping_totals(&regions);
+    let summary: Vec<(&str, usize)> = totals.iter().map(|t| (t.name.as_str(), t.regions)).collect();
+    assert_eq!(
+        summary,
+        [
+            ("[private]", 1),
+            ("[heap]", 1),
+            (LIBXUL, 2),
+            ("/home/alice/.mozilla/firefox/abcd.default/places.sqlite", 1),
+            ("/usr/lib/firefox/firefox", 1),
+            ("[stack]", 1),
+        ]
+    );
+    assert_eq!(totals[2].rss, 0x0360_0000);
+
+    let mut app = fixture_app(BASIC_FIXTURE);
+    app.selected_index = 2;
+    app.memory_map_pid = 3001;
+    app.memory_map = Some(Ok(regions));
+    app.mode = AppMode::MemoryMap;
+    assert_snapshot("memory_map_120x40", &render(&mut app, 120, 40));
+    app.memory_map_by_file = true;
+    assert_snapshot("memory_map_files_80x24", &render(&mut app, 80, 24));
+}
+
+#[test]
+fn snapshot_environment_search_and_access_denied() {
+    let mut app = fixture_app(BASIC_FIXTURE);
+    app.selected_index = 2;
+    app.environment_pid = 3001;
+    app.environment = Some(fixture_environment());
+    app.mode = AppMode::Environment;
+    // Search matches names and values, ignoring case
+    app.environment_search = "bin".to_string();
+    let shown: Vec<&str> = app.shown_environment().iter().map(|(n, _)| n.as_str()).collect();
+    assert_eq!(shown, ["PATH", "SHELL"]);
+    app.environment_search = "path".to_string();
+    app.environment_search_editing = true;
+    assert_snapshot("environment_search_80x24", &render(&mut app, 80, 24));
+
+    // Someone else's process: the start time is still public
+    let start = 1_700_000_000;
+    app.environment = Some(ProcessEnvironment {
+        exe: Err(QueryError::AccessDenied),
+        cwd: Err(QueryError::AccessDenied),
+        vars: Err(QueryError::AccessDenied),
+        start_time: Some(start),
+    });
+    app.environment_search.clear();
+    app.environment_search_editing = false;
+    let local = chrono::DateTime::from_timestamp(start, 0)
+        .unwrap()
+        .with_timezone(&chrono::Local)
+        .format("%Y-%m-%d %H:%M:%S")
+        .to_string();
+    let screen = render(&mut app, 120, 40).replace(&local, "YYYY-MM-DD HH:MM:SS");
+    assert_snapshot("environment_denied_120x40", &screen);
+}
+
+#[test]
+fn snapshot_tree_and_filter() {
+    let mut app = fixture_app(BASIC_FIXTURE);
+    app.tree_view = true;
+    app.build_tree_view();
+    assert_snapshot("main_tree_120x40", &render(&mut app, 120, 40));
+
+    let mut app = fixture_app(BASIC_FIXTURE);
+    app.filter_query = "firefox|postgres".to_string();
+    app.apply_filter();
+    assert_snapshot("main_filter_80x24", &render(&mut app, 80, 24));
+
+    let mut app = fixture_app(BASIC_FIXTURE);
+    app.compact_mode = true;
+    assert_snapshot("main_compact_80x24", &render(&mut app, 80, 24));
+}
+
+#[test]
+fn filter_bar_flags_parse_errors() {
+    let mut app = fixture_app(BASIC_FIXTURE);
+    app.filter_query = "cpu > 50".to_string();
+    app.apply_filter();
+    let busy = app.filtered_processes.len();
+    assert!(busy > 0 && busy < app.processes.len());
+
+    // Mid-edit the query doesn't parse: flagged, last good filter kept
+    app.mode = AppMode::Filter;
+    app.filter_query = "cpu > 50 && user ==".to_string();
+    app.apply_filter();
+    assert_eq!(app.filtered_processes.len(), busy);
+    let screen = render(&mut app, 80, 24);
+    let bar = screen.lines().nth(22).unwrap();
+    assert_eq!(bar, "Filter: cpu > 50 && user ==_  ✗ expected a value (at 20)");
+
+    app.filter_query.push_str(" root");
+    app.apply_filter();
+    assert!(app.filter_error.is_none());
+    assert!(app.filtered_processes.iter().all(|p| p.user == "root" && p.cpu_usage > 50.0));
+}
+
+#[test]
+fn snapshot_replay_header() {
+    let mut app = fixture_app(BASIC_FIXTURE);
+    let rows = header_height(&app, 24, 80);
+    app.playback = Some(PlaybackStatus { timestamp_ms: 0, position: 41, total: 3600, speed: 4, live_offset_ms: None });
+    assert_eq!(header_height(&app, 24, 80), rows + 1);
+    let screen = render(&mut app, 80, 24);
+    // Local-time rendering of the timestamp varies by machine; check the rest
+    let first = screen.lines().next().unwrap();
+    assert!(first.starts_with(" Replay: "));
+    assert!(first.ends_with("  [42/3600]  ▶ 4x"));
+    assert_snapshot("replay_80x24", &screen.lines().skip(1).collect::<Vec<_>>().join("\n"));
+}
+
+#[test]
+fn rewind_header_shows_offset_from_live() {
+    let mut app = fixture_app(BASIC_FIXTURE);
+    app.playback = Some(PlaybackStatus { live_offset_ms: Some(42_300), ..Default::default() });
+    let screen = render(&mut app, 80, 24);
+    let first = screen.lines().next().unwrap();
+    assert!(first.starts_with(" Rewind: -00:42  at "));
+    assert!(first.ends_with("  [/] step  Esc live"));
+
+    app.playback = Some(PlaybackStatus { live_offset_ms: Some(3_725_000), ..Default::default() });
+    let screen = render(&mut app, 80, 24);
+    assert!(screen.starts_with(" Rewind: -1:02:05  at "));
+}
+
+#[test]
+fn process_actions_are_refused_while_viewing_history() {
+    use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
+    let press = |app: &mut App, code: KeyCode| crate::input::handle_input(app, KeyEvent::new(code, KeyModifiers::NONE));
+
+    let mut app = fixture_app(BASIC_FIXTURE);
+    app.playback = Some(PlaybackStatus { live_offset_ms: Some(2_000), ..Default::default() });
+    for code in [KeyCode::F(9)].into_iter().chain("kalmeD".chars().map(KeyCode::Char)) {
+        press(&mut app, code);
+        assert_eq!(app.mode, AppMode::Normal, "{:?}", code);
+    }
+    assert!(app.dashboard.is_none());
+
+    // The same keys work again once back on live data
+    app.playback = None;
+    press(&mut app, KeyCode::Char('D'));
+    assert_eq!(app.mode, AppMode::Dashboard);
+    app.mode = AppMode::Normal;
+    press(&mut app, KeyCode::Char('k'));
+    assert_eq!(app.mode, AppMode::Kill);
+}
+
+#[test]
+fn snapshot_meter_modes() {
+    // Both fixture frames, so the graphs have something to draw
+    let mut source = FakeSource::from_script(BASIC_FIXTURE);
+    let mut app = App::new();
+    app.current_user = "alice".to_string();
+    source.refresh(&mut app);
+    source.refresh(&mut app);
+
+    let mode = |name: &str, mode: MeterMode| Meter { name: name.to_string(), mode };
+    app.left_meters = vec![
+        mode("AllCPUs", MeterMode::Graph),
+        mode("Memory", MeterMode::Graph),
+        mode("Swap", MeterMode::Text),
+        mode("Network", MeterMode::Led),
+    ];
+    app.right_meters = vec![
+        mode("AllCPUs", MeterMode::Text),
+        mode("Tasks", MeterMode::Led),
+        mode("Load average", MeterMode::Text),
+    ];
+    // Left: 4 core rows + Graph (4) + Text (1) + LED (3), + margin
+    assert_eq!(header_height(&app, 40, 120), 14);
+    assert_snapshot("meter_modes_120x40", &render(&mut app, 120, 40));
+
+    // Compact mode keeps the modes on its two rows: CPU graph, Mem text
+    app.compact_mode = true;
+    app.left_meters[1].mode = MeterMode::Text;
+    assert_snapshot("meter_modes_compact_80x24", &render(&mut app, 80, 24));
+}
+
+#[test]
+fn footer_message_replaces_fkeys_briefly() {
+    let mut app = fixture_app(BASIC_FIXTURE);
+    app.footer_message = Some(("Saved /tmp/pstop-main-20260314-021745.csv".to_string(), std::time::Instant::now()));
+    let screen = render(&mut app, 80, 24);
+    assert_eq!(screen.lines().last().unwrap(), " Saved /tmp/pstop-main-20260314-021745.csv");
+
+    app.footer_message = Some(("old".to_string(), std::time::Instant::now() - std::time::Duration::from_secs(60)));
+    let screen = render(&mut app, 80, 24);
+    assert!(screen.lines().last().unwrap().starts_with("F1Help"));
+}
+
+#[test]
+fn snapshot_many_cores() {
+    for &(cores, w, h) in &[(64, 80, 24), (64, 200, 50), (128, 200, 50), (16, 60, 20)] {
+        let mut app = fixture_app(&many_cores_script(cores));
+        let screen = render(&mut app, w, h);
+        assert_snapshot(&format!("cores{}_{}x{}", cores, w, h), &screen);
+    }
+}
+
+// ── Layout math ─────────────────────────────────────────────────────────
+
+#[test]
+fn cpu_columns_fit_terminal() {
+    // Few cores: always one sub-column per panel
+    assert_eq!(cpu_column_count(1, 24, 80), 2);
+    assert_eq!(cpu_column_count(8, 24, 80), 2);
+    assert_eq!(cpu_column_count(16, 50, 200), 2);
+    // Many cores on a big terminal: widen until rows fit
+    assert_eq!(cpu_column_count(64, 50, 200), 4);
+    assert_eq!(cpu_column_count(128, 50, 200), 8);
+    // Many cores on a small terminal: fall back to the widest even count
+    assert_eq!(cpu_column_count(64, 24, 80), 10);
+    // Terminal too short for any CPU rows
+    assert_eq!(cpu_column_count(16, 8, 80), 2);
+}
+
+#[test]
+fn header_height_tracks_cores_and_meters() {
+    let mut app = fixture_app(BASIC_FIXTURE);
+    // 8 cores split 4/4, one sub-column each, + 3 info meters + margin
+    assert_eq!(header_height(&app, 24, 80), 9);
+    app.header_margin = false;
+    assert_eq!(header_height(&app, 24, 80), 7);
+    app.right_meters.push(Meter::new("Clock"));
+    assert_eq!(header_height(&app, 24, 80), 8);
+    app.compact_mode = true;
+    assert_eq!(header_height(&app, 24, 80), 2);
+
+    let empty = App::new();
+    assert_eq!(header_height(&empty, 24, 80), 5);
+
+    // 64 cores at 200x50: 4 columns → 2 per panel → 16 rows + 3 + 2
+    let app = fixture_app(&many_cores_script(64));
+    assert_eq!(header_height(&app, 50, 200), 21);
+}
+
+#[test]
+fn narrow_terminals_drop_low_priority_columns_first() {
+    let all: HashSet<ProcessSortField> = HEADERS.iter().map(|(_, _, f, _)| *f).collect();
+
+    let shown = compute_display_columns(HEADERS, &all, 200, ProcessSortField::Cpu);
+    assert_eq!(shown, all);
+
+    let shown = compute_display_columns(HEADERS, &all, 60, ProcessSortField::Cpu);
+    let expected: HashSet<ProcessSortField> = [
+        ProcessSortField::Pid,
+        ProcessSortField::User,
+        ProcessSortField::ResMem,
+        ProcessSortField::Cpu,
+        ProcessSortField::Mem,
+        ProcessSortField::Command,
+    ].into_iter().collect();
+    assert_eq!(shown, expected);
+
+    // The sort column survives even though its priority is low
+    let shown = compute_display_columns(HEADERS, &all, 60, ProcessSortField::Time);
+    assert!(shown.contains(&ProcessSortField::Time));
+    assert!(!shown.contains(&ProcessSortField::User));
+    assert!(shown.contains(&ProcessSortField::Command));
+}
+
+#[test]
+fn history_columns_sort_by_average_and_peak() {
+    // "burst" spiked on the first tick only; "steady" has been busy since
+    let mut script = String::new();
+    for (burst, steady) in [(90, 10), (5, 30), (5, 30)] {
+        script.push_str(&format!(
+            "proc pid=10 ppid=1 user=alice name=burst cpu={} res=8M\n\
+             proc pid=20 ppid=1 user=alice name=steady cpu={} res=4M\ntick\n",
+            burst, steady
+        ));
+    }
+    let mut source = FakeSource::from_script(&script);
+    let mut app = App::new();
+    for _ in 0..3 {
+        source.refresh(&mut app);
+    }
+    let order = |app: &App| app.filtered_processes.iter().map(|p| p.name.clone()).collect::<Vec<_>>();
+    assert_eq!(order(&app), ["steady", "burst"]);
+
+    for field in [ProcessSortField::CpuHist, ProcessSortField::CpuPeak, ProcessSortField::ResPeak] {
+        app.sort_field = field;
+        app.sort_processes();
+        assert_eq!(order(&app), ["burst", "steady"], "{:?}", field);
+    }
+
+    app.visible_columns = [ProcessSortField::Pid, ProcessSortField::CpuHist, ProcessSortField::CpuPeak, ProcessSortField::Command]
+        .into_iter()
+        .collect();
+    let table = crate::ui::process_table::plain_text_table(&app);
+    assert_eq!(table[0], "PID    CPU HIST   CPU PEAK Command");
+    assert_eq!(table[1], "    10        █▁▁     90.0 burst");
+    assert_eq!(table[2], "    20        ▁▃▃     30.0 steady");
+}
+
+#[test]
+fn thread_rows_sort_and_show_their_state() {
+    // One worker spins while its siblings wait
+    let script = "mem total=16G used=4G\n\
+         proc pid=4100 ppid=1 user=alice name=indexer cmd=\"indexer --jobs 3\" state=S cpu=101.5 res=300M thr=4 time=90000\n\
+         thread pid=4101 ppid=4100 user=alice name=worker-1 wait=do_epoll_wait csw=20411 state=S cpu=0.5 time=1200\n\
+         thread pid=4102 ppid=4100 user=alice name=worker-2 wait=futex_wait_queue csw=5120 state=S cpu=1.0 time=1200\n\
+         thread pid=4103 ppid=4100 user=alice name=worker-3 start=0x7ff61a2b3c40 csw=88 state=R cpu=99.8 time=87500\n";
+    let mut app = App::new();
+    app.current_user = "alice".to_string();
+    app.show_threads = true;
+    FakeSource::from_script(script).refresh(&mut app);
+    let thread = |pid: u32| app.processes.iter().find(|p| p.pid == pid).unwrap();
+    assert_eq!(thread(4102).thread_detail(), "wait:futex_wait_queue csw:5120");
+    assert_eq!(thread(4103).thread_detail(), "start:0x7ff61a2b3c40 csw:88");
+    // What they carry stays out of the command line the filters see
+    assert!(thread(4102).command.is_empty());
+    app.filter_query = "futex".to_string();
+    app.apply_filter();
+    assert!(app.filtered_processes.is_empty());
+    app.filter_query.clear();
+    app.apply_filter();
+    app.sort_processes();
+
+    let order: Vec<u32> = app.filtered_processes.iter().map(|p| p.pid).collect();
+    assert_eq!(order, vec![4100, 4103, 4102, 4101]);
+    app.sort_field = ProcessSortField::Time;
+    app.sort_processes();
+    assert_eq!(app.filtered_processes[1].pid, 4103);
+
+    // Processes show their name only; thread rows add what they carry
+    app.visible_columns = [ProcessSortField::Pid, ProcessSortField::Status, ProcessSortField::Cpu, ProcessSortField::Command]
+        .into_iter()
+        .collect();
+    let table = crate::ui::process_table::plain_text_table(&app);
+    assert_eq!(table[1], "  4100 S 101.5 indexer");
+    assert_eq!(table[2], "  4103 R  99.8 worker-3 start:0x7ff61a2b3c40 csw:88");
+
+    app.sort_field = ProcessSortField::Cpu;
+    app.sort_processes();
+    app.visible_columns = App::new().visible_columns;
+    let screen = render(&mut app, 80, 24);
+    assert_snapshot("thread_rows_80x24", &screen);
+}
+
+#[test]
+fn snapshot_process_dashboard() {
+    // A build agent ramping up over six ticks, then exiting
+    let mut script = String::from("mem total=16G used=4G\n");
+    for (i, (cpu, res, thr)) in [(5, 100, 2), (40, 180, 4), (95, 420, 9), (180, 900, 16), (120, 760, 12), (60, 300, 6)]
+        .into_iter()
+        .enumerate()
+    {
+        script.push_str(&format!(
+            "proc pid=1 ppid=0 user=root name=init cpu=0.1 res=12M\n\
+             proc pid=50 ppid=1 user=svc_build name=agent cmd=\"/opt/agent/run\" cpu=1.0 res=64M\n\
+             proc pid=200 ppid=50 user=svc_build name=make cmd=\"make -j8 all\" state=R cpu={} res={}M shr=40M thr={} io_r={}K io_w={}K time={}\n\
+             proc pid=300 ppid=200 user=svc_build name=cc1 cpu=50.0 res=90M\n\
+             proc pid=301 ppid=300 user=svc_build name=as cpu=5.0 res=9M\n\
+             proc pid=400 ppid=1 user=alice name=bash cpu=0.0 res=5M\ntick\n",
+            cpu, res, thr, i * 300, (5 - i) * 100, 100 + i * 2
+        ));
+    }
+    script.push_str("proc pid=1 ppid=0 user=root name=init cpu=0.1 res=12M\ntick\n");
+
+    let mut source = FakeSource::from_script(&script);
+    let mut app = App::new();
+    source.refresh(&mut app);
+    // The followed process wins over the selection
+    app.follow_pid = Some(200);
+    app.selected_index = app.filtered_processes.iter().position(|p| p.pid == 400).unwrap();
+    app.open_dashboard();
+    assert_eq!(app.mode, AppMode::Dashboard);
+    for _ in 0..5 {
+        source.refresh(&mut app);
+    }
+    let dash = app.dashboard.as_ref().unwrap();
+    assert_eq!((dash.pid, dash.samples().len()), (200, 6));
+    assert_eq!(dash.stats(|s| s.threads as f64).max, 16.0);
+
+    for &(w, h) in &[(80, 24), (120, 40)] {
+        assert_snapshot(&format!("dashboard_{}x{}", w, h), &render(&mut app, w, h));
+    }
+
+    source.refresh(&mut app);
+    assert!(app.dashboard.as_ref().unwrap().exited);
+    assert_snapshot("dashboard_exited_120x40", &render(&mut app, 120, 40));
+}
+
+// ── Fake source ─────────────────────────────────────────────────────────
+
+#[test]
+fn snapshot_governor_history() {
+    let mut app = fixture_app(BASIC_FIXTURE);
+    for (name, fields) in [
+        ("build_hog", &[("match", "cpu > 80"), ("for", "60s"), ("action", "lower_priority")][..]),
+        ("pin_docker", &[("match", "name == dockerd"), ("action", "affinity 0-1")]),
+        ("dns", &[("match", "name == dnsmasq"), ("action", "kill 9"), ("dry_run", "1")]),
+        ("broken", &[("match", "cpu >"), ("action", "kill")]),
+    ] {
+        let mut policy = Policy::named(name);
+        for (key, value) in fields {
+            policy.set(key, value);
+        }
+        app.governor.policies.push(policy);
+    }
+    app.governor.enabled = true;
+    // 2026-01-01 12:00:00 UTC, then the build hogs a minute later
+    let start = 1_767_268_800_000;
+    for now in [start, start + 60_000] {
+        let processes = app.processes.clone();
+        app.governor.tick(&processes, now, |pid, _| Some(if pid == 4101 { Outcome::Failed } else { Outcome::Done }));
+    }
+    assert_eq!(app.governor.history.len(), 6);
+    assert_eq!(app.governor.errors().len(), 1);
+
+    app.mode = AppMode::Governor;
+    // Times are shown in local time, which varies by machine
+    let local = |ms: i64| {
+        chrono::DateTime::from_timestamp_millis(ms).unwrap().with_timezone(&chrono::Local).format("%H:%M:%S").to_string()
+    };
+    let screen = render(&mut app, 120, 40)
+        .replace(&local(start + 60_000), "T+60    ")
+        .replace(&local(start), "T+0     ");
+    assert_snapshot("governor_120x40", &screen);
+}
+
+#[test]
+fn snapshot_connections() {
+    use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
+    let press = |app: &mut App, code: KeyCode| crate::input::handle_input(app, KeyEvent::new(code, KeyModifiers::NONE));
+
+    let mut app = fixture_app(BASIC_FIXTURE);
+    app.active_tab = ProcessTab::Net;
+    app.connections = fixture_connections();
+    press(&mut app, KeyCode::Enter);
+    assert_eq!(app.mode, AppMode::Connections);
+    assert_eq!(app.connections_pid, 3001);
+    assert_eq!(app.shown_connections().len(), 5);
+    let screen = render(&mut app, 120, 40);
+    assert_snapshot("connections_120x40", &screen);
+
+    // Who talks to 10.0.4.12, across all processes
+    press(&mut app, KeyCode::Tab);
+    press(&mut app, KeyCode::Char('/'));
+    for c in "10.0.4.12 established".chars() {
+        press(&mut app, KeyCode::Char(c));
+    }
+    press(&mut app, KeyCode::Enter);
+    let pids: Vec<u32> = app.shown_connections().iter().map(|c| c.pid).collect();
+    assert_eq!(pids, vec![0, 3001]);
+    let screen = render(&mut app, 80, 24);
+    assert_snapshot("connections_filtered_80x24", &screen);
+
+    // Ports match either end
+    app.connections_filter = ":5432".to_string();
+    assert_eq!(app.shown_connections().len(), 2);
+    app.connections_filter = "listen".to_string();
+    assert_eq!(app.shown_connections()[0].local.port(), 5432);
+
+    press(&mut app, KeyCode::Esc);
+    assert_eq!(app.mode, AppMode::Normal);
+}
+
+#[test]
+fn snapshot_ports_tab() {
+    use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
+    let press = |app: &mut App, code: KeyCode| crate::input::handle_input(app, KeyEvent::new(code, KeyModifiers::NONE));
+    let listen = |local: &str, pid: u32| Connection {
+        protocol: Protocol::Tcp,
+        local: local.parse().unwrap(),
+        remote: if local.starts_with('[') { "[::]:0" } else { "0.0.0.0:0" }.parse().unwrap(),
+        state: Some(TcpState::Listen),
+        pid,
+    };
+
+    let mut app = fixture_app(BASIC_FIXTURE);
+    app.active_tab = ProcessTab::Ports;
+    app.connections = fixture_connections();
+    app.connections.extend([listen("0.0.0.0:22", 1201), listen("[::]:22", 1201), listen("0.0.0.0:631", 0)]);
+    app.connections.push(Connection {
+        protocol: Protocol::Udp,
+        local: "127.0.0.1:53".parse().unwrap(),
+        remote: "0.0.0.0:0".parse().unwrap(),
+        state: None,
+        pid: 8123,
+    });
+    app.apply_filter();
+    let ports: Vec<u16> = app.ports.iter().map(|p| p.socket.local.port()).collect();
+    assert_eq!(ports, vec![22, 22, 53, 631, 5353, 5432]);
+    let screen = render(&mut app, 100, 30);
+    assert_snapshot("ports_100x30", &screen);
+
+    // Sockets of matching processes, plus those whose address matches
+    app.filter_query = "postgres|:53".to_string();
+    app.apply_filter();
+    let ports: Vec<u16> = app.ports.iter().map(|p| p.socket.local.port()).collect();
+    assert_eq!(ports, vec![53, 5353, 5432]);
+    let screen = render(&mut app, 80, 24);
+    assert_snapshot("ports_filtered_80x24", &screen);
+
+    // F9 targets the owner of the selected socket
+    press(&mut app, KeyCode::End);
+    press(&mut app, KeyCode::F(9));
+    assert_eq!(app.mode, AppMode::Kill);
+    assert_eq!(app.kill_target().map(|p| p.pid), Some(1500));
+    assert!(render(&mut app, 120, 40).contains("Target: PID 1500 (postgres)"));
+    press(&mut app, KeyCode::Esc);
+
+    // Sort by PID; sockets with no known owner have no process to signal
+    app.filter_query.clear();
+    app.apply_filter();
+    press(&mut app, KeyCode::Char('N'));
+    assert_eq!(app.ports.last().map(|p| p.socket.pid), Some(0));
+    press(&mut app, KeyCode::End);
+    assert!(app.kill_target().is_none());
+}
+
+#[test]
+fn snapshot_disk_io() {
+    use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
+    let press = |app: &mut App, code: KeyCode| crate::input::handle_input(app, KeyEvent::new(code, KeyModifiers::NONE));
+
+    let mut source = FakeSource::from_script(BASIC_FIXTURE);
+    let mut app = App::new();
+    app.current_user = "alice".to_string();
+    source.refresh(&mut app);
+    source.refresh(&mut app);
+    assert_eq!(app.disk_io_info.busy_percent, 62.5);
+    assert_eq!(app.meter_history.disk.back(), Some(&0.625));
+
+    // The meter in each of its modes, as saved in the config file
+    assert!(crate::ui::setup_menu::AVAILABLE_METERS.contains(&"DiskIO"));
+    app.right_meters = ["DiskIO", "DiskIO:text", "DiskIO:graph", "DiskIO:led"].iter().map(|v| Meter::from_config(v)).collect();
+    assert_eq!(app.right_meters[2].mode, MeterMode::Graph);
+    assert_eq!(app.right_meters[3].config_value(), "DiskIO:led");
+    let screen = render(&mut app, 120, 40);
+    assert!(screen.contains("Dsk: R 48.0 M/s W 12.8 M/s 62%"));
+    assert_snapshot("disk_io_meters_120x40", &screen);
+
+    press(&mut app, KeyCode::Char('O'));
+    assert_eq!(app.mode, AppMode::Disks);
+    for &(w, h) in &[(80, 24), (120, 40)] {
+        let screen = render(&mut app, w, h);
+        assert_snapshot(&format!("disks_{}x{}", w, h), &screen);
+    }
+    press(&mut app, KeyCode::Esc);
+    assert_eq!(app.mode, AppMode::Normal);
+}
+
+#[test]
+fn snapshot_filesystems() {
+    use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
+    let press = |app: &mut App, code: KeyCode| crate::input::handle_input(app, KeyEvent::new(code, KeyModifiers::NONE));
+
+    let mut app = fixture_app(BASIC_FIXTURE);
+    // The fullest writable filesystem; the squashfs image is always full
+    let fullest = crate::system::filesystem::fullest(&app.filesystems).unwrap();
+    assert_eq!(fullest.mount_point, "/home/alice/build");
+    assert!(fullest.used_percent() > 90.0);
+    assert_eq!(app.filesystems[1].inode_percent(), None);
+    assert_eq!(app.color_scheme.fill_color(fullest.used_percent()), app.color_scheme.col_cpu_high);
+
+    assert!(crate::ui::setup_menu::AVAILABLE_METERS.contains(&"Disk usage"));
+    app.right_meters = ["Disk usage", "Disk usage:text", "Disk usage:led"].iter().map(|v| Meter::from_config(v)).collect();
+    assert_eq!(app.right_meters[2].config_value(), "Disk usage:led");
+    let screen = render(&mut app, 120, 40);
+    assert!(screen.contains("Disk: /home/alice/build 1.71T/1.80T"));
+    assert_snapshot("disk_usage_meters_120x40", &screen);
+
+    assert!(app.has_meter("Disk usage"));
+
+    // Ctrl-D is not d
+    crate::input::handle_input(&mut app, KeyEvent::new(KeyCode::Char('d'), KeyModifiers::CONTROL));
+    assert_eq!(app.mode, AppMode::Normal);
+    press(&mut app, KeyCode::Char('d'));
+    assert_eq!(app.mode, AppMode::Filesystems);
+    for &(w, h) in SIZES {
+        let screen = render(&mut app, w, h);
+        assert_snapshot(&format!("filesystems_{}x{}", w, h), &screen);
+    }
+    press(&mut app, KeyCode::Esc);
+    assert_eq!(app.mode, AppMode::Normal);
+}
+
+#[test]
+fn snapshot_new_and_exited_processes() {
+    // sleep exits and make starts between the two ticks
+    let script = "mem total=16G used=4G\nuptime 5000\n\
+        proc pid=1 ppid=0 user=root name=init cmd=/sbin/init cpu=0.1 res=12M time=400000\n\
+        proc pid=1202 ppid=1 user=alice name=bash cmd=-bash cpu=0.0 res=5M time=2000\n\
+        proc pid=7000 ppid=1202 user=alice name=sleep cmd=\"sleep 600\" cpu=0.0 res=1M time=100\n\
+        tick\n\
+        proc pid=1 ppid=0 user=root name=init cmd=/sbin/init cpu=0.1 res=12M time=400000\n\
+        proc pid=1202 ppid=1 user=alice name=bash cmd=-bash cpu=0.0 res=5M time=2000\n\
+        proc pid=7100 ppid=1202 user=alice name=make cmd=\"make -j8\" state=R cpu=12.0 res=30M time=0\n\
+        tick\n";
+    let mut source = FakeSource::from_script(script);
+    let mut app = App::new();
+    app.highlight_changes = true;
+    source.refresh(&mut app);
+    source.refresh(&mut app);
+
+    let pids: Vec<u32> = app.filtered_processes.iter().map(|p| p.pid).collect();
+    assert_eq!(pids, vec![7100, 1, 1202, 7000]);
+    assert_eq!(app.total_tasks, 3);
+    app.selected_index = 3;
+    let screen = render(&mut app, 80, 24);
+    assert_snapshot("highlight_changes_80x24", &screen);
+
+    // The ghost stays grey even when selected; the new process is tinted
+    let mut terminal = Terminal::new(TestBackend::new(80, 24)).unwrap();
+    terminal.draw(|f| super::draw(f, &app)).unwrap();
+    let buffer = terminal.backend().buffer();
+    let row_of = |pid: &str| (0..24).find(|&y| (0..7).map(|x| buffer[(x, y)].symbol()).collect::<String>().trim() == pid).unwrap();
+    let cs = &app.color_scheme;
+    assert_eq!(buffer[(1, row_of("7000"))].fg, cs.process_ghost);
+    assert_eq!(buffer[(1, row_of("7000"))].bg, cs.process_selected_bg);
+    assert_eq!(buffer[(1, row_of("7100"))].bg, cs.process_new_bg);
+    assert_eq!(buffer[(79, row_of("1202"))].bg, cs.process_bg);
+
+    // Ghosts are only drawn: no actions on their PID, no rows in batch/export
+    assert!(app.selected_live_process().is_none());
+    assert!(app.kill_target().is_none());
+    app.toggle_tag_selected();
+    assert!(app.tagged_pids.is_empty());
+    let table = crate::ui::process_table::plain_text_table(&app);
+    assert_eq!(table.len(), 1 + 3);
+    assert!(!table.iter().any(|line| line.contains("sleep 600")));
+
+    // Off: no ghosts
+    app.highlight_changes = false;
+    app.apply_filter();
+    assert_eq!(app.filtered_processes.len(), 3);
+}
+
+#[test]
+fn snapshot_process_event_log() {
+    use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
+    let press = |app: &mut App, code: KeyCode| crate::input::handle_input(app, KeyEvent::new(code, KeyModifiers::NONE));
+
+    // A build spawning compilers; the first cc1 exits and its PID is reused
+    let head = "uptime 5000\n\
+        proc pid=1 ppid=0 user=root name=init cmd=/sbin/init res=12M time=500000\n\
+        proc pid=4100 ppid=1 user=alice name=make cmd=\"make -j2\" res=20M time=300000\n";
+    let script = format!(
+        "{head}tick\n\
+         {head}proc pid=4200 ppid=4100 user=alice name=cc1 cmd=\"cc1 main.c\" cpu=96.0 res=80M time=100\n\
+         proc pid=4201 ppid=4100 user=alice name=cc1 cmd=\"cc1 util.c\" cpu=40.0 res=30M time=100\ntick\n\
+         {head}proc pid=4200 ppid=4100 user=alice name=cc1 cmd=\"cc1 main.c\" cpu=70.0 res=210M time=200\ntick\n\
+         {head}proc pid=4201 ppid=4100 user=alice name=as cmd=\"as -o main.o\" cpu=3.0 res=4M time=0\ntick\n"
+    );
+    let mut source = FakeSource::from_script(&script);
+    let mut app = App::new();
+    // Recorded clock, so the times on screen don't depend on when this runs
+    let start = 1_767_268_800_000;
+    for i in 0..4 {
+        app.playback = Some(PlaybackStatus { timestamp_ms: start + i * 2000, total: 4, ..Default::default() });
+        source.refresh(&mut app);
+    }
+    let kinds: Vec<(&str, u32)> = app.lifecycle.log().iter().map(|e| (e.kind.label(), e.process.pid)).collect();
+    assert_eq!(kinds, [("START", 4200), ("START", 4201), ("EXIT", 4201), ("EXIT", 4200), ("START", 4201)]);
+    let cc1 = &app.lifecycle.log()[3];
+    assert_eq!((cc1.lifetime_secs, cc1.peak_cpu, cc1.peak_res), (2, 96.0, 210 << 20));
+
+    press(&mut app, KeyCode::Char('E'));
+    assert_eq!(app.mode, AppMode::Events);
+    let local = |ms: i64| {
+        chrono::DateTime::from_timestamp_millis(ms).unwrap().with_timezone(&chrono::Local).format("%H:%M:%S").to_string()
+    };
+    let screen = |app: &mut App| {
+        let text = render(app, 120, 30);
+        let mut text = text.lines().skip(1).collect::<Vec<_>>().join("\n");
+        for i in 1..4 {
+            text = text.replace(&local(start + i * 2000), &format!("T+{}s    ", i * 2)[..8]);
+        }
+        text
+    };
+    assert_snapshot("events_120x30", &screen(&mut app));
+
+    // Its own filter, typed after '/', and Tab for exits only
+    press(&mut app, KeyCode::Char('/'));
+    for c in "cc1".chars() {
+        press(&mut app, KeyCode::Char(c));
+    }
+    press(&mut app, KeyCode::Enter);
+    press(&mut app, KeyCode::Tab);
+    press(&mut app, KeyCode::Tab);
+    let shown: Vec<u32> = app.shown_events().iter().map(|e| e.process.pid).collect();
+    assert_eq!(shown, [4201, 4200]);
+    assert_snapshot("events_filtered_120x30", &screen(&mut app));
+
+    // 'w' appends what's shown
+    let path = std::env::temp_dir().join(format!("pstop-events-{}.log", std::process::id()));
+    let _ = std::fs::remove_file(&path);
+    app.event_log_path = path.to_string_lossy().into_owned();
+    press(&mut app, KeyCode::Char('w'));
+    press(&mut app, KeyCode::Char('w'));
+    let written = std::fs::read_to_string(&path).unwrap();
+    let _ = std::fs::remove_file(&path);
+    assert_eq!(written.lines().count(), 4);
+    assert!(written.lines().next().unwrap().contains(" EXIT  pid=4201 ppid=4100 user=alice name=cc1 lifetime=1s"));
+    press(&mut app, KeyCode::Esc);
+    assert_eq!(app.mode, AppMode::Normal);
+}
+
+#[test]
+fn fake_source_steps_through_frames() {
+    let mut source = FakeSource::from_script(BASIC_FIXTURE);
+    assert_eq!(source.frame_count(), 2);
+
+    let mut app = App::new();
+    source.refresh(&mut app);
+    assert_eq!(app.cpu_info.cores.len(), 8);
+    assert_eq!(app.cpu_info.cores[3].usage_percent, 91.5);
+    assert_eq!(app.uptime_seconds, 93784);
+    assert_eq!(app.total_tasks, 18);
+    assert_eq!(app.net_processes.len(), 5);
+    assert_eq!(app.gpu_processes.len(), 3);
+
+    // Second frame replaces the core list but keeps everything else
+    source.refresh(&mut app);
+    assert_eq!(app.cpu_info.cores[7].usage_percent, 99.0);
+    assert_eq!(app.uptime_seconds, 93786);
+    assert_eq!(app.total_tasks, 18);
+
+    // Past the end: stays on the last frame
+    source.refresh(&mut app);
+    assert_eq!(app.uptime_seconds, 93786);
+    assert_eq!(app.tick, 3);
+}
@@ -0,0 +1,99 @@
+use ratatui::Frame;
+use ratatui::layout::{Alignment, Constraint, Direction, Layout, Rect};
+use ratatui::style::{Color, Modifier, Style};
+use ratatui::text::{Line, Span};
+use ratatui::widgets::{Block, Borders, Clear, Paragraph};
+
+use crate::app::App;
+
+/// Draw the saved views picker ('v' key)
+pub fn draw_views_menu(f: &mut Frame, app: &App) {
+    let area = centered_rect(50, 60, f.area());
+    f.render_widget(Clear, area);
+
+    let selected = Style::default().fg(Color::Black).bg(Color::Cyan).add_modifier(Modifier::BOLD);
+    let mut lines: Vec<Line> = Vec::new();
+
+    if app.saved_views.is_empty() {
+        lines.push(Line::from(Span::styled("  (no saved views yet)", Style::default().fg(Color::DarkGray))));
+    }
+    for (i, view) in app.saved_views.iter().enumerate() {
+        let is_active = app.active_view.as_ref() == Some(&view.name);
+        let prefix = if is_active { "● " } else { "  " };
+        // Filter summary after the name, so similar views can be told apart
+        let summary = match (view.filter_query.is_empty(), &view.user_filter) {
+            (false, Some(user)) => format!("  {}  user:{}", view.filter_query, user),
+            (false, None) => format!("  {}", view.filter_query),
+            (true, Some(user)) => format!("  user:{}", user),
+            (true, None) => String::new(),
+        };
+
+        let (name_style, summary_style) = if i == app.view_menu_index && app.view_name_input.is_none() {
+            (selected, selected)
+        } else if is_active {
+            (Style::default().fg(Color::Green).add_modifier(Modifier::BOLD), Style::default().fg(Color::DarkGray))
+        } else {
+            (Style::default().fg(Color::White), Style::default().fg(Color::DarkGray))
+        };
+        lines.push(Line::from(vec![
+            Span::styled(format!("{}{}", prefix, view.name), name_style),
+            Span::styled(summary, summary_style),
+        ]));
+    }
+
+    // Last row: save the current view, or the name being typed
+    let save_line = match &app.view_name_input {
+        Some(name) => Line::from(vec![
+            Span::styled("  Save as: ", Style::default().fg(Color::Yellow).add_modifier(Modifier::BOLD)),
+            Span::styled(name.clone(), Style::default().fg(Color::White)),
+            Span::styled("_", Style::default().fg(Color::White).add_modifier(Modifier::SLOW_BLINK)),
+        ]),
+        None if app.view_menu_index == app.saved_views.len() => {
+            Line::from(Span::styled("  [Save current view]", selected))
+        }
+        None => Line::from(Span::styled("  [Save current view]", Style::default().fg(Color::Yellow))),
+    };
+    lines.push(Line::from(""));
+    lines.push(save_line);
+
+    lines.push(Line::from(""));
+    let hint = if app.view_name_input.is_some() {
+        " Enter Save  Esc Cancel "
+    } else {
+        " Enter Apply  d Delete  Esc Close "
+    };
+    lines.push(Line::from(Span::styled(hint, Style::default().fg(Color::DarkGray))));
+
+    let paragraph = Paragraph::new(lines)
+        .block(
+            Block::default()
+                .borders(Borders::ALL)
+                .title(" Saved Views ")
+                .title_alignment(Alignment::Center)
+                .border_style(Style::default().fg(Color::Cyan)),
+        )
+        .style(Style::default().fg(Color::White).bg(Color::Black));
+
+    f.render_widget(paragraph, area);
+}
+
+/// Create a centered rectangle
+fn centered_rect(percent_x: u16, percent_y: u16, area: Rect) -> Rect {
+    let popup_layout = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([
+            Constraint::Percentage((100 - percent_y) / 2),
+            Constraint::Percentage(percent_y),
+            Constraint::Percentage((100 - percent_y) / 2),
+        ])
+        .split(area);
+
+    Layout::default()
+        .direction(Direction::Horizontal)
+        .constraints([
+            Constraint::Percentage((100 - percent_x) / 2),
+            Constraint::Percentage(percent_x),
+            Constraint::Percentage((100 - percent_x) / 2),
+        ])
+        .split(popup_layout[1])[1]
+}
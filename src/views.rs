@@ -0,0 +1,238 @@
+//! Named views: a filter, sort, tab, tree layout and column set saved under
+//! a name, recalled from the `v` picker or with `pstop --view <name>`.
+//!
+//! Views live in the config file as `view.<name>.<key>=<value>` lines.
+//! Collapsed tree nodes are stored by process name rather than PID, so a
+//! view still collapses "chrome" after Chrome restarts; `*` in a stored name
+//! matches any run of characters.
+
+use std::collections::HashSet;
+
+use crate::app::{App, ProcessTab};
+use crate::system::process::ProcessSortField;
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct SavedView {
+    pub name: String,
+    pub filter_query: String,
+    pub user_filter: Option<String>,
+    pub active_tab: ProcessTab,
+    pub sort_field: ProcessSortField,
+    pub sort_ascending: bool,
+    pub tree_view: bool,
+    /// Name patterns of the collapsed tree nodes
+    pub collapsed: Vec<String>,
+    pub visible_columns: Vec<ProcessSortField>,
+}
+
+impl SavedView {
+    /// The view as it is on screen now
+    pub fn capture(app: &App, name: &str) -> Self {
+        let mut collapsed: Vec<String> = app
+            .processes
+            .iter()
+            .filter(|p| app.collapsed_pids.contains(&p.pid))
+            .map(|p| p.name.clone())
+            .collect();
+        collapsed.sort();
+        collapsed.dedup();
+        Self {
+            name: name.to_string(),
+            filter_query: app.filter_query.clone(),
+            user_filter: app.user_filter.clone(),
+            active_tab: app.active_tab,
+            sort_field: app.sort_field,
+            sort_ascending: app.sort_ascending,
+            tree_view: app.tree_view,
+            collapsed,
+            visible_columns: ProcessSortField::all()
+                .iter()
+                .copied()
+                .filter(|f| app.visible_columns.contains(f))
+                .collect(),
+        }
+    }
+
+    /// Switch the App to this view and rebuild the process list
+    pub fn apply(&self, app: &mut App) {
+        app.filter_query = self.filter_query.clone();
+        app.user_filter = self.user_filter.clone();
+        app.active_tab = self.active_tab;
+        app.sort_field = self.sort_field;
+        app.sort_ascending = self.sort_ascending;
+        app.tree_view = self.tree_view;
+        if !self.visible_columns.is_empty() {
+            app.visible_columns = self.visible_columns.iter().copied().collect();
+        }
+        app.collapsed_pids = app
+            .processes
+            .iter()
+            .filter(|p| self.collapsed.iter().any(|pattern| name_matches(pattern, &p.name)))
+            .map(|p| p.pid)
+            .collect::<HashSet<u32>>();
+        app.active_view = Some(self.name.clone());
+
+        app.apply_filter();
+        app.sort_processes();
+        if app.tree_view {
+            app.build_tree_view();
+        }
+        app.selected_index = 0;
+        app.scroll_offset = 0;
+        app.clamp_selection();
+    }
+
+    /// A view with the default settings, for the config loader to fill in
+    pub fn named(name: &str) -> Self {
+        Self {
+            name: name.to_string(),
+            filter_query: String::new(),
+            user_filter: None,
+            active_tab: ProcessTab::Main,
+            sort_field: ProcessSortField::Cpu,
+            sort_ascending: false,
+            tree_view: false,
+            collapsed: Vec::new(),
+            visible_columns: Vec::new(),
+        }
+    }
+
+    /// `view.<name>.<key>=<value>` lines for the config file
+    pub fn config_lines(&self) -> Vec<String> {
+        let all = ProcessSortField::all();
+        let b = |v: bool| if v { "1" } else { "0" };
+        let columns: Vec<String> = self
+            .visible_columns
+            .iter()
+            .filter_map(|col| all.iter().position(|f| f == col))
+            .map(|i| i.to_string())
+            .collect();
+        let prefix = format!("view.{}.", self.name);
+        vec![
+            format!("{}filter={}", prefix, self.filter_query),
+            format!("{}user={}", prefix, self.user_filter.as_deref().unwrap_or("")),
+            format!("{}tab={}", prefix, self.active_tab.name()),
+            format!("{}sort_field={}", prefix, self.sort_field.index()),
+            format!("{}sort_ascending={}", prefix, b(self.sort_ascending)),
+            format!("{}tree_view={}", prefix, b(self.tree_view)),
+            format!("{}collapsed={}", prefix, self.collapsed.join(";")),
+            format!("{}visible_columns={}", prefix, columns.join(",")),
+        ]
+    }
+
+    /// Set one field from a config line; unknown keys are ignored
+    pub fn set(&mut self, key: &str, value: &str) {
+        let all = ProcessSortField::all();
+        match key {
+            "filter" => self.filter_query = value.to_string(),
+            "user" => self.user_filter = (!value.is_empty()).then(|| value.to_string()),
+            "tab" => {
+                if let Some(tab) = ProcessTab::parse(value) {
+                    self.active_tab = tab;
+                }
+            }
+            "sort_field" => {
+                if let Some(&field) = value.parse::<usize>().ok().and_then(|i| all.get(i)) {
+                    self.sort_field = field;
+                }
+            }
+            "sort_ascending" => self.sort_ascending = value == "1",
+            "tree_view" => self.tree_view = value == "1",
+            "collapsed" => {
+                self.collapsed = value.split(';').map(str::trim).filter(|s| !s.is_empty()).map(str::to_string).collect();
+            }
+            "visible_columns" => {
+                self.visible_columns = value
+                    .split(',')
+                    .filter_map(|s| s.trim().parse::<usize>().ok())
+                    .filter_map(|i| all.get(i).copied())
+                    .collect();
+            }
+            _ => {}
+        }
+    }
+}
+
+/// Is `name` acceptable as a view name? (It becomes part of a config key.)
+pub fn valid_name(name: &str) -> bool {
+    let name = name.trim();
+    !name.is_empty() && !name.contains(['=', '\n', '\r'])
+}
+
+/// Case-insensitive match of a process name against a pattern where `*`
+/// stands for any run of characters
+pub fn name_matches(pattern: &str, name: &str) -> bool {
+    let pattern = pattern.to_lowercase();
+    let name = name.to_lowercase();
+    let mut parts = pattern.split('*');
+    let first = parts.next().unwrap_or("");
+    let Some(mut rest) = name.strip_prefix(first) else { return false };
+    let parts: Vec<&str> = parts.collect();
+    let Some((last, middle)) = parts.split_last() else {
+        return rest.is_empty();
+    };
+    for part in middle {
+        match rest.find(part) {
+            Some(i) => rest = &rest[i + part.len()..],
+            None => return false,
+        }
+    }
+    rest.len() >= last.len() && rest.ends_with(last)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::system::fake::FakeSource;
+
+    #[test]
+    fn capture_and_apply_round_trip() {
+        let mut app = FakeSource::basic_app();
+        app.filter_query = "firefox|postgres".to_string();
+        app.sort_field = ProcessSortField::ResMem;
+        app.sort_ascending = true;
+        app.tree_view = true;
+        app.active_tab = ProcessTab::Io;
+        app.visible_columns = [ProcessSortField::Pid, ProcessSortField::ResMem, ProcessSortField::Command].into_iter().collect();
+        let parent = app.processes.iter().find(|p| p.name.contains("firefox")).unwrap();
+        app.collapsed_pids.insert(parent.pid);
+        let view = SavedView::capture(&app, "browsers");
+        assert_eq!(view.collapsed, vec![parent.name.clone()]);
+        assert_eq!(view.visible_columns, [ProcessSortField::Pid, ProcessSortField::ResMem, ProcessSortField::Command]);
+
+        // Through the config format and back
+        let mut loaded = SavedView::named("browsers");
+        for line in view.config_lines() {
+            let (key, value) = line.split_once('=').unwrap();
+            let prop = key.strip_prefix("view.browsers.").unwrap();
+            loaded.set(prop, value.trim());
+        }
+        assert_eq!(loaded, view);
+
+        // Onto a fresh App: same list, same collapsed node
+        let mut other = FakeSource::basic_app();
+        loaded.apply(&mut other);
+        assert_eq!(other.active_view.as_deref(), Some("browsers"));
+        assert_eq!(other.active_tab, ProcessTab::Io);
+        assert!(other.collapsed_pids.contains(&parent.pid));
+        app.apply_filter();
+        app.sort_processes();
+        app.build_tree_view();
+        let pids = |a: &App| a.filtered_processes.iter().map(|p| p.pid).collect::<Vec<_>>();
+        assert_eq!(pids(&other), pids(&app));
+    }
+
+    #[test]
+    fn name_patterns() {
+        assert!(name_matches("chrome", "Chrome"));
+        assert!(!name_matches("chrome", "chrome_crashpad"));
+        assert!(name_matches("chrome*", "chrome_crashpad"));
+        assert!(name_matches("*sql*", "postgresql"));
+        assert!(name_matches("p*g*s", "postgres"));
+        assert!(!name_matches("p*g*x", "postgres"));
+        assert!(!name_matches("ab*ba", "aba"));
+        assert!(valid_name(" build agents "));
+        assert!(!valid_name("a=b"));
+        assert!(!valid_name("  "));
+    }
+}